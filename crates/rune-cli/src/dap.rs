@@ -0,0 +1,707 @@
+//! A server for the [Debug Adapter Protocol] which is spoken over stdio.
+//!
+//! [Debug Adapter Protocol]: https://microsoft.github.io/debug-adapter-protocol/
+
+use crate::{Config, ExitCode, SharedFlags};
+use anyhow::{anyhow, bail, Context as _, Result};
+use codespan_reporting::term::termcolor::NoColor;
+use rune::compile::FileSourceLoader;
+use rune::runtime::debugger::{Debugger, SourceLine, Stop};
+use rune::{Context, Diagnostics, Options, Source, SourceId, Sources, Unit, Value, Vm};
+use rune_modules::capture_io::CaptureIo;
+use serde::Deserialize;
+use serde_json::json;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use structopt::StructOpt;
+
+/// The only thread which is ever reported.
+const THREAD_ID: i64 = 1;
+
+/// The name of the function which expressions are evaluated in.
+const EVALUATE: &str = "dap_evaluate";
+
+#[derive(StructOpt, Debug, Clone)]
+pub(crate) struct Flags {
+    #[structopt(flatten)]
+    pub(crate) shared: SharedFlags,
+}
+
+/// A request sent by the client.
+#[derive(Debug, Deserialize)]
+struct Request {
+    seq: i64,
+    command: String,
+    #[serde(default)]
+    arguments: serde_json::Value,
+}
+
+/// Breakpoints requested for a source before the program was launched.
+type Pending = Vec<(PathBuf, Vec<usize>)>;
+
+/// The connection to the client.
+struct Adapter {
+    /// Sequence number of the next message sent.
+    seq: i64,
+    /// If lines reported by the client are one-based.
+    lines_start_at1: bool,
+    stdout: io::Stdout,
+}
+
+impl Adapter {
+    fn send(&mut self, mut message: serde_json::Value) -> Result<()> {
+        message["seq"] = json!(self.seq);
+        self.seq += 1;
+
+        let body = serde_json::to_vec(&message)?;
+        let mut stdout = self.stdout.lock();
+        write!(stdout, "Content-Length: {}\r\n\r\n", body.len())?;
+        stdout.write_all(&body)?;
+        stdout.flush()?;
+        Ok(())
+    }
+
+    /// Send a successful response to the given request.
+    fn respond(&mut self, request: &Request, body: serde_json::Value) -> Result<()> {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request.seq,
+            "success": true,
+            "command": request.command,
+            "body": body,
+        }))
+    }
+
+    /// Send an error response to the given request.
+    fn fail(&mut self, request: &Request, message: impl fmt::Display) -> Result<()> {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request.seq,
+            "success": false,
+            "command": request.command,
+            "message": message.to_string(),
+        }))
+    }
+
+    fn event(&mut self, event: &str, body: serde_json::Value) -> Result<()> {
+        self.send(json!({
+            "type": "event",
+            "event": event,
+            "body": body,
+        }))
+    }
+
+    /// Send output to be displayed by the client.
+    fn output(&mut self, category: &str, output: &str) -> Result<()> {
+        if output.is_empty() {
+            return Ok(());
+        }
+
+        self.event("output", json!({ "category": category, "output": output }))
+    }
+
+    /// Convert a zero-based line into a client line.
+    fn line_to_client(&self, line: usize) -> usize {
+        if self.lines_start_at1 {
+            line + 1
+        } else {
+            line
+        }
+    }
+
+    /// Convert a client line into a zero-based line.
+    fn line_from_client(&self, line: usize) -> usize {
+        if self.lines_start_at1 {
+            line.saturating_sub(1)
+        } else {
+            line
+        }
+    }
+}
+
+/// A program which has been built and is ready to be debugged.
+struct Program {
+    context: Context,
+    /// Options the program was built with, which are also used when
+    /// evaluating expressions.
+    options: Options,
+    unit: Arc<Unit>,
+    sources: Sources,
+    capture: CaptureIo,
+    stop_on_entry: bool,
+}
+
+pub(crate) async fn run(c: &Config, flags: &Flags, options: &Options) -> Result<ExitCode> {
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+
+    let mut adapter = Adapter {
+        seq: 1,
+        lines_start_at1: true,
+        stdout: io::stdout(),
+    };
+
+    let mut pending = Pending::new();
+
+    while let Some(request) = read_request(&mut input)? {
+        match request.command.as_str() {
+            "initialize" => {
+                adapter.lines_start_at1 =
+                    request.arguments["linesStartAt1"].as_bool().unwrap_or(true);
+
+                adapter.respond(
+                    &request,
+                    json!({
+                        "supportsConfigurationDoneRequest": true,
+                        "supportsEvaluateForHovers": true,
+                    }),
+                )?;
+
+                adapter.event("initialized", json!({}))?;
+            }
+            "setBreakpoints" => {
+                let path = source_path(&request.arguments);
+                let lines = requested_lines(&request.arguments);
+
+                // NB: breakpoints can't be verified until the program is built.
+                let breakpoints = lines
+                    .iter()
+                    .map(|line| json!({ "verified": true, "line": line }))
+                    .collect::<Vec<_>>();
+
+                if let Some(path) = path {
+                    let lines = lines.iter().map(|l| adapter.line_from_client(*l)).collect();
+                    pending.retain(|(p, _)| *p != path);
+                    pending.push((path, lines));
+                }
+
+                adapter.respond(&request, json!({ "breakpoints": breakpoints }))?;
+            }
+            "launch" => match launch(c, flags, options, &request.arguments) {
+                Ok((program, diagnostics)) => {
+                    adapter.output("stderr", &diagnostics)?;
+                    adapter.respond(&request, json!({}))?;
+                    return session(&mut adapter, &mut input, program, pending).await;
+                }
+                Err(error) => {
+                    adapter.fail(&request, error)?;
+                }
+            },
+            "disconnect" | "terminate" => {
+                adapter.respond(&request, json!({}))?;
+                return Ok(ExitCode::Success);
+            }
+            _ => {
+                adapter.fail(&request, "program has not been launched")?;
+            }
+        }
+    }
+
+    Ok(ExitCode::Success)
+}
+
+/// Build the program requested by a launch request, returning it together
+/// with any diagnostics produced.
+fn launch(
+    c: &Config,
+    flags: &Flags,
+    options: &Options,
+    arguments: &serde_json::Value,
+) -> Result<(Program, String)> {
+    let path = match arguments["program"].as_str() {
+        Some(program) => PathBuf::from(program),
+        None => bail!("missing `program` to launch"),
+    };
+
+    let capture = CaptureIo::new();
+    let context = flags.shared.context_with_capture(c, &capture)?;
+
+    let source = Source::from_path(&path)
+        .with_context(|| anyhow!("cannot read file: {}", path.display()))?;
+
+    let mut sources = Sources::new();
+    sources.insert(source);
+
+    let mut diagnostics = if flags.shared.warnings {
+        Diagnostics::new()
+    } else {
+        Diagnostics::without_warnings()
+    };
+
+    let mut source_loader = FileSourceLoader::new();
+
+    let result = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .with_options(options)
+        .with_source_loader(&mut source_loader)
+        .build();
+
+    let mut out = NoColor::new(Vec::new());
+    diagnostics.emit(&mut out, &sources)?;
+    let output = String::from_utf8_lossy(&out.into_inner()).into_owned();
+
+    let unit = match result {
+        Ok(unit) => Arc::new(unit),
+        Err(..) => bail!("failed to build {}\n{}", path.display(), output),
+    };
+
+    let program = Program {
+        context,
+        options: *options,
+        unit,
+        sources,
+        capture,
+        stop_on_entry: arguments["stopOnEntry"].as_bool().unwrap_or(false),
+    };
+
+    Ok((program, output))
+}
+
+/// Serve requests for a launched program.
+async fn session(
+    adapter: &mut Adapter,
+    input: &mut impl BufRead,
+    program: Program,
+    pending: Pending,
+) -> Result<ExitCode> {
+    let runtime = Arc::new(program.context.runtime());
+    let mut vm = Vm::new(runtime, program.unit.clone());
+
+    let execution = match vm.execute(["main"], ()) {
+        Ok(execution) => execution,
+        Err(error) => {
+            adapter.output("stderr", &format!("{}\n", error))?;
+            adapter.event("terminated", json!({}))?;
+            return Ok(ExitCode::VmError);
+        }
+    };
+
+    let mut debugger = Debugger::new(execution, &program.sources);
+
+    for (path, lines) in pending {
+        if let Some(source_id) = find_source(&program.sources, &path) {
+            for line in lines {
+                debugger.set_breakpoint(source_id, line);
+            }
+        }
+    }
+
+    let mut terminated = false;
+
+    while let Some(request) = read_request(input)? {
+        let command = request.command.as_str();
+
+        if terminated && matches!(command, "continue" | "next" | "stepIn" | "stepOut") {
+            adapter.fail(&request, "program has terminated")?;
+            continue;
+        }
+
+        let result = match command {
+            "setBreakpoints" => {
+                let body = set_breakpoints(adapter, &mut debugger, &program.sources, &request);
+                adapter.respond(&request, body)?;
+                continue;
+            }
+            "configurationDone" => {
+                adapter.respond(&request, json!({}))?;
+
+                if program.stop_on_entry {
+                    stopped(adapter, "entry")?;
+                    continue;
+                }
+
+                debugger.async_resume().await
+            }
+            "threads" => {
+                adapter.respond(
+                    &request,
+                    json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] }),
+                )?;
+                continue;
+            }
+            "stackTrace" => {
+                let body = stack_trace(adapter, &debugger, &program.sources);
+                adapter.respond(&request, body)?;
+                continue;
+            }
+            "scopes" => {
+                let frame = request.arguments["frameId"].as_i64().unwrap_or_default();
+
+                adapter.respond(
+                    &request,
+                    json!({
+                        "scopes": [{
+                            "name": "Locals",
+                            "variablesReference": frame + 1,
+                            "expensive": false,
+                        }]
+                    }),
+                )?;
+                continue;
+            }
+            "variables" => {
+                let reference = request.arguments["variablesReference"]
+                    .as_u64()
+                    .unwrap_or_default() as usize;
+
+                let variables = locals(&mut debugger, reference.saturating_sub(1))
+                    .into_iter()
+                    .map(|(name, value)| {
+                        json!({
+                            "name": name,
+                            "value": display(debugger.execution_mut().vm_mut(), &value),
+                            "variablesReference": 0,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                adapter.respond(&request, json!({ "variables": variables }))?;
+                continue;
+            }
+            "evaluate" => {
+                match evaluate(&program, &mut debugger, &request.arguments).await {
+                    Ok(result) => {
+                        adapter.respond(
+                            &request,
+                            json!({ "result": result, "variablesReference": 0 }),
+                        )?;
+                    }
+                    Err(error) => {
+                        adapter.fail(&request, error)?;
+                    }
+                }
+
+                adapter.output("stdout", &program.capture.drain_utf8()?)?;
+                continue;
+            }
+            "continue" => {
+                adapter.respond(&request, json!({ "allThreadsContinued": true }))?;
+                debugger.async_resume().await
+            }
+            "next" => {
+                adapter.respond(&request, json!({}))?;
+                debugger.async_step_over().await
+            }
+            "stepIn" => {
+                adapter.respond(&request, json!({}))?;
+                debugger.async_step_into().await
+            }
+            "stepOut" => {
+                adapter.respond(&request, json!({}))?;
+                debugger.async_step_out().await
+            }
+            "disconnect" | "terminate" => {
+                adapter.respond(&request, json!({}))?;
+                return Ok(ExitCode::Success);
+            }
+            command => {
+                adapter.fail(&request, format!("unsupported request `{}`", command))?;
+                continue;
+            }
+        };
+
+        adapter.output("stdout", &program.capture.drain_utf8()?)?;
+
+        match result {
+            Ok(Stop::Breakpoint) => {
+                stopped(adapter, "breakpoint")?;
+            }
+            Ok(Stop::Complete(..)) => {
+                adapter.event("exited", json!({ "exitCode": 0 }))?;
+                adapter.event("terminated", json!({}))?;
+                terminated = true;
+            }
+            Ok(..) => {
+                stopped(adapter, "step")?;
+            }
+            Err(error) => {
+                let mut out = NoColor::new(Vec::new());
+                error.emit(&mut out, &program.sources)?;
+                adapter.output("stderr", &String::from_utf8_lossy(&out.into_inner()))?;
+                adapter.event("exited", json!({ "exitCode": ExitCode::VmError as i32 }))?;
+                adapter.event("terminated", json!({}))?;
+                terminated = true;
+            }
+        }
+    }
+
+    Ok(ExitCode::Success)
+}
+
+/// Notify the client that execution stopped.
+fn stopped(adapter: &mut Adapter, reason: &str) -> Result<()> {
+    adapter.event(
+        "stopped",
+        json!({
+            "reason": reason,
+            "threadId": THREAD_ID,
+            "allThreadsStopped": true,
+        }),
+    )
+}
+
+/// Replace all breakpoints in a source.
+fn set_breakpoints(
+    adapter: &Adapter,
+    debugger: &mut Debugger<&mut Vm>,
+    sources: &Sources,
+    request: &Request,
+) -> serde_json::Value {
+    let lines = requested_lines(&request.arguments);
+
+    let source_id = source_path(&request.arguments).and_then(|path| find_source(sources, &path));
+
+    if let Some(source_id) = source_id {
+        let existing = debugger
+            .breakpoints()
+            .filter(|b| b.source_id == source_id)
+            .collect::<Vec<_>>();
+
+        for breakpoint in existing {
+            debugger.remove_breakpoint(breakpoint.source_id, breakpoint.line);
+        }
+    }
+
+    let breakpoints = lines
+        .into_iter()
+        .map(|line| {
+            let verified = match source_id {
+                Some(source_id) => {
+                    debugger.set_breakpoint(source_id, adapter.line_from_client(line))
+                }
+                None => false,
+            };
+
+            json!({ "verified": verified, "line": line })
+        })
+        .collect::<Vec<_>>();
+
+    json!({ "breakpoints": breakpoints })
+}
+
+fn stack_trace(
+    adapter: &Adapter,
+    debugger: &Debugger<&mut Vm>,
+    sources: &Sources,
+) -> serde_json::Value {
+    let frames = debugger.frames();
+
+    let stack_frames = frames
+        .iter()
+        .enumerate()
+        .map(|(id, frame)| {
+            let name = match debugger.signature(frame) {
+                Some(signature) => signature.to_string(),
+                None => String::from("?"),
+            };
+
+            let mut stack_frame = json!({
+                "id": id,
+                "name": name,
+                "line": 0,
+                "column": 0,
+            });
+
+            if let Some(SourceLine {
+                source_id, line, ..
+            }) = frame.line
+            {
+                stack_frame["line"] = json!(adapter.line_to_client(line));
+                stack_frame["column"] = json!(if adapter.lines_start_at1 { 1 } else { 0 });
+                stack_frame["source"] = source(sources, source_id);
+            }
+
+            stack_frame
+        })
+        .collect::<Vec<_>>();
+
+    json!({ "stackFrames": stack_frames, "totalFrames": frames.len() })
+}
+
+/// Evaluate an expression with access to the locals of a frame.
+///
+/// This is done by building a new unit from the program being debugged, with
+/// an additional function which takes the locals as arguments.
+async fn evaluate(
+    program: &Program,
+    debugger: &mut Debugger<&mut Vm>,
+    arguments: &serde_json::Value,
+) -> Result<String> {
+    let expression = match arguments["expression"].as_str() {
+        Some(expression) => expression,
+        None => bail!("missing expression"),
+    };
+
+    let frame = arguments["frameId"].as_u64().unwrap_or_default() as usize;
+
+    // NB: later declarations shadow earlier ones with the same name.
+    let mut locals = locals(debugger, frame);
+    locals.reverse();
+    let mut names = Vec::new();
+    locals.retain(|(name, _)| {
+        let keep = !names.contains(name);
+        names.push(name.clone());
+        keep
+    });
+
+    let main = match program.sources.get(SourceId::new(0)) {
+        Some(main) => main,
+        None => bail!("missing program source"),
+    };
+
+    let source = format!(
+        "{}\npub fn {}({}) {{\n{}\n}}\n",
+        main.as_str(),
+        EVALUATE,
+        locals
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+        expression
+    );
+
+    let mut sources = Sources::new();
+    sources.insert(Source::with_path(main.name(), source, main.path()));
+
+    let mut diagnostics = Diagnostics::without_warnings();
+    let mut source_loader = FileSourceLoader::new();
+
+    let result = rune::prepare(&mut sources)
+        .with_context(&program.context)
+        .with_diagnostics(&mut diagnostics)
+        .with_options(&program.options)
+        .with_source_loader(&mut source_loader)
+        .build();
+
+    let unit = match result {
+        Ok(unit) => Arc::new(unit),
+        Err(..) => {
+            let mut out = NoColor::new(Vec::new());
+            diagnostics.emit(&mut out, &sources)?;
+            bail!("{}", String::from_utf8_lossy(&out.into_inner()).trim());
+        }
+    };
+
+    let runtime = debugger.execution().vm().context().clone();
+    let mut vm = Vm::new(runtime, unit);
+
+    let args = locals
+        .into_iter()
+        .map(|(_, value)| value)
+        .collect::<Vec<_>>();
+
+    let value = vm.execute([EVALUATE], args)?.async_complete().await?;
+    Ok(display(&mut vm, &value))
+}
+
+/// Get the named locals of the frame with the given index.
+fn locals(debugger: &mut Debugger<&mut Vm>, frame: usize) -> Vec<(String, Value)> {
+    let frames = debugger.frames();
+
+    let frame = match frames.get(frame) {
+        Some(frame) => frame,
+        None => return Vec::new(),
+    };
+
+    debugger
+        .locals(frame)
+        .into_iter()
+        .map(|(name, value)| (name.to_owned(), value.clone()))
+        .collect()
+}
+
+/// Format a value for display in the client.
+fn display(vm: &mut Vm, value: &Value) -> String {
+    let mut s = String::new();
+
+    match vm.with(|| value.string_debug(&mut s)) {
+        Ok(Ok(())) => s,
+        _ => format!("{:?}", value),
+    }
+}
+
+/// Describe a source to the client.
+fn source(sources: &Sources, source_id: SourceId) -> serde_json::Value {
+    let mut source = json!({ "name": sources.name(source_id).unwrap_or_default() });
+
+    if let Some(path) = sources.path(source_id) {
+        source["path"] = json!(path.display().to_string());
+    }
+
+    source
+}
+
+/// Get the path of the source in a request.
+fn source_path(arguments: &serde_json::Value) -> Option<PathBuf> {
+    Some(PathBuf::from(arguments["source"]["path"].as_str()?))
+}
+
+/// Get the lines of breakpoints in a setBreakpoints request.
+fn requested_lines(arguments: &serde_json::Value) -> Vec<usize> {
+    let breakpoints = match arguments["breakpoints"].as_array() {
+        Some(breakpoints) => breakpoints,
+        None => return Vec::new(),
+    };
+
+    breakpoints
+        .iter()
+        .filter_map(|b| Some(b["line"].as_u64()? as usize))
+        .collect()
+}
+
+/// Find the source which was loaded from the given path.
+fn find_source(sources: &Sources, path: &Path) -> Option<SourceId> {
+    let path = path.canonicalize().ok()?;
+
+    for index in 0.. {
+        let source_id = SourceId::new(index);
+        let source = sources.get(source_id)?;
+
+        if let Some(p) = source.path() {
+            if p.canonicalize().ok().as_deref() == Some(path.as_path()) {
+                return Some(source_id);
+            }
+        }
+    }
+
+    None
+}
+
+/// Read a single request, returning `None` when the input is closed.
+fn read_request(input: &mut impl BufRead) -> Result<Option<Request>> {
+    let mut length = None;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let line = line.trim_end();
+
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = Some(value.trim().parse::<usize>()?);
+            }
+        }
+    }
+
+    let length = match length {
+        Some(length) => length,
+        None => bail!("missing Content-Length header"),
+    };
+
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
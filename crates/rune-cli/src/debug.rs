@@ -0,0 +1,227 @@
+use crate::{ExitCode, Io, SharedFlags};
+use anyhow::Result;
+use rune::runtime::debugger::{DebugFrame, Debugger, SourceLine, Stop};
+use rune::{Context, SourceId, Sources, Unit, Vm};
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+use structopt::StructOpt;
+
+const HELP: &str = r#"Commands:
+  break <line>    Set a breakpoint on the given line (alias: b).
+  delete <line>   Remove the breakpoint on the given line (alias: d).
+  breakpoints     List all breakpoints.
+  continue        Run until the next breakpoint (alias: c).
+  step            Step to the next line, entering calls (alias: s).
+  next            Step to the next line, stepping over calls (alias: n).
+  finish          Run until the current function returns (alias: f).
+  backtrace       List the call frames (alias: bt).
+  locals [frame]  List the local variables of a frame (alias: l).
+  help            Show this help (alias: h).
+  quit            Stop debugging (alias: q).
+"#;
+
+#[derive(StructOpt, Debug, Clone)]
+pub(crate) struct Flags {
+    /// Set a breakpoint on the given line before starting.
+    #[structopt(short = "b", long = "break")]
+    breakpoints: Vec<usize>,
+
+    #[structopt(flatten)]
+    pub(crate) shared: SharedFlags,
+}
+
+pub(crate) async fn run(
+    io: &mut Io<'_>,
+    flags: &Flags,
+    context: &Context,
+    unit: Arc<Unit>,
+    sources: &Sources,
+) -> Result<ExitCode> {
+    // NB: breakpoints are set in the file being debugged, which is always the
+    // first source.
+    let source_id = SourceId::new(0);
+
+    let runtime = Arc::new(context.runtime());
+    let mut vm = Vm::new(runtime, unit);
+    let mut debugger = Debugger::new(vm.execute(["main"], ())?, sources);
+
+    for line in &flags.breakpoints {
+        set_breakpoint(io, &mut debugger, source_id, *line)?;
+    }
+
+    writeln!(io.stdout, "Stopped at entry (type help for help)")?;
+    print_line(io, sources, debugger.line())?;
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+        write!(io.stdout, "(debug) ")?;
+        io.stdout.flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+
+        let mut parts = line.split_whitespace();
+
+        let command = match parts.next() {
+            Some(command) => command,
+            None => continue,
+        };
+
+        let arg = parts.next().map(str::parse::<usize>);
+
+        let result = match (command, arg) {
+            ("break" | "b", Some(Ok(line))) => {
+                set_breakpoint(io, &mut debugger, source_id, line)?;
+                continue;
+            }
+            ("delete" | "d", Some(Ok(line))) => {
+                if !debugger.remove_breakpoint(source_id, line.saturating_sub(1)) {
+                    writeln!(io.stdout, "No breakpoint on line {}", line)?;
+                }
+
+                continue;
+            }
+            ("breakpoints", None) => {
+                let mut breakpoints = debugger.breakpoints().collect::<Vec<_>>();
+                breakpoints.sort_by_key(|b| (b.source_id, b.line));
+
+                for breakpoint in breakpoints {
+                    print_line(io, sources, Some(breakpoint))?;
+                }
+
+                continue;
+            }
+            ("backtrace" | "bt", None) => {
+                for (n, frame) in debugger.frames().iter().enumerate() {
+                    write!(io.stdout, "#{} {} ", n, function_name(&debugger, frame))?;
+                    print_line(io, sources, frame.line)?;
+                }
+
+                continue;
+            }
+            ("locals" | "l", arg) => {
+                let n = match arg {
+                    Some(Ok(n)) => n,
+                    None => 0,
+                    Some(Err(..)) => {
+                        writeln!(io.stdout, "Expected a frame number")?;
+                        continue;
+                    }
+                };
+
+                let frames = debugger.frames();
+
+                let frame = match frames.get(n) {
+                    Some(frame) => frame,
+                    None => {
+                        writeln!(io.stdout, "No frame #{}", n)?;
+                        continue;
+                    }
+                };
+
+                for (name, value) in debugger.locals(frame) {
+                    writeln!(io.stdout, "  {} = {:?}", name, value)?;
+                }
+
+                continue;
+            }
+            ("help" | "h", None) => {
+                write!(io.stdout, "{}", HELP)?;
+                continue;
+            }
+            ("quit" | "q", None) => break,
+            ("continue" | "c", None) => debugger.async_resume().await,
+            ("step" | "s", None) => debugger.async_step_into().await,
+            ("next" | "n", None) => debugger.async_step_over().await,
+            ("finish" | "f", None) => debugger.async_step_out().await,
+            _ => {
+                writeln!(
+                    io.stdout,
+                    "Unknown command `{}` (type help for help)",
+                    line.trim()
+                )?;
+                continue;
+            }
+        };
+
+        match result {
+            Ok(Stop::Complete(value)) => {
+                writeln!(io.stdout, "Completed with {:?}", value)?;
+                return Ok(ExitCode::Success);
+            }
+            Ok(Stop::Breakpoint) => {
+                write!(io.stdout, "Breakpoint at ")?;
+                print_line(io, sources, debugger.line())?;
+            }
+            Ok(..) => {
+                print_line(io, sources, debugger.line())?;
+            }
+            Err(error) => {
+                error.emit(io.stdout, sources)?;
+                return Ok(ExitCode::VmError);
+            }
+        }
+    }
+
+    Ok(ExitCode::Success)
+}
+
+/// Set a breakpoint on the given one-based line.
+fn set_breakpoint(
+    io: &mut Io<'_>,
+    debugger: &mut Debugger<&mut Vm>,
+    source_id: SourceId,
+    line: usize,
+) -> Result<()> {
+    if !debugger.set_breakpoint(source_id, line.saturating_sub(1)) {
+        writeln!(io.stdout, "No code on line {}", line)?;
+    }
+
+    Ok(())
+}
+
+/// Print a source line together with its location.
+fn print_line(io: &mut Io<'_>, sources: &Sources, line: Option<SourceLine>) -> Result<()> {
+    let line = match line {
+        Some(line) => line,
+        None => {
+            writeln!(io.stdout, "<unknown>")?;
+            return Ok(());
+        }
+    };
+
+    let source = match sources.get(line.source_id) {
+        Some(source) => source,
+        None => {
+            writeln!(io.stdout, "<unknown>")?;
+            return Ok(());
+        }
+    };
+
+    let text = source
+        .line_range(line.line)
+        .and_then(|range| source.get(range))
+        .unwrap_or_default();
+
+    writeln!(
+        io.stdout,
+        "{}:{}: {}",
+        source.name(),
+        line.line + 1,
+        text.trim()
+    )?;
+
+    Ok(())
+}
+
+/// Get the name of the function a frame is in.
+fn function_name(debugger: &Debugger<&mut Vm>, frame: &DebugFrame) -> String {
+    match debugger.signature(frame) {
+        Some(signature) => signature.to_string(),
+        None => String::from("?"),
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{visitor, Args, Io};
 use anyhow::{anyhow, Context as _, Result};
-use rune::compile::{FileSourceLoader, ItemBuf};
+use rune::compile::{FileSourceLoader, FileUnitCache, ItemBuf};
 use rune::Diagnostics;
 use rune::{Context, Hash, Options, Source, Sources, Unit};
 use std::collections::VecDeque;
@@ -8,7 +8,7 @@ use std::ffi::OsStr;
 use std::fs;
 use std::io;
 use std::{path::Path, sync::Arc};
-use tracing::{error, trace};
+use tracing::trace;
 
 pub(crate) struct Load {
     pub(crate) unit: Arc<Unit>,
@@ -27,68 +27,37 @@ pub(crate) fn load(
 ) -> Result<Load> {
     let shared = args.cmd.shared();
 
-    let bytecode_path = path.with_extension("rnc");
-
     let source =
         Source::from_path(path).with_context(|| anyhow!("cannot read file: {}", path.display()))?;
 
     let mut sources = Sources::new();
     sources.insert(source);
 
-    let use_cache = options.bytecode && should_cache_be_used(path, &bytecode_path)?;
-
-    // TODO: how do we deal with tests discovery for bytecode loading
-    let maybe_unit = if use_cache {
-        let f = fs::File::open(&bytecode_path)?;
+    trace!("building file: {}", path.display());
 
-        match bincode::deserialize_from::<_, Unit>(f) {
-            Ok(unit) => {
-                trace!("using cache: {}", bytecode_path.display());
-                Some(Arc::new(unit))
-            }
-            Err(e) => {
-                error!("failed to deserialize: {}: {}", bytecode_path.display(), e);
-                None
-            }
-        }
+    let mut diagnostics = if shared.warnings {
+        Diagnostics::new()
     } else {
-        None
+        Diagnostics::without_warnings()
     };
 
-    let (unit, functions) = match maybe_unit {
-        Some(unit) => (unit, Default::default()),
-        None => {
-            trace!("building file: {}", path.display());
-
-            let mut diagnostics = if shared.warnings {
-                Diagnostics::new()
-            } else {
-                Diagnostics::without_warnings()
-            };
-
-            let mut functions = visitor::FunctionVisitor::new(attribute);
-            let mut source_loader = FileSourceLoader::new();
-
-            let result = rune::prepare(&mut sources)
-                .with_context(context)
-                .with_diagnostics(&mut diagnostics)
-                .with_options(options)
-                .with_visitor(&mut functions)
-                .with_source_loader(&mut source_loader)
-                .build();
-
-            diagnostics.emit(io.stdout, &sources)?;
-            let unit = result?;
+    let mut functions = visitor::FunctionVisitor::new(attribute);
+    let mut source_loader = FileSourceLoader::new();
+    let mut unit_cache = FileUnitCache::new(shared.cache_dir(path));
 
-            if options.bytecode {
-                trace!("serializing cache: {}", bytecode_path.display());
-                let f = fs::File::create(&bytecode_path)?;
-                bincode::serialize_into(f, &unit)?;
-            }
-
-            (Arc::new(unit), functions.into_functions())
-        }
-    };
+    // TODO: how do we deal with tests discovery for bytecode loading
+    let result = rune::prepare(&mut sources)
+        .with_context(context)
+        .with_diagnostics(&mut diagnostics)
+        .with_options(options)
+        .with_visitor(&mut functions)
+        .with_source_loader(&mut source_loader)
+        .with_unit_cache(&mut unit_cache)
+        .build();
+
+    diagnostics.emit(io.stdout, &sources)?;
+    let unit = Arc::new(result?);
+    let functions = functions.into_functions();
 
     Ok(Load {
         unit,
@@ -97,19 +66,6 @@ pub(crate) fn load(
     })
 }
 
-/// Test if path `a` is newer than path `b`.
-fn should_cache_be_used(source: &Path, cached: &Path) -> io::Result<bool> {
-    let source = fs::metadata(source)?;
-
-    let cached = match fs::metadata(cached) {
-        Ok(cached) => cached,
-        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(false),
-        Err(error) => return Err(error),
-    };
-
-    Ok(source.modified()? < cached.modified()?)
-}
-
 pub(crate) fn recurse_paths(
     recursive: bool,
     first: Box<Path>,
@@ -51,8 +51,12 @@ use tracing_subscriber::filter::EnvFilter;
 
 mod benches;
 mod check;
+mod dap;
+mod debug;
 mod doc;
+mod fmt;
 mod loader;
+mod repl;
 mod run;
 mod tests;
 mod visitor;
@@ -76,6 +80,14 @@ enum Command {
     Bench(benches::Flags),
     /// Run the designated script
     Run(run::Flags),
+    /// Step through the designated script with a debugger
+    Debug(debug::Flags),
+    /// Format the given scripts
+    Fmt(fmt::Flags),
+    /// Start an interactive session, evaluating each input as it is entered
+    Repl(repl::Flags),
+    /// Serve the debug adapter protocol over stdio
+    Dap(dap::Flags),
 }
 
 impl Command {
@@ -92,6 +104,10 @@ impl Command {
             Command::Run(args) => {
                 args.propagate_related_flags();
             }
+            Command::Debug(..) => {}
+            Command::Fmt(..) => {}
+            Command::Repl(..) => {}
+            Command::Dap(..) => {}
         }
     }
 
@@ -102,6 +118,10 @@ impl Command {
             Command::Test(..) => "Testing",
             Command::Bench(..) => "Benchmarking",
             Command::Run(..) => "Running",
+            Command::Debug(..) => "Debugging",
+            Command::Fmt(..) => "Formatting",
+            Command::Repl(..) => "Evaluating",
+            Command::Dap(..) => "Debugging",
         }
     }
 
@@ -112,13 +132,21 @@ impl Command {
             Command::Test(args) => &args.shared,
             Command::Bench(args) => &args.shared,
             Command::Run(args) => &args.shared,
+            Command::Debug(args) => &args.shared,
+            Command::Fmt(args) => &args.shared,
+            Command::Repl(args) => &args.shared,
+            Command::Dap(args) => &args.shared,
         }
     }
 
     fn bins_test(&self) -> Option<WorkspaceFilter<'_>> {
         if !matches!(
             self,
-            Command::Run(..) | Command::Check(..) | Command::Doc(..)
+            Command::Run(..)
+                | Command::Debug(..)
+                | Command::Check(..)
+                | Command::Doc(..)
+                | Command::Fmt(..)
         ) {
             return None;
         }
@@ -135,7 +163,7 @@ impl Command {
     fn tests_test(&self) -> Option<WorkspaceFilter<'_>> {
         if !matches!(
             self,
-            Command::Test(..) | Command::Check(..) | Command::Doc(..)
+            Command::Test(..) | Command::Check(..) | Command::Doc(..) | Command::Fmt(..)
         ) {
             return None;
         }
@@ -152,7 +180,11 @@ impl Command {
     fn examples_test(&self) -> Option<WorkspaceFilter<'_>> {
         if !matches!(
             self,
-            Command::Run(..) | Command::Check(..) | Command::Doc(..)
+            Command::Run(..)
+                | Command::Debug(..)
+                | Command::Check(..)
+                | Command::Doc(..)
+                | Command::Fmt(..)
         ) {
             return None;
         }
@@ -169,7 +201,7 @@ impl Command {
     fn benches_test(&self) -> Option<WorkspaceFilter<'_>> {
         if !matches!(
             self,
-            Command::Bench(..) | Command::Check(..) | Command::Doc(..)
+            Command::Bench(..) | Command::Check(..) | Command::Doc(..) | Command::Fmt(..)
         ) {
             return None;
         }
@@ -211,9 +243,19 @@ struct SharedFlags {
     /// macros[=<true/false>] - Enable or disable macros (experimental).
     ///
     /// bytecode[=<true/false>] - Enable or disable bytecode caching (experimental).
+    ///
+    /// static-types[=<true/false>] - Check type annotations at compile time where possible.
+    ///
+    /// type-check[=<true/false>] - Report likely type errors as warnings.
     #[structopt(name = "option", short = "O", number_of_values = 1)]
     compiler_options: Vec<String>,
 
+    /// The directory to store cached bytecode in when bytecode caching is
+    /// enabled. Defaults to a `rune` directory in the cache directory of the
+    /// current user, or a `.rune-cache` directory next to the script.
+    #[structopt(long = "cache-dir", parse(from_os_str))]
+    cache_dir: Option<PathBuf>,
+
     /// Run with the following binary from a loaded manifest. This requires a
     /// `Rune.toml` manifest.
     #[structopt(long = "bin")]
@@ -263,6 +305,32 @@ struct Config {
 }
 
 impl SharedFlags {
+    /// The directory to store cached bytecode for the script at `path` in.
+    fn cache_dir(&self, path: &Path) -> PathBuf {
+        if let Some(cache_dir) = &self.cache_dir {
+            return cache_dir.clone();
+        }
+
+        // NB: the system temporary directory is shared between users, so
+        // it's not a safe place to load bytecode from.
+        let user_cache = if cfg!(windows) {
+            std::env::var_os("LOCALAPPDATA").map(PathBuf::from)
+        } else {
+            std::env::var_os("XDG_CACHE_HOME")
+                .map(PathBuf::from)
+                .filter(|path| path.is_absolute())
+                .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+        };
+
+        match user_cache {
+            Some(user_cache) => user_cache.join("rune"),
+            None => path
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .join(".rune-cache"),
+        }
+    }
+
     /// Construct a rune context according to the specified argument.
     fn context(&self, c: &Config) -> Result<Context, ContextError> {
         let mut context = rune_modules::default_context()?;
@@ -327,7 +395,13 @@ impl Args {
                 options.test(true);
                 options.bytecode(false);
             }
-            Command::Bench(_) | Command::Doc(..) | Command::Run(_) => (),
+            Command::Bench(_)
+            | Command::Doc(..)
+            | Command::Run(_)
+            | Command::Debug(..)
+            | Command::Fmt(..)
+            | Command::Repl(..)
+            | Command::Dap(..) => (),
         }
 
         for option in &self.cmd.shared().compiler_options {
@@ -534,6 +608,19 @@ fn populate_config(io: &mut Io<'_>, c: &mut Config, args: &Args) -> Result<()> {
 async fn main_with_out(io: &mut Io<'_>, mut args: Args) -> Result<ExitCode> {
     let mut c = Config::default();
     args.cmd.propagate_related_flags(&mut c);
+
+    // NB: the repl reads its input interactively instead of from paths.
+    if let Command::Repl(flags) = &args.cmd {
+        let options = args.options()?;
+        return repl::run(io, &c, flags, &options).await;
+    }
+
+    // NB: the program to debug is provided by the client when it's launched.
+    if let Command::Dap(flags) = &args.cmd {
+        let options = args.options()?;
+        return dap::run(&c, flags, &options).await;
+    }
+
     populate_config(io, &mut c, &args)?;
 
     let entries = std::mem::take(&mut c.entries);
@@ -586,6 +673,8 @@ async fn run_path(
     match &args.cmd {
         Command::Check(flags) => check::run(io, c, flags, options, path),
         Command::Doc(flags) => doc::run(io, c, flags, options, path),
+        Command::Fmt(flags) => fmt::run(io, flags, path),
+        Command::Repl(..) | Command::Dap(..) => Ok(ExitCode::Success),
         Command::Test(flags) => {
             let capture_io = rune_modules::capture_io::CaptureIo::new();
             let context = flags.shared.context_with_capture(c, &capture_io)?;
@@ -625,5 +714,10 @@ async fn run_path(
             let load = loader::load(io, &context, args, options, path, visitor::Attribute::None)?;
             run::run(io, c, flags, &context, load.unit, &load.sources).await
         }
+        Command::Debug(flags) => {
+            let context = flags.shared.context(c)?;
+            let load = loader::load(io, &context, args, options, path, visitor::Attribute::None)?;
+            debug::run(io, flags, &context, load.unit, &load.sources).await
+        }
     }
 }
@@ -0,0 +1,405 @@
+use crate::{Config, ExitCode, Io, SharedFlags};
+use anyhow::{Context as _, Result};
+use rune::ast::{self, Spanned};
+use rune::compile::FileSourceLoader;
+use rune::runtime::RuntimeContext;
+use rune::{Context, Diagnostics, FromValue, Options, Source, Sources, Value, Vm};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use structopt::StructOpt;
+
+/// The name of the function which each input is compiled into.
+const ENTRY: &str = "repl_entry";
+
+/// The name of the variable which holds the value of the input.
+const VALUE: &str = "repl_value";
+
+const HELP: &str = r#"Enter statements, expressions or items to evaluate them.
+
+Variables bound with `let` and items like functions and structs are kept
+between inputs.
+
+Commands:
+  :help     Show this help.
+  :history  Show the input history.
+  :reset    Forget all variables and items.
+  :quit     Exit the repl (or press Ctrl-D).
+"#;
+
+#[derive(StructOpt, Debug, Clone)]
+pub(crate) struct Flags {
+    /// The file to store input history in. Defaults to `.rune_history` in the
+    /// home directory.
+    #[structopt(long = "history", parse(from_os_str))]
+    history: Option<PathBuf>,
+
+    #[structopt(flatten)]
+    pub(crate) shared: SharedFlags,
+}
+
+impl Flags {
+    /// The file to store input history in.
+    fn history(&self) -> PathBuf {
+        if let Some(history) = &self.history {
+            return history.clone();
+        }
+
+        let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+
+        match home {
+            Some(home) => Path::new(&home).join(".rune_history"),
+            None => std::env::temp_dir().join("rune").join("history"),
+        }
+    }
+}
+
+/// The state which is kept between inputs.
+#[derive(Default)]
+struct State {
+    /// The source of all items which have been defined so far.
+    items: String,
+    /// Variables bound by earlier inputs together with their values.
+    bindings: Vec<(String, Value)>,
+}
+
+/// What inputs are evaluated with.
+struct Env<'a> {
+    context: &'a Context,
+    runtime: Arc<RuntimeContext>,
+    options: &'a Options,
+    /// Display warnings.
+    warnings: bool,
+}
+
+/// The outcome of evaluating a single input.
+enum Eval {
+    /// The input was handled, successfully or not.
+    Done,
+    /// The input is not complete and more lines are needed.
+    Incomplete,
+}
+
+pub(crate) async fn run(
+    io: &mut Io<'_>,
+    c: &Config,
+    flags: &Flags,
+    options: &Options,
+) -> Result<ExitCode> {
+    let context = flags.shared.context(c)?;
+
+    let env = Env {
+        context: &context,
+        runtime: Arc::new(context.runtime()),
+        options,
+        warnings: flags.shared.warnings,
+    };
+
+    let mut state = State::default();
+
+    for path in &flags.shared.paths {
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("reading file: {}", path.display()))?;
+        state.items.push_str(&source);
+        state.items.push('\n');
+    }
+
+    let history_path = flags.history();
+    let mut history = match fs::read_to_string(&history_path) {
+        Ok(history) => history.lines().map(String::from).collect::<Vec<_>>(),
+        Err(..) => Vec::new(),
+    };
+
+    writeln!(
+        io.stdout,
+        "Rune {} (type :help for help)",
+        crate::VERSION.trim()
+    )?;
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut input = String::new();
+
+    loop {
+        write!(
+            io.stdout,
+            "{}",
+            if input.is_empty() { "> " } else { "... " }
+        )?;
+        io.stdout.flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+
+        if input.is_empty() {
+            match line.trim() {
+                "" => continue,
+                ":quit" | ":exit" | ":q" => break,
+                ":help" => {
+                    write!(io.stdout, "{}", HELP)?;
+                    continue;
+                }
+                ":history" => {
+                    for (n, entry) in history.iter().enumerate() {
+                        writeln!(io.stdout, "{:>5}  {}", n + 1, entry)?;
+                    }
+
+                    continue;
+                }
+                ":reset" => {
+                    state = State::default();
+                    continue;
+                }
+                _ => {}
+            }
+        }
+
+        // NB: an empty line forces incomplete input to be evaluated, so that
+        // errors are reported.
+        let force = line.trim().is_empty();
+
+        input.push_str(&line);
+        input.push('\n');
+
+        match eval(io, &env, &mut state, &input, force).await? {
+            Eval::Incomplete => continue,
+            Eval::Done => {}
+        }
+
+        let entry = input.trim_end().replace('\n', " ");
+        input.clear();
+
+        if let Err(error) = append_history(&history_path, &entry) {
+            tracing::warn!("failed to write history: {}", error);
+        }
+
+        history.push(entry);
+    }
+
+    writeln!(io.stdout)?;
+    Ok(ExitCode::Success)
+}
+
+/// Evaluate one input, updating the state if it succeeds.
+async fn eval(
+    io: &mut Io<'_>,
+    env: &Env<'_>,
+    state: &mut State,
+    input: &str,
+    force: bool,
+) -> Result<Eval> {
+    // NB: parse the input as the body of a block so that both items and
+    // statements are accepted.
+    let wrapped = format!("{{{}}}", input);
+
+    let block = match rune::parse::parse_all::<ast::Block>(&wrapped, rune::SourceId::empty(), false)
+    {
+        Ok(block) => block,
+        Err(error) => {
+            // Errors at the very end means that the input is incomplete, like
+            // an open brace which has not been closed yet.
+            if !force && error.span().start.into_usize() + 1 >= wrapped.len() {
+                return Ok(Eval::Incomplete);
+            }
+
+            let mut sources = Sources::new();
+            let source_id = sources.insert(Source::new("<repl>", wrapped));
+            let mut diagnostics = Diagnostics::new();
+            diagnostics.error(source_id, error);
+            diagnostics.emit(&mut io.stdout.lock(), &sources)?;
+            return Ok(Eval::Done);
+        }
+    };
+
+    let text = |span: ast::Span| &wrapped[span.range()];
+
+    let mut items = state.items.clone();
+    let mut body = String::new();
+    let mut names = state
+        .bindings
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    let arguments = names.len();
+
+    let mut has_value = false;
+    let mut statements = block.statements.iter().peekable();
+
+    while let Some(stmt) = statements.next() {
+        match stmt {
+            ast::Stmt::Item(..) => {
+                items.push_str(text(stmt.span()));
+                items.push('\n');
+            }
+            ast::Stmt::Expr(expr) if statements.peek().is_none() => {
+                body.push_str(&format!("let {} = {};\n", VALUE, text(expr.span())));
+                has_value = true;
+            }
+            ast::Stmt::Local(local) => {
+                let mut bound = Vec::new();
+                pat_names(&local.pat, &text, &mut bound);
+
+                for name in bound {
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+
+                body.push_str(text(stmt.span()));
+                body.push('\n');
+            }
+            stmt => {
+                body.push_str(text(stmt.span()));
+                body.push('\n');
+            }
+        }
+    }
+
+    if !has_value {
+        body.push_str(&format!("let {} = ();\n", VALUE));
+    }
+
+    let source = format!(
+        "{items}\npub fn {entry}({args}) {{\n{body}[{value}, {names}]\n}}\n",
+        items = items,
+        entry = ENTRY,
+        value = VALUE,
+        args = names[..arguments].join(", "),
+        body = body,
+        names = names.join(", "),
+    );
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("<repl>", source));
+
+    let mut diagnostics = if env.warnings {
+        Diagnostics::new()
+    } else {
+        Diagnostics::without_warnings()
+    };
+
+    let mut source_loader = FileSourceLoader::new();
+
+    let result = rune::prepare(&mut sources)
+        .with_context(env.context)
+        .with_diagnostics(&mut diagnostics)
+        .with_options(env.options)
+        .with_source_loader(&mut source_loader)
+        .build();
+
+    diagnostics.emit(&mut io.stdout.lock(), &sources)?;
+
+    let unit = match result {
+        Ok(unit) => Arc::new(unit),
+        Err(..) => return Ok(Eval::Done),
+    };
+
+    let args = state
+        .bindings
+        .iter()
+        .map(|(_, value)| value.clone())
+        .collect::<Vec<_>>();
+
+    let mut vm = Vm::new(env.runtime.clone(), unit);
+
+    // NB: runtime errors are reported, but leave the state as it was so that
+    // the session can continue.
+    let mut execution = match vm.execute([ENTRY], args) {
+        Ok(execution) => execution,
+        Err(error) => {
+            error.emit(io.stdout, &sources)?;
+            return Ok(Eval::Done);
+        }
+    };
+
+    let output = match execution.async_complete().await {
+        Ok(output) => output,
+        Err(error) => {
+            error.emit(io.stdout, &sources)?;
+            return Ok(Eval::Done);
+        }
+    };
+
+    let mut values = match vm.with(|| Vec::<Value>::from_value(output)) {
+        Ok(values) => values.into_iter(),
+        Err(error) => {
+            error.emit(io.stdout, &sources)?;
+            return Ok(Eval::Done);
+        }
+    };
+    let value = values.next().unwrap_or_default();
+
+    state.items = items;
+    state.bindings = names.into_iter().zip(values).collect();
+
+    if !matches!(value, Value::Unit) {
+        let mut s = String::new();
+
+        match vm.with(|| value.string_debug(&mut s)) {
+            Ok(result) => result?,
+            Err(error) => {
+                error.emit(io.stdout, &sources)?;
+                return Ok(Eval::Done);
+            }
+        }
+
+        writeln!(io.stdout, "{}", s)?;
+    }
+
+    Ok(Eval::Done)
+}
+
+/// Collect the names of all variables bound by a pattern.
+fn pat_names<'a>(pat: &ast::Pat, text: &impl Fn(ast::Span) -> &'a str, out: &mut Vec<String>) {
+    match pat {
+        ast::Pat::PatPath(pat) => {
+            let path = &pat.path;
+
+            if path.global.is_none() && path.rest.is_empty() {
+                if let ast::PathSegment::Ident(ident) = &path.first {
+                    out.push(text(ident.span()).to_owned());
+                }
+            }
+        }
+        ast::Pat::PatVec(pat) => {
+            for (pat, _) in &pat.items {
+                pat_names(pat, text, out);
+            }
+        }
+        ast::Pat::PatTuple(pat) => {
+            for (pat, _) in &pat.items {
+                pat_names(pat, text, out);
+            }
+        }
+        ast::Pat::PatObject(pat) => {
+            for (pat, _) in &pat.items {
+                pat_names(pat, text, out);
+            }
+        }
+        ast::Pat::PatBinding(pat) => {
+            pat_names(&pat.pat, text, out);
+        }
+        ast::Pat::PatOr(pat) => {
+            // NB: every alternative binds the same variables.
+            pat_names(&pat.first, text, out);
+        }
+        _ => {}
+    }
+}
+
+/// Append an entry to the history file.
+fn append_history(path: &Path, entry: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+
+    writeln!(file, "{}", entry)
+}
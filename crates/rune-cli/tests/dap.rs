@@ -0,0 +1,245 @@
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+const SOURCE: &str = r#"fn add(a, b) {
+    let sum = a + b;
+    sum
+}
+
+pub fn main() {
+    let x = 1;
+    let y = add(x, 2);
+    y
+}
+"#;
+
+/// A debug adapter running as a child process, which is driven over stdio.
+struct Client {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    messages: mpsc::Receiver<Value>,
+    seq: i64,
+}
+
+impl Client {
+    fn start(args: &[&str]) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_rune"))
+            .arg("dap")
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("failed to spawn debug adapter");
+
+        let stdin = child.stdin.take();
+        let mut stdout = BufReader::new(child.stdout.take().unwrap());
+        let (tx, messages) = mpsc::channel();
+
+        thread::spawn(move || {
+            while let Some(message) = read_message(&mut stdout) {
+                if tx.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            child,
+            stdin,
+            messages,
+            seq: 0,
+        }
+    }
+
+    /// Send a request and wait for its successful response.
+    fn request(&mut self, command: &str, arguments: Value) -> Value {
+        let message = self.respond(command, arguments);
+        assert_eq!(message["success"], json!(true), "{}", message);
+        message["body"].clone()
+    }
+
+    /// Send a request and wait for its response.
+    fn respond(&mut self, command: &str, arguments: Value) -> Value {
+        self.seq += 1;
+        let seq = self.seq;
+
+        let message = json!({
+            "seq": seq,
+            "type": "request",
+            "command": command,
+            "arguments": arguments,
+        });
+
+        let body = serde_json::to_vec(&message).unwrap();
+        let stdin = self.stdin.as_mut().unwrap();
+        write!(stdin, "Content-Length: {}\r\n\r\n", body.len()).unwrap();
+        stdin.write_all(&body).unwrap();
+        stdin.flush().unwrap();
+
+        loop {
+            let message = self.recv();
+
+            if message["type"] == "response" && message["request_seq"] == json!(seq) {
+                return message;
+            }
+        }
+    }
+
+    /// Wait for the given event.
+    fn event(&mut self, event: &str) -> Value {
+        loop {
+            let message = self.recv();
+
+            if message["type"] == "event" && message["event"] == event {
+                return message["body"].clone();
+            }
+        }
+    }
+
+    fn recv(&mut self) -> Value {
+        self.messages
+            .recv_timeout(Duration::from_secs(30))
+            .expect("timed out waiting for debug adapter")
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        // NB: the adapter exits once its input is closed.
+        self.stdin.take();
+        let _ = self.child.wait();
+    }
+}
+
+fn read_message(reader: &mut impl BufRead) -> Option<Value> {
+    let mut length = None;
+
+    loop {
+        let mut line = String::new();
+
+        if reader.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+
+        let line = line.trim_end();
+
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some(value) = line.strip_prefix("Content-Length: ") {
+            length = value.parse::<usize>().ok();
+        }
+    }
+
+    let mut body = vec![0; length?];
+    reader.read_exact(&mut body).ok()?;
+    serde_json::from_slice(&body).ok()
+}
+
+/// Write the test program to a temporary file.
+fn program(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rune-dap-{}-{}.rn", name, std::process::id()));
+    std::fs::write(&path, SOURCE).unwrap();
+    path
+}
+
+#[test]
+fn test_breakpoint_session() {
+    let path = program("session");
+    let mut client = Client::start(&[]);
+
+    let capabilities = client.request("initialize", json!({ "linesStartAt1": true }));
+    assert_eq!(
+        capabilities["supportsConfigurationDoneRequest"],
+        json!(true)
+    );
+    client.event("initialized");
+
+    let source = json!({ "path": path });
+
+    let breakpoints = client.request(
+        "setBreakpoints",
+        json!({ "source": source, "breakpoints": [{ "line": 3 }] }),
+    );
+    assert_eq!(breakpoints["breakpoints"][0]["line"], json!(3));
+
+    client.request("launch", json!({ "program": path }));
+    client.request("configurationDone", json!({}));
+
+    let stopped = client.event("stopped");
+    assert_eq!(stopped["reason"], "breakpoint");
+
+    let trace = client.request("stackTrace", json!({ "threadId": 1 }));
+    let frames = trace["stackFrames"].as_array().unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0]["line"], json!(3));
+    assert!(frames[0]["name"].as_str().unwrap().contains("add"));
+    assert_eq!(frames[1]["line"], json!(8));
+
+    let scopes = client.request("scopes", json!({ "frameId": frames[0]["id"] }));
+    let reference = scopes["scopes"][0]["variablesReference"].clone();
+
+    let variables = client.request("variables", json!({ "variablesReference": reference }));
+
+    let mut variables = variables["variables"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| (v["name"].as_str().unwrap(), v["value"].as_str().unwrap()))
+        .collect::<Vec<_>>();
+
+    variables.sort();
+    assert_eq!(variables, [("a", "1"), ("b", "2"), ("sum", "3")]);
+
+    client.request("continue", json!({ "threadId": 1 }));
+
+    let exited = client.event("exited");
+    assert_eq!(exited["exitCode"], json!(0));
+    client.event("terminated");
+
+    drop(client);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_evaluate_with_options() {
+    let path = program("options");
+    let mut client = Client::start(&["-O", "static-types"]);
+
+    client.request("initialize", json!({ "linesStartAt1": true }));
+    client.event("initialized");
+
+    client.request(
+        "setBreakpoints",
+        json!({ "source": { "path": path }, "breakpoints": [{ "line": 3 }] }),
+    );
+
+    client.request("launch", json!({ "program": path }));
+    client.request("configurationDone", json!({}));
+    client.event("stopped");
+
+    let result = client.request("evaluate", json!({ "expression": "sum * 2", "frameId": 0 }));
+    assert_eq!(result["result"], "6");
+
+    // NB: expressions are built with the options the program was launched
+    // with, so annotations are checked statically.
+    let response = client.respond(
+        "evaluate",
+        json!({ "expression": "let a: String = 42; a", "frameId": 0 }),
+    );
+
+    assert_eq!(response["success"], json!(false), "{}", response);
+    let message = response["message"].as_str().unwrap();
+    assert!(message.contains("mismatched types"), "{}", message);
+
+    drop(client);
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,124 @@
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// The prompt which the repl prints once it's ready for more input.
+const PROMPT: &str = "> ";
+
+/// A repl running as a child process, which is driven over stdio.
+struct Repl {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    output: mpsc::Receiver<Vec<u8>>,
+    buf: String,
+    history: PathBuf,
+}
+
+impl Repl {
+    fn start(name: &str) -> Self {
+        let history =
+            std::env::temp_dir().join(format!("rune-repl-{}-{}", name, std::process::id()));
+
+        let mut child = Command::new(env!("CARGO_BIN_EXE_rune"))
+            .arg("repl")
+            .arg("--history")
+            .arg(&history)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("failed to spawn repl");
+
+        let stdin = child.stdin.take();
+        let mut stdout = child.stdout.take().unwrap();
+        let (tx, output) = mpsc::channel();
+
+        thread::spawn(move || {
+            let mut buf = [0; 1024];
+
+            while let Ok(n @ 1..) = stdout.read(&mut buf) {
+                if tx.send(buf[..n].to_vec()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut repl = Self {
+            child,
+            stdin,
+            output,
+            buf: String::new(),
+            history,
+        };
+
+        let banner = repl.prompt();
+        assert!(banner.starts_with("Rune "), "{}", banner);
+        repl
+    }
+
+    /// Enter a line and collect the output up until the next prompt.
+    fn eval(&mut self, line: &str) -> String {
+        let stdin = self.stdin.as_mut().unwrap();
+        writeln!(stdin, "{}", line).unwrap();
+        stdin.flush().unwrap();
+        self.prompt()
+    }
+
+    /// Wait for the next prompt, returning the output before it.
+    fn prompt(&mut self) -> String {
+        while !self.buf.ends_with(PROMPT) {
+            let chunk = self
+                .output
+                .recv_timeout(Duration::from_secs(30))
+                .expect("timed out waiting for repl");
+
+            self.buf.push_str(&String::from_utf8(chunk).unwrap());
+        }
+
+        let output = self.buf[..self.buf.len() - PROMPT.len()].to_owned();
+        self.buf.clear();
+        output
+    }
+}
+
+impl Drop for Repl {
+    fn drop(&mut self) {
+        // NB: the repl exits once its input is closed.
+        self.stdin.take();
+        let _ = self.child.wait();
+        let _ = std::fs::remove_file(&self.history);
+    }
+}
+
+#[test]
+fn test_persistent_bindings() {
+    let mut repl = Repl::start("bindings");
+
+    assert_eq!(repl.eval("let x = 40;"), "");
+    assert_eq!(repl.eval("fn add(a, b) { a + b }"), "");
+    assert_eq!(repl.eval("let y = add(x, 2);"), "");
+    assert_eq!(repl.eval("y"), "42\n");
+    assert_eq!(repl.eval("[x, y]"), "[40, 42]\n");
+}
+
+#[test]
+fn test_recover_from_errors() {
+    let mut repl = Repl::start("errors");
+
+    assert_eq!(repl.eval("let x = 1;"), "");
+
+    let output = repl.eval("let y = x / 0;");
+    assert!(output.contains("division by zero"), "{}", output);
+
+    // NB: a failed input doesn't bind anything.
+    let output = repl.eval("y");
+    assert!(output.contains("error"), "{}", output);
+
+    let output = repl.eval("x.missing()");
+    assert!(output.contains("missing instance function"), "{}", output);
+
+    assert_eq!(repl.eval("x + 1"), "2\n");
+}
@@ -0,0 +1,264 @@
+use std::collections::BTreeMap;
+
+use lsp::{CompletionItem, CompletionItemKind};
+use ropey::Rope;
+use rune::compile::{ComponentRef, ContextSignature, Item, MetaKind};
+use rune::{Context, InstFnKind};
+
+use crate::state::Index;
+
+/// What is being completed at the cursor.
+#[derive(Debug)]
+enum CompletionContext {
+    /// An instance function after a `.`, like `value.le`.
+    Instance { prefix: String },
+    /// An item in a path, like `std::collections::Ha`.
+    Path { path: Vec<String>, prefix: String },
+    /// A plain identifier, like a local variable.
+    Ident { prefix: String },
+}
+
+impl CompletionContext {
+    /// Figure out what is being completed by looking at the text in front of
+    /// the given character offset.
+    fn new(content: &Rope, char_offset: usize) -> Self {
+        let mut chars = content.chars_at(char_offset).reversed();
+        let mut word = Vec::new();
+        let mut before = None;
+
+        for c in &mut chars {
+            if c.is_alphanumeric() || matches!(c, '_' | ':') {
+                word.push(c);
+            } else {
+                before = Some(c);
+                break;
+            }
+        }
+
+        let word = word.into_iter().rev().collect::<String>();
+
+        if before == Some('.') && !word.contains(':') {
+            return Self::Instance { prefix: word };
+        }
+
+        if !word.contains("::") {
+            return Self::Ident { prefix: word };
+        }
+
+        let mut path = word
+            .split("::")
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        let prefix = if word.ends_with("::") {
+            String::new()
+        } else {
+            path.pop().unwrap_or_default()
+        };
+
+        Self::Path { path, prefix }
+    }
+}
+
+/// Collect completions at the given position in a source.
+pub(crate) fn complete(
+    context: &Context,
+    index: &Index,
+    content: &Rope,
+    char_offset: usize,
+) -> Vec<CompletionItem> {
+    let mut results = BTreeMap::new();
+
+    match CompletionContext::new(content, char_offset) {
+        CompletionContext::Instance { prefix } => {
+            complete_instance_fns(context, &prefix, &mut results);
+        }
+        CompletionContext::Path { path, prefix } => {
+            complete_items(context, index, &path, &prefix, &mut results);
+        }
+        CompletionContext::Ident { prefix } => {
+            let offset = content.char_to_byte(char_offset);
+            complete_locals(index, content, offset, &prefix, &mut results);
+            complete_items(context, index, &[], &prefix, &mut results);
+        }
+    }
+
+    results.into_values().collect()
+}
+
+/// Complete native instance functions.
+fn complete_instance_fns(
+    context: &Context,
+    prefix: &str,
+    results: &mut BTreeMap<String, CompletionItem>,
+) {
+    for (_, signature) in context.iter_functions() {
+        let name = match signature {
+            ContextSignature::Instance {
+                name: InstFnKind::Instance(name),
+                ..
+            } => name,
+            _ => continue,
+        };
+
+        if !name.starts_with(prefix) {
+            continue;
+        }
+
+        results
+            .entry(name.to_string())
+            .or_insert_with(|| CompletionItem {
+                label: name.to_string(),
+                kind: Some(CompletionItemKind::METHOD),
+                detail: Some(signature.to_string()),
+                ..Default::default()
+            });
+    }
+}
+
+/// Complete local variables which are declared before the given byte offset,
+/// in a scope which covers it.
+fn complete_locals(
+    index: &Index,
+    content: &Rope,
+    offset: usize,
+    prefix: &str,
+    results: &mut BTreeMap<String, CompletionItem>,
+) {
+    for (span, scope) in &index.locals {
+        let start = span.start.into_usize();
+        let end = span.end.into_usize();
+
+        if end > offset || end > content.len_bytes() {
+            continue;
+        }
+
+        if offset < scope.start.into_usize() || offset > scope.end.into_usize() {
+            continue;
+        }
+
+        let name = content
+            .slice(content.byte_to_char(start)..content.byte_to_char(end))
+            .to_string();
+
+        if !name.starts_with(prefix) {
+            continue;
+        }
+
+        results.insert(
+            name.clone(),
+            CompletionItem {
+                label: name,
+                kind: Some(CompletionItemKind::VARIABLE),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+/// Complete items in the context and in the compiled sources which are
+/// children of the given path.
+fn complete_items(
+    context: &Context,
+    index: &Index,
+    path: &[String],
+    prefix: &str,
+    results: &mut BTreeMap<String, CompletionItem>,
+) {
+    for (_, signature) in context.iter_functions() {
+        if let ContextSignature::Function { item, .. } = signature {
+            let detail = signature.to_string();
+            complete_item(
+                item,
+                CompletionItemKind::FUNCTION,
+                detail,
+                path,
+                prefix,
+                results,
+            );
+        }
+    }
+
+    for (_, ty) in context.iter_types() {
+        let detail = ty.item.to_string();
+        complete_item(
+            &ty.item,
+            CompletionItemKind::STRUCT,
+            detail,
+            path,
+            prefix,
+            results,
+        );
+    }
+
+    for (item, kind) in &index.items {
+        let kind = match kind {
+            MetaKind::UnitStruct | MetaKind::TupleStruct | MetaKind::Struct => {
+                CompletionItemKind::STRUCT
+            }
+            MetaKind::UnitVariant | MetaKind::TupleVariant | MetaKind::StructVariant => {
+                CompletionItemKind::ENUM_MEMBER
+            }
+            MetaKind::Enum => CompletionItemKind::ENUM,
+            MetaKind::Function { .. } | MetaKind::ConstFn => CompletionItemKind::FUNCTION,
+            MetaKind::Const => CompletionItemKind::CONSTANT,
+            MetaKind::Trait => CompletionItemKind::INTERFACE,
+            MetaKind::Module => CompletionItemKind::MODULE,
+            _ => continue,
+        };
+
+        complete_item(item, kind, item.to_string(), path, prefix, results);
+    }
+}
+
+/// Complete the component of `item` which follows `path`, if it matches the
+/// given prefix. Intermediate components are completed as modules.
+fn complete_item(
+    item: &Item,
+    kind: CompletionItemKind,
+    detail: String,
+    path: &[String],
+    prefix: &str,
+    results: &mut BTreeMap<String, CompletionItem>,
+) {
+    let mut components = Vec::new();
+
+    for c in item.iter() {
+        match c {
+            ComponentRef::Crate(s) | ComponentRef::Str(s) => components.push(s),
+            ComponentRef::Id(..) => return,
+        }
+    }
+
+    if components.len() <= path.len() || !components.iter().zip(path).all(|(a, b)| *a == b) {
+        return;
+    }
+
+    let name = components[path.len()];
+
+    if !name.starts_with(prefix) {
+        return;
+    }
+
+    let (kind, detail) = if components.len() == path.len() + 1 {
+        (kind, Some(detail))
+    } else {
+        (CompletionItemKind::MODULE, None)
+    };
+
+    let completion = CompletionItem {
+        label: name.to_owned(),
+        kind: Some(kind),
+        detail,
+        ..Default::default()
+    };
+
+    match results.get(name) {
+        // Prefer concrete items over modules with the same name.
+        Some(existing) if existing.kind != Some(CompletionItemKind::MODULE) => {}
+        _ => {
+            results.insert(name.to_owned(), completion);
+        }
+    }
+}
@@ -25,7 +25,7 @@ pub struct NotificationMessage<T> {
     pub params: T,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ResponseMessage<T, D> {
     pub jsonrpc: V2,
     pub id: Option<RequestId>,
@@ -33,7 +33,7 @@ pub struct ResponseMessage<T, D> {
     pub error: Option<ResponseError<D>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy)]
 pub enum Code {
     ParseError = -32700,
     InvalidRequest = -32600,
@@ -47,13 +47,51 @@ pub enum Code {
     RequestCancelled = -32800,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl serde::Serialize for Code {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i32(*self as i32)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct ResponseError<D> {
     pub code: Code,
     pub message: String,
     pub data: Option<D>,
 }
 
+/// An error raised by a request handler, which is sent to the client as the
+/// response to the request.
+#[derive(Debug, Clone)]
+pub struct RequestError {
+    pub code: Code,
+    pub message: String,
+}
+
+impl RequestError {
+    /// Construct an error for a request with invalid parameters.
+    pub fn invalid_params<M>(message: M) -> Self
+    where
+        M: fmt::Display,
+    {
+        Self {
+            code: Code::InvalidParams,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+impl std::error::Error for RequestError {}
+
 #[derive(Debug, PartialEq, Clone, Copy, Hash, Eq)]
 pub struct V2;
 
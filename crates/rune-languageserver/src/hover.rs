@@ -0,0 +1,66 @@
+use std::fmt::Write;
+
+use rune::compile::{ContextSignature, Item, MetaKind};
+use rune::Context;
+
+use crate::state::Index;
+
+/// Render the hover contents for the given item as markdown.
+pub(crate) fn item(context: &Context, index: &Index, item: &Item, kind: MetaKind) -> String {
+    let mut out = String::new();
+
+    out.push_str("```rune\n");
+
+    let _ = match kind {
+        MetaKind::UnitStruct | MetaKind::TupleStruct | MetaKind::Struct => {
+            writeln!(out, "struct {}", item)
+        }
+        MetaKind::UnitVariant | MetaKind::TupleVariant | MetaKind::StructVariant => {
+            writeln!(out, "variant {}", item)
+        }
+        MetaKind::Enum => writeln!(out, "enum {}", item),
+        MetaKind::Function { type_hash, .. } => {
+            if let Some(signature) = index.functions.get(&type_hash) {
+                writeln!(out, "fn {}", signature)
+            } else if let Some(signature) = context_signature(context, type_hash) {
+                writeln!(out, "fn {}", signature)
+            } else {
+                writeln!(out, "fn {}", item)
+            }
+        }
+        MetaKind::Const => writeln!(out, "const {}", item),
+        MetaKind::ConstFn => writeln!(out, "const fn {}", item),
+        MetaKind::Trait => writeln!(out, "trait {}", item),
+        MetaKind::Module => writeln!(out, "mod {}", item),
+        _ => writeln!(out, "{}", item),
+    };
+
+    out.push_str("```");
+
+    if let Some(docs) = index.docs.get(item) {
+        out.push_str("\n\n");
+
+        for line in docs {
+            // NB: doc comments are usually written as `/// text`, so strip
+            // the single space following the marker.
+            let line = line.strip_prefix(' ').unwrap_or(line);
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+/// Render the hover contents for a local variable.
+pub(crate) fn local(name: &str) -> String {
+    format!("```rune\nlet {}\n```", name)
+}
+
+/// Find the signature of a native function.
+fn context_signature(context: &Context, type_hash: rune::Hash) -> Option<&ContextSignature> {
+    context
+        .iter_functions()
+        .find(|(hash, _)| *hash == type_hash)
+        .map(|(_, signature)| signature)
+}
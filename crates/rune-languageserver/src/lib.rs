@@ -30,8 +30,10 @@
 //!
 //! This is part of the [Rune language](https://rune-rs.github.io).
 
+mod completion;
 mod connection;
 pub mod envelope;
+mod hover;
 mod server;
 mod state;
 
@@ -55,6 +57,11 @@ pub fn run(context: Context, options: Options) -> Result<()> {
     server.request_handler::<lsp::request::Initialize, _, _>(initialize);
 
     server.request_handler::<lsp::request::GotoDefinition, _, _>(goto_definition);
+    server.request_handler::<lsp::request::Completion, _, _>(completion);
+    server.request_handler::<lsp::request::HoverRequest, _, _>(hover);
+    server.request_handler::<lsp::request::References, _, _>(references);
+    server.request_handler::<lsp::request::Rename, _, _>(rename);
+    server.request_handler::<lsp::request::Formatting, _, _>(formatting);
 
     server.notification_handler::<lsp::notification::DidOpenTextDocument, _, _>(
         did_open_text_document,
@@ -110,6 +117,14 @@ async fn initialize(
             lsp::TextDocumentSyncKind::INCREMENTAL,
         )),
         definition_provider: Some(lsp::OneOf::Left(true)),
+        references_provider: Some(lsp::OneOf::Left(true)),
+        rename_provider: Some(lsp::OneOf::Left(true)),
+        document_formatting_provider: Some(lsp::OneOf::Left(true)),
+        hover_provider: Some(lsp::HoverProviderCapability::Simple(true)),
+        completion_provider: Some(lsp::CompletionOptions {
+            trigger_characters: Some(vec![String::from("."), String::from(":")]),
+            ..Default::default()
+        }),
         ..Default::default()
     };
 
@@ -146,6 +161,73 @@ async fn goto_definition(
     Ok(position.map(lsp::GotoDefinitionResponse::Scalar))
 }
 
+/// Handle completion request.
+async fn completion(
+    state: State,
+    _: Output,
+    params: lsp::CompletionParams,
+) -> Result<Option<lsp::CompletionResponse>> {
+    let results = state
+        .complete(
+            &params.text_document_position.text_document.uri,
+            params.text_document_position.position,
+        )
+        .await;
+
+    Ok(results.map(lsp::CompletionResponse::Array))
+}
+
+/// Handle hover request.
+async fn hover(state: State, _: Output, params: lsp::HoverParams) -> Result<Option<lsp::Hover>> {
+    Ok(state
+        .hover(
+            &params.text_document_position_params.text_document.uri,
+            params.text_document_position_params.position,
+        )
+        .await)
+}
+
+/// Handle find references request.
+async fn references(
+    state: State,
+    _: Output,
+    params: lsp::ReferenceParams,
+) -> Result<Option<Vec<lsp::Location>>> {
+    Ok(state
+        .find_references(
+            &params.text_document_position.text_document.uri,
+            params.text_document_position.position,
+            params.context.include_declaration,
+        )
+        .await)
+}
+
+/// Handle rename request.
+async fn rename(
+    state: State,
+    _: Output,
+    params: lsp::RenameParams,
+) -> Result<Option<lsp::WorkspaceEdit>> {
+    let edit = state
+        .rename(
+            &params.text_document_position.text_document.uri,
+            params.text_document_position.position,
+            &params.new_name,
+        )
+        .await?;
+
+    Ok(edit)
+}
+
+/// Handle formatting request.
+async fn formatting(
+    state: State,
+    _: Output,
+    params: lsp::DocumentFormattingParams,
+) -> Result<Option<Vec<lsp::TextEdit>>> {
+    Ok(state.format(&params.text_document.uri).await)
+}
+
 /// Handle open text document.
 async fn did_open_text_document(
     state: State,
@@ -99,6 +99,7 @@ fn main() -> Result<()> {
 
     let mut options = Options::default();
     options.macros(true);
+    options.type_check(true);
 
     rune_languageserver::run(context, options)
 }
@@ -1,5 +1,5 @@
 use crate::connection::Output;
-use crate::envelope::{Code, IncomingMessage};
+use crate::envelope::{Code, IncomingMessage, RequestError};
 use crate::State;
 use anyhow::Result;
 use hashbrown::HashMap;
@@ -89,6 +89,9 @@ impl Server {
     }
 
     /// Register a request handler.
+    ///
+    /// If the handler fails with a [RequestError], it's sent to the client as
+    /// the response to the request.
     pub fn request_handler<T, H, O>(&mut self, handler: H)
     where
         T: lsp::request::Request,
@@ -99,8 +102,20 @@ impl Server {
             Box::pin(async move {
                 use serde::de::Deserialize as _;
                 let params = <T::Params>::deserialize(incoming.params)?;
-                let result = handler(state, output.clone(), params).await?;
-                output.response(incoming.id, result).await?;
+
+                match handler(state, output.clone(), params).await {
+                    Ok(result) => {
+                        output.response(incoming.id, result).await?;
+                    }
+                    Err(error) => {
+                        let error = error.downcast::<RequestError>()?;
+
+                        output
+                            .error(incoming.id, error.code, error.message, None::<()>)
+                            .await?;
+                    }
+                }
+
                 Ok(())
             })
         });
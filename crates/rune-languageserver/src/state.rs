@@ -8,16 +8,18 @@ use anyhow::{anyhow, Result};
 use hashbrown::HashMap;
 use lsp::Url;
 use ropey::Rope;
-use rune::ast::{Span, Spanned};
+use rune::ast::{self, Span, Spanned};
 use rune::compile::{
-    CompileError, CompileVisitor, ComponentRef, FileSourceLoader, Item, LinkerError, Location,
-    MetaKind, MetaRef, SourceMeta,
+    CompileError, CompileVisitor, ComponentRef, FileSourceLoader, Item, ItemBuf, LinkerError,
+    Location, MetaKind, MetaRef, SourceMeta,
 };
 use rune::diagnostics::{Diagnostic, FatalDiagnosticKind};
-use rune::{Context, Options, SourceId};
+use rune::runtime::debug::DebugSignature;
+use rune::{Context, Hash, Options, SourceId};
 use tokio::sync::RwLockWriteGuard;
 use tokio::sync::{mpsc, RwLock};
 
+use crate::envelope::RequestError;
 use crate::Output;
 
 /// Shared server state.
@@ -106,6 +108,126 @@ impl State {
         Some(location)
     }
 
+    /// Collect completions at the given uri and LSP position.
+    pub async fn complete(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+    ) -> Option<Vec<lsp::CompletionItem>> {
+        let sources = self.inner.sources.read().await;
+        let source = sources.get(uri)?;
+        let offset = rope_utf16_position(&source.content, position).ok()?;
+
+        Some(crate::completion::complete(
+            &self.inner.context,
+            &source.index,
+            &source.content,
+            offset,
+        ))
+    }
+
+    /// Describe whatever is at the given uri and LSP position.
+    pub async fn hover(&self, uri: &Url, position: lsp::Position) -> Option<lsp::Hover> {
+        let sources = self.inner.sources.read().await;
+        let source = sources.get(uri)?;
+        let offset = rope_utf16_position(&source.content, position).ok()?;
+        let offset = source.content.char_to_byte(offset);
+
+        let (span, value) = if let Some((span, (item, kind))) = find_at(&source.index.metas, offset)
+        {
+            let value = crate::hover::item(&self.inner.context, &source.index, item, *kind);
+            (span, value)
+        } else {
+            let (span, definition) = find_at(&source.index.definitions, offset)?;
+
+            if !matches!(definition.kind, DefinitionKind::Local) {
+                return None;
+            }
+
+            let name = source.content.get_byte_slice(span.range())?.to_string();
+            (span, crate::hover::local(&name))
+        };
+
+        Some(lsp::Hover {
+            contents: lsp::HoverContents::Markup(lsp::MarkupContent {
+                kind: lsp::MarkupKind::Markdown,
+                value,
+            }),
+            range: Some(source.span_to_lsp_range(span)),
+        })
+    }
+
+    /// Find all references to whatever is at the given uri and LSP position.
+    pub async fn find_references(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+        include_declaration: bool,
+    ) -> Option<Vec<lsp::Location>> {
+        let sources = self.inner.sources.read().await;
+        let references = sources.find_references(uri, position, include_declaration)?;
+
+        Some(
+            references
+                .into_iter()
+                .map(|(uri, range)| lsp::Location { uri, range })
+                .collect(),
+        )
+    }
+
+    /// Rename whatever is at the given uri and LSP position.
+    pub async fn rename(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+        new_name: &str,
+    ) -> Result<Option<lsp::WorkspaceEdit>, RequestError> {
+        if !is_ident(new_name) {
+            return Err(RequestError::invalid_params(format_args!(
+                "`{}` is not a valid name",
+                new_name
+            )));
+        }
+
+        let sources = self.inner.sources.read().await;
+
+        let references = match sources.find_references(uri, position, true) {
+            Some(references) => references,
+            None => return Ok(None),
+        };
+
+        let mut changes = std::collections::HashMap::<Url, Vec<lsp::TextEdit>>::new();
+
+        for (uri, range) in references {
+            changes.entry(uri).or_default().push(lsp::TextEdit {
+                range,
+                new_text: new_name.to_owned(),
+            });
+        }
+
+        Ok(Some(lsp::WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }))
+    }
+
+    /// Format the source at the given uri, returning the edits to apply.
+    pub async fn format(&self, uri: &Url) -> Option<Vec<lsp::TextEdit>> {
+        let sources = self.inner.sources.read().await;
+        let source = sources.get(uri)?;
+        let content = source.content.to_string();
+        let formatted = rune::fmt::format_source(&content).ok()?;
+
+        if formatted == content {
+            return Some(Vec::new());
+        }
+
+        Some(vec![lsp::TextEdit {
+            range: source.span_to_lsp_range(Span::new(0, content.len())),
+            new_text: formatted,
+        }])
+    }
+
     /// Rebuild the current project.
     pub async fn rebuild(&self, output: &Output) -> Result<()> {
         let mut inner = self.inner.sources.write().await;
@@ -133,7 +255,7 @@ impl State {
             let mut diagnostics = rune::Diagnostics::new();
             let mut visitor = Visitor::new(Index::default());
 
-            let _ = rune::prepare(&mut sources)
+            let result = rune::prepare(&mut sources)
                 .with_context(&self.inner.context)
                 .with_diagnostics(&mut diagnostics)
                 .with_options(&self.inner.options)
@@ -221,11 +343,21 @@ impl State {
                 }
             }
 
-            builds.push((url.clone(), sources, visitor.into_index()));
+            let mut index = visitor.into_index();
+
+            if let Some(debug_info) = result.as_ref().ok().and_then(|unit| unit.debug_info()) {
+                for (hash, signature) in &debug_info.functions {
+                    index.functions.insert(*hash, signature.clone());
+                }
+            }
+
+            builds.push((url.clone(), sources, index));
         }
 
         for (url, build_sources, index) in builds {
             if let Some(source) = inner.sources.get_mut(&url) {
+                // NB: always replace the index, since spans in an index from
+                // an earlier build don't correspond to the current text.
                 source.index = index;
                 source.build_sources = Some(build_sources);
             }
@@ -298,6 +430,101 @@ impl Sources {
             self.removed.push((url.clone(), source));
         }
     }
+
+    /// Find the name ranges of all references to whatever is at the given
+    /// position, across all open sources.
+    fn find_references(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+        include_declaration: bool,
+    ) -> Option<Vec<(Url, lsp::Range)>> {
+        let source = self.get(uri)?;
+        let offset = rope_utf16_position(&source.content, position).ok()?;
+        let offset = source.content.char_to_byte(offset);
+
+        let mut references = Vec::new();
+
+        match source.target_at(offset)? {
+            Target::Local(declaration) => {
+                let root = source.build_sources.as_ref()?.get(SourceId::new(0))?;
+
+                if include_declaration {
+                    references.push((uri.clone(), span_to_lsp_range(root, declaration)?));
+                }
+
+                for (span, definition) in &source.index.definitions {
+                    if let (DefinitionKind::Local, DefinitionSource::Location(location)) =
+                        (&definition.kind, &definition.source)
+                    {
+                        if location.span == declaration {
+                            references.push((uri.clone(), span_to_lsp_range(root, *span)?));
+                        }
+                    }
+                }
+            }
+            Target::Item(item) => {
+                let name = match item.last()? {
+                    ComponentRef::Str(name) => name,
+                    _ => return None,
+                };
+
+                for (url, source) in &self.sources {
+                    let build_sources = match &source.build_sources {
+                        Some(build_sources) => build_sources,
+                        None => continue,
+                    };
+
+                    let mut found = Vec::new();
+
+                    if include_declaration {
+                        for (def, meta) in &source.index.item_defs {
+                            if *def == item {
+                                found.push((meta.location, false));
+                            }
+                        }
+                    }
+
+                    for (location, used) in &source.index.references {
+                        if *used == item {
+                            found.push((*location, true));
+                        }
+                    }
+
+                    for (location, used) in found {
+                        let (url, source) = resolve_source(url, build_sources, location.source_id)?;
+
+                        let span = if used {
+                            reference_name_span(source.as_str(), location.span, name)
+                        } else {
+                            declaration_name_span(source.as_str(), location.span, name)
+                        };
+
+                        let span = match span {
+                            Some(span) => span,
+                            None => continue,
+                        };
+
+                        let reference = (url, span_to_lsp_range(source, span)?);
+
+                        if !references.contains(&reference) {
+                            references.push(reference);
+                        }
+                    }
+                }
+            }
+        }
+
+        Some(references)
+    }
+}
+
+/// Something which can be referenced.
+enum Target {
+    /// A local variable, identified by the span of its declaration.
+    Local(Span),
+    /// An item.
+    Item(ItemBuf),
 }
 
 /// A single open source.
@@ -324,6 +551,46 @@ impl Source {
         None
     }
 
+    /// Find what is referenced at the given byte offset, either by a use or
+    /// by a declaration.
+    fn target_at(&self, offset: usize) -> Option<Target> {
+        if let Some((_, (item, _))) = find_at(&self.index.metas, offset) {
+            return Some(Target::Item(item.clone()));
+        }
+
+        for (span, definition) in &self.index.definitions {
+            if let (DefinitionKind::Local, DefinitionSource::Location(location)) =
+                (&definition.kind, &definition.source)
+            {
+                if span_contains(*span, offset) || span_contains(location.span, offset) {
+                    return Some(Target::Local(location.span));
+                }
+            }
+        }
+
+        let root = self.build_sources.as_ref()?.get(SourceId::new(0))?;
+
+        for (item, meta) in &self.index.item_defs {
+            if meta.location.source_id.into_index() != 0 {
+                continue;
+            }
+
+            let name = match item.last() {
+                Some(ComponentRef::Str(name)) => name,
+                _ => continue,
+            };
+
+            match declaration_name_span(root.as_str(), meta.location.span, name) {
+                Some(span) if span_contains(span, offset) => {
+                    return Some(Target::Item(item.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+
     /// Modify the given lsp range in the file.
     pub fn modify_lsp_range(&mut self, range: lsp::Range, content: &str) -> Result<()> {
         let start = rope_utf16_position(&self.content, range.start)?;
@@ -379,6 +646,110 @@ impl fmt::Display for Source {
     }
 }
 
+/// Find the entry in the given span map which covers the given byte offset.
+fn find_at<T>(map: &BTreeMap<Span, T>, offset: usize) -> Option<(Span, &T)> {
+    let (span, value) = map
+        .range(..=Span::new(offset, u32::MAX))
+        .rev()
+        .find(|(span, _)| span.end.into_usize() >= offset)?;
+
+    if span.start.into_usize() > offset {
+        return None;
+    }
+
+    Some((*span, value))
+}
+
+/// Test if the given span covers the given byte offset, including its end.
+fn span_contains(span: Span, offset: usize) -> bool {
+    span.start.into_usize() <= offset && offset <= span.end.into_usize()
+}
+
+/// Find the span of the name of the item declared at `span`, by parsing the
+/// declaration.
+fn declaration_name_span(source: &str, span: Span, name: &str) -> Option<Span> {
+    let text = source.get(span.range())?;
+
+    let ident = match rune::parse::parse_all::<ast::Item>(text, SourceId::EMPTY, false) {
+        Ok(ast::Item::Fn(item)) => item.name,
+        Ok(ast::Item::Enum(item)) => item.name,
+        Ok(ast::Item::Struct(item)) => item.ident,
+        Ok(ast::Item::Trait(item)) => item.ident,
+        Ok(ast::Item::Mod(item)) => item.name,
+        Ok(ast::Item::Const(item)) => item.name,
+        Ok(..) => return None,
+        Err(..) => {
+            rune::parse::parse_all::<ast::ItemVariant>(text, SourceId::EMPTY, false)
+                .ok()?
+                .name
+        }
+    };
+
+    ident_span(text, span, &ident, name)
+}
+
+/// Find the span of the name of the item referenced at `span`, which is the
+/// last identifier in the path used.
+fn reference_name_span(source: &str, span: Span, name: &str) -> Option<Span> {
+    let text = source.get(span.range())?;
+    let path = rune::parse::parse_all::<ast::Path>(text, SourceId::EMPTY, false).ok()?;
+
+    let ident = path
+        .rest
+        .iter()
+        .rev()
+        .map(|(_, segment)| segment)
+        .chain(std::iter::once(&path.first))
+        .find_map(|segment| match segment {
+            ast::PathSegment::Ident(ident) => Some(ident),
+            _ => None,
+        })?;
+
+    ident_span(text, span, ident, name)
+}
+
+/// Get the span of an identifier parsed from the `text` of `span`, if it is
+/// `name`.
+fn ident_span(text: &str, span: Span, ident: &ast::Ident, name: &str) -> Option<Span> {
+    let ident = ident.span();
+
+    if text.get(ident.range())? != name {
+        return None;
+    }
+
+    let start = span.start.into_usize();
+    Some(Span::new(
+        start + ident.start.into_usize(),
+        start + ident.end.into_usize(),
+    ))
+}
+
+/// Test if the given string is a valid identifier, which excludes keywords.
+fn is_ident(name: &str) -> bool {
+    rune::parse::parse_all::<rune::ast::Ident>(name, SourceId::EMPTY, false).is_ok()
+}
+
+/// Resolve the url and source of a source id in a build.
+///
+/// Source `0` is always the source which was built from `url`.
+fn resolve_source<'a>(
+    url: &Url,
+    build_sources: &'a rune::Sources,
+    source_id: SourceId,
+) -> Option<(Url, &'a rune::Source)> {
+    let source = build_sources.get(source_id)?;
+
+    let url = if source_id.into_index() == 0 {
+        url.clone()
+    } else if let Some(path) = source.path() {
+        Url::from_file_path(path).ok()?
+    } else {
+        Url::parse(source.name()).ok()?
+    };
+
+    Some((url, source))
+}
+
 /// Convert the given span into an lsp range.
 fn span_to_lsp_range(source: &rune::Source, span: Span) -> Option<lsp::Range> {
     let (line, character) = source.pos_to_utf16cu_linecol(span.start.into_usize());
@@ -492,7 +863,22 @@ where
 #[derive(Default)]
 pub struct Index {
     /// Spans mapping to their corresponding definitions.
-    definitions: BTreeMap<Span, Definition>,
+    pub(crate) definitions: BTreeMap<Span, Definition>,
+    /// Items which were registered while building.
+    pub(crate) items: Vec<(ItemBuf, MetaKind)>,
+    /// Spans mapping to the items they refer to, including native items.
+    pub(crate) metas: BTreeMap<Span, (ItemBuf, MetaKind)>,
+    /// Documentation comments of items.
+    pub(crate) docs: HashMap<ItemBuf, Vec<String>>,
+    /// Signatures of functions in the built unit.
+    pub(crate) functions: HashMap<Hash, DebugSignature>,
+    /// Uses of items in all sources of the build.
+    pub(crate) references: Vec<(Location, ItemBuf)>,
+    /// Declarations of items in all sources of the build.
+    pub(crate) item_defs: Vec<(ItemBuf, SourceMeta)>,
+    /// Spans where local variables are declared, mapping to the span of the
+    /// scope they're declared in.
+    pub(crate) locals: BTreeMap<Span, Span>,
 }
 
 /// A definition source.
@@ -580,11 +966,27 @@ impl Visitor {
 }
 
 impl CompileVisitor for Visitor {
+    fn register_meta(&mut self, meta: MetaRef<'_>) {
+        self.index.items.push((meta.item.to_owned(), meta.kind));
+
+        if let Some(source) = meta.source {
+            self.index
+                .item_defs
+                .push((meta.item.to_owned(), source.clone()));
+        }
+    }
+
     fn visit_meta(&mut self, location: Location, meta: MetaRef<'_>) {
+        self.index.references.push((location, meta.item.to_owned()));
+
         if location.source_id.into_index() != 0 {
             return;
         }
 
+        self.index
+            .metas
+            .insert(location.span, (meta.item.to_owned(), meta.kind));
+
         let source = match meta.source {
             Some(source) => source,
             None => return,
@@ -627,6 +1029,23 @@ impl CompileVisitor for Visitor {
         }
     }
 
+    fn visit_variable_decl(&mut self, source_id: SourceId, var_span: Span, scope: Span) {
+        if source_id.into_index() != 0 {
+            return;
+        }
+
+        let entry = self.index.locals.entry(var_span).or_insert(scope);
+        *entry = entry.join(scope);
+    }
+
+    fn visit_doc_comment(&mut self, _: Location, item: &Item, docstr: &str) {
+        self.index
+            .docs
+            .entry(item.to_owned())
+            .or_default()
+            .push(docstr.to_owned());
+    }
+
     fn visit_mod(&mut self, source_id: SourceId, span: Span) {
         if source_id.into_index() != 0 {
             return;
@@ -712,3 +1131,51 @@ impl<'a> rune::compile::SourceLoader for SourceLoader<'a> {
         self.base.load(root, item, span)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{declaration_name_span, is_ident, reference_name_span};
+    use rune::ast::Span;
+
+    #[test]
+    fn test_is_ident() {
+        assert!(is_ident("foo"));
+        assert!(is_ident("foo_2"));
+        assert!(!is_ident(""));
+        assert!(!is_ident("_"));
+        assert!(!is_ident("2foo"));
+        assert!(!is_ident("foo bar"));
+        assert!(!is_ident("foo-bar"));
+        assert!(!is_ident("fn"));
+        assert!(!is_ident("self"));
+        assert!(!is_ident("match"));
+    }
+
+    #[test]
+    fn test_declaration_name_span() {
+        let source = "/// Call `foo` // and `foo`.\nfn foo(foo_bar) { foo_bar + foo }";
+        let span = Span::new(0, source.len());
+
+        // NB: the mentions in the doc comment are skipped.
+        let name = declaration_name_span(source, span, "foo").unwrap();
+        assert_eq!(name, Span::new(32, 35));
+        assert_eq!(declaration_name_span(source, span, "foo_bar"), None);
+
+        let source = "enum Foo { Bar(a), Baz }";
+        let variant = Span::new(11, 17);
+        assert_eq!(
+            declaration_name_span(source, variant, "Bar"),
+            Some(Span::new(11, 14))
+        );
+    }
+
+    #[test]
+    fn test_reference_name_span() {
+        let source = "let a = foo::bar::<T>(bar);";
+        let span = Span::new(8, 21);
+
+        let name = reference_name_span(source, span, "bar").unwrap();
+        assert_eq!(name, Span::new(13, 16));
+        assert_eq!(reference_name_span(source, span, "foo"), None);
+    }
+}
@@ -0,0 +1,385 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+const URI: &str = "file:///main.rn";
+
+const SOURCE: &str = r#"/// Add one to a number.
+fn add_one(n) {
+    n + 1
+}
+
+pub fn main() {
+    let value = 41;
+    add_one(value)
+}
+"#;
+
+/// A language server running as a child process, which is driven over stdio.
+struct Client {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    messages: mpsc::Receiver<Value>,
+    id: u64,
+}
+
+impl Client {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_rune-languageserver"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("failed to spawn language server");
+
+        let stdin = child.stdin.take();
+        let mut stdout = BufReader::new(child.stdout.take().unwrap());
+        let (tx, messages) = mpsc::channel();
+
+        thread::spawn(move || {
+            while let Some(message) = read_message(&mut stdout) {
+                if tx.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut client = Self {
+            child,
+            stdin,
+            messages,
+            id: 0,
+        };
+
+        client.request("initialize", json!({ "capabilities": {} }));
+        client.notify("initialized", json!({}));
+        client
+    }
+
+    fn send(&mut self, message: Value) {
+        let body = serde_json::to_vec(&message).unwrap();
+        let stdin = self.stdin.as_mut().unwrap();
+        write!(stdin, "Content-Length: {}\r\n\r\n", body.len()).unwrap();
+        stdin.write_all(&body).unwrap();
+        stdin.flush().unwrap();
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    /// Send a request and wait for its result.
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        let message = self.respond(method, params);
+        assert_eq!(message["error"], Value::Null, "{}", message);
+        message["result"].clone()
+    }
+
+    /// Send a request and wait for its response.
+    fn respond(&mut self, method: &str, params: Value) -> Value {
+        self.id += 1;
+        let id = self.id;
+
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+
+        loop {
+            let message = self.recv();
+
+            if message["id"] == json!(id) {
+                return message;
+            }
+        }
+    }
+
+    /// Wait for diagnostics to be published for the test source, which
+    /// indicates that it has been rebuilt.
+    fn diagnostics(&mut self) -> Value {
+        loop {
+            let message = self.recv();
+
+            if message["method"] == "textDocument/publishDiagnostics"
+                && message["params"]["uri"] == URI
+            {
+                return message["params"]["diagnostics"].clone();
+            }
+        }
+    }
+
+    fn recv(&mut self) -> Value {
+        self.messages
+            .recv_timeout(Duration::from_secs(30))
+            .expect("timed out waiting for language server")
+    }
+
+    fn open(&mut self, text: &str) -> Value {
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": { "uri": URI, "languageId": "rune", "version": 1, "text": text },
+            }),
+        );
+
+        self.diagnostics()
+    }
+
+    fn position(&mut self, method: &str, line: u32, character: u32, extra: Value) -> Value {
+        let params = position_params(line, character, extra);
+        self.request(method, params)
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        // NB: the server exits once its input is closed.
+        self.stdin.take();
+        let _ = self.child.wait();
+    }
+}
+
+fn position_params(line: u32, character: u32, extra: Value) -> Value {
+    let mut params = json!({
+        "textDocument": { "uri": URI },
+        "position": { "line": line, "character": character },
+    });
+
+    if let (Some(params), Value::Object(extra)) = (params.as_object_mut(), extra) {
+        params.extend(extra);
+    }
+
+    params
+}
+
+fn read_message(reader: &mut impl BufRead) -> Option<Value> {
+    let mut length = None;
+
+    loop {
+        let mut line = String::new();
+
+        if reader.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+
+        let line = line.trim_end();
+
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some(value) = line.strip_prefix("Content-Length: ") {
+            length = value.parse::<usize>().ok();
+        }
+    }
+
+    let mut body = vec![0; length?];
+    reader.read_exact(&mut body).ok()?;
+    serde_json::from_slice(&body).ok()
+}
+
+fn labels(completions: &Value) -> Vec<&str> {
+    completions
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap())
+        .collect()
+}
+
+fn ranges(locations: &Value) -> Vec<(u64, u64, u64)> {
+    let mut ranges = locations
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|location| {
+            let range = location.get("range").unwrap_or(location);
+            (
+                range["start"]["line"].as_u64().unwrap(),
+                range["start"]["character"].as_u64().unwrap(),
+                range["end"]["character"].as_u64().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    ranges.sort();
+    ranges
+}
+
+#[test]
+fn test_completion() {
+    let mut client = Client::start();
+    assert_eq!(client.open(SOURCE), json!([]));
+
+    // Completing `add_one(va` in `main`.
+    let completions = client.position("textDocument/completion", 7, 14, json!({}));
+    let names = labels(&completions);
+    assert!(names.contains(&"value"), "{:?}", names);
+    assert!(!names.contains(&"add_one"), "{:?}", names);
+
+    // Completing `add_` in `main`.
+    let completions = client.position("textDocument/completion", 7, 8, json!({}));
+    let names = labels(&completions);
+    assert!(names.contains(&"add_one"), "{:?}", names);
+}
+
+#[test]
+fn test_completion_scopes() {
+    let mut client = Client::start();
+
+    let source = r#"fn first(value) {
+    let vanished = value;
+    {
+        let visible = 1;
+    }
+    vanished
+}
+
+pub fn main() {
+    let var = first(1);
+    var
+}
+"#;
+
+    assert_eq!(client.open(source), json!([]));
+
+    // Completing `v` at the end of `first`.
+    let completions = client.position("textDocument/completion", 5, 5, json!({}));
+    let names = labels(&completions);
+    assert!(names.contains(&"value"), "{:?}", names);
+    assert!(names.contains(&"vanished"), "{:?}", names);
+    assert!(!names.contains(&"visible"), "{:?}", names);
+    assert!(!names.contains(&"var"), "{:?}", names);
+
+    // Completing `v` at the end of `main`.
+    let completions = client.position("textDocument/completion", 10, 5, json!({}));
+    let names = labels(&completions);
+    assert!(names.contains(&"var"), "{:?}", names);
+    assert!(!names.contains(&"value"), "{:?}", names);
+    assert!(!names.contains(&"vanished"), "{:?}", names);
+}
+
+#[test]
+fn test_completion_after_failed_build() {
+    let mut client = Client::start();
+    assert_eq!(client.open(SOURCE), json!([]));
+
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": URI, "version": 2 },
+            "contentChanges": [{
+                "range": {
+                    "start": { "line": 0, "character": 0 },
+                    "end": { "line": 9, "character": 0 },
+                },
+                "text": format!("pub fn main() {{\n{}    v +\n}}\n", "    // ...\n".repeat(10)),
+            }],
+        }),
+    );
+
+    assert_ne!(client.diagnostics(), json!([]));
+
+    // NB: locals from the last successful build must not be offered, since
+    // their spans refer to the old text.
+    let completions = client.position("textDocument/completion", 11, 4, json!({}));
+
+    let locals = completions
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|item| item["kind"] == json!(6))
+        .collect::<Vec<_>>();
+
+    assert!(locals.is_empty(), "{:?}", locals);
+}
+
+#[test]
+fn test_hover() {
+    let mut client = Client::start();
+    client.open(SOURCE);
+
+    let hover = client.position("textDocument/hover", 7, 6, json!({}));
+    let value = hover["contents"]["value"].as_str().unwrap();
+    assert!(value.starts_with("```rune\nfn add_one"), "{}", value);
+    assert!(value.contains("Add one to a number."), "{}", value);
+    assert_eq!(
+        hover["range"]["start"],
+        json!({ "line": 7, "character": 4 })
+    );
+
+    let hover = client.position("textDocument/hover", 7, 14, json!({}));
+    assert_eq!(hover["contents"]["value"], "```rune\nlet value\n```");
+}
+
+#[test]
+fn test_references() {
+    let mut client = Client::start();
+    client.open(SOURCE);
+
+    let context = json!({ "context": { "includeDeclaration": true } });
+    let references = client.position("textDocument/references", 6, 9, context);
+    assert_eq!(ranges(&references), [(6, 8, 13), (7, 12, 17)]);
+
+    let context = json!({ "context": { "includeDeclaration": false } });
+    let references = client.position("textDocument/references", 7, 6, context);
+    assert_eq!(ranges(&references), [(7, 4, 11)]);
+}
+
+#[test]
+fn test_rename() {
+    let mut client = Client::start();
+    client.open(SOURCE);
+
+    let edit = client.position(
+        "textDocument/rename",
+        7,
+        6,
+        json!({ "newName": "plus_one" }),
+    );
+
+    let edits = &edit["changes"][URI];
+    assert_eq!(ranges(edits), [(1, 3, 10), (7, 4, 11)]);
+    assert!(edits
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|edit| edit["newText"] == "plus_one"));
+
+    // Keywords are not valid names.
+    let response = client.respond(
+        "textDocument/rename",
+        position_params(7, 6, json!({ "newName": "fn" })),
+    );
+
+    assert_eq!(response["error"]["code"], json!(-32602), "{}", response);
+    assert_eq!(response["result"], Value::Null);
+}
+
+#[test]
+fn test_rename_commented() {
+    let mut client = Client::start();
+
+    let source = r#"/// Calls `add_one` // twice.
+fn add_one(n) { n + 1 } // add_one
+
+pub fn main() {
+    add_one(add_one(1)) // add_one
+}
+"#;
+
+    assert_eq!(client.open(source), json!([]));
+
+    let edit = client.position(
+        "textDocument/rename",
+        4,
+        6,
+        json!({ "newName": "plus_one" }),
+    );
+
+    assert_eq!(
+        ranges(&edit["changes"][URI]),
+        [(1, 3, 10), (4, 4, 11), (4, 12, 19)]
+    );
+}
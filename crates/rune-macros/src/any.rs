@@ -124,6 +124,42 @@ pub(crate) fn expand_install_with(
         }
     }
 
+    let protocol = &tokens.protocol;
+
+    if attrs.eq {
+        installers.push(quote_spanned! { input.span() =>
+            module.inst_fn(#protocol::EQ, |this: &Self, other: &Self| {
+                ::std::cmp::PartialEq::eq(this, other)
+            })?;
+        });
+    }
+
+    if attrs.hash {
+        installers.push(quote_spanned! { input.span() =>
+            module.inst_fn(#protocol::HASH, |this: &Self| {
+                let mut hasher = ::std::collections::hash_map::DefaultHasher::new();
+                ::std::hash::Hash::hash(this, &mut hasher);
+                ::std::hash::Hasher::finish(&hasher) as i64
+            })?;
+        });
+    }
+
+    if attrs.cmp {
+        installers.push(quote_spanned! { input.span() =>
+            module.inst_fn(#protocol::CMP, |this: &Self, other: &Self| {
+                ::std::cmp::Ord::cmp(this, other)
+            })?;
+        });
+    }
+
+    if attrs.partial_cmp {
+        installers.push(quote_spanned! { input.span() =>
+            module.inst_fn(#protocol::PARTIAL_CMP, |this: &Self, other: &Self| {
+                ::std::cmp::PartialOrd::partial_cmp(this, other)
+            })?;
+        });
+    }
+
     if let Some(install_with) = &attrs.install_with {
         installers.push(quote_spanned! { input.span() =>
             #install_with(module)?;
@@ -421,8 +457,6 @@ where
     Ok(quote! {
         impl #impl_generics #any for #ident #ty_generics #where_clause {
             fn type_hash() -> #hash {
-                // Safety: `Hash` asserts that it is layout compatible with `TypeId`.
-                // TODO: remove this once we can have transmute-like functionality in a const fn.
                 #hash::from_type_id(std::any::TypeId::of::<Self>())
             }
         }
@@ -69,6 +69,15 @@ pub(crate) struct TypeAttrs {
     pub(crate) install_with: Option<syn::Path>,
     /// `#[rune(parse = "..")]` type attribute.
     pub(crate) parse: ParseKind,
+    /// `#[rune(eq)]` to implement `Protocol::EQ` through `PartialEq`.
+    pub(crate) eq: bool,
+    /// `#[rune(hash)]` to implement `Protocol::HASH` through `Hash`.
+    pub(crate) hash: bool,
+    /// `#[rune(cmp)]` to implement `Protocol::CMP` through `Ord`.
+    pub(crate) cmp: bool,
+    /// `#[rune(partial_cmp)]` to implement `Protocol::PARTIAL_CMP` through
+    /// `PartialOrd`.
+    pub(crate) partial_cmp: bool,
 }
 
 /// Parsed variant attributes.
@@ -493,6 +502,22 @@ impl Context {
 
                         attrs.install_with = Some(install_with);
                     }
+                    // Parse `#[rune(eq)]`.
+                    Meta(Path(path)) if path == EQ => {
+                        attrs.eq = true;
+                    }
+                    // Parse `#[rune(hash)]`.
+                    Meta(Path(path)) if path == HASH => {
+                        attrs.hash = true;
+                    }
+                    // Parse `#[rune(cmp)]`.
+                    Meta(Path(path)) if path == CMP => {
+                        attrs.cmp = true;
+                    }
+                    // Parse `#[rune(partial_cmp)]`.
+                    Meta(Path(path)) if path == PARTIAL_CMP => {
+                        attrs.partial_cmp = true;
+                    }
                     meta => {
                         self.errors
                             .push(syn::Error::new_spanned(meta, "unsupported type attribute"));
@@ -20,26 +20,442 @@
 //! # }
 //! ```
 //!
+//! To restrict scripts to a single directory, install the module with
+//! [module_with_root] instead. All paths are then resolved relative to the
+//! root, and any path which would escape it results in a permission denied
+//! error:
+//!
+//! ```rust
+//! # fn main() -> rune::Result<()> {
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(&rune_modules::fs::module_with_root("/srv/scripts")?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! Use it in Rune:
 //!
 //! ```rust,ignore
 //! fn main() {
 //!     let file = fs::read_to_string("file.txt").await?;
 //!     println(`{file}`);
+//!
+//!     let dir = fs::read_dir(".").await?;
+//!
+//!     while let Some(entry) = dir.next().await? {
+//!         println(`{entry.path()}`);
+//!     }
 //! }
 //! ```
 
+use rune::runtime::{Bytes, Value, VmError};
+use rune::{Any, ContextError, Module};
+use std::collections::VecDeque;
 use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
 use tokio::fs;
-use rune::{Module, ContextError};
+use tokio::io::AsyncWriteExt;
 
 /// Construct the `fs` module.
 pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    install(Root { root: None })
+}
+
+/// Construct the `fs` module, restricted to the given root directory.
+///
+/// Relative and absolute paths used by scripts are both resolved relative to
+/// `root`. Paths which escape the root, either through `..` components or
+/// through symbolic links, are rejected.
+pub fn module_with_root<P>(root: P) -> Result<Module, ContextError>
+where
+    P: Into<PathBuf>,
+{
+    install(Root {
+        root: Some(root.into()),
+    })
+}
+
+/// Register an async function which resolves its paths through the given
+/// root.
+macro_rules! function {
+    ($module:ident, $root:ident, $name:literal, $f:ident($($arg:ident: $ty:ty),*)) => {{
+        let root = $root.clone();
+        $module.async_function([$name], move |$($arg: $ty),*| $f(root.clone(), $($arg),*))?;
+    }};
+}
+
+fn install(root: Root) -> Result<Module, ContextError> {
+    let root = Arc::new(root);
+
     let mut module = Module::with_crate("fs");
-    module.async_function(["read_to_string"], read_to_string)?;
+    module.ty::<Metadata>()?;
+    module.ty::<ReadDir>()?;
+    module.ty::<DirEntry>()?;
+
+    function!(module, root, "read_to_string", read_to_string(path: String));
+    function!(module, root, "read", read(path: String));
+    function!(module, root, "write", write(path: String, contents: Value));
+    function!(module, root, "append", append(path: String, contents: Value));
+    function!(module, root, "create_dir", create_dir(path: String));
+    function!(module, root, "create_dir_all", create_dir_all(path: String));
+    function!(module, root, "remove_file", remove_file(path: String));
+    function!(module, root, "remove_dir", remove_dir(path: String));
+    function!(module, root, "remove_dir_all", remove_dir_all(path: String));
+    function!(module, root, "rename", rename(from: String, to: String));
+    function!(module, root, "copy", copy(from: String, to: String));
+    function!(module, root, "metadata", metadata(path: String));
+    function!(module, root, "exists", exists(path: String));
+    function!(module, root, "read_dir", read_dir(path: String));
+
+    module.inst_fn("len", Metadata::len)?;
+    module.inst_fn("is_dir", Metadata::is_dir)?;
+    module.inst_fn("is_file", Metadata::is_file)?;
+    module.inst_fn("is_symlink", Metadata::is_symlink)?;
+    module.inst_fn("modified", Metadata::modified)?;
+
+    module.async_inst_fn("next", ReadDir::next)?;
+
+    module.inst_fn("path", DirEntry::path)?;
+    module.inst_fn("file_name", DirEntry::file_name)?;
+    module.async_inst_fn("metadata", DirEntry::metadata)?;
     Ok(module)
 }
 
-async fn read_to_string(path: &str) -> io::Result<String> {
-    fs::read_to_string(path).await
+/// The root which all paths are resolved against, if any.
+struct Root {
+    root: Option<PathBuf>,
+}
+
+/// The maximum number of symbolic links followed while resolving a path.
+const MAX_SYMLINKS: usize = 40;
+
+impl Root {
+    /// Resolve a path provided by a script into a path on the host, following
+    /// all symbolic links.
+    async fn resolve(&self, path: &str) -> io::Result<PathBuf> {
+        self.resolve_with(path, true).await
+    }
+
+    /// Resolve a path provided by a script into a path on the host, without
+    /// following the final component if it is a symbolic link.
+    ///
+    /// This is used for operations which act on the link itself, like
+    /// removing or renaming it.
+    async fn resolve_link(&self, path: &str) -> io::Result<PathBuf> {
+        self.resolve_with(path, false).await
+    }
+
+    async fn resolve_with(&self, path: &str, follow_last: bool) -> io::Result<PathBuf> {
+        let root = match &self.root {
+            Some(root) => fs::canonicalize(root).await?,
+            None => return Ok(PathBuf::from(path)),
+        };
+
+        let mut remaining = VecDeque::new();
+
+        for component in Path::new(path).components() {
+            match component {
+                Component::Prefix(..) | Component::RootDir | Component::CurDir => (),
+                Component::ParentDir => {
+                    if remaining.pop_back().is_none() {
+                        return Err(escapes_root());
+                    }
+                }
+                Component::Normal(name) => remaining.push_back(name.to_owned()),
+            }
+        }
+
+        // NB: the path is resolved one component at a time without following
+        // symbolic links, so that every link can be checked before it is
+        // followed. The resolved path only contains links in components which
+        // don't exist yet, and those can't escape the root.
+        let mut current = root.clone();
+        let mut links = 0;
+
+        while let Some(name) = remaining.pop_front() {
+            let candidate = current.join(&name);
+
+            if remaining.is_empty() && !follow_last {
+                current = candidate;
+                break;
+            }
+
+            let metadata = match fs::symlink_metadata(&candidate).await {
+                Ok(metadata) => metadata,
+                Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                    current = candidate;
+                    current.extend(remaining.drain(..));
+                    break;
+                }
+                Err(error) => return Err(error),
+            };
+
+            if !metadata.file_type().is_symlink() {
+                current = candidate;
+                continue;
+            }
+
+            links += 1;
+
+            if links > MAX_SYMLINKS {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "too many levels of symbolic links",
+                ));
+            }
+
+            let target = lexical_join(&current, &fs::read_link(&candidate).await?);
+
+            // A link is only followed if its target can be proven to stay
+            // inside of the root, in which case resolution restarts from the
+            // root with the components of the target.
+            let target = match target.strip_prefix(&root) {
+                Ok(target) => target,
+                Err(..) => return Err(escapes_root()),
+            };
+
+            for component in target.components().rev() {
+                if let Component::Normal(name) = component {
+                    remaining.push_front(name.to_owned());
+                }
+            }
+
+            current = root.clone();
+        }
+
+        Ok(current)
+    }
+}
+
+/// Join the target of a symbolic link onto the directory containing it,
+/// resolving `.` and `..` components lexically.
+fn lexical_join(dir: &Path, target: &Path) -> PathBuf {
+    let mut path = dir.to_owned();
+
+    for component in target.components() {
+        match component {
+            Component::Prefix(prefix) => path = PathBuf::from(prefix.as_os_str()),
+            Component::RootDir => path.push(Component::RootDir.as_os_str()),
+            Component::CurDir => (),
+            Component::ParentDir => {
+                path.pop();
+            }
+            Component::Normal(name) => path.push(name),
+        }
+    }
+
+    path
+}
+
+fn escapes_root() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        "path escapes the filesystem root",
+    )
+}
+
+/// Convert the contents being written into bytes.
+fn contents_to_bytes(contents: Value) -> Result<Vec<u8>, VmError> {
+    Ok(match contents {
+        Value::String(s) => s.borrow_ref()?.as_bytes().to_vec(),
+        Value::StaticString(s) => s.as_bytes().to_vec(),
+        Value::Bytes(bytes) => bytes.borrow_ref()?.to_vec(),
+        actual => return Err(VmError::expected::<String>(actual.type_info()?)),
+    })
+}
+
+async fn read_to_string(root: Arc<Root>, path: String) -> io::Result<String> {
+    fs::read_to_string(root.resolve(&path).await?).await
+}
+
+async fn read(root: Arc<Root>, path: String) -> io::Result<Bytes> {
+    let bytes = fs::read(root.resolve(&path).await?).await?;
+    Ok(Bytes::from_vec(bytes))
+}
+
+async fn write(root: Arc<Root>, path: String, contents: Value) -> Result<io::Result<()>, VmError> {
+    let contents = contents_to_bytes(contents)?;
+
+    Ok(async move {
+        let path = root.resolve(&path).await?;
+        fs::write(path, contents).await
+    }
+    .await)
+}
+
+async fn append(
+    root: Arc<Root>,
+    path: String,
+    contents: Value,
+) -> Result<io::Result<()>, VmError> {
+    let contents = contents_to_bytes(contents)?;
+
+    Ok(async move {
+        let path = root.resolve(&path).await?;
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+
+        file.write_all(&contents).await?;
+        file.flush().await
+    }
+    .await)
+}
+
+async fn create_dir(root: Arc<Root>, path: String) -> io::Result<()> {
+    fs::create_dir(root.resolve(&path).await?).await
+}
+
+async fn create_dir_all(root: Arc<Root>, path: String) -> io::Result<()> {
+    fs::create_dir_all(root.resolve(&path).await?).await
+}
+
+async fn remove_file(root: Arc<Root>, path: String) -> io::Result<()> {
+    fs::remove_file(root.resolve_link(&path).await?).await
+}
+
+async fn remove_dir(root: Arc<Root>, path: String) -> io::Result<()> {
+    fs::remove_dir(root.resolve_link(&path).await?).await
+}
+
+async fn remove_dir_all(root: Arc<Root>, path: String) -> io::Result<()> {
+    fs::remove_dir_all(root.resolve_link(&path).await?).await
+}
+
+async fn rename(root: Arc<Root>, from: String, to: String) -> io::Result<()> {
+    fs::rename(root.resolve_link(&from).await?, root.resolve_link(&to).await?).await
+}
+
+/// Copy the contents of one file to another, returning the number of bytes
+/// copied.
+async fn copy(root: Arc<Root>, from: String, to: String) -> io::Result<u64> {
+    fs::copy(root.resolve(&from).await?, root.resolve(&to).await?).await
+}
+
+/// Get the metadata of the given path.
+///
+/// Symbolic links are followed, but whether the path itself is a link is
+/// reported by `is_symlink`.
+async fn metadata(root: Arc<Root>, path: String) -> io::Result<Metadata> {
+    let link = fs::symlink_metadata(root.resolve_link(&path).await?).await?;
+    let inner = fs::metadata(root.resolve(&path).await?).await?;
+
+    Ok(Metadata {
+        inner,
+        symlink: link.file_type().is_symlink(),
+    })
+}
+
+/// Test if the given path exists.
+async fn exists(root: Arc<Root>, path: String) -> io::Result<bool> {
+    match fs::metadata(root.resolve(&path).await?).await {
+        Ok(..) => Ok(true),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(error) => Err(error),
+    }
+}
+
+async fn read_dir(root: Arc<Root>, path: String) -> io::Result<ReadDir> {
+    let inner = fs::read_dir(root.resolve(&path).await?).await?;
+
+    Ok(ReadDir {
+        inner,
+        path: PathBuf::from(path),
+    })
+}
+
+/// Metadata information about a file.
+#[derive(Any)]
+struct Metadata {
+    inner: std::fs::Metadata,
+    /// If the metadata was looked up through a symbolic link.
+    symlink: bool,
+}
+
+impl Metadata {
+    /// The size of the file in bytes.
+    fn len(&self) -> u64 {
+        self.inner.len()
+    }
+
+    fn is_dir(&self) -> bool {
+        self.inner.is_dir()
+    }
+
+    fn is_file(&self) -> bool {
+        self.inner.is_file()
+    }
+
+    fn is_symlink(&self) -> bool {
+        self.symlink
+    }
+
+    /// The last modification time, as seconds since the UNIX epoch.
+    fn modified(&self) -> io::Result<f64> {
+        let modified = self.inner.modified()?;
+
+        match modified.duration_since(UNIX_EPOCH) {
+            Ok(duration) => Ok(duration.as_secs_f64()),
+            Err(error) => Ok(-error.duration().as_secs_f64()),
+        }
+    }
+}
+
+/// An asynchronous stream over the entries in a directory.
+#[derive(Any)]
+struct ReadDir {
+    inner: fs::ReadDir,
+    /// The path of the directory, as provided by the script.
+    path: PathBuf,
+}
+
+impl ReadDir {
+    /// Get the next entry in the directory, or `None` if there are no more
+    /// entries.
+    async fn next(&mut self) -> io::Result<Option<DirEntry>> {
+        let inner = match self.inner.next_entry().await? {
+            Some(inner) => inner,
+            None => return Ok(None),
+        };
+
+        Ok(Some(DirEntry {
+            path: self.path.join(inner.file_name()),
+            inner,
+        }))
+    }
+}
+
+/// An entry inside of a directory.
+#[derive(Any)]
+struct DirEntry {
+    inner: fs::DirEntry,
+    /// The path of the entry, relative to the path passed to `read_dir`.
+    path: PathBuf,
+}
+
+impl DirEntry {
+    fn path(&self) -> String {
+        self.path.to_string_lossy().into_owned()
+    }
+
+    fn file_name(&self) -> String {
+        self.inner.file_name().to_string_lossy().into_owned()
+    }
+
+    /// Get the metadata of the entry.
+    ///
+    /// Like `DirEntry::metadata` in Rust, this doesn't follow symbolic links.
+    async fn metadata(&self) -> io::Result<Metadata> {
+        let inner = self.inner.metadata().await?;
+
+        Ok(Metadata {
+            symlink: inner.file_type().is_symlink(),
+            inner,
+        })
+    }
 }
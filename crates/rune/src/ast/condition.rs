@@ -9,11 +9,13 @@ use crate::ast::prelude::*;
 ///
 /// testing::roundtrip::<ast::Condition>("true");
 /// testing::roundtrip::<ast::Condition>("let [a, ..] = v");
+/// testing::roundtrip::<ast::Condition>("let Some(a) = v && a > 1 && let Some(b) = w");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
 #[non_exhaustive]
 pub enum Condition {
-    /// A regular expression.
+    /// A regular expression, which might be a `&&`-chain containing `let`
+    /// expressions.
     Expr(ast::Expr),
     /// A pattern match.
     ExprLet(ast::ExprLet),
@@ -21,9 +23,9 @@ pub enum Condition {
 
 impl Parse for Condition {
     fn parse(p: &mut Parser) -> Result<Self, ParseError> {
-        Ok(match p.nth(0)? {
-            K![let] => Self::ExprLet(ast::ExprLet::parse_without_eager_brace(p)?),
-            _ => Self::Expr(ast::Expr::parse_without_eager_brace(p)?),
+        Ok(match ast::Expr::parse_without_eager_brace(p)? {
+            ast::Expr::Let(expr_let) => Self::ExprLet(expr_let),
+            expr => Self::Expr(expr),
         })
     }
 }
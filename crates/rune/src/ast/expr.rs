@@ -229,33 +229,6 @@ impl Expr {
         }
     }
 
-    /// Check if this expression is a literal expression.
-    ///
-    /// There are exactly two kinds of literal expressions:
-    /// * Ones that are ExprLit
-    /// * Unary expressions which are the negate operation.
-    pub(crate) fn is_lit(&self) -> bool {
-        match self {
-            Self::Lit(..) => return true,
-            Self::Unary(ast::ExprUnary {
-                op: ast::UnOp::Neg(..),
-                expr,
-                ..
-            }) => {
-                return matches!(
-                    &**expr,
-                    Self::Lit(ast::ExprLit {
-                        lit: ast::Lit::Number(..),
-                        ..
-                    })
-                );
-            }
-            _ => (),
-        }
-
-        false
-    }
-
     /// Internal function to construct a literal expression.
     pub(crate) fn from_lit(lit: ast::Lit) -> Self {
         Self::Lit(ast::ExprLit {
@@ -281,6 +254,16 @@ impl Expr {
         Self::parse_with(p, NOT_EAGER_BRACE, EAGER_BINARY, CALLABLE)
     }
 
+    /// Parse the scrutinee of a `let` expression.
+    ///
+    /// This binds tighter than lazy boolean operators, so that `let` can be
+    /// chained with `&&` like `let Some(a) = b && c`.
+    pub(crate) fn parse_let_scrutinee(p: &mut Parser<'_>) -> Result<Self, ParseError> {
+        let lhs = primary(p, &mut vec![], NOT_EAGER_BRACE, CALLABLE)?;
+        let lookahead = ast::BinOp::from_peeker(p.peeker());
+        binary(p, lhs, lookahead, LET_SCRUTINEE_PRECEDENCE, NOT_EAGER_BRACE)
+    }
+
     /// Helper to perform a parse with the given meta.
     pub(crate) fn parse_with_meta(
         p: &mut Parser<'_>,
@@ -432,6 +415,10 @@ impl Peek for Expr {
     }
 }
 
+/// The minimum precedence of binary operators in a `let` scrutinee, which
+/// excludes `&&` and `||`.
+const LET_SCRUTINEE_PRECEDENCE: usize = 5;
+
 /// Primary parse entry point.
 fn primary(
     p: &mut Parser<'_>,
@@ -37,18 +37,7 @@ impl ExprLet {
             let_token: parser.parse()?,
             pat: parser.parse()?,
             eq: parser.parse()?,
-            expr: Box::new(ast::Expr::parse_without_eager_brace(parser)?),
-        })
-    }
-
-    /// Parse a let expression without eager bracing.
-    pub(crate) fn parse_without_eager_brace(parser: &mut Parser) -> Result<Self, ParseError> {
-        Ok(Self {
-            attributes: vec![],
-            let_token: parser.parse()?,
-            pat: parser.parse()?,
-            eq: parser.parse()?,
-            expr: Box::new(ast::Expr::parse_without_eager_brace(parser)?),
+            expr: Box::new(ast::Expr::parse_let_scrutinee(parser)?),
         })
     }
 }
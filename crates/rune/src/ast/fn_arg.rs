@@ -10,6 +10,8 @@ use crate::ast::prelude::*;
 /// testing::roundtrip::<ast::FnArg>("self");
 /// testing::roundtrip::<ast::FnArg>("_");
 /// testing::roundtrip::<ast::FnArg>("abc");
+/// testing::roundtrip::<ast::FnArg>("abc: int");
+/// testing::roundtrip::<ast::FnArg>("(a, b): (int, int)");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
 #[non_exhaustive]
@@ -18,13 +20,39 @@ pub enum FnArg {
     SelfValue(T![self]),
     /// Function argument is a pattern binding.
     Pat(ast::Pat),
+    /// Function argument is a pattern binding with a type annotation.
+    Typed(FnArgTyped),
 }
 
 impl Parse for FnArg {
     fn parse(p: &mut Parser<'_>) -> Result<Self, ParseError> {
         Ok(match p.nth(0)? {
             K![self] => Self::SelfValue(p.parse()?),
-            _ => Self::Pat(p.parse()?),
+            _ => {
+                let pat = ast::Pat::parse_without_or(p)?;
+
+                if p.peek::<T![:]>()? {
+                    Self::Typed(FnArgTyped {
+                        pat,
+                        colon: p.parse()?,
+                        ty: p.parse()?,
+                    })
+                } else {
+                    Self::Pat(pat)
+                }
+            }
         })
     }
 }
+
+/// A function argument with a type annotation, like `a: int`.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct FnArgTyped {
+    /// The pattern of the argument.
+    pub pat: ast::Pat,
+    /// The `:` token.
+    pub colon: T![:],
+    /// The type of the argument.
+    pub ty: ast::Type,
+}
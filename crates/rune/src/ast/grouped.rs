@@ -118,18 +118,21 @@ macro_rules! grouped {
             }
         }
 
-        impl<T, S> Parse for $name<T, S>
+        impl<T, S> $name<T, S>
         where
-            T: Parse,
             S: Peek + Parse,
         {
-            fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
+            /// Parse the group using a custom parser for each element.
+            pub(crate) fn parse_with(
+                parser: &mut Parser<'_>,
+                mut parse: impl FnMut(&mut Parser<'_>) -> Result<T, ParseError>,
+            ) -> Result<Self, ParseError> {
                 let open = parser.parse()?;
 
                 let mut $field = Vec::new();
 
                 while !parser.peek::<$close>()? {
-                    let expr = parser.parse()?;
+                    let expr = parse(parser)?;
                     let sep = parser.parse::<Option<S>>()?;
                     let is_end = sep.is_none();
                     $field.push((expr, sep));
@@ -149,6 +152,16 @@ macro_rules! grouped {
             }
         }
 
+        impl<T, S> Parse for $name<T, S>
+        where
+            T: Parse,
+            S: Peek + Parse,
+        {
+            fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
+                Self::parse_with(parser, T::parse)
+            }
+        }
+
         impl<T, S> Peek for $name<T, S> {
             fn peek(p: &mut Peeker<'_>) -> bool {
                 <$open>::peek(p)
@@ -11,6 +11,8 @@ use crate::ast::prelude::*;
 /// testing::roundtrip::<ast::ItemImpl>("impl Foo { fn test(self) { } }");
 /// testing::roundtrip::<ast::ItemImpl>("#[variant(enum_= \"SuperHero\", x = \"1\")] impl Foo { fn test(self) { } }");
 /// testing::roundtrip::<ast::ItemImpl>("#[xyz] impl Foo { #[jit] fn test(self) { } }");
+/// testing::roundtrip::<ast::ItemImpl>("impl Bar for Foo { fn test(self) { } }");
+/// testing::roundtrip::<ast::ItemImpl>("impl bar::Bar for std::string::String {}");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
 #[non_exhaustive]
@@ -20,6 +22,9 @@ pub struct ItemImpl {
     pub attributes: Vec<ast::Attribute>,
     /// The `impl` keyword.
     pub impl_: T![impl],
+    /// The trait being implemented, followed by the `for` keyword.
+    #[rune(iter)]
+    pub trait_: Option<(ast::Path, T![for])>,
     /// Path of the implementation.
     pub path: ast::Path,
     /// The open brace.
@@ -37,7 +42,15 @@ impl ItemImpl {
         attributes: Vec<ast::Attribute>,
     ) -> Result<Self, ParseError> {
         let impl_ = parser.parse()?;
-        let path = parser.parse()?;
+        let mut path = parser.parse()?;
+
+        let trait_ = if parser.peek::<T![for]>()? {
+            let for_ = parser.parse()?;
+            Some((std::mem::replace(&mut path, parser.parse()?), for_))
+        } else {
+            None
+        };
+
         let open = parser.parse()?;
 
         let mut functions = vec![];
@@ -51,6 +64,7 @@ impl ItemImpl {
         Ok(Self {
             attributes,
             impl_,
+            trait_,
             path,
             open,
             functions,
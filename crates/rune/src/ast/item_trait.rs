@@ -0,0 +1,147 @@
+use crate::ast::prelude::*;
+
+/// A trait item.
+///
+/// # Examples
+///
+/// ```
+/// use rune::{ast, testing};
+///
+/// testing::roundtrip::<ast::ItemTrait>("trait Foo {}");
+/// testing::roundtrip::<ast::ItemTrait>("trait Foo { fn bar(self); }");
+/// testing::roundtrip::<ast::ItemTrait>("pub trait Foo { fn bar(self, a); fn baz(self) { self.bar(1) } }");
+/// testing::roundtrip::<ast::ItemTrait>("#[doc = \"x\"] trait Foo { #[doc = \"y\"] async fn bar(self); }");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct ItemTrait {
+    /// The attributes of the trait.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The visibility of the trait.
+    #[rune(optional)]
+    pub visibility: ast::Visibility,
+    /// The `trait` keyword.
+    pub trait_token: T![trait],
+    /// The name of the trait.
+    pub ident: ast::Ident,
+    /// The open brace.
+    pub open: T!['{'],
+    /// The functions of the trait.
+    pub functions: Vec<ItemTraitFn>,
+    /// The close brace.
+    pub close: T!['}'],
+}
+
+impl ItemTrait {
+    /// Parse a `trait` item with the given attributes and visibility.
+    pub(crate) fn parse_with_meta(
+        p: &mut Parser<'_>,
+        attributes: Vec<ast::Attribute>,
+        visibility: ast::Visibility,
+    ) -> Result<Self, ParseError> {
+        let trait_token = p.parse()?;
+        let ident = p.parse()?;
+        let open = p.parse()?;
+
+        let mut functions = Vec::new();
+
+        while !p.peek::<ast::CloseBrace>()? {
+            functions.push(p.parse()?);
+        }
+
+        let close = p.parse()?;
+
+        Ok(Self {
+            attributes,
+            visibility,
+            trait_token,
+            ident,
+            open,
+            functions,
+            close,
+        })
+    }
+}
+
+item_parse!(Trait, ItemTrait, "trait item");
+
+/// A function in a trait.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub enum ItemTraitFn {
+    /// A function which every implementation has to provide, like
+    /// `fn bar(self);`.
+    Required(ItemTraitFnDecl),
+    /// A function with a default body, which is used by implementations that
+    /// don't provide their own.
+    Provided(ast::ItemFn),
+}
+
+impl ItemTraitFn {
+    /// The name of the function.
+    pub fn name(&self) -> &ast::Ident {
+        match self {
+            Self::Required(f) => &f.name,
+            Self::Provided(f) => &f.name,
+        }
+    }
+}
+
+impl Parse for ItemTraitFn {
+    fn parse(p: &mut Parser<'_>) -> Result<Self, ParseError> {
+        let attributes = p.parse()?;
+        let async_token = p.parse()?;
+        let fn_token = p.parse()?;
+        let name = p.parse()?;
+        let args = p.parse()?;
+        let output = p.parse()?;
+
+        Ok(match p.nth(0)? {
+            K![;] => Self::Required(ItemTraitFnDecl {
+                attributes,
+                async_token,
+                fn_token,
+                name,
+                args,
+                output,
+                semi_colon: p.parse()?,
+            }),
+            _ => Self::Provided(ast::ItemFn {
+                id: Default::default(),
+                attributes,
+                visibility: ast::Visibility::Inherited,
+                const_token: None,
+                async_token,
+                fn_token,
+                name,
+                args,
+                output,
+                body: p.parse()?,
+            }),
+        })
+    }
+}
+
+/// The declaration of a required function in a trait.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct ItemTraitFnDecl {
+    /// The attributes of the function.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The optional `async` keyword.
+    #[rune(iter)]
+    pub async_token: Option<T![async]>,
+    /// The `fn` token.
+    pub fn_token: T![fn],
+    /// The name of the function.
+    pub name: ast::Ident,
+    /// The arguments of the function.
+    pub args: ast::Parenthesized<ast::FnArg, T![,]>,
+    /// The optional return type of the function.
+    #[rune(iter)]
+    pub output: Option<(T![->], ast::Type)>,
+    /// The terminating semicolon.
+    pub semi_colon: T![;],
+}
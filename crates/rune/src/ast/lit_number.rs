@@ -24,6 +24,8 @@ pub struct LitNumber {
 /// testing::roundtrip::<ast::LitNumber>("42.42");
 /// testing::roundtrip::<ast::LitNumber>("0.42");
 /// testing::roundtrip::<ast::LitNumber>("0.42e10");
+/// testing::roundtrip::<ast::LitNumber>("10u32");
+/// testing::roundtrip::<ast::LitNumber>("0xffu64");
 /// ```
 impl Parse for LitNumber {
     fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
@@ -78,8 +80,33 @@ impl<'a> Resolve<'a> for LitNumber {
             ast::NumberBase::Decimal => (0, 10),
         };
 
-        let number = num::BigInt::from_str_radix(&string[s..], radix).map_err(err_span(span))?;
-        return Ok(ast::Number::Integer(number));
+        let string = &string[s..];
+
+        // NB: type suffixes can't be confused with digits, since neither `u`
+        // nor `i` are valid digits in any base.
+        let (string, suffix) = match string.find(['u', 'i']) {
+            Some(n) => {
+                let suffix = match &string[n..] {
+                    "u8" => Some(ast::NumberSuffix::U8),
+                    "u16" => Some(ast::NumberSuffix::U16),
+                    "u32" => Some(ast::NumberSuffix::U32),
+                    "u64" => Some(ast::NumberSuffix::U64),
+                    "i32" => Some(ast::NumberSuffix::I32),
+                    "i64" => None,
+                    _ => return Err(ResolveError::new(span, ResolveErrorKind::BadNumberLiteral)),
+                };
+
+                (&string[..n], suffix)
+            }
+            None => (string, None),
+        };
+
+        let number = num::BigInt::from_str_radix(string, radix).map_err(err_span(span))?;
+
+        return Ok(match suffix {
+            Some(suffix) => ast::Number::Sized(number, suffix),
+            None => ast::Number::Integer(number),
+        });
 
         fn err_span<E>(span: Span) -> impl Fn(E) -> ResolveError {
             move |_| ResolveError::new(span, ResolveErrorKind::BadNumberLiteral)
@@ -1,6 +1,7 @@
 use crate::ast::prelude::*;
 
-/// A local variable declaration `let <pattern> = <expr>;`
+/// A local variable declaration `let <pattern> = <expr>;`, optionally followed
+/// by a diverging `else` block if the pattern is refutable.
 ///
 /// # Examples
 ///
@@ -8,8 +9,10 @@ use crate::ast::prelude::*;
 /// use rune::{ast, testing};
 ///
 /// testing::roundtrip::<ast::Local>("let x = 1;");
+/// testing::roundtrip::<ast::Local>("let x: int = 1;");
 /// testing::roundtrip::<ast::Local>("#[attr] let a = f();");
 /// testing::roundtrip::<ast::Local>("let a = b{}().foo[0].await;");
+/// testing::roundtrip::<ast::Local>("let Some(a) = b else { return; };");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Parse, Spanned)]
 #[non_exhaustive]
@@ -21,11 +24,17 @@ pub struct Local {
     pub let_token: T![let],
     /// The name of the binding.
     pub pat: ast::Pat,
+    /// The optional type annotation of the binding.
+    #[rune(iter)]
+    pub ty: Option<(T![:], ast::Type)>,
     /// The equality keyword.
     pub eq: T![=],
     /// The expression the binding is assigned to.
     #[rune(parse_with = "parse_expr")]
     pub expr: ast::Expr,
+    /// The `else` block to run if the pattern doesn't match.
+    #[rune(iter)]
+    pub expr_else: Option<ast::ExprElse>,
     /// Trailing semicolon of the local.
     pub semi: T![;],
 }
@@ -133,6 +133,7 @@ mod item_fn;
 mod item_impl;
 mod item_mod;
 mod item_struct;
+mod item_trait;
 mod item_use;
 mod label;
 mod lit;
@@ -152,6 +153,7 @@ mod spanned;
 mod spanned_error;
 mod stmt;
 mod token;
+mod ty;
 pub(super) mod utils;
 mod vis;
 
@@ -188,7 +190,7 @@ pub use self::expr_vec::ExprVec;
 pub use self::expr_while::ExprWhile;
 pub use self::expr_yield::ExprYield;
 pub use self::file::{File, Shebang};
-pub use self::fn_arg::FnArg;
+pub use self::fn_arg::{FnArg, FnArgTyped};
 pub use self::grouped::{AngleBracketed, Braced, Bracketed, Parenthesized};
 pub use self::ident::Ident;
 pub use self::item::Item;
@@ -198,6 +200,7 @@ pub use self::item_fn::ItemFn;
 pub use self::item_impl::ItemImpl;
 pub use self::item_mod::{ItemInlineBody, ItemMod, ItemModBody};
 pub use self::item_struct::{Field, ItemStruct, ItemStructBody};
+pub use self::item_trait::{ItemTrait, ItemTraitFn, ItemTraitFnDecl};
 pub use self::item_use::{ItemUse, ItemUsePath, ItemUseSegment};
 pub use self::label::Label;
 pub use self::lit::Lit;
@@ -209,7 +212,9 @@ pub use self::lit_number::LitNumber;
 pub use self::lit_str::LitStr;
 pub use self::local::Local;
 pub use self::macro_call::MacroCall;
-pub use self::pat::{Pat, PatBinding, PatLit, PatObject, PatPath, PatTuple, PatVec};
+pub use self::pat::{
+    Pat, PatBinding, PatLit, PatObject, PatOr, PatPath, PatRange, PatTuple, PatVec,
+};
 pub use self::path::{Path, PathKind, PathSegment, PathSegmentExpr};
 pub use self::span::{ByteIndex, Span};
 pub use self::spanned::{OptionSpanned, Spanned};
@@ -217,9 +222,10 @@ pub use self::spanned_error::SpannedError;
 pub(crate) use self::spanned_error::WithSpan;
 pub use self::stmt::{ItemOrExpr, Stmt, StmtSemi, StmtSortKey};
 pub use self::token::{
-    BuiltIn, CopySource, Delimiter, LitSource, Number, NumberBase, NumberSource, NumberText,
-    StrSource, StrText, Token,
+    BuiltIn, CopySource, Delimiter, LitSource, Number, NumberBase, NumberSource, NumberSuffix,
+    NumberText, StrSource, StrText, Token,
 };
+pub use self::ty::Type;
 pub use self::vis::Visibility;
 
 macro_rules! decl_tokens {
@@ -20,6 +20,10 @@ pub enum Pat {
     PatBinding(PatBinding),
     /// The rest pattern `..`.
     PatRest(PatRest),
+    /// An or-pattern `a | b`.
+    PatOr(PatOr),
+    /// A range pattern `a..=b`.
+    PatRange(PatRange),
 }
 
 /// Parsing a block expression.
@@ -41,23 +45,71 @@ pub enum Pat {
 /// testing::roundtrip::<ast::Pat>("var");
 /// testing::roundtrip::<ast::Pat>("_");
 /// testing::roundtrip::<ast::Pat>("Foo(n)");
+/// testing::roundtrip::<ast::Pat>("1 | 2 | 3");
+/// testing::roundtrip::<ast::Pat>("(a, 1) | (1, a)");
+/// testing::roundtrip::<ast::Pat>("1..10");
+/// testing::roundtrip::<ast::Pat>("-10..=-1");
+/// testing::roundtrip::<ast::Pat>("'a'..='z'");
+/// testing::roundtrip::<ast::Pat>("b'0'..=b'9'");
+/// testing::roundtrip::<ast::Pat>("10..");
+/// testing::roundtrip::<ast::Pat>("..=10");
 /// ```
 impl Parse for Pat {
     fn parse(p: &mut Parser<'_>) -> Result<Self, ParseError> {
+        let first = Self::parse_without_or(p)?;
+        Self::parse_or(p, first)
+    }
+}
+
+impl Pat {
+    /// Parse the remaining alternatives of an or-pattern, if any.
+    fn parse_or(p: &mut Parser<'_>, first: Self) -> Result<Self, ParseError> {
+        if !p.peek::<T![|]>()? {
+            return Ok(first);
+        }
+
+        let mut rest = Vec::new();
+
+        while p.peek::<T![|]>()? {
+            rest.push((p.parse()?, Self::parse_without_or(p)?));
+        }
+
+        Ok(Self::PatOr(PatOr {
+            first: Box::new(first),
+            rest,
+        }))
+    }
+
+    /// Parse a pattern which is not an or-pattern, like the arguments of a
+    /// closure where `|` terminates the argument list.
+    pub(crate) fn parse_without_or(p: &mut Parser<'_>) -> Result<Self, ParseError> {
+        Self::parse_inner(p, false)
+    }
+
+    /// Parse a pattern inside of an object pattern, where fields can be bound
+    /// to other patterns like `{ a: b }`.
+    fn parse_object_item(p: &mut Parser<'_>) -> Result<Self, ParseError> {
+        let first = Self::parse_inner(p, true)?;
+        Self::parse_or(p, first)
+    }
+
+    fn parse_inner(p: &mut Parser<'_>, binding: bool) -> Result<Self, ParseError> {
         let attributes = p.parse::<Vec<ast::Attribute>>()?;
 
-        match p.nth(0)? {
-            K![byte] => {
-                return Ok(Self::PatLit(PatLit {
-                    attributes,
-                    expr: Box::new(ast::Expr::from_lit(ast::Lit::Byte(p.parse()?))),
-                }));
+        if let Some(start) = parse_range_bound(p)? {
+            if matches!(p.nth(0)?, K![..] | K![..=]) {
+                return Self::parse_range(p, attributes, Some(start));
             }
-            K![char] => {
-                return Ok(Self::PatLit(PatLit {
-                    attributes,
-                    expr: Box::new(ast::Expr::from_lit(ast::Lit::Char(p.parse()?))),
-                }));
+
+            return Ok(Self::PatLit(PatLit {
+                attributes,
+                expr: Box::new(start),
+            }));
+        }
+
+        match p.nth(0)? {
+            K![..=] => {
+                return Self::parse_range(p, attributes, None);
             }
             K![bytestr] => {
                 return Ok(Self::PatLit(PatLit {
@@ -73,7 +125,7 @@ impl Parse for Pat {
             }
             K![str] => {
                 return Ok(match p.nth(1)? {
-                    K![:] => Self::PatBinding(PatBinding {
+                    K![:] if binding => Self::PatBinding(PatBinding {
                         attributes,
                         key: ast::ObjectKey::LitStr(p.parse()?),
                         colon: p.parse()?,
@@ -85,12 +137,6 @@ impl Parse for Pat {
                     }),
                 });
             }
-            K![number] => {
-                return Ok(Self::PatLit(PatLit {
-                    attributes,
-                    expr: Box::new(ast::Expr::from_lit(ast::Lit::Number(p.parse()?))),
-                }));
-            }
             K![..] => {
                 return Ok(Self::PatRest(PatRest {
                     attributes,
@@ -118,19 +164,9 @@ impl Parse for Pat {
                 return Ok(Self::PatObject(PatObject {
                     attributes,
                     ident: p.parse()?,
-                    items: p.parse()?,
+                    items: ast::Braced::parse_with(p, Self::parse_object_item)?,
                 }))
             }
-            K![-] => {
-                let expr: ast::Expr = p.parse()?;
-
-                if expr.is_lit() {
-                    return Ok(Self::PatLit(PatLit {
-                        attributes,
-                        expr: Box::new(expr),
-                    }));
-                }
-            }
             K![_] => {
                 return Ok(Self::PatIgnore(PatIgnore {
                     attributes,
@@ -149,9 +185,9 @@ impl Parse for Pat {
                     K!['{'] => Self::PatObject(PatObject {
                         attributes,
                         ident: ast::ObjectIdent::Named(path),
-                        items: p.parse()?,
+                        items: ast::Braced::parse_with(p, Self::parse_object_item)?,
                     }),
-                    K![:] => Self::PatBinding(PatBinding {
+                    K![:] if binding => Self::PatBinding(PatBinding {
                         attributes,
                         key: ast::ObjectKey::Path(path),
                         colon: p.parse()?,
@@ -165,6 +201,43 @@ impl Parse for Pat {
 
         Err(ParseError::expected(p.tok_at(0)?, "pattern"))
     }
+
+    /// Parse the remainder of a range pattern after its start.
+    fn parse_range(
+        p: &mut Parser<'_>,
+        attributes: Vec<ast::Attribute>,
+        start: Option<ast::Expr>,
+    ) -> Result<Self, ParseError> {
+        let limits = p.parse()?;
+        let end = parse_range_bound(p)?;
+
+        if start.is_none() && end.is_none() {
+            return Err(ParseError::expected(p.tok_at(0)?, "end of range pattern"));
+        }
+
+        Ok(Self::PatRange(PatRange {
+            attributes,
+            start: start.map(Box::new),
+            limits,
+            end: end.map(Box::new),
+        }))
+    }
+}
+
+/// Parse a literal which can be used as a bound in a range pattern, like a
+/// character or a possibly negated number.
+fn parse_range_bound(p: &mut Parser<'_>) -> Result<Option<ast::Expr>, ParseError> {
+    Ok(Some(match p.nth(0)? {
+        K![byte] => ast::Expr::from_lit(ast::Lit::Byte(p.parse()?)),
+        K![char] => ast::Expr::from_lit(ast::Lit::Char(p.parse()?)),
+        K![number] => ast::Expr::from_lit(ast::Lit::Number(p.parse()?)),
+        K![-] if matches!(p.nth(1)?, K![number]) => ast::Expr::Unary(ast::ExprUnary {
+            attributes: Vec::new(),
+            op: p.parse()?,
+            expr: Box::new(ast::Expr::from_lit(ast::Lit::Number(p.parse()?))),
+        }),
+        _ => return Ok(None),
+    }))
 }
 
 impl Peek for Pat {
@@ -174,7 +247,7 @@ impl Peek for Pat {
             K!['['] => true,
             K![#] => matches!(p.nth(1), K!['{']),
             K![_] => true,
-            K![..] => true,
+            K![..] | K![..=] => true,
             K![byte] | K![char] | K![number] | K![str] => true,
             K![true] | K![false] => true,
             K![-] => matches!(p.nth(1), K![number]),
@@ -194,6 +267,43 @@ pub struct PatLit {
     pub expr: Box<ast::Expr>,
 }
 
+/// An or-pattern, which matches if any of its alternatives match.
+///
+/// Every alternative must bind the same variables.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct PatOr {
+    /// The first alternative.
+    pub first: Box<ast::Pat>,
+    /// The remaining alternatives, each preceded by a `|`.
+    #[rune(iter)]
+    pub rest: Vec<(T![|], ast::Pat)>,
+}
+
+impl PatOr {
+    /// Iterate over all alternatives of the pattern.
+    pub fn alternatives(&self) -> impl Iterator<Item = &ast::Pat> {
+        std::iter::once(&*self.first).chain(self.rest.iter().map(|(_, pat)| pat))
+    }
+}
+
+/// A range pattern, like `'a'..='z'` or `10..`.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct PatRange {
+    /// Attributes associated with the range pattern.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The start of the range.
+    #[rune(iter)]
+    pub start: Option<Box<ast::Expr>>,
+    /// The limits of the range.
+    pub limits: ast::ExprRangeLimits,
+    /// The end of the range.
+    #[rune(iter)]
+    pub end: Option<Box<ast::Expr>>,
+}
+
 /// The rest pattern `..` and associated attributes.
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
 #[non_exhaustive]
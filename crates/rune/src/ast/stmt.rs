@@ -118,6 +118,7 @@ impl Peek for ItemOrExpr {
             K![enum] => true,
             K![struct] => true,
             K![impl] => true,
+            K![trait] => true,
             K![async] => matches!(p.nth(1), K![fn]),
             K![fn] => true,
             K![mod] => true,
@@ -166,6 +166,8 @@ pub enum Number {
     Float(f64),
     /// An integer literal number.
     Integer(num::BigInt),
+    /// An integer literal number with a type suffix, like `10u32`.
+    Sized(num::BigInt, NumberSuffix),
 }
 
 impl Number {
@@ -203,7 +205,9 @@ impl Number {
         use std::ops::Neg;
 
         let number = match self {
-            Number::Float(_) => return Err(ParseError::new(span, ParseErrorKind::BadNumber)),
+            Number::Float(_) | Number::Sized(..) => {
+                return Err(ParseError::new(span, ParseErrorKind::BadNumber))
+            }
             Number::Integer(n) => {
                 if neg {
                     to(&n.clone().neg())
@@ -258,6 +262,48 @@ impl fmt::Display for Number {
         match self {
             Self::Float(n) => write!(f, "{}", n),
             Self::Integer(n) => write!(f, "{}", n),
+            Self::Sized(n, suffix) => write!(f, "{}{}", n, suffix),
+        }
+    }
+}
+
+/// The type suffix of a sized integer literal, like `u32` in `10u32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum NumberSuffix {
+    /// An unsigned 8-bit integer, which is a byte.
+    U8,
+    /// An unsigned 16-bit integer.
+    U16,
+    /// An unsigned 32-bit integer.
+    U32,
+    /// An unsigned 64-bit integer.
+    U64,
+    /// A signed 32-bit integer.
+    I32,
+}
+
+impl NumberSuffix {
+    /// The inclusive bounds of the values of the suffixed type.
+    pub(crate) fn bounds(self) -> (i128, i128) {
+        match self {
+            Self::U8 => (0, u8::MAX.into()),
+            Self::U16 => (0, u16::MAX.into()),
+            Self::U32 => (0, u32::MAX.into()),
+            Self::U64 => (0, u64::MAX.into()),
+            Self::I32 => (i32::MIN.into(), i32::MAX.into()),
+        }
+    }
+}
+
+impl fmt::Display for NumberSuffix {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::U8 => write!(fmt, "u8"),
+            Self::U16 => write!(fmt, "u16"),
+            Self::U32 => write!(fmt, "u32"),
+            Self::U64 => write!(fmt, "u64"),
+            Self::I32 => write!(fmt, "i32"),
         }
     }
 }
@@ -0,0 +1,40 @@
+use crate::ast::prelude::*;
+
+/// A type annotation, like the `int` in `let a: int = 1;`.
+///
+/// # Examples
+///
+/// ```
+/// use rune::{ast, testing};
+///
+/// testing::roundtrip::<ast::Type>("int");
+/// testing::roundtrip::<ast::Type>("std::string::String");
+/// testing::roundtrip::<ast::Type>("()");
+/// testing::roundtrip::<ast::Type>("(int, String)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub enum Type {
+    /// A type referenced by path, like `String` or `std::collections::HashMap`.
+    Path(ast::Path),
+    /// A tuple type, like `()` or `(int, String)`.
+    Tuple(ast::Parenthesized<Box<Type>, T![,]>),
+}
+
+impl Parse for Type {
+    fn parse(p: &mut Parser<'_>) -> Result<Self, ParseError> {
+        Ok(match p.nth(0)? {
+            K!['('] => Self::Tuple(p.parse()?),
+            _ => Self::Path(p.parse()?),
+        })
+    }
+}
+
+impl Peek for Type {
+    fn peek(p: &mut Peeker<'_>) -> bool {
+        match p.nth(0) {
+            K!['('] => true,
+            _ => ast::Path::peek(p),
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use crate::ast::Span;
 use crate::compile;
 use crate::compile::{
-    CompileVisitor, FileSourceLoader, NoopCompileVisitor, Options, Pool, SourceLoader,
+    CachedUnit, CompileVisitor, FileSourceLoader, NoopCompileVisitor, Options, Pool, SourceLoader,
+    UnitCache, UnitCacheKey,
 };
 use crate::runtime::Unit;
 use crate::{Context, Diagnostics, SourceId, Sources};
@@ -66,6 +67,7 @@ pub fn prepare(sources: &mut Sources) -> Build<'_> {
         options: None,
         visitor: None,
         source_loader: None,
+        unit_cache: None,
     }
 }
 
@@ -77,6 +79,7 @@ pub struct Build<'a> {
     options: Option<&'a Options>,
     visitor: Option<&'a mut dyn compile::CompileVisitor>,
     source_loader: Option<&'a mut dyn SourceLoader>,
+    unit_cache: Option<&'a mut dyn UnitCache>,
 }
 
 impl<'a> Build<'a> {
@@ -127,6 +130,49 @@ impl<'a> Build<'a> {
         self
     }
 
+    /// Modify the current [Build] to configure the given [UnitCache].
+    ///
+    /// The cache is only used if bytecode caching has been enabled through
+    /// [Options::bytecode]. Units are keyed by the sources being built, the
+    /// [Context] and the [Options] in use, so a cached unit is only used if
+    /// none of them have changed. Sources loaded from the filesystem during
+    /// compilation are checked for changes when a cached unit is loaded, and
+    /// are added to the built sources so that debug information still
+    /// resolves.
+    ///
+    /// Note that if a cached unit is used no compilation takes place, so any
+    /// configured [CompileVisitor] will not be called and no diagnostics will
+    /// be emitted.
+    ///
+    /// ```
+    /// use rune::compile::FileUnitCache;
+    /// use rune::{Context, Options, Source, Sources};
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let dir = std::env::temp_dir().join("rune-doc-unit-cache");
+    /// let mut cache = FileUnitCache::new(&dir);
+    ///
+    /// let context = Context::with_default_modules()?;
+    ///
+    /// let mut options = Options::default();
+    /// options.bytecode(true);
+    ///
+    /// let mut sources = Sources::new();
+    /// sources.insert(Source::new("entry", "pub fn main() { 42 }"));
+    ///
+    /// let unit = rune::prepare(&mut sources)
+    ///     .with_context(&context)
+    ///     .with_options(&options)
+    ///     .with_unit_cache(&mut cache)
+    ///     .build()?;
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn with_unit_cache(mut self, unit_cache: &'a mut dyn UnitCache) -> Self {
+        self.unit_cache = Some(unit_cache);
+        self
+    }
+
     /// Build a [Unit] with the current configuration.
     pub fn build(mut self) -> Result<Unit, BuildError> {
         let default_context;
@@ -167,6 +213,34 @@ impl<'a> Build<'a> {
             }
         };
 
+        let mut unit_cache = match self.unit_cache.take() {
+            Some(unit_cache) if options.bytecode => Some(unit_cache),
+            _ => None,
+        };
+
+        let mut cache_key = None;
+
+        if let Some(unit_cache) = &mut unit_cache {
+            let key = UnitCacheKey::new(self.sources, context, options);
+
+            match unit_cache.load(&key) {
+                Ok(Some(cached)) => {
+                    if let Some(unit) = cached.restore(&key, self.sources) {
+                        tracing::trace!("using cached unit: {}", key);
+                        return Ok(unit);
+                    }
+
+                    tracing::trace!("cached unit is out of date: {}", key);
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    tracing::warn!("failed to load cached unit: {}: {}", key, error);
+                }
+            }
+
+            cache_key = Some((key, self.sources.len()));
+        }
+
         let mut default_visitor;
 
         let visitor = match self.visitor.take() {
@@ -213,12 +287,27 @@ impl<'a> Build<'a> {
             }
         }
 
-        match unit.build(Span::empty()) {
-            Ok(unit) => Ok(unit),
+        let unit = match unit.build(Span::empty()) {
+            Ok(unit) => unit,
             Err(error) => {
                 diagnostics.error(SourceId::empty(), error);
-                Err(BuildError)
+                return Err(BuildError);
+            }
+        };
+
+        if let (Some(unit_cache), Some((key, inputs))) = (unit_cache, cache_key) {
+            match CachedUnit::new(key, self.sources, inputs, &unit) {
+                Some(cached) => {
+                    if let Err(error) = unit_cache.store(&key, &cached) {
+                        tracing::warn!("failed to store cached unit: {}: {}", key, error);
+                    }
+                }
+                None => {
+                    tracing::trace!("unit can't be cached: {}", key);
+                }
             }
         }
+
+        Ok(unit)
     }
 }
@@ -35,6 +35,9 @@ pub(crate) struct Assembly {
     pub(crate) label_count: usize,
     /// The collection of functions required by this assembly.
     pub(crate) required_functions: HashMap<Hash, Vec<(Span, SourceId)>>,
+    /// Named variables with their stack offset, by the instruction offset they
+    /// are declared at.
+    pub(crate) variables: Vec<(usize, Box<str>, usize)>,
 }
 
 impl Assembly {
@@ -48,6 +51,7 @@ impl Assembly {
             comments: Default::default(),
             label_count,
             required_functions: Default::default(),
+            variables: Default::default(),
         }
     }
 
@@ -73,6 +77,13 @@ impl Assembly {
         Ok(label)
     }
 
+    /// Record that the named variable lives at the given stack offset from the
+    /// current instruction offset.
+    pub(crate) fn variable(&mut self, name: &str, offset: usize) {
+        self.variables
+            .push((self.instructions.len(), name.into(), offset));
+    }
+
     /// Add a jump to the given label.
     pub(crate) fn jump(&mut self, label: Label, span: Span) {
         self.instructions.push((AssemblyInst::Jump { label }, span));
@@ -166,8 +166,19 @@ pub enum CompileErrorKind {
     UnsupportedPattern { meta: Meta },
     #[error("this kind of expression is not supported as a pattern")]
     UnsupportedPatternExpr,
+    #[error("variable `{name}` is not bound in all patterns")]
+    VariableNotBoundInAllPatterns { name: String },
+    #[error(
+        "`else` block of `let` must diverge, like with `return`, `break`, `continue` or `panic`"
+    )]
+    LetElseMustDiverge,
     #[error("not a valid binding")]
     UnsupportedBinding,
+    #[error("mismatched types, expected `{expected}` but found `{actual}`")]
+    TypeMismatch {
+        expected: Box<str>,
+        actual: Box<str>,
+    },
     #[error("floating point numbers cannot be used in patterns")]
     MatchFloatInPattern,
     #[error("duplicate key in literal object")]
@@ -178,6 +189,14 @@ pub enum CompileErrorKind {
     AwaitOutsideFunction,
     #[error("instance function declared outside of `impl` block")]
     InstanceFunctionOutsideImpl,
+    #[error("trait functions must take `self` as their first argument")]
+    TraitFunctionNotInstance,
+    #[error("missing function `{name}` required by trait `{item}`")]
+    MissingTraitFunction { name: Box<str>, item: ItemBuf },
+    #[error("function `{name}` is not a member of trait `{item}`")]
+    NotTraitFunction { name: Box<str>, item: ItemBuf },
+    #[error("function `{name}` is declared more than once in trait")]
+    DuplicateTraitFunction { name: Box<str> },
     #[error("import `{item}` (imported in prelude) does not exist")]
     MissingPreludeModule { item: ItemBuf },
     #[error("unsupported tuple index `{number}`")]
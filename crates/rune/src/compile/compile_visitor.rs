@@ -13,6 +13,13 @@ pub trait CompileVisitor {
     /// Visit a variable use.
     fn visit_variable_use(&mut self, _source_id: SourceId, _var_span: Span, _span: Span) {}
 
+    /// Visit a variable declaration, together with the span of the scope it's
+    /// declared in.
+    ///
+    /// This may be called several times for a single variable, with scopes
+    /// which cover more and more code.
+    fn visit_variable_decl(&mut self, _source_id: SourceId, _var_span: Span, _scope: Span) {}
+
     /// Visit something that is a module.
     fn visit_mod(&mut self, _source_id: SourceId, _span: Span) {}
 
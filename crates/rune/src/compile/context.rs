@@ -239,6 +239,7 @@ impl Context {
         this.install(&crate::modules::io::module(stdio)?)?;
         this.install(&crate::modules::iter::module()?)?;
         this.install(&crate::modules::mem::module()?)?;
+        this.install(&crate::modules::num::module()?)?;
         this.install(&crate::modules::object::module()?)?;
         this.install(&crate::modules::ops::module()?)?;
         this.install(&crate::modules::option::module()?)?;
@@ -365,6 +366,11 @@ impl Context {
         self.functions.get(&hash)
     }
 
+    /// Look up the signature of the given native function.
+    pub(crate) fn lookup_signature(&self, hash: Hash) -> Option<&ContextSignature> {
+        self.functions_info.get(&hash)
+    }
+
     /// Lookup the given macro handler.
     pub(crate) fn lookup_macro(&self, hash: Hash) -> Option<&Arc<MacroHandler>> {
         self.macros.get(&hash)
@@ -389,6 +395,34 @@ impl Context {
         self.has_default_modules
     }
 
+    /// Calculate a hash identifying everything which has been installed into
+    /// the context.
+    ///
+    /// This is used to key cached units, since a unit compiled against one
+    /// context is not guaranteed to link against another.
+    pub(crate) fn cache_hash(&self) -> Hash {
+        fn sorted<'a, I>(iter: I) -> Vec<Hash>
+        where
+            I: IntoIterator<Item = &'a Hash>,
+        {
+            let mut hashes = iter.into_iter().copied().collect::<Vec<_>>();
+            hashes.sort();
+            hashes
+        }
+
+        let mut items = self.meta.keys().collect::<Vec<_>>();
+        items.sort();
+
+        Hash::of((
+            self.has_default_modules,
+            items,
+            sorted(self.functions.keys()),
+            sorted(self.macros.keys()),
+            sorted(self.types.keys()),
+            sorted(self.constants.keys()),
+        ))
+    }
+
     /// Install the given meta.
     fn install_meta(&mut self, meta: ContextMeta) -> Result<(), ContextError> {
         match self.meta.entry(meta.item.clone()) {
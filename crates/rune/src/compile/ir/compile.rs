@@ -1,7 +1,7 @@
 use crate::ast::{self, Span, Spanned};
 use crate::compile::ir::{self, IrError, IrValue};
 use crate::hir;
-use crate::parse::Resolve;
+use crate::parse::{ParseErrorKind, Resolve};
 use crate::query::Query;
 use crate::runtime::{Bytes, Shared};
 use crate::SourceId;
@@ -104,6 +104,7 @@ pub(crate) fn expr(hir: &hir::Expr<'_>, c: &mut IrCompiler<'_>) -> Result<ir::Ir
 
                 let const_value = match n {
                     ast::Number::Integer(n) => IrValue::Integer(n),
+                    ast::Number::Sized(n, suffix) => sized(line.span, n, suffix)?,
                     ast::Number::Float(n) => IrValue::Float(n),
                 };
 
@@ -236,6 +237,7 @@ fn lit(hir: &ast::Lit, c: &mut IrCompiler<'_>) -> Result<ir::Ir, IrError> {
 
             let const_value = match n {
                 ast::Number::Integer(n) => IrValue::Integer(n),
+                ast::Number::Sized(n, suffix) => sized(span, n, suffix)?,
                 ast::Number::Float(n) => IrValue::Float(n),
             };
 
@@ -257,6 +259,31 @@ fn lit(hir: &ast::Lit, c: &mut IrCompiler<'_>) -> Result<ir::Ir, IrError> {
     })
 }
 
+/// Convert a sized integer literal into a value, checking that it's in bounds.
+fn sized(span: Span, n: num::BigInt, suffix: ast::NumberSuffix) -> Result<IrValue, IrError> {
+    use num::ToPrimitive as _;
+
+    let value = match suffix {
+        ast::NumberSuffix::U8 => n.to_u8().map(IrValue::Byte),
+        ast::NumberSuffix::U16 => n.to_u16().map(IrValue::U16),
+        ast::NumberSuffix::U32 => n.to_u32().map(IrValue::U32),
+        ast::NumberSuffix::U64 => n.to_u64().map(IrValue::U64),
+        ast::NumberSuffix::I32 => n.to_i32().map(IrValue::I32),
+    };
+
+    match value {
+        Some(value) => Ok(value),
+        None => {
+            let (min, max) = suffix.bounds();
+
+            Err(IrError::new(
+                span,
+                ParseErrorKind::BadSizedNumberOutOfBounds { suffix, min, max },
+            ))
+        }
+    }
+}
+
 #[instrument]
 fn expr_tuple(
     span: Span,
@@ -468,6 +495,18 @@ fn condition(hir: &hir::Condition<'_>, c: &mut IrCompiler<'_>) -> Result<ir::IrC
                 ir,
             }))
         }
+        hir::Condition::Chain(chain) => {
+            let mut conditions = Vec::with_capacity(chain.conditions.len());
+
+            for hir in chain.conditions {
+                conditions.push(condition(hir, c)?);
+            }
+
+            Ok(ir::IrCondition::Chain(ir::IrChain {
+                span: chain.span,
+                conditions,
+            }))
+        }
     }
 }
 
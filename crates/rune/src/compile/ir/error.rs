@@ -1,7 +1,7 @@
 use crate::ast::{Spanned, SpannedError};
 use crate::compile::{IrValue, Meta};
 use crate::hir::{HirError, HirErrorKind};
-use crate::parse::{ResolveError, ResolveErrorKind};
+use crate::parse::{ParseErrorKind, ResolveError, ResolveErrorKind};
 use crate::query::{QueryError, QueryErrorKind};
 use crate::runtime::{AccessError, TypeInfo, TypeOf};
 use crate::shared::{ScopeError, ScopeErrorKind};
@@ -80,6 +80,12 @@ pub enum IrErrorKind {
         #[from]
         error: ResolveErrorKind,
     },
+    #[error("{error}")]
+    ParseError {
+        #[source]
+        #[from]
+        error: ParseErrorKind,
+    },
     /// A scope error.
     #[error("scope error: {error}")]
     ScopeError {
@@ -215,6 +215,15 @@ fn eval_ir_condition(
             let value = eval_ir(&ir_let.ir, interp, used)?;
             ir_let.pat.matches(interp, value, ir)?
         }
+        ir::IrCondition::Chain(chain) => {
+            for ir in &chain.conditions {
+                if let IrValue::Bool(false) = eval_ir_condition(ir, interp, used)? {
+                    return Ok(IrValue::Bool(false));
+                }
+            }
+
+            true
+        }
     }))
 }
 
@@ -191,7 +191,14 @@ impl IrFn {
             if let hir::FnArg::Pat(hir::Pat {
                 kind: hir::PatKind::PatPath(path),
                 ..
-            }) = arg
+            })
+            | hir::FnArg::Typed(
+                hir::Pat {
+                    kind: hir::PatKind::PatPath(path),
+                    ..
+                },
+                _,
+            ) = arg
             {
                 if let Some(ident) = path.try_as_ident() {
                     args.push(c.resolve(ident)?.into());
@@ -314,6 +321,18 @@ pub enum IrCondition {
     Ir(Ir),
     /// A pattern match.
     Let(IrLet),
+    /// A `&&`-chain of conditions.
+    Chain(IrChain),
+}
+
+/// A `&&`-chain of conditions.
+#[derive(Debug, Clone, Spanned)]
+pub struct IrChain {
+    /// The span of the chain.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The conditions in the chain.
+    pub(crate) conditions: Vec<IrCondition>,
 }
 
 /// A pattern match.
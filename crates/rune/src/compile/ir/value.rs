@@ -18,6 +18,14 @@ pub enum IrValue {
     Bool(bool),
     /// An integer constant.
     Integer(num::BigInt),
+    /// An unsigned 16-bit integer constant.
+    U16(u16),
+    /// An unsigned 32-bit integer constant.
+    U32(u32),
+    /// An unsigned 64-bit integer constant.
+    U64(u64),
+    /// A signed 32-bit integer constant.
+    I32(i32),
     /// An float constant.
     Float(f64),
     /// A string constant designated by its slot.
@@ -62,6 +70,10 @@ impl IrValue {
             ConstValue::Char(c) => Self::Char(*c),
             ConstValue::Bool(b) => Self::Bool(*b),
             ConstValue::Integer(n) => Self::Integer((*n).into()),
+            ConstValue::U16(n) => Self::U16(*n),
+            ConstValue::U32(n) => Self::U32(*n),
+            ConstValue::U64(n) => Self::U64(*n),
+            ConstValue::I32(n) => Self::I32(*n),
             ConstValue::Float(n) => Self::Float(*n),
             ConstValue::String(s) => Self::String(Shared::new(s.clone())),
             ConstValue::StaticString(s) => Self::String(Shared::new((***s).to_owned())),
@@ -121,6 +133,10 @@ impl IrValue {
 
                 ConstValue::Integer(n)
             }
+            IrValue::U16(n) => ConstValue::U16(n),
+            IrValue::U32(n) => ConstValue::U32(n),
+            IrValue::U64(n) => ConstValue::U64(n),
+            IrValue::I32(n) => ConstValue::I32(n),
             IrValue::Float(f) => ConstValue::Float(f),
             IrValue::String(s) => {
                 let s = s.take().map_err(IrError::access(spanned))?;
@@ -179,6 +195,10 @@ impl IrValue {
             Self::String(..) => TypeInfo::StaticType(rt::STRING_TYPE),
             Self::Bytes(..) => TypeInfo::StaticType(rt::BYTES_TYPE),
             Self::Integer(..) => TypeInfo::StaticType(rt::INTEGER_TYPE),
+            Self::U16(..) => TypeInfo::StaticType(rt::U16_TYPE),
+            Self::U32(..) => TypeInfo::StaticType(rt::U32_TYPE),
+            Self::U64(..) => TypeInfo::StaticType(rt::U64_TYPE),
+            Self::I32(..) => TypeInfo::StaticType(rt::I32_TYPE),
             Self::Float(..) => TypeInfo::StaticType(rt::FLOAT_TYPE),
             Self::Option(..) => TypeInfo::StaticType(rt::OPTION_TYPE),
             Self::Vec(..) => TypeInfo::StaticType(rt::VEC_TYPE),
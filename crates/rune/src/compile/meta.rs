@@ -9,7 +9,7 @@ use crate::collections::HashSet;
 use crate::compile::attrs::Attributes;
 use crate::compile::{Item, ItemBuf, ItemId, Location, ModId, Pool, Visibility};
 use crate::parse::{Id, ParseError, ResolveContext};
-use crate::query::ImportEntry;
+use crate::query::{ImportEntry, TraitFunction};
 use crate::runtime::ConstValue;
 use crate::Hash;
 
@@ -73,6 +73,8 @@ pub enum MetaKind {
     Const,
     /// Item describes a constant function.
     ConstFn,
+    /// Item describes a trait.
+    Trait,
     /// Item describes an import.
     Import,
     /// Item describes a module.
@@ -121,6 +123,9 @@ impl fmt::Display for Meta {
             MetaKind::ConstFn => {
                 write!(fmt, "const fn {}", self.item)?;
             }
+            MetaKind::Trait => {
+                write!(fmt, "trait {}", self.item)?;
+            }
             MetaKind::Import => {
                 write!(fmt, "import {}", self.item)?;
             }
@@ -324,6 +329,7 @@ impl PrivMeta {
             PrivMetaKind::Variant { .. } => None,
             PrivMetaKind::Const { .. } => None,
             PrivMetaKind::ConstFn { .. } => None,
+            PrivMetaKind::Trait { .. } => None,
             PrivMetaKind::Import { .. } => None,
             PrivMetaKind::Module => None,
         }
@@ -374,6 +380,9 @@ pub(crate) enum PrivMetaKind {
         /// The type hash associated with this meta kind.
         type_hash: Hash,
 
+        /// The number of arguments the function takes, if known.
+        args: Option<usize>,
+
         /// Whether this function has a `#[test]` annotation
         is_test: bool,
 
@@ -408,6 +417,11 @@ pub(crate) enum PrivMetaKind {
         /// Opaque identifier for the constant function.
         id: Id,
     },
+    /// A trait.
+    Trait {
+        /// The functions declared by the trait.
+        functions: Arc<[TraitFunction]>,
+    },
     /// Purely an import.
     Import {
         /// The entry being imported.
@@ -461,6 +475,7 @@ impl PrivMetaKind {
             PrivMetaKind::AsyncBlock { .. } => MetaKind::AsyncBlock,
             PrivMetaKind::Const { .. } => MetaKind::Const,
             PrivMetaKind::ConstFn { .. } => MetaKind::ConstFn,
+            PrivMetaKind::Trait { .. } => MetaKind::Trait,
             PrivMetaKind::Import { .. } => MetaKind::Import,
             PrivMetaKind::Module => MetaKind::Module,
         }
@@ -8,10 +8,10 @@ use crate::ast::{Span, Spanned};
 use crate::hir;
 use crate::macros::Storage;
 use crate::parse::Resolve;
-use crate::query::{Build, BuildEntry, Query};
+use crate::query::{Build, BuildEntry, Query, Used};
 use crate::shared::{Consts, Gen};
 use crate::worker::{LoadFileKind, Task, Worker};
-use crate::{Diagnostics, Sources};
+use crate::{Diagnostics, Hash, Sources};
 
 mod assembly;
 pub(crate) use self::assembly::{Assembly, AssemblyInst};
@@ -41,6 +41,9 @@ pub use self::item::{Component, ComponentRef, IntoComponent, Item, ItemBuf};
 mod source_loader;
 pub use self::source_loader::{FileSourceLoader, SourceLoader};
 
+mod unit_cache;
+pub use self::unit_cache::{CachedUnit, FileUnitCache, UnitCache, UnitCacheError, UnitCacheKey};
+
 mod unit_builder;
 pub use self::unit_builder::LinkerError;
 pub(crate) use self::unit_builder::UnitBuilder;
@@ -190,7 +193,7 @@ impl CompileBuildEntry<'_> {
             context: self.context,
             q: self.q.borrow(),
             asm,
-            scopes: self::v1::Scopes::new(),
+            scopes: self::v1::Scopes::new(span),
             contexts: vec![span],
             loops: self::v1::Loops::new(),
             options: self.options,
@@ -198,6 +201,55 @@ impl CompileBuildEntry<'_> {
         }
     }
 
+    /// Compile an instance function and associate it with the given type.
+    fn instance_function(
+        &mut self,
+        location: Location,
+        item: ItemId,
+        type_hash: Hash,
+        function: &crate::query::Function,
+        used: Used,
+    ) -> Result<(), CompileError> {
+        use self::v1::assemble;
+
+        let args = format_fn_args(
+            self.q.sources,
+            location,
+            function.ast.args.iter().map(|(a, _)| a),
+        )?;
+
+        let span = function.ast.span();
+        let count = function.ast.args.len();
+
+        let mut asm = self.q.unit.new_assembly(location);
+        let mut c = self.compiler1(location, span, &mut asm);
+
+        let arena = hir::Arena::new();
+        let ctx = hir::lowering::Ctx::new(&arena, c.q.borrow());
+        let hir = hir::lowering::item_fn(&ctx, &function.ast)?;
+        assemble::fn_from_item_fn(&hir, &mut c, true)?;
+        c.visit_declared_vars();
+
+        if used.is_unused() {
+            c.diagnostics.not_used(location.source_id, span, None);
+        } else {
+            let name = function.ast.name.resolve(resolve_context!(self.q))?;
+
+            self.q.unit.new_instance_function(
+                location,
+                self.q.pool.item(item),
+                type_hash,
+                name,
+                count,
+                asm,
+                function.call,
+                args,
+            )?;
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self, entry))]
     fn compile(mut self, entry: BuildEntry) -> Result<(), CompileError> {
         let BuildEntry {
@@ -243,6 +295,7 @@ impl CompileBuildEntry<'_> {
                 let hir = hir::lowering::item_fn(&ctx, &f.ast)?;
                 let mut c = self.compiler1(location, span, &mut asm);
                 assemble::fn_from_item_fn(&hir, &mut c, false)?;
+                c.visit_declared_vars();
 
                 if used.is_unused() {
                     self.diagnostics.not_used(location.source_id, span, None);
@@ -260,16 +313,7 @@ impl CompileBuildEntry<'_> {
             Build::InstanceFunction(f) => {
                 tracing::trace!("instance function: {}", self.q.pool.item(item_meta.item));
 
-                use self::v1::assemble;
-
-                let args = format_fn_args(
-                    self.q.sources,
-                    location,
-                    f.function.ast.args.iter().map(|(a, _)| a),
-                )?;
-
                 let span = f.function.ast.span();
-                let count = f.function.ast.args.len();
 
                 let mut c = self.compiler1(location, span, &mut asm);
                 let meta = c.lookup_meta(f.instance_span, f.impl_item)?;
@@ -278,25 +322,95 @@ impl CompileBuildEntry<'_> {
                     CompileError::expected_meta(span, meta.info(c.q.pool), "instance function")
                 })?;
 
-                let arena = hir::Arena::new();
-                let ctx = hir::lowering::Ctx::new(&arena, c.q.borrow());
-                let hir = hir::lowering::item_fn(&ctx, &f.function.ast)?;
-                assemble::fn_from_item_fn(&hir, &mut c, true)?;
+                self.instance_function(location, item_meta.item, type_hash, &f.function, used)?;
+            }
+            Build::TraitImpl(t) => {
+                tracing::trace!("trait impl: {}", self.q.pool.item(item_meta.item));
 
-                if used.is_unused() {
-                    c.diagnostics.not_used(location.source_id, span, None);
-                } else {
-                    let name = f.function.ast.name.resolve(resolve_context!(self.q))?;
+                let span = location.span;
 
-                    self.q.unit.new_instance_function(
-                        location,
-                        self.q.pool.item(item_meta.item),
+                let (type_hash, trait_item, functions) = {
+                    let mut c = self.compiler1(location, span, &mut asm);
+
+                    let arena = hir::Arena::new();
+                    let ctx = hir::lowering::Ctx::new(&arena, c.q.borrow());
+                    let trait_path = hir::lowering::path(&ctx, &t.trait_path)?;
+                    let type_path = hir::lowering::path(&ctx, &t.type_path)?;
+
+                    let named = c.convert_path(&type_path)?;
+                    let meta = c.lookup_meta(t.type_path.span(), named.item)?;
+
+                    let type_hash = meta.type_hash_of().ok_or_else(|| {
+                        CompileError::expected_meta(&t.type_path, meta.info(c.q.pool), "type")
+                    })?;
+
+                    let named = c.convert_path(&trait_path)?;
+                    let meta = c.lookup_meta(t.trait_path.span(), named.item)?;
+
+                    let functions = match &meta.kind {
+                        PrivMetaKind::Trait { functions } => functions.clone(),
+                        _ => {
+                            return Err(CompileError::expected_meta(
+                                &t.trait_path,
+                                meta.info(c.q.pool),
+                                "trait",
+                            ));
+                        }
+                    };
+
+                    (type_hash, named.item, functions)
+                };
+
+                let mut names = Vec::with_capacity(t.functions.len());
+
+                for (_, f) in &t.functions {
+                    let name = f.ast.name.resolve(resolve_context!(self.q))?;
+
+                    if !functions.iter().any(|tf| *tf.name == *name) {
+                        return Err(CompileError::new(
+                            &f.ast.name,
+                            CompileErrorKind::NotTraitFunction {
+                                name: name.into(),
+                                item: self.q.pool.item(trait_item).to_owned(),
+                            },
+                        ));
+                    }
+
+                    names.push(Box::<str>::from(name));
+                }
+
+                for tf in functions.iter() {
+                    if names.iter().any(|name| **name == *tf.name) {
+                        continue;
+                    }
+
+                    let function = match &tf.default {
+                        Some(function) => function,
+                        None => {
+                            return Err(CompileError::new(
+                                span,
+                                CompileErrorKind::MissingTraitFunction {
+                                    name: tf.name.clone(),
+                                    item: self.q.pool.item(trait_item).to_owned(),
+                                },
+                            ));
+                        }
+                    };
+
+                    // NB: default functions are compiled once for every type
+                    // implementing the trait.
+                    let item = self.q.pool.item(item_meta.item).extended(tf.name.as_ref());
+                    let item = self.q.pool.alloc_item(item);
+                    self.instance_function(tf.location, item, type_hash, function, used)?;
+                }
+
+                for (item_meta, function) in &t.functions {
+                    self.instance_function(
+                        item_meta.location,
+                        item_meta.item,
                         type_hash,
-                        name,
-                        count,
-                        asm,
-                        f.function.call,
-                        args,
+                        function,
+                        used,
                     )?;
                 }
             }
@@ -317,6 +431,7 @@ impl CompileBuildEntry<'_> {
                 let hir = hir::lowering::expr_closure(&ctx, &closure.ast)?;
                 let mut c = self.compiler1(location, span, &mut asm);
                 assemble::closure_from_expr_closure(span, &mut c, &hir, &closure.captures)?;
+                c.visit_declared_vars();
 
                 if used.is_unused() {
                     c.diagnostics
@@ -346,6 +461,7 @@ impl CompileBuildEntry<'_> {
 
                 let mut c = self.compiler1(location, span, &mut asm);
                 assemble::closure_from_block(&hir, &mut c, &b.captures)?;
+                c.visit_declared_vars();
 
                 if used.is_unused() {
                     self.diagnostics
@@ -450,8 +566,8 @@ where
             ast::FnArg::SelfValue(..) => {
                 args.push("self".into());
             }
-            ast::FnArg::Pat(pat) => {
-                let span = pat.span();
+            ast::FnArg::Pat(..) | ast::FnArg::Typed(..) => {
+                let span = arg.span();
 
                 if let Some(s) = sources.source(location.source_id, span) {
                     args.push(s.into());
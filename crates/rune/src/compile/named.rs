@@ -1,5 +1,5 @@
 use crate::compile::InstallWith;
-use crate::runtime::RawStr;
+use crate::runtime::{RawStr, SizedInteger};
 
 /// The trait used for something that can be statically named.
 pub trait Named {
@@ -40,6 +40,30 @@ impl Named for u8 {
 
 impl InstallWith for u8 {}
 
+impl Named for SizedInteger<u16> {
+    const BASE_NAME: RawStr = RawStr::from_str("u16");
+}
+
+impl InstallWith for SizedInteger<u16> {}
+
+impl Named for SizedInteger<u32> {
+    const BASE_NAME: RawStr = RawStr::from_str("u32");
+}
+
+impl InstallWith for SizedInteger<u32> {}
+
+impl Named for SizedInteger<u64> {
+    const BASE_NAME: RawStr = RawStr::from_str("u64");
+}
+
+impl InstallWith for SizedInteger<u64> {}
+
+impl Named for SizedInteger<i32> {
+    const BASE_NAME: RawStr = RawStr::from_str("i32");
+}
+
+impl InstallWith for SizedInteger<i32> {}
+
 impl Named for char {
     const BASE_NAME: RawStr = RawStr::from_str("char");
 }
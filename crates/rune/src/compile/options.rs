@@ -10,7 +10,7 @@ pub struct ParseOptionError {
 /// Options that can be provided to the compiler.
 ///
 /// See [Build::with_options][crate::Build::with_options].
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Hash)]
 pub struct Options {
     /// Perform link-time checks.
     pub(crate) link_checks: bool,
@@ -22,6 +22,11 @@ pub struct Options {
     pub(crate) macros: bool,
     /// Support (experimental) bytecode caching.
     pub bytecode: bool,
+    /// Statically check type annotations where possible.
+    pub(crate) static_types: bool,
+    /// Run the gradual type checker and report likely type errors as
+    /// warnings.
+    pub(crate) type_check: bool,
 
     /// Compile for and enable test features
     pub cfg_test: bool,
@@ -55,6 +60,12 @@ impl Options {
             Some("bytecode") => {
                 self.bytecode = it.next() != Some("false");
             }
+            Some("static-types") => {
+                self.static_types = it.next() != Some("false");
+            }
+            Some("type-check") => {
+                self.type_check = it.next() != Some("false");
+            }
             Some("test") => {
                 self.cfg_test = it.next() != Some("false");
             }
@@ -94,10 +105,34 @@ impl Options {
     }
 
     /// Set if bytecode caching is enabled or not. Defaults to `false`.
+    ///
+    /// This has no effect unless a [UnitCache][crate::compile::UnitCache] is
+    /// provided through [Build::with_unit_cache][crate::Build::with_unit_cache].
     pub fn bytecode(&mut self, enabled: bool) {
         self.bytecode = enabled;
     }
 
+    /// Set if type annotations should be checked statically where possible.
+    /// Defaults to `false`.
+    ///
+    /// Annotated function arguments are always checked at runtime when the
+    /// function is called, this additionally reports mismatches which can be
+    /// detected at compile time, like `let a: String = 42;`.
+    pub fn static_types(&mut self, enabled: bool) {
+        self.static_types = enabled;
+    }
+
+    /// Set if the gradual type checker should run. Defaults to `false`.
+    ///
+    /// The type checker infers the types of expressions where they are known
+    /// at compile time, like literals, constructors and annotated bindings,
+    /// and reports obvious mistakes as warnings. Like calling an instance
+    /// function which doesn't exist or using `+` between a string and an
+    /// integer.
+    pub fn type_check(&mut self, enabled: bool) {
+        self.type_check = enabled;
+    }
+
     /// Memoize the instance function in a loop. Defaults to `false`.
     pub fn memoize_instance_fn(&mut self, enabled: bool) {
         self.memoize_instance_fn = enabled;
@@ -112,6 +147,8 @@ impl Default for Options {
             debug_info: true,
             macros: true,
             bytecode: false,
+            static_types: false,
+            type_check: false,
             cfg_test: false,
             v2: false,
         }
@@ -10,7 +10,7 @@ use crate::compile::{
     PrivMeta, PrivMetaKind, PrivVariantMeta,
 };
 use crate::query::{QueryError, QueryErrorKind};
-use crate::runtime::debug::{DebugArgs, DebugSignature};
+use crate::runtime::debug::{DebugArgs, DebugSignature, DebugVariable};
 use crate::runtime::{
     Call, ConstValue, DebugInfo, DebugInst, Inst, Label, Protocol, Rtti, StaticString, Unit,
     UnitFn, VariantRtti,
@@ -522,6 +522,7 @@ impl UnitBuilder {
                 );
             }
             PrivMetaKind::ConstFn { .. } => (),
+            PrivMetaKind::Trait { .. } => (),
             PrivMetaKind::Import { .. } => (),
             PrivMetaKind::Module { .. } => (),
         }
@@ -630,6 +631,11 @@ impl UnitBuilder {
             ));
         }
 
+        if let Some(protocol) = Protocol::from_script_name(name) {
+            self.functions
+                .insert(Hash::instance_function(type_hash, protocol), info);
+        }
+
         self.constants.insert(
             Hash::instance_function(hash, Protocol::INTO_TYPE_NAME),
             ConstValue::String(signature.path.to_string()),
@@ -672,6 +678,15 @@ impl UnitBuilder {
 
         self.required_functions.extend(assembly.required_functions);
 
+        let base = self.instructions.len();
+        let debug = self.debug.get_or_insert_with(Default::default);
+
+        for (pos, name, offset) in assembly.variables {
+            debug
+                .variables
+                .push(DebugVariable::new(name, offset, base + pos));
+        }
+
         for (pos, (inst, span)) in assembly.instructions.into_iter().enumerate() {
             let mut comment = None::<Box<str>>;
             let label = assembly.labels_rev.get(&pos).copied();
@@ -0,0 +1,307 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::compile::{Context, Options};
+use crate::runtime::Unit;
+use crate::{Hash, Source, Sources};
+
+/// Magic bytes at the start of every file written by [FileUnitCache].
+const MAGIC: [u8; 4] = *b"RNUC";
+
+/// The version of the cache format. Bump this whenever the layout of
+/// [CachedUnit] or any type it contains changes in an incompatible way.
+const FORMAT_VERSION: u32 = 5;
+
+/// Error raised when interacting with a [UnitCache].
+#[derive(Debug, Error)]
+pub enum UnitCacheError {
+    /// An I/O error.
+    #[error("I/O error")]
+    Io(#[from] io::Error),
+    /// Failed to encode or decode a cached unit.
+    #[error("failed to encode or decode cached unit")]
+    Encoding(#[from] bincode::Error),
+}
+
+/// The key a cached unit is stored under.
+///
+/// It is derived from the version of Rune, the contents of all sources which
+/// are provided to the build, the [Context] being compiled against and the
+/// compiler [Options].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UnitCacheKey {
+    hash: Hash,
+}
+
+impl UnitCacheKey {
+    /// Calculate the cache key for the given build inputs.
+    pub fn new(sources: &Sources, context: &Context, options: &Options) -> Self {
+        let sources = sources
+            .source_ids()
+            .flat_map(|id| sources.get(id))
+            .map(source_hash)
+            .collect::<Vec<_>>();
+
+        let hash = Hash::of((
+            env!("CARGO_PKG_VERSION"),
+            FORMAT_VERSION,
+            sources,
+            context.cache_hash(),
+            options,
+        ));
+
+        Self { hash }
+    }
+
+    /// Get the underlying hash of the key.
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+}
+
+impl fmt::Display for UnitCacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.hash.fmt(f)
+    }
+}
+
+/// A source which was loaded during compilation, like through a `mod foo;`
+/// declaration.
+///
+/// These need to be re-read when a cached unit is loaded, both to check that
+/// they are still up to date and so that the source ids referenced by debug
+/// information resolve to the same sources.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedSource {
+    /// The path the source was loaded from.
+    path: PathBuf,
+    /// The hash of the source at the time it was compiled.
+    hash: Hash,
+}
+
+/// A compiled unit as it is stored in a [UnitCache].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedUnit {
+    /// The key the unit was stored under.
+    key: UnitCacheKey,
+    /// Sources which were loaded during compilation.
+    loaded: Vec<CachedSource>,
+    /// The compiled unit.
+    unit: Unit,
+}
+
+impl CachedUnit {
+    /// Construct a cached unit out of a freshly built unit.
+    ///
+    /// `sources` are the sources after compilation, of which all but the first
+    /// `inputs` have been loaded during the build. Returns `None` if the unit
+    /// can't be cached because a loaded source does not originate from a path.
+    pub(crate) fn new(
+        key: UnitCacheKey,
+        sources: &Sources,
+        inputs: usize,
+        unit: &Unit,
+    ) -> Option<Self> {
+        let mut loaded = Vec::new();
+
+        for id in sources.source_ids().skip(inputs) {
+            let source = sources.get(id)?;
+
+            loaded.push(CachedSource {
+                path: source.path()?.to_owned(),
+                hash: source_hash(source),
+            });
+        }
+
+        Some(Self {
+            key,
+            loaded,
+            unit: unit.clone(),
+        })
+    }
+
+    /// Get the key the unit was stored under.
+    pub fn key(&self) -> UnitCacheKey {
+        self.key
+    }
+
+    /// Get the cached unit.
+    pub fn unit(&self) -> &Unit {
+        &self.unit
+    }
+
+    /// Coerce into the cached unit.
+    pub fn into_unit(self) -> Unit {
+        self.unit
+    }
+
+    /// Validate the cached unit against the given key, reloading any sources
+    /// which were loaded during compilation into `sources`.
+    ///
+    /// Returns `None` if the cached unit is out of date, in which case
+    /// `sources` is left untouched.
+    pub(crate) fn restore(self, key: &UnitCacheKey, sources: &mut Sources) -> Option<Unit> {
+        if self.key != *key {
+            return None;
+        }
+
+        let mut loaded = Vec::with_capacity(self.loaded.len());
+
+        for cached in &self.loaded {
+            let source = Source::from_path(&cached.path).ok()?;
+
+            if source_hash(&source) != cached.hash {
+                return None;
+            }
+
+            loaded.push(source);
+        }
+
+        for source in loaded {
+            sources.insert(source);
+        }
+
+        Some(self.unit)
+    }
+}
+
+/// A cache of compiled units.
+///
+/// This is consulted by [Build][crate::Build] if a cache has been provided
+/// through [Build::with_unit_cache][crate::Build::with_unit_cache] and the
+/// `bytecode` compiler option is enabled.
+pub trait UnitCache {
+    /// Load the cached unit stored under the given key, if any.
+    fn load(&mut self, key: &UnitCacheKey) -> Result<Option<CachedUnit>, UnitCacheError>;
+
+    /// Store a unit under the given key.
+    fn store(&mut self, key: &UnitCacheKey, unit: &CachedUnit) -> Result<(), UnitCacheError>;
+}
+
+/// A filesystem-based unit cache, which stores each unit as a separate file in
+/// a directory.
+pub struct FileUnitCache {
+    root: PathBuf,
+}
+
+impl FileUnitCache {
+    /// Construct a new filesystem-based unit cache storing units in the given
+    /// directory. The directory is created when the first unit is stored.
+    ///
+    /// On unix, units are only loaded if they are owned by the current user
+    /// and the directory isn't writable by everyone while being owned by
+    /// someone else, since loading a unit means running its code.
+    pub fn new<P>(root: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self {
+            root: root.as_ref().to_owned(),
+        }
+    }
+
+    /// Get the path a unit with the given key is stored at.
+    pub fn path(&self, key: &UnitCacheKey) -> PathBuf {
+        self.root.join(format!("{}.rnc", key))
+    }
+}
+
+impl UnitCache for FileUnitCache {
+    fn load(&mut self, key: &UnitCacheKey) -> Result<Option<CachedUnit>, UnitCacheError> {
+        let mut f = match fs::File::open(self.path(key)) {
+            Ok(f) => f,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+
+        if !is_trusted(&self.root, &f.metadata()?)? {
+            tracing::warn!(
+                "ignoring untrusted cached unit: {}",
+                self.path(key).display()
+            );
+            return Ok(None);
+        }
+
+        let mut header = [0u8; 8];
+        f.read_exact(&mut header)?;
+
+        if header[..4] != MAGIC || header[4..] != FORMAT_VERSION.to_le_bytes() {
+            return Ok(None);
+        }
+
+        let unit = bincode::deserialize_from(io::BufReader::new(f))?;
+        Ok(Some(unit))
+    }
+
+    fn store(&mut self, key: &UnitCacheKey, unit: &CachedUnit) -> Result<(), UnitCacheError> {
+        create_dir(&self.root)?;
+
+        let path = self.path(key);
+        // Write to a temporary file first so that concurrent readers never
+        // observe a partially written unit.
+        let temp = path.with_extension(format!("rnc.{}.tmp", std::process::id()));
+
+        let result = (|| {
+            let mut f = io::BufWriter::new(fs::File::create(&temp)?);
+            f.write_all(&MAGIC)?;
+            f.write_all(&FORMAT_VERSION.to_le_bytes())?;
+            bincode::serialize_into(&mut f, unit)?;
+            f.flush()?;
+            Ok::<_, UnitCacheError>(())
+        })();
+
+        if let Err(error) = result {
+            let _ = fs::remove_file(&temp);
+            return Err(error);
+        }
+
+        fs::rename(&temp, &path)?;
+        Ok(())
+    }
+}
+
+/// Create the cache directory, which is only accessible by the current user.
+fn create_dir(root: &Path) -> io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+
+    builder.create(root)
+}
+
+/// Test if a cached unit with the given metadata stored in `root` can be
+/// trusted.
+#[cfg(unix)]
+fn is_trusted(root: &Path, entry: &fs::Metadata) -> io::Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+
+    // SAFETY: `getuid` is always successful and has no side effects.
+    let uid = unsafe { libc::getuid() };
+
+    if entry.uid() != uid {
+        return Ok(false);
+    }
+
+    let root = fs::metadata(root)?;
+    Ok(root.uid() == uid || root.mode() & 0o002 == 0)
+}
+
+#[cfg(not(unix))]
+fn is_trusted(_: &Path, _: &fs::Metadata) -> io::Result<bool> {
+    Ok(true)
+}
+
+/// Hash the name and contents of a source.
+fn source_hash(source: &Source) -> Hash {
+    Hash::of((source.name(), source.as_str()))
+}
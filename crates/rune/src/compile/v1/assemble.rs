@@ -7,10 +7,10 @@ use rune_macros::__instrument_ast as instrument;
 use crate::ast;
 use crate::ast::{Span, Spanned};
 use crate::collections::{HashMap, HashSet};
-use crate::compile::v1::{Assembler, Loop, Needs, Scope, Var};
+use crate::compile::v1::{typeck, Assembler, Loop, Needs, Scope, Var};
 use crate::compile::{
-    CaptureMeta, CompileError, CompileErrorKind, CompileResult, Item, PrivMeta, PrivMetaKind,
-    PrivStructMeta, PrivVariantMeta,
+    CaptureMeta, CompileError, CompileErrorKind, CompileResult, Item, ItemBuf, PrivMeta,
+    PrivMetaKind, PrivStructMeta, PrivVariantMeta,
 };
 use crate::hash::ParametersBuilder;
 use crate::hir;
@@ -294,7 +294,8 @@ fn pat(
 
             if let Some(ident) = named.as_local() {
                 load(c, Needs::Value)?;
-                c.scopes.decl_var(ident, span)?;
+                let offset = c.scopes.decl_var(ident, span)?;
+                c.asm.variable(ident, offset);
                 return Ok(false);
             }
 
@@ -316,6 +317,11 @@ fn pat(
             pat_object(span, c, hir, false_label, &load)?;
             Ok(true)
         }
+        hir::PatKind::PatOr(hir) => Ok(pat_or(span, c, hir, false_label, load)?),
+        hir::PatKind::PatRange(hir) => {
+            pat_range(span, c, hir, false_label, load)?;
+            Ok(true)
+        }
         _ => Err(CompileError::new(
             hir,
             CompileErrorKind::UnsupportedPatternExpr,
@@ -323,6 +329,168 @@ fn pat(
     }
 }
 
+/// Assemble an or-pattern.
+///
+/// Each alternative is matched in a scope of its own. The variables bound by
+/// the alternative which matched are collected into a tuple, so that they can
+/// be declared in the same order no matter which alternative matched.
+#[instrument]
+fn pat_or(
+    span: Span,
+    c: &mut Assembler<'_>,
+    hir: &[hir::Pat<'_>],
+    false_label: Label,
+    load: &dyn Fn(&mut Assembler<'_>, Needs) -> CompileResult<()>,
+) -> CompileResult<bool> {
+    load(c, Needs::Value)?;
+    let offset = c.scopes.decl_anon(span)?;
+
+    let matched_label = c.asm.new_label("pat_or_matched");
+    let mut expected = None::<Vec<(String, usize)>>;
+    let mut can_fail = false;
+
+    for hir in hir {
+        let span = hir.span();
+        let next_label = c.asm.new_label("pat_or_next");
+
+        let guard = c.scopes.push_child(span)?;
+
+        let load = move |c: &mut Assembler<'_>, needs: Needs| {
+            if needs.value() {
+                c.asm.push(Inst::Copy { offset }, span);
+            }
+
+            Ok(())
+        };
+
+        can_fail = pat(hir, c, next_label, &load)?;
+
+        let names = c.scopes.local_names(span)?;
+        pat_or_check_names(span, expected.as_deref(), &names)?;
+
+        let scope = c.scopes.pop(guard, span)?;
+
+        if names.is_empty() {
+            c.locals_pop(scope.local_var_count, span);
+        } else {
+            for (_, offset) in &names {
+                c.asm.push(Inst::Copy { offset: *offset }, span);
+            }
+
+            c.asm.push(Inst::Tuple { count: names.len() }, span);
+            c.locals_clean(scope.local_var_count, span);
+        }
+
+        c.asm.jump(matched_label, span);
+        c.asm.label(next_label)?;
+
+        if expected.is_none() {
+            expected = Some(names);
+        }
+    }
+
+    if can_fail {
+        c.locals_pop(c.scopes.local_var_count(span)?, span);
+        c.asm.jump(false_label, span);
+    }
+
+    c.asm.label(matched_label)?;
+
+    let names = expected.unwrap_or_default();
+
+    if !names.is_empty() {
+        let offset = c.scopes.decl_anon(span)?;
+
+        for (index, (name, _)) in names.iter().enumerate() {
+            c.asm.push(Inst::TupleIndexGetAt { offset, index }, span);
+            let offset = c.scopes.decl_var(name, span)?;
+            c.asm.variable(name, offset);
+        }
+    }
+
+    Ok(can_fail)
+}
+
+/// Check that an alternative of an or-pattern binds the same variables as the
+/// first one.
+fn pat_or_check_names(
+    span: Span,
+    expected: Option<&[(String, usize)]>,
+    names: &[(String, usize)],
+) -> CompileResult<()> {
+    let expected = match expected {
+        Some(expected) => expected,
+        None => return Ok(()),
+    };
+
+    let missing = expected
+        .iter()
+        .find(|(name, _)| !names.iter().any(|(n, _)| n == name))
+        .or_else(|| {
+            names
+                .iter()
+                .find(|(name, _)| !expected.iter().any(|(n, _)| n == name))
+        });
+
+    if let Some((name, _)) = missing {
+        return Err(CompileError::new(
+            span,
+            CompileErrorKind::VariableNotBoundInAllPatterns { name: name.clone() },
+        ));
+    }
+
+    Ok(())
+}
+
+/// Assemble a range pattern.
+#[instrument]
+fn pat_range(
+    span: Span,
+    c: &mut Assembler<'_>,
+    hir: &hir::PatRange<'_>,
+    false_label: Label,
+    load: &dyn Fn(&mut Assembler<'_>, Needs) -> CompileResult<()>,
+) -> CompileResult<()> {
+    let start = match hir.start {
+        Some(hir) => Some(pat_range_bound(c, hir)?),
+        None => None,
+    };
+
+    let end = match hir.end {
+        Some(hir) => Some(pat_range_bound(c, hir)?),
+        None => None,
+    };
+
+    let limits = match hir.limits {
+        hir::ExprRangeLimits::HalfOpen => InstRangeLimits::HalfOpen,
+        hir::ExprRangeLimits::Closed => InstRangeLimits::Closed,
+    };
+
+    load(c, Needs::Value)?;
+    c.asm.push(Inst::MatchRange { start, end, limits }, span);
+    c.asm
+        .pop_and_jump_if_not(c.scopes.local_var_count(span)?, false_label, span);
+    Ok(())
+}
+
+/// Resolve the bound of a range pattern, which must be an integer, character
+/// or byte literal.
+fn pat_range_bound(c: &mut Assembler<'_>, hir: &hir::Expr<'_>) -> CompileResult<InstValue> {
+    let span = hir.span();
+
+    Ok(match pat_lit_inst(span, c, hir)? {
+        Some(Inst::EqInteger { integer }) => InstValue::Integer(integer),
+        Some(Inst::EqChar { char }) => InstValue::Char(char),
+        Some(Inst::EqByte { byte }) => InstValue::Byte(byte),
+        _ => {
+            return Err(CompileError::new(
+                span,
+                CompileErrorKind::UnsupportedPatternExpr,
+            ))
+        }
+    })
+}
+
 /// Assemble a pattern literal.
 #[instrument]
 fn pat_lit(
@@ -438,6 +606,48 @@ fn condition(
                 c.asm.jump(then_label, span);
             };
 
+            let scope = c.scopes.pop(expected, span)?;
+            Ok(scope)
+        }
+        hir::Condition::Chain(chain) => {
+            let span = chain.span();
+
+            let false_label = c.asm.new_label("if_condition_false");
+
+            let scope = c.scopes.child(span)?;
+            let expected = c.scopes.push(scope);
+
+            // NB: every link in the chain jumps to the false label on failure,
+            // cleaning up all the variables bound so far.
+            for condition in chain.conditions {
+                match condition {
+                    hir::Condition::ExprLet(expr_let) => {
+                        let load = |c: &mut Assembler<'_>, needs: Needs| {
+                            expr(expr_let.expr, c, needs)?.apply(c)?;
+                            Ok(())
+                        };
+
+                        pat(expr_let.pat, c, false_label, &load)?;
+                    }
+                    hir::Condition::Expr(e) => {
+                        let span = e.span();
+
+                        expr(e, c, Needs::Value)?.apply(c)?;
+                        let count = c.scopes.local_var_count(span)?;
+                        c.asm.pop_and_jump_if_not(count, false_label, span);
+                    }
+                    hir::Condition::Chain(chain) => {
+                        return Err(CompileError::msg(
+                            chain,
+                            "nested condition chains are not supported",
+                        ));
+                    }
+                }
+            }
+
+            c.asm.jump(then_label, span);
+            c.asm.label(false_label)?;
+
             let scope = c.scopes.pop(expected, span)?;
             Ok(scope)
         }
@@ -819,7 +1029,8 @@ fn pat_object(
             }
             Binding::Ident(_, key) => {
                 c.asm.push(Inst::ObjectIndexGetAt { offset, slot }, span);
-                c.scopes.decl_var(key, span)?;
+                let offset = c.scopes.decl_var(key, span)?;
+                c.asm.variable(key, offset);
             }
         }
     }
@@ -883,7 +1094,8 @@ pub(crate) fn closure_from_block(
     let guard = c.scopes.push_child(span)?;
 
     for capture in captures {
-        c.scopes.new_var(&capture.ident, span)?;
+        let offset = c.scopes.new_var(&capture.ident, span)?;
+        c.asm.variable(&capture.ident, offset);
     }
 
     return_(c, span, hir, block)?;
@@ -1101,6 +1313,38 @@ fn const_(
 
             c.asm.push(Inst::integer(n), span);
         }
+        ConstValue::U16(n) => {
+            c.asm.push(
+                Inst::Push {
+                    value: InstValue::U16(*n),
+                },
+                span,
+            );
+        }
+        ConstValue::U32(n) => {
+            c.asm.push(
+                Inst::Push {
+                    value: InstValue::U32(*n),
+                },
+                span,
+            );
+        }
+        ConstValue::U64(n) => {
+            c.asm.push(
+                Inst::Push {
+                    value: InstValue::U64(*n),
+                },
+                span,
+            );
+        }
+        ConstValue::I32(n) => {
+            c.asm.push(
+                Inst::Push {
+                    value: InstValue::I32(*n),
+                },
+                span,
+            );
+        }
         ConstValue::Float(n) => {
             c.asm.push(Inst::float(*n), span);
         }
@@ -1968,6 +2212,19 @@ pub(crate) fn closure_from_expr_closure(
                 let offset = c.scopes.decl_anon(pat.span())?;
                 patterns.push((pat, offset));
             }
+            hir::FnArg::Typed(pat, ty) => {
+                let offset = c.scopes.decl_anon(pat.span())?;
+                let hash = type_hash(ty, c)?;
+                c.asm.push(
+                    Inst::CheckArgType {
+                        offset,
+                        arg: offset,
+                        hash,
+                    },
+                    arg.span(),
+                );
+                patterns.push((pat, offset));
+            }
         }
     }
 
@@ -1975,7 +2232,8 @@ pub(crate) fn closure_from_expr_closure(
         c.asm.push(Inst::PushTuple, span);
 
         for capture in captures {
-            c.scopes.new_var(&capture.ident, span)?;
+            let offset = c.scopes.new_var(&capture.ident, span)?;
+            c.asm.variable(&capture.ident, offset);
         }
     }
 
@@ -2503,97 +2761,6 @@ fn expr_let(hir: &hir::ExprLet<'_>, c: &mut Assembler<'_>, needs: Needs) -> Comp
     Ok(Asm::top(span))
 }
 
-#[instrument]
-fn expr_match(
-    span: Span,
-    c: &mut Assembler<'_>,
-    hir: &hir::ExprMatch<'_>,
-    needs: Needs,
-) -> CompileResult<Asm> {
-    let expected_scopes = c.scopes.push_child(span)?;
-
-    expr(hir.expr, c, Needs::Value)?.apply(c)?;
-    // Offset of the expression.
-    let offset = c.scopes.decl_anon(span)?;
-
-    let end_label = c.asm.new_label("match_end");
-    let mut branches = Vec::new();
-
-    for branch in hir.branches {
-        let span = branch.span();
-
-        let branch_label = c.asm.new_label("match_branch");
-        let match_false = c.asm.new_label("match_false");
-
-        let scope = c.scopes.child(span)?;
-        let parent_guard = c.scopes.push(scope);
-
-        let load = move |this: &mut Assembler, needs: Needs| {
-            if needs.value() {
-                this.asm.push(Inst::Copy { offset }, span);
-            }
-
-            Ok(())
-        };
-
-        pat(branch.pat, c, match_false, &load)?;
-
-        let scope = if let Some(condition) = branch.condition {
-            let span = condition.span();
-
-            let scope = c.scopes.child(span)?;
-            let guard = c.scopes.push(scope);
-
-            expr(condition, c, Needs::Value)?.apply(c)?;
-            c.clean_last_scope(span, guard, Needs::Value)?;
-            let scope = c.scopes.pop(parent_guard, span)?;
-
-            c.asm
-                .pop_and_jump_if_not(scope.local_var_count, match_false, span);
-
-            c.asm.jump(branch_label, span);
-            scope
-        } else {
-            c.scopes.pop(parent_guard, span)?
-        };
-
-        c.asm.jump(branch_label, span);
-        c.asm.label(match_false)?;
-
-        branches.push((branch_label, scope));
-    }
-
-    // what to do in case nothing matches and the pattern doesn't have any
-    // default match branch.
-    if needs.value() {
-        c.asm.push(Inst::unit(), span);
-    }
-
-    c.asm.jump(end_label, span);
-
-    let mut it = hir.branches.iter().zip(&branches).peekable();
-
-    while let Some((branch, (label, scope))) = it.next() {
-        let span = branch.span();
-
-        c.asm.label(*label)?;
-
-        let expected = c.scopes.push(scope.clone());
-        expr(branch.body, c, needs)?.apply(c)?;
-        c.clean_last_scope(span, expected, needs)?;
-
-        if it.peek().is_some() {
-            c.asm.jump(end_label, span);
-        }
-    }
-
-    c.asm.label(end_label)?;
-
-    // pop the implicit scope where we store the anonymous match variable.
-    c.clean_last_scope(span, expected_scopes, needs)?;
-    Ok(Asm::top(span))
-}
-
 /// Compile a literal object.
 #[instrument]
 fn expr_object(
@@ -2966,7 +3133,8 @@ fn expr_select(
                     named.assert_not_generic()?;
 
                     if let Some(local) = named.as_local() {
-                        c.scopes.decl_var(local, path.span())?;
+                        let offset = c.scopes.decl_var(local, path.span())?;
+                        c.asm.variable(local, offset);
                         break;
                     }
                 }
@@ -3132,6 +3300,10 @@ fn expr_unary(
 
                 c.asm.push(Inst::integer(n), span);
             }
+            ast::Number::Sized(int, suffix) => {
+                let value = sized_integer(span, &int.neg(), suffix)?;
+                c.asm.push(Inst::Push { value }, span);
+            }
         }
 
         return Ok(Asm::top(span));
@@ -3278,6 +3450,10 @@ pub(crate) fn fn_from_item_fn(
 ) -> CompileResult<()> {
     let span = hir.span();
 
+    if c.options.type_check {
+        typeck::item_fn(hir, c)?;
+    }
+
     let mut patterns = Vec::new();
     let mut first = true;
 
@@ -3288,12 +3464,26 @@ pub(crate) fn fn_from_item_fn(
                     return Err(CompileError::new(*span, CompileErrorKind::UnsupportedSelf));
                 }
 
-                c.scopes.new_var(SELF, *span)?;
+                let offset = c.scopes.new_var(SELF, *span)?;
+                c.asm.variable(SELF, offset);
             }
             hir::FnArg::Pat(pat) => {
                 let offset = c.scopes.decl_anon(pat.span())?;
                 patterns.push((pat, offset));
             }
+            hir::FnArg::Typed(pat, ty) => {
+                let offset = c.scopes.decl_anon(pat.span())?;
+                let hash = type_hash(ty, c)?;
+                c.asm.push(
+                    Inst::CheckArgType {
+                        offset,
+                        arg: offset,
+                        hash,
+                    },
+                    arg.span(),
+                );
+                patterns.push((pat, offset));
+            }
         }
 
         first = false;
@@ -3303,6 +3493,14 @@ pub(crate) fn fn_from_item_fn(
         pat_with_offset(pat, c, offset)?;
     }
 
+    if let Some(output) = hir.output {
+        let hash = type_hash(output, c)?;
+
+        if let Some(hir::Stmt::Expr(tail)) = hir.body.statements.last() {
+            check_static_type(output, hash, tail, c)?;
+        }
+    }
+
     if hir.body.statements.is_empty() {
         let total_var_count = c.scopes.total_var_count(span)?;
         c.locals_pop(total_var_count, span);
@@ -3324,6 +3522,110 @@ pub(crate) fn fn_from_item_fn(
     Ok(())
 }
 
+/// Resolve the type hash of a type annotation.
+fn type_hash(hir: &hir::Type<'_>, c: &mut Assembler<'_>) -> CompileResult<Hash> {
+    match hir.kind {
+        hir::TypeKind::Path(path) => {
+            let named = c.convert_path(path)?;
+            named.assert_not_generic()?;
+
+            let meta = c.lookup_meta(path.span(), named.item)?;
+
+            match &meta.kind {
+                PrivMetaKind::Unknown { type_hash, .. }
+                | PrivMetaKind::Struct { type_hash, .. }
+                | PrivMetaKind::Enum { type_hash, .. } => Ok(*type_hash),
+                _ => Err(CompileError::expected_meta(
+                    path,
+                    meta.info(c.q.pool),
+                    "type",
+                )),
+            }
+        }
+        hir::TypeKind::Tuple([]) => Ok(crate::runtime::UNIT_TYPE.hash),
+        hir::TypeKind::Tuple(..) => Ok(crate::runtime::TUPLE_TYPE.hash),
+    }
+}
+
+/// Check that an expression matches the type annotation it's assigned to.
+///
+/// This only does something if static type checking is enabled, and only for
+/// expressions whose type is known without running them.
+fn check_static_type(
+    ty: &hir::Type<'_>,
+    hash: Hash,
+    hir: &hir::Expr<'_>,
+    c: &mut Assembler<'_>,
+) -> CompileResult<()> {
+    if !c.options.static_types {
+        return Ok(());
+    }
+
+    let (actual_hash, actual) = match static_type(hir, c)? {
+        Some(actual) => actual,
+        None => return Ok(()),
+    };
+
+    if actual_hash != hash {
+        let expected = c.q.sources.source(c.source_id, ty.span()).unwrap_or("?");
+
+        return Err(CompileError::new(
+            hir,
+            CompileErrorKind::TypeMismatch {
+                expected: expected.into(),
+                actual,
+            },
+        ));
+    }
+
+    Ok(())
+}
+
+/// Get the type of an expression if it's known without running it, like for
+/// literals and constructors.
+fn static_type(
+    hir: &hir::Expr<'_>,
+    c: &mut Assembler<'_>,
+) -> CompileResult<Option<(Hash, Box<str>)>> {
+    use crate::runtime::{self as rt, StaticType};
+
+    let ty: &StaticType = match hir.kind {
+        hir::ExprKind::Lit(lit) => typeck::lit(lit, c)?,
+        hir::ExprKind::Group(hir) => return static_type(hir, c),
+        hir::ExprKind::Unary(hir::ExprUnary {
+            op: ast::UnOp::Neg(..),
+            expr,
+        }) => return static_type(expr, c),
+        hir::ExprKind::Tuple(seq) if seq.items.is_empty() => rt::UNIT_TYPE,
+        hir::ExprKind::Tuple(..) => rt::TUPLE_TYPE,
+        hir::ExprKind::Vec(..) => rt::VEC_TYPE,
+        hir::ExprKind::MacroCall(hir::MacroCall::Template(..)) => rt::STRING_TYPE,
+        hir::ExprKind::Object(hir::ExprObject { path: None, .. }) => rt::OBJECT_TYPE,
+        hir::ExprKind::Object(hir::ExprObject {
+            path: Some(path), ..
+        }) => {
+            let named = c.convert_path(path)?;
+            let meta = c.lookup_meta(path.span(), named.item)?;
+
+            return Ok(match &meta.kind {
+                PrivMetaKind::Struct { type_hash, .. } => Some((
+                    *type_hash,
+                    c.q.pool.item(meta.item_meta.item).to_string().into(),
+                )),
+                PrivMetaKind::Variant {
+                    enum_hash,
+                    enum_item,
+                    ..
+                } => Some((*enum_hash, c.q.pool.item(*enum_item).to_string().into())),
+                _ => None,
+            });
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some((ty.hash, ty.name.to_string().into())))
+}
+
 /// Assemble a literal value.
 #[instrument]
 fn lit(hir: &ast::Lit, c: &mut Assembler<'_>, needs: Needs) -> CompileResult<Asm> {
@@ -3410,22 +3712,68 @@ fn lit_number(hir: &ast::LitNumber, c: &mut Assembler<'_>, needs: Needs) -> Comp
 
             c.asm.push(Inst::integer(n), span);
         }
+        ast::Number::Sized(number, suffix) => {
+            let value = sized_integer(span, &number, suffix)?;
+            c.asm.push(Inst::Push { value }, span);
+        }
     }
 
     Ok(Asm::top(span))
 }
 
+/// Convert a sized integer literal into a value, checking that it's in bounds.
+fn sized_integer(
+    span: Span,
+    number: &num::BigInt,
+    suffix: ast::NumberSuffix,
+) -> CompileResult<InstValue> {
+    let value = match suffix {
+        ast::NumberSuffix::U8 => number.to_u8().map(InstValue::Byte),
+        ast::NumberSuffix::U16 => number.to_u16().map(InstValue::U16),
+        ast::NumberSuffix::U32 => number.to_u32().map(InstValue::U32),
+        ast::NumberSuffix::U64 => number.to_u64().map(InstValue::U64),
+        ast::NumberSuffix::I32 => number.to_i32().map(InstValue::I32),
+    };
+
+    match value {
+        Some(value) => Ok(value),
+        None => {
+            let (min, max) = suffix.bounds();
+
+            Err(CompileError::new(
+                span,
+                ParseErrorKind::BadSizedNumberOutOfBounds { suffix, min, max },
+            ))
+        }
+    }
+}
+
 /// Assemble a local expression.
 #[instrument]
 fn local(hir: &hir::Local<'_>, c: &mut Assembler<'_>, needs: Needs) -> CompileResult<Asm> {
     let span = hir.span();
 
+    if let Some(ty) = hir.ty {
+        let hash = type_hash(ty, c)?;
+        check_static_type(ty, hash, hir.expr, c)?;
+    }
+
     let load = |c: &mut Assembler, needs: Needs| {
         // NB: assignments "move" the value being assigned.
         expr(hir.expr, c, needs)?.apply(c)?;
         Ok(())
     };
 
+    if let Some(expr_else) = hir.expr_else {
+        local_else(span, hir.pat, expr_else, c, &load)?;
+
+        if needs.value() {
+            c.asm.push(Inst::unit(), span);
+        }
+
+        return Ok(Asm::top(span));
+    }
+
     let false_label = c.asm.new_label("let_panic");
 
     if pat(hir.pat, c, false_label, &load)? {
@@ -3452,3 +3800,238 @@ fn local(hir: &hir::Local<'_>, c: &mut Assembler<'_>, needs: Needs) -> CompileRe
 
     Ok(Asm::top(span))
 }
+
+/// Assemble a `let` with a diverging `else` block.
+///
+/// The pattern is matched in a child scope, so that a failed match only cleans
+/// up what it declared itself before running the `else` block. The bound
+/// variables are then moved out through a tuple and redeclared in the current
+/// scope.
+fn local_else(
+    span: Span,
+    hir: &hir::Pat<'_>,
+    expr_else: &hir::ExprElse<'_>,
+    c: &mut Assembler<'_>,
+    load: &dyn Fn(&mut Assembler<'_>, Needs) -> CompileResult<()>,
+) -> CompileResult<()> {
+    if !block_diverges(expr_else.block, c)? {
+        return Err(CompileError::new(
+            expr_else,
+            CompileErrorKind::LetElseMustDiverge,
+        ));
+    }
+
+    let else_label = c.asm.new_label("let_else");
+    let ok_label = c.asm.new_label("let_ok");
+
+    let guard = c.scopes.push_child(span)?;
+    let can_fail = pat(hir, c, else_label, load)?;
+    let names = c.scopes.local_names(span)?;
+    let scope = c.scopes.pop(guard, span)?;
+
+    if names.is_empty() {
+        c.locals_pop(scope.local_var_count, span);
+    } else {
+        for (_, offset) in &names {
+            c.asm.push(Inst::Copy { offset: *offset }, span);
+        }
+
+        c.asm.push(Inst::Tuple { count: names.len() }, span);
+        c.locals_clean(scope.local_var_count, span);
+    }
+
+    if can_fail {
+        c.asm.jump(ok_label, span);
+        c.asm.label(else_label)?;
+        block(expr_else.block, c, Needs::None)?.apply(c)?;
+        c.asm.label(ok_label)?;
+    } else {
+        c.diagnostics
+            .not_used(c.source_id, expr_else.span(), c.context());
+    }
+
+    if !names.is_empty() {
+        let offset = c.scopes.decl_anon(span)?;
+
+        for (index, (name, _)) in names.iter().enumerate() {
+            c.asm.push(Inst::TupleIndexGetAt { offset, index }, span);
+            let offset = c.scopes.decl_var(name, span)?;
+            c.asm.variable(name, offset);
+        }
+    }
+
+    Ok(())
+}
+
+/// Test if the given block unconditionally diverges.
+fn block_diverges(hir: &hir::Block<'_>, c: &mut Assembler<'_>) -> CompileResult<bool> {
+    for stmt in hir.statements {
+        let diverges = match *stmt {
+            hir::Stmt::Local(local) => expr_diverges(local.expr, c)?,
+            hir::Stmt::Expr(hir) | hir::Stmt::Semi(hir) => expr_diverges(hir, c)?,
+            hir::Stmt::Item(..) => false,
+        };
+
+        if diverges {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Test if the given expression unconditionally diverges.
+fn expr_diverges(hir: &hir::Expr<'_>, c: &mut Assembler<'_>) -> CompileResult<bool> {
+    Ok(match hir.kind {
+        hir::ExprKind::Return(..) | hir::ExprKind::Break(..) | hir::ExprKind::Continue(..) => true,
+        hir::ExprKind::Group(hir) => expr_diverges(hir, c)?,
+        hir::ExprKind::Block(hir) => {
+            matches!(hir.kind, hir::ExprBlockKind::Default) && block_diverges(hir.block, c)?
+        }
+        hir::ExprKind::If(hir) => {
+            let expr_else = match hir.expr_else {
+                Some(expr_else) => expr_else,
+                None => return Ok(false),
+            };
+
+            if !block_diverges(hir.block, c)? || !block_diverges(expr_else.block, c)? {
+                return Ok(false);
+            }
+
+            for branch in hir.expr_else_ifs {
+                if !block_diverges(branch.block, c)? {
+                    return Ok(false);
+                }
+            }
+
+            true
+        }
+        hir::ExprKind::Match(hir) => {
+            if expr_diverges(hir.expr, c)? {
+                return Ok(true);
+            }
+
+            if hir.branches.is_empty() {
+                return Ok(false);
+            }
+
+            for branch in hir.branches {
+                if !expr_diverges(branch.body, c)? {
+                    return Ok(false);
+                }
+            }
+
+            true
+        }
+        hir::ExprKind::Call(call) => {
+            let path = match call.expr.kind {
+                hir::ExprKind::Path(path) => path,
+                _ => return Ok(false),
+            };
+
+            let named = c.convert_path(path)?;
+
+            let panic = Hash::type_hash(&ItemBuf::with_crate_item("std", ["panic"]));
+
+            match c.try_lookup_meta(path.span(), named.item)? {
+                Some(meta) => matches!(
+                    meta.kind,
+                    PrivMetaKind::Function { type_hash, .. } if type_hash == panic
+                ),
+                None => false,
+            }
+        }
+        _ => false,
+    })
+}
+
+#[instrument]
+fn expr_match(
+    span: Span,
+    c: &mut Assembler<'_>,
+    hir: &hir::ExprMatch<'_>,
+    needs: Needs,
+) -> CompileResult<Asm> {
+    let expected_scopes = c.scopes.push_child(span)?;
+
+    expr(hir.expr, c, Needs::Value)?.apply(c)?;
+    // Offset of the expression.
+    let offset = c.scopes.decl_anon(span)?;
+
+    let end_label = c.asm.new_label("match_end");
+    let mut branches = Vec::new();
+
+    for branch in hir.branches {
+        let span = branch.span();
+
+        let branch_label = c.asm.new_label("match_branch");
+        let match_false = c.asm.new_label("match_false");
+
+        let scope = c.scopes.child(span)?;
+        let parent_guard = c.scopes.push(scope);
+
+        let load = move |this: &mut Assembler, needs: Needs| {
+            if needs.value() {
+                this.asm.push(Inst::Copy { offset }, span);
+            }
+
+            Ok(())
+        };
+
+        pat(branch.pat, c, match_false, &load)?;
+
+        let scope = if let Some(condition) = branch.condition {
+            let span = condition.span();
+
+            let scope = c.scopes.child(span)?;
+            let guard = c.scopes.push(scope);
+
+            expr(condition, c, Needs::Value)?.apply(c)?;
+            c.clean_last_scope(span, guard, Needs::Value)?;
+            let scope = c.scopes.pop(parent_guard, span)?;
+
+            c.asm
+                .pop_and_jump_if_not(scope.local_var_count, match_false, span);
+
+            c.asm.jump(branch_label, span);
+            scope
+        } else {
+            c.scopes.pop(parent_guard, span)?
+        };
+
+        c.asm.jump(branch_label, span);
+        c.asm.label(match_false)?;
+
+        branches.push((branch_label, scope));
+    }
+
+    // what to do in case nothing matches and the pattern doesn't have any
+    // default match branch.
+    if needs.value() {
+        c.asm.push(Inst::unit(), span);
+    }
+
+    c.asm.jump(end_label, span);
+
+    let mut it = hir.branches.iter().zip(&branches).peekable();
+
+    while let Some((branch, (label, scope))) = it.next() {
+        let span = branch.span();
+
+        c.asm.label(*label)?;
+
+        let expected = c.scopes.push(scope.clone());
+        expr(branch.body, c, needs)?.apply(c)?;
+        c.clean_last_scope(span, expected, needs)?;
+
+        if it.peek().is_some() {
+            c.asm.jump(end_label, span);
+        }
+    }
+
+    c.asm.label(end_label)?;
+
+    // pop the implicit scope where we store the anonymous match variable.
+    c.clean_last_scope(span, expected_scopes, needs)?;
+    Ok(Asm::top(span))
+}
@@ -11,6 +11,7 @@ use crate::{Context, Diagnostics, SourceId};
 pub(crate) mod assemble;
 mod loops;
 mod scopes;
+mod typeck;
 
 pub(crate) use self::loops::{Loop, Loops};
 pub(crate) use self::scopes::{Scope, ScopeGuard, Scopes, Var};
@@ -147,6 +148,16 @@ impl<'a> Assembler<'a> {
         Ok(())
     }
 
+    /// Report the variables declared while assembling to the visitor,
+    /// together with the span of the scope they're declared in.
+    pub(crate) fn visit_declared_vars(&mut self) {
+        for (var, scope) in self.scopes.take_declared() {
+            self.q
+                .visitor
+                .visit_variable_decl(self.source_id, var, scope);
+        }
+    }
+
     /// Get the latest relevant warning context.
     pub(crate) fn context(&self) -> Option<Span> {
         self.contexts.last().copied()
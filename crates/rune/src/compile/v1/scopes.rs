@@ -49,8 +49,13 @@ impl Var {
 
 #[derive(Debug, Clone)]
 pub(crate) struct Scope {
+    /// The span of the code which the scope covers.
+    span: Span,
     /// Named variables.
     locals: HashMap<String, Var>,
+    /// Where all variables in the scope are declared, including the ones
+    /// which have been shadowed.
+    declared: Vec<Span>,
     /// The number of variables.
     pub(crate) total_var_count: usize,
     /// The number of variables local to this scope.
@@ -59,18 +64,22 @@ pub(crate) struct Scope {
 
 impl Scope {
     /// Construct a new locals handlers.
-    fn new() -> Scope {
+    fn new(span: Span) -> Scope {
         Self {
+            span,
             locals: HashMap::new(),
+            declared: Vec::new(),
             total_var_count: 0,
             local_var_count: 0,
         }
     }
 
     /// Construct a new child scope.
-    fn child(&self) -> Self {
+    fn child(&self, span: Span) -> Self {
         Self {
+            span,
             locals: HashMap::new(),
+            declared: Vec::new(),
             total_var_count: self.total_var_count,
             local_var_count: 0,
         }
@@ -88,6 +97,7 @@ impl Scope {
 
         self.total_var_count += 1;
         self.local_var_count += 1;
+        self.declared.push(span);
 
         if let Some(old) = self.locals.insert(name.to_owned(), local) {
             return Err(CompileError::new(
@@ -119,6 +129,7 @@ impl Scope {
 
         self.total_var_count += 1;
         self.local_var_count += 1;
+        self.declared.push(span);
         offset
     }
 
@@ -190,13 +201,17 @@ pub(crate) struct ScopeGuard(usize);
 
 pub(crate) struct Scopes {
     scopes: Vec<Scope>,
+    /// Variables in scopes which have been popped, together with the span of
+    /// the scope they're declared in.
+    declared: Vec<(Span, Span)>,
 }
 
 impl Scopes {
-    /// Construct a new collection of scopes.
-    pub(crate) fn new() -> Self {
+    /// Construct a new collection of scopes for code covering the given span.
+    pub(crate) fn new(span: Span) -> Self {
         Self {
-            scopes: vec![Scope::new()],
+            scopes: vec![Scope::new(span)],
+            declared: Vec::new(),
         }
     }
 
@@ -326,23 +341,45 @@ impl Scopes {
 
     /// Pop the last scope and compare with the expected length.
     pub(crate) fn pop_unchecked(&mut self, span: Span) -> CompileResult<Scope> {
-        let scope = self
+        let mut scope = self
             .scopes
             .pop()
             .ok_or_else(|| CompileError::msg(span, "missing parent scope"))?;
 
+        // NB: a scope might be pushed again after it's been popped, like the
+        // scope of an `if let` condition which also covers its block.
+        scope.span = scope.span.join(span);
+
+        for var in &scope.declared {
+            self.declared.push((*var, scope.span));
+        }
+
         Ok(scope)
     }
 
+    /// Take the variables which have been declared so far, together with the
+    /// span of the scope they're declared in.
+    pub(crate) fn take_declared(&mut self) -> Vec<(Span, Span)> {
+        let mut declared = std::mem::take(&mut self.declared);
+
+        for scope in &self.scopes {
+            for var in &scope.declared {
+                declared.push((*var, scope.span));
+            }
+        }
+
+        declared
+    }
+
     /// Construct a new child scope and return its guard.
     pub(crate) fn push_child(&mut self, span: Span) -> CompileResult<ScopeGuard> {
-        let scope = self.last(span)?.child();
+        let scope = self.last(span)?.child(span);
         Ok(self.push(scope))
     }
 
     /// Construct a new child scope.
     pub(crate) fn child(&mut self, span: Span) -> CompileResult<Scope> {
-        Ok(self.last(span)?.child())
+        Ok(self.last(span)?.child(span))
     }
 
     /// Get the local var count of the top scope.
@@ -350,6 +387,20 @@ impl Scopes {
         Ok(self.last(span)?.local_var_count)
     }
 
+    /// Get the names and offsets of the variables declared in the top scope,
+    /// sorted by name.
+    pub(crate) fn local_names(&self, span: Span) -> CompileResult<Vec<(String, usize)>> {
+        let mut names = self
+            .last(span)?
+            .locals
+            .iter()
+            .map(|(name, var)| (name.clone(), var.offset))
+            .collect::<Vec<_>>();
+
+        names.sort();
+        Ok(names)
+    }
+
     /// Get the total var count of the top scope.
     pub(crate) fn total_var_count(&self, span: Span) -> CompileResult<usize> {
         Ok(self.last(span)?.total_var_count)
@@ -0,0 +1,787 @@
+//! A gradual type checker which runs over HIR.
+//!
+//! The types of expressions are inferred where they are known at compile
+//! time, like for literals, constructors and annotated bindings. Anything else
+//! is left unknown and never reported on, so only obvious mistakes are
+//! reported and they are reported as warnings.
+
+use crate::ast;
+use crate::ast::{Span, Spanned};
+use crate::collections::{HashMap, HashSet};
+use crate::compile::v1::Assembler;
+use crate::compile::{
+    CompileResult, ContextSignature, ItemId, PrivMeta, PrivMetaKind, PrivVariantMeta,
+};
+use crate::hir;
+use crate::parse::Resolve;
+use crate::runtime::{self as rt, Protocol, StaticType};
+use crate::Hash;
+
+/// A type which has been inferred.
+#[derive(Debug, Clone)]
+struct Ty {
+    /// The type hash of the type.
+    hash: Hash,
+    /// The item the type is declared as, if any.
+    item: Option<ItemId>,
+    /// The name of the type as it's reported.
+    name: Box<str>,
+}
+
+impl Ty {
+    fn from_static(ty: &StaticType) -> Self {
+        Self {
+            hash: ty.hash,
+            item: None,
+            name: ty.name.to_string().into(),
+        }
+    }
+
+    fn is(&self, ty: &StaticType) -> bool {
+        self.hash == ty.hash
+    }
+}
+
+/// Test if the given type is one of the integer types.
+fn is_integer(ty: &Ty) -> bool {
+    [
+        rt::INTEGER_TYPE,
+        rt::BYTE_TYPE,
+        rt::U16_TYPE,
+        rt::U32_TYPE,
+        rt::U64_TYPE,
+        rt::I32_TYPE,
+    ]
+    .iter()
+    .any(|t| ty.is(t))
+}
+
+/// Type check the given function.
+pub(crate) fn item_fn(hir: &hir::ItemFn<'_>, c: &mut Assembler<'_>) -> CompileResult<()> {
+    let mut cx = TypeCheck {
+        c,
+        scopes: Vec::new(),
+        assigned: HashSet::new(),
+        report: false,
+    };
+
+    // NB: The first pass collects every variable that is assigned to, since
+    // their types can't be known without flow analysis. The second pass
+    // reports.
+    cx.item_fn(hir)?;
+    cx.report = true;
+    cx.item_fn(hir)?;
+    Ok(())
+}
+
+/// Get the type of a literal.
+pub(crate) fn lit(hir: &ast::Lit, c: &mut Assembler<'_>) -> CompileResult<&'static StaticType> {
+    Ok(match hir {
+        ast::Lit::Bool(..) => rt::BOOL_TYPE,
+        ast::Lit::Byte(..) => rt::BYTE_TYPE,
+        ast::Lit::Str(..) => rt::STRING_TYPE,
+        ast::Lit::ByteStr(..) => rt::BYTES_TYPE,
+        ast::Lit::Char(..) => rt::CHAR_TYPE,
+        ast::Lit::Number(number) => match number.resolve(resolve_context!(c.q))? {
+            ast::Number::Float(..) => rt::FLOAT_TYPE,
+            ast::Number::Integer(..) => rt::INTEGER_TYPE,
+            ast::Number::Sized(_, suffix) => match suffix {
+                ast::NumberSuffix::U8 => rt::BYTE_TYPE,
+                ast::NumberSuffix::U16 => rt::U16_TYPE,
+                ast::NumberSuffix::U32 => rt::U32_TYPE,
+                ast::NumberSuffix::U64 => rt::U64_TYPE,
+                ast::NumberSuffix::I32 => rt::I32_TYPE,
+            },
+        },
+    })
+}
+
+struct TypeCheck<'a, 'b> {
+    c: &'a mut Assembler<'b>,
+    /// Variables in scope and their types, if known.
+    scopes: Vec<HashMap<Box<str>, Option<Ty>>>,
+    /// Names of variables which are assigned to.
+    assigned: HashSet<Box<str>>,
+    /// If warnings should be reported.
+    report: bool,
+}
+
+impl TypeCheck<'_, '_> {
+    fn item_fn(&mut self, hir: &hir::ItemFn<'_>) -> CompileResult<()> {
+        self.scopes.push(HashMap::new());
+
+        for arg in hir.args {
+            self.fn_arg(arg)?;
+        }
+
+        self.block(hir.body)?;
+        self.scopes.pop();
+        Ok(())
+    }
+
+    fn fn_arg(&mut self, hir: &hir::FnArg<'_>) -> CompileResult<()> {
+        match *hir {
+            hir::FnArg::SelfValue(..) => (),
+            hir::FnArg::Pat(pat) => self.bind(pat, None)?,
+            hir::FnArg::Typed(pat, ty) => {
+                let ty = self.ty(ty)?;
+                self.bind(pat, ty)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Declare the variables bound by a pattern.
+    fn bind(&mut self, hir: &hir::Pat<'_>, ty: Option<Ty>) -> CompileResult<()> {
+        match hir.kind {
+            hir::PatKind::PatPath(path) => {
+                if let Some(ident) = path.try_as_ident() {
+                    let name: Box<str> = ident.resolve(resolve_context!(self.c.q))?.into();
+                    let ty = ty.filter(|_| !self.assigned.contains(&name));
+
+                    if let Some(scope) = self.scopes.last_mut() {
+                        scope.insert(name, ty);
+                    }
+                }
+            }
+            hir::PatKind::PatVec(items)
+            | hir::PatKind::PatTuple(items)
+            | hir::PatKind::PatObject(items) => {
+                for pat in items.items {
+                    self.bind(pat, None)?;
+                }
+            }
+            hir::PatKind::PatBinding(binding) => {
+                self.bind(binding.pat, None)?;
+            }
+            hir::PatKind::PatOr(pats) => {
+                for pat in pats {
+                    self.bind(pat, None)?;
+                }
+            }
+            hir::PatKind::PatIgnore
+            | hir::PatKind::PatRest
+            | hir::PatKind::PatLit(..)
+            | hir::PatKind::PatRange(..) => (),
+        }
+
+        Ok(())
+    }
+
+    /// Look up a variable, returning `None` if it's not in scope.
+    fn lookup(&self, name: &str) -> Option<Option<Ty>> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .cloned()
+    }
+
+    /// Get the type an annotation refers to.
+    fn ty(&mut self, hir: &hir::Type<'_>) -> CompileResult<Option<Ty>> {
+        let (hash, item) = match hir.kind {
+            hir::TypeKind::Path(path) => {
+                let named = self.c.convert_path(path)?;
+
+                let meta = match self.c.try_lookup_meta(path.span(), named.item)? {
+                    Some(meta) => meta,
+                    None => return Ok(None),
+                };
+
+                match meta.kind {
+                    PrivMetaKind::Unknown { type_hash } => (type_hash, None),
+                    PrivMetaKind::Struct { type_hash, .. } | PrivMetaKind::Enum { type_hash } => {
+                        (type_hash, Some(meta.item_meta.item))
+                    }
+                    _ => return Ok(None),
+                }
+            }
+            hir::TypeKind::Tuple([]) => return Ok(Some(Ty::from_static(rt::UNIT_TYPE))),
+            hir::TypeKind::Tuple(..) => return Ok(Some(Ty::from_static(rt::TUPLE_TYPE))),
+        };
+
+        let name = self
+            .c
+            .q
+            .sources
+            .source(self.c.source_id, hir.span())
+            .unwrap_or("?");
+
+        Ok(Some(Ty {
+            hash,
+            item,
+            name: name.into(),
+        }))
+    }
+
+    /// Get the type constructed through the given meta.
+    fn constructed(&self, meta: &PrivMeta) -> Option<Ty> {
+        let (hash, item) = match meta.kind {
+            PrivMetaKind::Struct { type_hash, .. } => (type_hash, meta.item_meta.item),
+            PrivMetaKind::Variant {
+                enum_hash,
+                enum_item,
+                ..
+            } => (enum_hash, enum_item),
+            _ => return None,
+        };
+
+        Some(Ty {
+            hash,
+            item: Some(item),
+            name: self.c.q.pool.item(item).to_string().into(),
+        })
+    }
+
+    fn block(&mut self, hir: &hir::Block<'_>) -> CompileResult<Option<Ty>> {
+        self.scopes.push(HashMap::new());
+
+        let mut last = None;
+
+        for stmt in hir.statements {
+            last = match *stmt {
+                hir::Stmt::Local(local) => {
+                    let ty = self.expr(local.expr)?;
+
+                    if let Some(expr_else) = local.expr_else {
+                        self.block(expr_else.block)?;
+                    }
+
+                    let ty = match local.ty {
+                        Some(ty) => self.ty(ty)?,
+                        None => ty,
+                    };
+
+                    self.bind(local.pat, ty)?;
+                    None
+                }
+                hir::Stmt::Expr(hir) => self.expr(hir)?,
+                hir::Stmt::Semi(hir) => {
+                    self.expr(hir)?;
+                    None
+                }
+                hir::Stmt::Item(..) => None,
+            };
+        }
+
+        self.scopes.pop();
+        Ok(last)
+    }
+
+    fn condition(&mut self, hir: &hir::Condition<'_>) -> CompileResult<()> {
+        match *hir {
+            hir::Condition::Expr(hir) => {
+                self.expr(hir)?;
+            }
+            hir::Condition::ExprLet(hir) => {
+                self.expr(hir.expr)?;
+                self.bind(hir.pat, None)?;
+            }
+            hir::Condition::Chain(hir) => {
+                for hir in hir.conditions {
+                    self.condition(hir)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn exprs(&mut self, hir: &[hir::Expr<'_>]) -> CompileResult<()> {
+        for hir in hir {
+            self.expr(hir)?;
+        }
+
+        Ok(())
+    }
+
+    fn expr(&mut self, hir: &hir::Expr<'_>) -> CompileResult<Option<Ty>> {
+        let ty = match hir.kind {
+            hir::ExprKind::Path(path) => return self.path(path),
+            hir::ExprKind::Assign(hir) => {
+                self.assigned(hir.lhs)?;
+                self.expr(hir.lhs)?;
+                self.expr(hir.rhs)?;
+                None
+            }
+            hir::ExprKind::Loop(hir) => {
+                self.scopes.push(HashMap::new());
+
+                if let Some(condition) = hir.condition {
+                    self.condition(condition)?;
+                }
+
+                self.block(hir.body)?;
+                self.scopes.pop();
+                None
+            }
+            hir::ExprKind::For(hir) => {
+                self.expr(hir.iter)?;
+                self.scopes.push(HashMap::new());
+                self.bind(hir.binding, None)?;
+                self.block(hir.body)?;
+                self.scopes.pop();
+                None
+            }
+            hir::ExprKind::Let(hir) => {
+                self.expr(hir.expr)?;
+                self.bind(hir.pat, None)?;
+                None
+            }
+            hir::ExprKind::If(hir) => {
+                self.scopes.push(HashMap::new());
+                self.condition(hir.condition)?;
+                self.block(hir.block)?;
+                self.scopes.pop();
+
+                for else_if in hir.expr_else_ifs {
+                    self.scopes.push(HashMap::new());
+                    self.condition(else_if.condition)?;
+                    self.block(else_if.block)?;
+                    self.scopes.pop();
+                }
+
+                if let Some(expr_else) = hir.expr_else {
+                    self.block(expr_else.block)?;
+                }
+
+                None
+            }
+            hir::ExprKind::Match(hir) => {
+                self.expr(hir.expr)?;
+
+                for branch in hir.branches {
+                    self.scopes.push(HashMap::new());
+                    self.bind(branch.pat, None)?;
+
+                    if let Some(condition) = branch.condition {
+                        self.expr(condition)?;
+                    }
+
+                    self.expr(branch.body)?;
+                    self.scopes.pop();
+                }
+
+                None
+            }
+            hir::ExprKind::Call(call) => return self.call(hir.span, call),
+            hir::ExprKind::FieldAccess(hir) => {
+                self.expr(hir.expr)?;
+                None
+            }
+            hir::ExprKind::Binary(binary) => return self.binary(hir.span, binary),
+            hir::ExprKind::Unary(hir) => {
+                let ty = self.expr(hir.expr)?;
+
+                match hir.op {
+                    ast::UnOp::Neg(..) => ty.filter(|ty| {
+                        ty.is(rt::INTEGER_TYPE) || ty.is(rt::I32_TYPE) || ty.is(rt::FLOAT_TYPE)
+                    }),
+                    ast::UnOp::Not(..) => ty.filter(|ty| is_integer(ty) || ty.is(rt::BOOL_TYPE)),
+                    _ => None,
+                }
+            }
+            hir::ExprKind::Index(hir) => {
+                self.expr(hir.target)?;
+                self.expr(hir.index)?;
+                None
+            }
+            hir::ExprKind::Block(hir) => {
+                let ty = self.block(hir.block)?;
+                ty.filter(|_| matches!(hir.kind, hir::ExprBlockKind::Default))
+            }
+            hir::ExprKind::Break(hir) => {
+                if let Some(hir::ExprBreakValue::Expr(hir)) = hir {
+                    self.expr(hir)?;
+                }
+
+                None
+            }
+            hir::ExprKind::Continue(..) => None,
+            hir::ExprKind::Yield(hir) | hir::ExprKind::Return(hir) => {
+                if let Some(hir) = hir {
+                    self.expr(hir)?;
+                }
+
+                None
+            }
+            hir::ExprKind::Await(hir) | hir::ExprKind::Try(hir) => {
+                self.expr(hir)?;
+                None
+            }
+            hir::ExprKind::Select(hir) => {
+                for branch in hir.branches {
+                    match *branch {
+                        hir::ExprSelectBranch::Pat(hir) => {
+                            self.expr(hir.expr)?;
+                            self.scopes.push(HashMap::new());
+                            self.bind(hir.pat, None)?;
+                            self.expr(hir.body)?;
+                            self.scopes.pop();
+                        }
+                        hir::ExprSelectBranch::Default(hir) => {
+                            self.expr(hir)?;
+                        }
+                    }
+                }
+
+                None
+            }
+            hir::ExprKind::Closure(hir) => {
+                self.scopes.push(HashMap::new());
+
+                for arg in hir.args {
+                    self.fn_arg(arg)?;
+                }
+
+                self.expr(hir.body)?;
+                self.scopes.pop();
+                None
+            }
+            hir::ExprKind::Lit(hir) => Some(Ty::from_static(lit(hir, self.c)?)),
+            hir::ExprKind::Object(hir) => {
+                for assign in hir.assignments {
+                    if let Some(hir) = assign.assign {
+                        self.expr(hir)?;
+                    }
+                }
+
+                match hir.path {
+                    Some(path) => {
+                        let named = self.c.convert_path(path)?;
+
+                        match self.c.try_lookup_meta(path.span(), named.item)? {
+                            Some(meta) => self.constructed(&meta),
+                            None => None,
+                        }
+                    }
+                    None => Some(Ty::from_static(rt::OBJECT_TYPE)),
+                }
+            }
+            hir::ExprKind::Tuple(hir) => {
+                self.exprs(hir.items)?;
+
+                if hir.items.is_empty() {
+                    Some(Ty::from_static(rt::UNIT_TYPE))
+                } else {
+                    Some(Ty::from_static(rt::TUPLE_TYPE))
+                }
+            }
+            hir::ExprKind::Vec(hir) => {
+                self.exprs(hir.items)?;
+                Some(Ty::from_static(rt::VEC_TYPE))
+            }
+            hir::ExprKind::Range(hir) => {
+                if let Some(from) = hir.from {
+                    self.expr(from)?;
+                }
+
+                if let Some(to) = hir.to {
+                    self.expr(to)?;
+                }
+
+                None
+            }
+            hir::ExprKind::Group(hir) => return self.expr(hir),
+            hir::ExprKind::MacroCall(hir) => match *hir {
+                hir::MacroCall::Template(hir) => {
+                    self.exprs(hir.exprs)?;
+                    Some(Ty::from_static(rt::STRING_TYPE))
+                }
+                hir::MacroCall::Format(hir) => {
+                    self.expr(hir.value)?;
+                    None
+                }
+                hir::MacroCall::File(..) => Some(Ty::from_static(rt::STRING_TYPE)),
+                hir::MacroCall::Line(..) => Some(Ty::from_static(rt::INTEGER_TYPE)),
+            },
+        };
+
+        Ok(ty)
+    }
+
+    /// Mark the variable being assigned to, if any.
+    fn assigned(&mut self, hir: &hir::Expr<'_>) -> CompileResult<()> {
+        if let hir::ExprKind::Path(path) = hir.kind {
+            if let Some(ident) = path.try_as_ident() {
+                let name = ident.resolve(resolve_context!(self.c.q))?;
+
+                if !self.assigned.contains(name) {
+                    self.assigned.insert(name.into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn path(&mut self, hir: &hir::Path<'_>) -> CompileResult<Option<Ty>> {
+        if let Some(ident) = hir.try_as_ident() {
+            let name = ident.resolve(resolve_context!(self.c.q))?;
+
+            if let Some(ty) = self.lookup(name) {
+                return Ok(ty);
+            }
+        }
+
+        if let Some(ast::PathKind::SelfValue) = hir.as_kind() {
+            return Ok(None);
+        }
+
+        let named = self.c.convert_path(hir)?;
+
+        if named.generics.is_some() {
+            return Ok(None);
+        }
+
+        let meta = match self.c.try_lookup_meta(hir.span(), named.item)? {
+            Some(meta) => meta,
+            None => return Ok(None),
+        };
+
+        Ok(match meta.kind {
+            PrivMetaKind::Struct {
+                variant: PrivVariantMeta::Unit,
+                ..
+            }
+            | PrivMetaKind::Variant {
+                variant: PrivVariantMeta::Unit,
+                ..
+            } => self.constructed(&meta),
+            _ => None,
+        })
+    }
+
+    fn call(&mut self, span: Span, hir: &hir::ExprCall<'_>) -> CompileResult<Option<Ty>> {
+        match hir.expr.kind {
+            hir::ExprKind::FieldAccess(access) => {
+                let target = self.expr(access.expr)?;
+                self.exprs(hir.args)?;
+
+                if let (Some(target), hir::ExprField::Path(path)) = (target, access.expr_field) {
+                    if let Some(ident) = path.try_as_ident() {
+                        self.instance_call(span, path.span(), &target, ident, hir.args.len())?;
+                    }
+                }
+
+                Ok(None)
+            }
+            hir::ExprKind::Path(path) => {
+                self.exprs(hir.args)?;
+                self.fn_call(span, path, hir.args.len())
+            }
+            _ => {
+                self.expr(hir.expr)?;
+                self.exprs(hir.args)?;
+                Ok(None)
+            }
+        }
+    }
+
+    fn instance_call(
+        &mut self,
+        span: Span,
+        name_span: Span,
+        target: &Ty,
+        ident: &ast::Ident,
+        args: usize,
+    ) -> CompileResult<()> {
+        let name: Box<str> = ident.resolve(resolve_context!(self.c.q))?.into();
+        let hash = Hash::instance_function(target.hash, Hash::instance_fn_name(&name));
+
+        // NB: Argument counts of instance functions include the instance
+        // itself.
+        if let Some(ContextSignature::Instance { args: expected, .. }) =
+            self.c.context.lookup_signature(hash)
+        {
+            if let Some(expected) = *expected {
+                self.check_args(span, expected.saturating_sub(1), args);
+            }
+
+            return Ok(());
+        }
+
+        if let Some(item) = target.item {
+            let item = self.c.q.pool.item(item).extended(&*name);
+            let item = self.c.q.pool.alloc_item(item);
+
+            if let Some(meta) = self.c.try_lookup_meta(name_span, item)? {
+                if let PrivMetaKind::Function {
+                    args: Some(expected),
+                    ..
+                } = meta.kind
+                {
+                    self.check_args(span, expected.saturating_sub(1), args);
+                }
+
+                return Ok(());
+            }
+        }
+
+        // NB: Instance functions declared through traits or for external types
+        // are not associated with their type until they are compiled, so we
+        // conservatively accept any name which is declared.
+        if self.c.q.contains_instance_fn_name(&name) {
+            return Ok(());
+        }
+
+        if self.report {
+            self.c.diagnostics.missing_instance_function(
+                self.c.source_id,
+                name_span,
+                target.hash,
+                &target.name,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn fn_call(
+        &mut self,
+        span: Span,
+        path: &hir::Path<'_>,
+        args: usize,
+    ) -> CompileResult<Option<Ty>> {
+        if let Some(ident) = path.try_as_ident() {
+            let name = ident.resolve(resolve_context!(self.c.q))?;
+
+            if self.lookup(name).is_some() {
+                return Ok(None);
+            }
+        }
+
+        let named = self.c.convert_path(path)?;
+
+        let meta = match self.c.try_lookup_meta(path.span(), named.item)? {
+            Some(meta) => meta,
+            None => return Ok(None),
+        };
+
+        match meta.kind {
+            PrivMetaKind::Function {
+                args: Some(expected),
+                ..
+            } => {
+                self.check_args(span, expected, args);
+            }
+            PrivMetaKind::Function {
+                type_hash,
+                args: None,
+                ..
+            } => {
+                if let Some(ContextSignature::Function {
+                    args: Some(expected),
+                    ..
+                }) = self.c.context.lookup_signature(type_hash)
+                {
+                    let expected = *expected;
+                    self.check_args(span, expected, args);
+                }
+            }
+            PrivMetaKind::Struct { .. } | PrivMetaKind::Variant { .. } => {
+                return Ok(self.constructed(&meta));
+            }
+            _ => (),
+        }
+
+        Ok(None)
+    }
+
+    fn check_args(&mut self, span: Span, expected: usize, actual: usize) {
+        if self.report && expected != actual {
+            self.c
+                .diagnostics
+                .bad_argument_count(self.c.source_id, span, expected, actual);
+        }
+    }
+
+    fn binary(&mut self, span: Span, hir: &hir::ExprBinary<'_>) -> CompileResult<Option<Ty>> {
+        if hir.op.is_assign() {
+            self.assigned(hir.lhs)?;
+        }
+
+        let lhs = self.expr(hir.lhs)?;
+        let rhs = self.expr(hir.rhs)?;
+
+        let protocol = match hir.op {
+            ast::BinOp::Add(..) => Protocol::ADD,
+            ast::BinOp::Sub(..) => Protocol::SUB,
+            ast::BinOp::Mul(..) => Protocol::MUL,
+            ast::BinOp::Div(..) => Protocol::DIV,
+            ast::BinOp::Rem(..) => Protocol::REM,
+            ast::BinOp::Eq(..)
+            | ast::BinOp::Neq(..)
+            | ast::BinOp::Gt(..)
+            | ast::BinOp::Lt(..)
+            | ast::BinOp::Gte(..)
+            | ast::BinOp::Lte(..)
+            | ast::BinOp::Is(..)
+            | ast::BinOp::IsNot(..)
+            | ast::BinOp::And(..)
+            | ast::BinOp::Or(..) => return Ok(Some(Ty::from_static(rt::BOOL_TYPE))),
+            _ => return Ok(None),
+        };
+
+        let (lhs, rhs) = match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) => (lhs, rhs),
+            _ => return Ok(None),
+        };
+
+        // Only operations between primitive types are fully known, anything
+        // else might be implemented through a protocol.
+        let primitive = |ty: &Ty| {
+            is_integer(ty)
+                || [
+                    rt::FLOAT_TYPE,
+                    rt::STRING_TYPE,
+                    rt::BOOL_TYPE,
+                    rt::CHAR_TYPE,
+                    rt::UNIT_TYPE,
+                ]
+                .iter()
+                .any(|t| ty.is(t))
+        };
+
+        if !primitive(&lhs) || !primitive(&rhs) {
+            return Ok(None);
+        }
+
+        let supported = lhs.hash == rhs.hash
+            && (is_integer(&lhs)
+                || lhs.is(rt::FLOAT_TYPE)
+                || self
+                    .c
+                    .context
+                    .lookup_signature(Hash::instance_function(lhs.hash, protocol))
+                    .is_some());
+
+        if supported {
+            return Ok(Some(lhs));
+        }
+
+        // Plain integers adapt to the sized integer they're used with.
+        let sized = |ty: &Ty| is_integer(ty) && !ty.is(rt::BYTE_TYPE);
+
+        if lhs.is(rt::INTEGER_TYPE) && sized(&rhs) {
+            return Ok(Some(rhs));
+        }
+
+        if sized(&lhs) && rhs.is(rt::INTEGER_TYPE) {
+            return Ok(Some(lhs));
+        }
+
+        if self.report {
+            self.c.diagnostics.unsupported_binary_operation(
+                self.c.source_id,
+                span,
+                hir.op,
+                (lhs.hash, &lhs.name),
+                (rhs.hash, &rhs.name),
+            );
+        }
+
+        Ok(None)
+    }
+}
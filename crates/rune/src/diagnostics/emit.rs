@@ -64,7 +64,7 @@ impl Diagnostics {
                     fatal_diagnostics_emit(e, out, sources, &config)?;
                 }
                 Diagnostic::Warning(w) => {
-                    warning_diagnostics_emit(w, out, sources, &config)?;
+                    warning_diagnostics_emit(self, w, out, sources, &config)?;
                 }
             }
         }
@@ -363,6 +363,7 @@ pub fn line_for(source: &Source, span: Span) -> Option<(usize, &str, Span)> {
 
 /// Helper to emit diagnostics for a warning.
 fn warning_diagnostics_emit<O>(
+    diagnostics: &Diagnostics,
     this: &WarningDiagnostic,
     out: &mut O,
     sources: &Sources,
@@ -433,6 +434,42 @@ where
                     .with_message("unnecessary semicolon"),
             );
 
+            None
+        }
+        WarningDiagnosticKind::MissingInstanceFunction { span, ty } => {
+            let name = sources.source(this.source_id(), *span).unwrap_or("?");
+            let ty = diagnostics.type_name(*ty).unwrap_or("?");
+
+            labels.push(
+                d::Label::primary(this.source_id(), span.range())
+                    .with_message(format!("no instance function `{}` for `{}`", name, ty)),
+            );
+
+            None
+        }
+        WarningDiagnosticKind::BadArgumentCount {
+            span,
+            expected,
+            actual,
+        } => {
+            labels.push(
+                d::Label::primary(this.source_id(), span.range()).with_message(format!(
+                    "expected {} arguments but got {}",
+                    expected, actual
+                )),
+            );
+
+            None
+        }
+        WarningDiagnosticKind::UnsupportedBinaryOperation { span, lhs, rhs, .. } => {
+            let lhs = diagnostics.type_name(*lhs).unwrap_or("?");
+            let rhs = diagnostics.type_name(*rhs).unwrap_or("?");
+
+            labels.push(
+                d::Label::primary(this.source_id(), span.range())
+                    .with_message(format!("`{}` and `{}` can't be used here", lhs, rhs)),
+            );
+
             None
         }
     };
@@ -31,8 +31,9 @@
 //! # Ok(()) }
 //! ```
 
-use crate::ast::Span;
-use crate::SourceId;
+use crate::ast::{BinOp, Span};
+use crate::collections::HashMap;
+use crate::{Hash, SourceId};
 
 mod fatal;
 pub use self::fatal::{FatalDiagnostic, FatalDiagnosticKind};
@@ -103,6 +104,8 @@ pub struct Diagnostics {
     has_error: bool,
     /// Indicates if diagnostics contains warnings.
     has_warning: bool,
+    /// Names of types referenced by warnings.
+    type_names: HashMap<Hash, Box<str>>,
 }
 
 impl Diagnostics {
@@ -112,6 +115,7 @@ impl Diagnostics {
             mode,
             has_error: false,
             has_warning: false,
+            type_names: HashMap::new(),
         }
     }
 
@@ -187,6 +191,11 @@ impl Diagnostics {
         self.diagnostics
     }
 
+    /// Get the name of a type referenced by a warning.
+    pub fn type_name(&self, hash: Hash) -> Option<&str> {
+        self.type_names.get(&hash).map(|name| &**name)
+    }
+
     /// Report an internal error.
     ///
     /// This should be used for programming invariants of the compiler which are
@@ -260,6 +269,69 @@ impl Diagnostics {
         );
     }
 
+    /// Indicate that an instance function is called which doesn't exist for
+    /// the type of the receiver.
+    pub fn missing_instance_function(
+        &mut self,
+        source_id: SourceId,
+        span: Span,
+        ty: Hash,
+        ty_name: &str,
+    ) {
+        self.type_name_insert(ty, ty_name);
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::MissingInstanceFunction { span, ty },
+        );
+    }
+
+    /// Indicate that a function is called with the wrong number of
+    /// arguments.
+    pub fn bad_argument_count(
+        &mut self,
+        source_id: SourceId,
+        span: Span,
+        expected: usize,
+        actual: usize,
+    ) {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::BadArgumentCount {
+                span,
+                expected,
+                actual,
+            },
+        );
+    }
+
+    /// Indicate that a binary operation is used with operands which don't
+    /// support it.
+    ///
+    /// Like `"a" + 1`. The operands are given as the hash and name of their
+    /// types.
+    pub fn unsupported_binary_operation(
+        &mut self,
+        source_id: SourceId,
+        span: Span,
+        op: BinOp,
+        (lhs, lhs_name): (Hash, &str),
+        (rhs, rhs_name): (Hash, &str),
+    ) {
+        self.type_name_insert(lhs, lhs_name);
+        self.type_name_insert(rhs, rhs_name);
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::UnsupportedBinaryOperation { span, op, lhs, rhs },
+        );
+    }
+
+    /// Remember the name of a type referenced by a warning.
+    fn type_name_insert(&mut self, hash: Hash, name: &str) {
+        if self.mode.warnings() && !self.type_names.contains_key(&hash) {
+            self.type_names.insert(hash, name.into());
+        }
+    }
+
     /// Push a warning to the collection of diagnostics.
     pub fn warning<T>(&mut self, source_id: SourceId, kind: T)
     where
@@ -1,5 +1,5 @@
-use crate::ast::Span;
-use crate::SourceId;
+use crate::ast::{BinOp, Span};
+use crate::{Hash, SourceId};
 use std::error;
 use std::fmt;
 use thiserror::Error;
@@ -38,6 +38,9 @@ impl WarningDiagnostic {
             WarningDiagnosticKind::TemplateWithoutExpansions { span, .. } => *span,
             WarningDiagnosticKind::RemoveTupleCallParams { span, .. } => *span,
             WarningDiagnosticKind::UnecessarySemiColon { span, .. } => *span,
+            WarningDiagnosticKind::MissingInstanceFunction { span, .. } => *span,
+            WarningDiagnosticKind::BadArgumentCount { span, .. } => *span,
+            WarningDiagnosticKind::UnsupportedBinaryOperation { span, .. } => *span,
         }
     }
 }
@@ -100,4 +103,41 @@ pub enum WarningDiagnosticKind {
         /// Span where the semi-colon is.
         span: Span,
     },
+    /// An instance function is called which doesn't exist for the type of
+    /// the receiver.
+    ///
+    /// The name of the type can be looked up through
+    /// [Diagnostics::type_name][crate::Diagnostics::type_name].
+    #[error("no such instance function")]
+    MissingInstanceFunction {
+        /// Span of the name of the called function.
+        span: Span,
+        /// The type of the receiver.
+        ty: Hash,
+    },
+    /// A function is called with the wrong number of arguments.
+    #[error("wrong number of arguments, expected {expected} but got {actual}")]
+    BadArgumentCount {
+        /// Span of the call.
+        span: Span,
+        /// The number of arguments the function expects.
+        expected: usize,
+        /// The number of arguments provided.
+        actual: usize,
+    },
+    /// A binary operation is used with operands which don't support it.
+    ///
+    /// The names of the types can be looked up through
+    /// [Diagnostics::type_name][crate::Diagnostics::type_name].
+    #[error("unsupported operation `{op}`")]
+    UnsupportedBinaryOperation {
+        /// Span of the operation.
+        span: Span,
+        /// The operator.
+        op: BinOp,
+        /// Type of the left-hand side.
+        lhs: Hash,
+        /// Type of the right-hand side.
+        rhs: Hash,
+    },
 }
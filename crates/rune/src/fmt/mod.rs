@@ -0,0 +1,46 @@
+//! Formatting of Rune source code.
+//!
+//! The formatter parses a source file and pretty-prints its syntax tree in a
+//! canonical style. Comments are collected separately by the lexer and
+//! re-attached to the closest item, statement, or field which follows them.
+//!
+//! ```
+//! let source = "pub fn main(){let a=[1,2,3];a}";
+//! let formatted = rune::fmt::format_source(source)?;
+//!
+//! assert_eq!(formatted, "pub fn main() {\n    let a = [1, 2, 3];\n    a\n}\n");
+//! # Ok::<_, rune::Error>(())
+//! ```
+
+mod printer;
+
+use crate::ast;
+use crate::parse::{Lexer, ParseError};
+use crate::SourceId;
+
+/// Format the given source in the canonical Rune style.
+///
+/// Returns an error if the source could not be parsed.
+pub fn format_source(source: &str) -> Result<String, ParseError> {
+    let file = crate::parse::parse_all::<ast::File>(source, SourceId::empty(), true)?;
+    let comments = comments(source)?;
+
+    let mut printer = printer::Printer::new(source, comments);
+    printer.file(&file);
+    Ok(printer.finish())
+}
+
+/// Collect the spans of all regular comments in the source. Doc comments are
+/// part of the syntax tree as attributes, so they are not included.
+fn comments(source: &str) -> Result<Vec<ast::Span>, ParseError> {
+    let mut lexer = Lexer::new(source, SourceId::empty(), true);
+    let mut comments = Vec::new();
+
+    while let Some(token) = lexer.next()? {
+        if let ast::Kind::Comment | ast::Kind::MultilineComment(..) = token.kind {
+            comments.push(token.span);
+        }
+    }
+
+    Ok(comments)
+}
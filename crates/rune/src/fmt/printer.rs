@@ -0,0 +1,1396 @@
+use crate::ast::{self, OptionSpanned, Span, Spanned};
+
+/// The maximum width of a line before lists are broken up over multiple lines.
+const MAX_WIDTH: usize = 100;
+
+/// The string used for one level of indentation.
+const INDENT: &str = "    ";
+
+/// How a delimited, comma-separated list is laid out.
+#[derive(Clone, Copy)]
+struct Delimiters {
+    open: &'static str,
+    close: &'static str,
+    /// Pad the inside of the delimiters with spaces when laid out on one line.
+    pad: bool,
+    /// Always lay out one element per line.
+    multiline: bool,
+    /// Add a trailing comma to single element lists laid out on one line,
+    /// like in tuples.
+    single_comma: bool,
+}
+
+impl Delimiters {
+    const fn new(open: &'static str, close: &'static str) -> Self {
+        Self {
+            open,
+            close,
+            pad: false,
+            multiline: false,
+            single_comma: false,
+        }
+    }
+
+    const fn pad(self) -> Self {
+        Self { pad: true, ..self }
+    }
+
+    const fn multiline(self) -> Self {
+        Self {
+            multiline: true,
+            ..self
+        }
+    }
+
+    const fn single_comma(self) -> Self {
+        Self {
+            single_comma: true,
+            ..self
+        }
+    }
+}
+
+const PAREN: Delimiters = Delimiters::new("(", ")");
+const BRACKET: Delimiters = Delimiters::new("[", "]");
+const BRACE: Delimiters = Delimiters::new("{", "}");
+const ANGLE: Delimiters = Delimiters::new("<", ">");
+
+/// State which is restored when a layout is abandoned.
+struct Snapshot {
+    len: usize,
+    comments: Vec<Span>,
+    indent: usize,
+    last_end: usize,
+    fresh: bool,
+}
+
+/// Pretty-printer for a parsed source file.
+pub(super) struct Printer<'a> {
+    source: &'a str,
+    /// Comments which have not been emitted yet, in source order.
+    comments: Vec<Span>,
+    out: String,
+    indent: usize,
+    /// The end of the last source element which was emitted.
+    last_end: usize,
+    /// If nothing has been written since a block was opened.
+    fresh: bool,
+    /// The number of doc comments which have been written inline, each of
+    /// which forces a line break.
+    inline_docs: usize,
+}
+
+impl<'a> Printer<'a> {
+    pub(super) fn new(source: &'a str, comments: Vec<Span>) -> Self {
+        Self {
+            source,
+            comments,
+            out: String::new(),
+            indent: 0,
+            last_end: 0,
+            fresh: true,
+            inline_docs: 0,
+        }
+    }
+
+    /// Finish printing, flushing any remaining comments.
+    pub(super) fn finish(mut self) -> String {
+        self.comments_before(self.source.len());
+
+        let len = self.out.trim_end().len();
+        self.out.truncate(len);
+
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+
+        self.out
+    }
+
+    pub(super) fn file(&mut self, file: &ast::File) {
+        if let Some(shebang) = &file.shebang {
+            self.verbatim(shebang.span);
+            self.last_end = shebang.span.end.into_usize();
+        }
+
+        for attribute in &file.attributes {
+            self.line(attribute.span());
+            self.verbatim(attribute.span());
+            self.last_end = attribute.span().end.into_usize();
+        }
+
+        self.items(&file.items);
+    }
+
+    fn items(&mut self, items: &[(ast::Item, Option<T![;]>)]) {
+        for (item, semi) in items {
+            self.line(item.span());
+            self.item(item, semi.as_ref());
+            self.last_end = item_end(item, semi.as_ref());
+        }
+    }
+
+    fn item(&mut self, item: &ast::Item, semi: Option<&T![;]>) {
+        match item {
+            ast::Item::Use(item) => {
+                self.attributes(&item.attributes);
+                self.visibility(&item.visibility);
+                self.write("use ");
+                self.use_path(&item.path);
+            }
+            ast::Item::Fn(item) => {
+                self.item_fn(item);
+            }
+            ast::Item::Enum(item) => {
+                self.attributes(&item.attributes);
+                self.visibility(&item.visibility);
+                self.write("enum ");
+                self.verbatim(item.name.span());
+                self.write(" ");
+                self.list(
+                    item.variants.as_slice(),
+                    item.variants.span(),
+                    BRACE.multiline(),
+                    |p, v| p.variant(v),
+                );
+            }
+            ast::Item::Struct(item) => {
+                self.attributes(&item.attributes);
+                self.visibility(&item.visibility);
+                self.write("struct ");
+                self.verbatim(item.ident.span());
+                self.struct_body(&item.body);
+            }
+            ast::Item::Impl(item) => {
+                self.attributes(&item.attributes);
+                self.write("impl ");
+
+                if let Some((path, _)) = &item.trait_ {
+                    self.path(path);
+                    self.write(" for ");
+                }
+
+                self.path(&item.path);
+                self.write(" ");
+
+                if item.functions.is_empty() && !self.has_comments(item.span()) {
+                    self.write("{}");
+                } else {
+                    self.open("{", item.open.span());
+
+                    for function in &item.functions {
+                        self.line(function.span());
+                        self.item_fn(function);
+                        self.last_end = function.span().end.into_usize();
+                    }
+
+                    self.close("}", item.close.span());
+                }
+            }
+            ast::Item::Trait(item) => {
+                self.attributes(&item.attributes);
+                self.visibility(&item.visibility);
+                self.write("trait ");
+                self.verbatim(item.ident.span());
+                self.write(" ");
+
+                if item.functions.is_empty() && !self.has_comments(item.span()) {
+                    self.write("{}");
+                } else {
+                    self.open("{", item.open.span());
+
+                    for function in &item.functions {
+                        self.line(function.span());
+
+                        match function {
+                            ast::ItemTraitFn::Required(decl) => self.item_fn_decl(decl),
+                            ast::ItemTraitFn::Provided(function) => self.item_fn(function),
+                        }
+
+                        self.last_end = function.span().end.into_usize();
+                    }
+
+                    self.close("}", item.close.span());
+                }
+            }
+            ast::Item::Mod(item) => {
+                self.attributes(&item.attributes);
+                self.visibility(&item.visibility);
+                self.write("mod ");
+                self.verbatim(item.name.span());
+
+                match &item.body {
+                    ast::ItemModBody::EmptyBody(..) => {
+                        self.write(";");
+                    }
+                    ast::ItemModBody::InlineBody(body) => {
+                        self.write(" ");
+
+                        if body.file.items.is_empty() && !self.has_comments(body.span()) {
+                            self.write("{}");
+                        } else {
+                            self.open("{", body.open.span());
+                            self.file(&body.file);
+                            self.close("}", body.close.span());
+                        }
+                    }
+                }
+            }
+            ast::Item::Const(item) => {
+                self.attributes(&item.attributes);
+                self.visibility(&item.visibility);
+                self.write("const ");
+                self.verbatim(item.name.span());
+                self.write(" = ");
+                self.expr(&item.expr);
+            }
+            ast::Item::MacroCall(item) => {
+                self.verbatim(item.span());
+            }
+        }
+
+        if semi.is_some() && needs_semi(item) {
+            self.write(";");
+        }
+    }
+
+    fn item_fn(&mut self, item: &ast::ItemFn) {
+        self.attributes(&item.attributes);
+        self.visibility(&item.visibility);
+
+        if item.const_token.is_some() {
+            self.write("const ");
+        }
+
+        if item.async_token.is_some() {
+            self.write("async ");
+        }
+
+        self.write("fn ");
+        self.verbatim(item.name.span());
+        self.list(item.args.as_slice(), item.args.span(), PAREN, |p, arg| {
+            p.fn_arg(arg)
+        });
+        self.output(&item.output);
+        self.write(" ");
+        self.block(&item.body);
+    }
+
+    fn item_fn_decl(&mut self, item: &ast::ItemTraitFnDecl) {
+        self.attributes(&item.attributes);
+
+        if item.async_token.is_some() {
+            self.write("async ");
+        }
+
+        self.write("fn ");
+        self.verbatim(item.name.span());
+        self.list(item.args.as_slice(), item.args.span(), PAREN, |p, arg| {
+            p.fn_arg(arg)
+        });
+        self.output(&item.output);
+        self.write(";");
+    }
+
+    fn output(&mut self, output: &Option<(T![->], ast::Type)>) {
+        if let Some((_, ty)) = output {
+            self.write(" -> ");
+            self.ty(ty);
+        }
+    }
+
+    fn fn_arg(&mut self, arg: &ast::FnArg) {
+        match arg {
+            ast::FnArg::SelfValue(..) => self.write("self"),
+            ast::FnArg::Pat(pat) => self.pat(pat),
+            ast::FnArg::Typed(typed) => {
+                self.pat(&typed.pat);
+                self.write(": ");
+                self.ty(&typed.ty);
+            }
+        }
+    }
+
+    fn ty(&mut self, ty: &ast::Type) {
+        match ty {
+            ast::Type::Path(path) => self.path(path),
+            ast::Type::Tuple(tuple) => {
+                self.list(
+                    tuple.as_slice(),
+                    tuple.span(),
+                    PAREN.single_comma(),
+                    |p, ty| p.ty(ty),
+                );
+            }
+        }
+    }
+
+    fn variant(&mut self, variant: &ast::ItemVariant) {
+        self.attributes(&variant.attributes);
+        self.verbatim(variant.name.span());
+
+        match &variant.body {
+            ast::ItemVariantBody::UnitBody => {}
+            ast::ItemVariantBody::TupleBody(fields) => {
+                self.list(fields.as_slice(), fields.span(), PAREN, |p, f| p.field(f));
+            }
+            ast::ItemVariantBody::StructBody(fields) => {
+                self.write(" ");
+                self.list(fields.as_slice(), fields.span(), BRACE.pad(), |p, f| {
+                    p.field(f)
+                });
+            }
+        }
+    }
+
+    fn struct_body(&mut self, body: &ast::ItemStructBody) {
+        match body {
+            ast::ItemStructBody::UnitBody => {}
+            ast::ItemStructBody::TupleBody(fields) => {
+                self.list(fields.as_slice(), fields.span(), PAREN, |p, f| p.field(f));
+            }
+            ast::ItemStructBody::StructBody(fields) => {
+                self.write(" ");
+                self.list(
+                    fields.as_slice(),
+                    fields.span(),
+                    BRACE.multiline(),
+                    |p, f| p.field(f),
+                );
+            }
+        }
+    }
+
+    fn field(&mut self, field: &ast::Field) {
+        self.inline_attributes(&field.attributes);
+        self.visibility(&field.visibility);
+        self.verbatim(field.name.span());
+    }
+
+    fn use_path(&mut self, path: &ast::ItemUsePath) {
+        if path.global.is_some() {
+            self.write("::");
+        }
+
+        self.use_segment(&path.first);
+
+        for (_, segment) in &path.segments {
+            self.write("::");
+            self.use_segment(segment);
+        }
+
+        if let Some((_, alias)) = &path.alias {
+            self.write(" as ");
+            self.verbatim(alias.span());
+        }
+    }
+
+    fn use_segment(&mut self, segment: &ast::ItemUseSegment) {
+        match segment {
+            ast::ItemUseSegment::PathSegment(segment) => self.path_segment(segment),
+            ast::ItemUseSegment::Wildcard(..) => self.write("*"),
+            ast::ItemUseSegment::Group(group) => {
+                self.list(group.as_slice(), group.span(), BRACE, |p, path| {
+                    p.use_path(path)
+                });
+            }
+        }
+    }
+
+    fn block(&mut self, block: &ast::Block) {
+        if block.statements.is_empty() && !self.has_comments(block.span()) {
+            self.write("{}");
+            self.last_end = block.span().end.into_usize();
+            return;
+        }
+
+        self.open("{", block.open.span());
+
+        for stmt in &block.statements {
+            self.line(stmt.span());
+            self.stmt(stmt);
+            self.last_end = stmt.span().end.into_usize();
+        }
+
+        self.close("}", block.close.span());
+    }
+
+    fn stmt(&mut self, stmt: &ast::Stmt) {
+        match stmt {
+            ast::Stmt::Local(local) => {
+                self.inline_attributes(&local.attributes);
+                self.write("let ");
+                self.pat(&local.pat);
+
+                if let Some((_, ty)) = &local.ty {
+                    self.write(": ");
+                    self.ty(ty);
+                }
+
+                self.write(" = ");
+                self.expr(&local.expr);
+
+                if let Some(expr_else) = &local.expr_else {
+                    self.write(" else ");
+                    self.block(&expr_else.block);
+                }
+
+                self.write(";");
+            }
+            ast::Stmt::Item(item, semi) => {
+                self.item(item, semi.as_ref());
+            }
+            ast::Stmt::Expr(expr) => {
+                self.expr(expr);
+            }
+            ast::Stmt::Semi(semi) => {
+                self.expr(&semi.expr);
+                self.write(";");
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &ast::Expr) {
+        let (root, links) = chain(expr);
+
+        if links
+            .iter()
+            .filter(|link| matches!(link, Link::Field(..) | Link::Await))
+            .count()
+            >= 2
+        {
+            self.chain(root, &links);
+            return;
+        }
+
+        match expr {
+            ast::Expr::Path(path) => {
+                self.path(path);
+            }
+            ast::Expr::Assign(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.expr(&expr.lhs);
+                self.write(" = ");
+                self.expr(&expr.rhs);
+            }
+            ast::Expr::While(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.label(&expr.label);
+                self.write("while ");
+                self.condition(&expr.condition);
+                self.write(" ");
+                self.block(&expr.body);
+            }
+            ast::Expr::Loop(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.label(&expr.label);
+                self.write("loop ");
+                self.block(&expr.body);
+            }
+            ast::Expr::For(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.label(&expr.label);
+                self.write("for ");
+                self.pat(&expr.binding);
+                self.write(" in ");
+                self.expr(&expr.iter);
+                self.write(" ");
+                self.block(&expr.body);
+            }
+            ast::Expr::Let(expr) => {
+                self.expr_let(expr);
+            }
+            ast::Expr::If(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.write("if ");
+                self.condition(&expr.condition);
+                self.write(" ");
+                self.block(&expr.block);
+
+                for expr_else_if in &expr.expr_else_ifs {
+                    self.write(" else if ");
+                    self.condition(&expr_else_if.condition);
+                    self.write(" ");
+                    self.block(&expr_else_if.block);
+                }
+
+                if let Some(expr_else) = &expr.expr_else {
+                    self.write(" else ");
+                    self.block(&expr_else.block);
+                }
+            }
+            ast::Expr::Match(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.write("match ");
+                self.expr(&expr.expr);
+                self.write(" ");
+
+                if expr.branches.is_empty() && !self.has_comments(expr.span()) {
+                    self.write("{}");
+                    return;
+                }
+
+                self.open("{", expr.open.span());
+
+                for (branch, comma) in &expr.branches {
+                    self.line(branch.span());
+                    self.pat(&branch.pat);
+
+                    if let Some((_, condition)) = &branch.condition {
+                        self.write(" if ");
+                        self.expr(condition);
+                    }
+
+                    self.write(" => ");
+                    self.branch_body(&branch.body);
+                    self.last_end = end_with(branch.span(), comma.as_ref());
+                }
+
+                self.close("}", expr.close.span());
+            }
+            ast::Expr::Call(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.expr(&expr.expr);
+                self.list(expr.args.as_slice(), expr.args.span(), PAREN, |p, e| {
+                    p.expr(e)
+                });
+            }
+            ast::Expr::FieldAccess(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.expr(&expr.expr);
+                self.write(".");
+
+                match &expr.expr_field {
+                    ast::ExprField::Path(path) => self.path(path),
+                    ast::ExprField::LitNumber(number) => self.verbatim(number.span()),
+                }
+            }
+            ast::Expr::Binary(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.expr(&expr.lhs);
+                self.write(&format!(" {} ", expr.op));
+                self.expr(&expr.rhs);
+            }
+            ast::Expr::Unary(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.write(&expr.op.to_string());
+                self.expr(&expr.expr);
+            }
+            ast::Expr::Index(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.expr(&expr.target);
+                self.write("[");
+                self.expr(&expr.index);
+                self.write("]");
+            }
+            ast::Expr::Break(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.write("break");
+
+                match expr.expr.as_deref() {
+                    Some(ast::ExprBreakValue::Expr(value)) => {
+                        self.write(" ");
+                        self.expr(value);
+                    }
+                    Some(ast::ExprBreakValue::Label(label)) => {
+                        self.write(" ");
+                        self.verbatim(label.span());
+                    }
+                    None => {}
+                }
+            }
+            ast::Expr::Continue(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.write("continue");
+
+                if let Some(label) = &expr.label {
+                    self.write(" ");
+                    self.verbatim(label.span());
+                }
+            }
+            ast::Expr::Yield(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.write("yield");
+
+                if let Some(value) = &expr.expr {
+                    self.write(" ");
+                    self.expr(value);
+                }
+            }
+            ast::Expr::Block(expr) => {
+                self.inline_attributes(&expr.attributes);
+
+                if expr.async_token.is_some() {
+                    self.write("async ");
+                }
+
+                if expr.const_token.is_some() {
+                    self.write("const ");
+                }
+
+                if expr.move_token.is_some() {
+                    self.write("move ");
+                }
+
+                self.block(&expr.block);
+            }
+            ast::Expr::Return(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.write("return");
+
+                if let Some(value) = &expr.expr {
+                    self.write(" ");
+                    self.expr(value);
+                }
+            }
+            ast::Expr::Await(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.expr(&expr.expr);
+                self.write(".await");
+            }
+            ast::Expr::Try(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.expr(&expr.expr);
+                self.write("?");
+            }
+            ast::Expr::Select(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.write("select ");
+
+                if expr.branches.is_empty() && !self.has_comments(expr.span()) {
+                    self.write("{}");
+                    return;
+                }
+
+                self.open("{", expr.open.span());
+
+                for (branch, comma) in &expr.branches {
+                    self.line(branch.span());
+
+                    match branch {
+                        ast::ExprSelectBranch::Pat(branch) => {
+                            self.pat(&branch.pat);
+                            self.write(" = ");
+                            self.expr(&branch.expr);
+                            self.write(" => ");
+                            self.branch_body(&branch.body);
+                        }
+                        ast::ExprSelectBranch::Default(branch) => {
+                            self.write("default => ");
+                            self.branch_body(&branch.body);
+                        }
+                    }
+
+                    self.last_end = end_with(branch.span(), comma.as_ref());
+                }
+
+                self.close("}", expr.close.span());
+            }
+            ast::Expr::Closure(expr) => {
+                self.inline_attributes(&expr.attributes);
+
+                if expr.async_token.is_some() {
+                    self.write("async ");
+                }
+
+                if expr.move_token.is_some() {
+                    self.write("move ");
+                }
+
+                match &expr.args {
+                    ast::ExprClosureArgs::Empty { .. } => {
+                        self.write("||");
+                    }
+                    ast::ExprClosureArgs::List { args, .. } => {
+                        self.write("|");
+
+                        for (index, (arg, _)) in args.iter().enumerate() {
+                            if index > 0 {
+                                self.write(", ");
+                            }
+
+                            self.fn_arg(arg);
+                        }
+
+                        self.write("|");
+                    }
+                }
+
+                self.write(" ");
+                self.expr(&expr.body);
+            }
+            ast::Expr::Lit(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.verbatim(expr.lit.span());
+            }
+            ast::Expr::Object(expr) => {
+                self.inline_attributes(&expr.attributes);
+                let delimiters = self.object_ident(&expr.ident);
+                let assignments = &expr.assignments;
+
+                self.list(
+                    assignments.as_slice(),
+                    assignments.span(),
+                    delimiters,
+                    |p, assign| {
+                        p.object_key(&assign.key);
+
+                        if let Some((_, value)) = &assign.assign {
+                            p.write(": ");
+                            p.expr(value);
+                        }
+                    },
+                );
+            }
+            ast::Expr::Tuple(expr) => {
+                self.inline_attributes(&expr.attributes);
+                let items = &expr.items;
+                self.list(
+                    items.as_slice(),
+                    items.span(),
+                    PAREN.single_comma(),
+                    |p, e| p.expr(e),
+                );
+            }
+            ast::Expr::Vec(expr) => {
+                self.inline_attributes(&expr.attributes);
+                let items = &expr.items;
+                self.list(items.as_slice(), items.span(), BRACKET, |p, e| p.expr(e));
+            }
+            ast::Expr::Range(expr) => {
+                self.inline_attributes(&expr.attributes);
+
+                if let Some(from) = &expr.from {
+                    self.expr(from);
+                }
+
+                match expr.limits {
+                    ast::ExprRangeLimits::HalfOpen(..) => self.write(".."),
+                    ast::ExprRangeLimits::Closed(..) => self.write("..="),
+                }
+
+                if let Some(to) = &expr.to {
+                    self.expr(to);
+                }
+            }
+            ast::Expr::Empty(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.expr(&expr.expr);
+            }
+            ast::Expr::Group(expr) => {
+                self.inline_attributes(&expr.attributes);
+                self.write("(");
+                self.expr(&expr.expr);
+                self.write(")");
+            }
+            ast::Expr::MacroCall(expr) => {
+                self.verbatim(expr.span());
+            }
+        }
+    }
+
+    /// Write a method chain, putting each link after the first on a line of
+    /// its own if it does not fit.
+    fn chain(&mut self, root: &ast::Expr, links: &[Link<'_>]) {
+        let snapshot = self.snapshot();
+        let line_start = self.out.rfind('\n').map_or(0, |n| n + 1);
+
+        self.links(root, links, false);
+
+        let line = self.out[line_start..].lines().next().unwrap_or_default();
+
+        if line.chars().count() <= MAX_WIDTH {
+            return;
+        }
+
+        self.restore(snapshot);
+        self.links(root, links, true);
+    }
+
+    fn links(&mut self, root: &ast::Expr, links: &[Link<'_>], broken: bool) {
+        self.expr(root);
+        self.indent += 1;
+
+        let mut first = true;
+
+        for link in links {
+            if let Link::Field(..) | Link::Await = link {
+                if broken && !first {
+                    self.newline();
+                }
+
+                first = false;
+            }
+
+            match link {
+                Link::Field(ast::ExprField::Path(path)) => {
+                    self.write(".");
+                    self.path(path);
+                }
+                Link::Field(ast::ExprField::LitNumber(number)) => {
+                    self.write(".");
+                    self.verbatim(number.span());
+                }
+                Link::Await => {
+                    self.write(".await");
+                }
+                Link::Call(args) => {
+                    self.list(args.as_slice(), args.span(), PAREN, |p, e| p.expr(e));
+                }
+                Link::Try => {
+                    self.write("?");
+                }
+            }
+        }
+
+        self.indent -= 1;
+    }
+
+    fn expr_let(&mut self, expr: &ast::ExprLet) {
+        self.inline_attributes(&expr.attributes);
+        self.write("let ");
+        self.pat(&expr.pat);
+        self.write(" = ");
+        self.expr(&expr.expr);
+    }
+
+    fn condition(&mut self, condition: &ast::Condition) {
+        match condition {
+            ast::Condition::Expr(expr) => self.expr(expr),
+            ast::Condition::ExprLet(expr) => self.expr_let(expr),
+        }
+    }
+
+    /// The body of a match or select branch, followed by a comma unless it
+    /// ends in a block.
+    fn branch_body(&mut self, body: &ast::Expr) {
+        self.expr(body);
+
+        if !matches!(
+            body,
+            ast::Expr::Block(..)
+                | ast::Expr::For(..)
+                | ast::Expr::While(..)
+                | ast::Expr::If(..)
+                | ast::Expr::Match(..)
+        ) {
+            self.write(",");
+        }
+    }
+
+    fn label(&mut self, label: &Option<(ast::Label, T![:])>) {
+        if let Some((label, _)) = label {
+            self.verbatim(label.span());
+            self.write(": ");
+        }
+    }
+
+    /// Write the identifier of an object, returning the delimiters to use for
+    /// its body.
+    fn object_ident(&mut self, ident: &ast::ObjectIdent) -> Delimiters {
+        match ident {
+            ast::ObjectIdent::Anonymous(..) => {
+                self.write("#");
+                BRACE
+            }
+            ast::ObjectIdent::Named(path) => {
+                self.path(path);
+                self.write(" ");
+                BRACE.pad()
+            }
+        }
+    }
+
+    fn object_key(&mut self, key: &ast::ObjectKey) {
+        match key {
+            ast::ObjectKey::LitStr(lit) => self.verbatim(lit.span()),
+            ast::ObjectKey::Path(path) => self.path(path),
+        }
+    }
+
+    fn pat(&mut self, pat: &ast::Pat) {
+        match pat {
+            ast::Pat::PatIgnore(pat) => {
+                self.inline_attributes(&pat.attributes);
+                self.write("_");
+            }
+            ast::Pat::PatPath(pat) => {
+                self.inline_attributes(&pat.attributes);
+                self.path(&pat.path);
+            }
+            ast::Pat::PatLit(pat) => {
+                self.inline_attributes(&pat.attributes);
+                self.expr(&pat.expr);
+            }
+            ast::Pat::PatVec(pat) => {
+                self.inline_attributes(&pat.attributes);
+                self.list(pat.items.as_slice(), pat.items.span(), BRACKET, |p, pat| {
+                    p.pat(pat)
+                });
+            }
+            ast::Pat::PatTuple(pat) => {
+                self.inline_attributes(&pat.attributes);
+
+                let delimiters = match &pat.path {
+                    Some(path) => {
+                        self.path(path);
+                        PAREN
+                    }
+                    None => PAREN.single_comma(),
+                };
+
+                self.list(
+                    pat.items.as_slice(),
+                    pat.items.span(),
+                    delimiters,
+                    |p, pat| p.pat(pat),
+                );
+            }
+            ast::Pat::PatObject(pat) => {
+                self.inline_attributes(&pat.attributes);
+                let delimiters = self.object_ident(&pat.ident);
+                self.list(
+                    pat.items.as_slice(),
+                    pat.items.span(),
+                    delimiters,
+                    |p, pat| p.pat(pat),
+                );
+            }
+            ast::Pat::PatBinding(pat) => {
+                self.inline_attributes(&pat.attributes);
+                self.object_key(&pat.key);
+                self.write(": ");
+                self.pat(&pat.pat);
+            }
+            ast::Pat::PatRest(pat) => {
+                self.inline_attributes(&pat.attributes);
+                self.write("..");
+            }
+            ast::Pat::PatOr(pat) => {
+                self.pat(&pat.first);
+
+                for (_, pat) in &pat.rest {
+                    self.write(" | ");
+                    self.pat(pat);
+                }
+            }
+            ast::Pat::PatRange(pat) => {
+                self.inline_attributes(&pat.attributes);
+
+                if let Some(start) = &pat.start {
+                    self.expr(start);
+                }
+
+                match pat.limits {
+                    ast::ExprRangeLimits::HalfOpen(..) => self.write(".."),
+                    ast::ExprRangeLimits::Closed(..) => self.write("..="),
+                }
+
+                if let Some(end) = &pat.end {
+                    self.expr(end);
+                }
+            }
+        }
+    }
+
+    fn path(&mut self, path: &ast::Path) {
+        if path.global.is_some() {
+            self.write("::");
+        }
+
+        self.path_segment(&path.first);
+
+        for (_, segment) in &path.rest {
+            self.write("::");
+            self.path_segment(segment);
+        }
+
+        if path.trailing.is_some() {
+            self.write("::");
+        }
+    }
+
+    fn path_segment(&mut self, segment: &ast::PathSegment) {
+        match segment {
+            ast::PathSegment::Generics(generics) => {
+                self.list(generics.as_slice(), generics.span(), ANGLE, |p, e| {
+                    p.expr(&e.expr)
+                });
+            }
+            segment => {
+                self.verbatim(segment.span());
+            }
+        }
+    }
+
+    /// Attributes on items, each on a line of its own.
+    fn attributes(&mut self, attributes: &[ast::Attribute]) {
+        for attribute in attributes {
+            self.verbatim(attribute.span());
+
+            // NB: the span of a line doc comment includes its line ending.
+            let len = self.out.trim_end().len();
+            self.out.truncate(len);
+            self.newline();
+        }
+    }
+
+    /// Attributes on expressions and fields, which are kept on the same line
+    /// unless they are doc comments.
+    fn inline_attributes(&mut self, attributes: &[ast::Attribute]) {
+        for attribute in attributes {
+            let text = self
+                .source
+                .get(attribute.span().range())
+                .unwrap_or_default();
+
+            if text.starts_with("//") || text.starts_with("/*") {
+                self.attributes(std::slice::from_ref(attribute));
+                self.inline_docs += 1;
+                continue;
+            }
+
+            self.verbatim(attribute.span());
+            self.write(" ");
+        }
+    }
+
+    fn visibility(&mut self, visibility: &ast::Visibility) {
+        if let Some(span) = visibility.option_span() {
+            self.verbatim(span);
+            self.write(" ");
+        }
+    }
+
+    /// Write a delimited, comma-separated list.
+    ///
+    /// The list is kept on one line if it fits and only its last element spans
+    /// multiple lines, otherwise each element is put on a line of its own.
+    fn list<T, S>(
+        &mut self,
+        items: &[(T, Option<S>)],
+        span: Span,
+        delimiters: Delimiters,
+        f: impl Fn(&mut Self, &T),
+    ) where
+        T: Spanned,
+        S: Spanned,
+    {
+        let open = Span::new(span.start, span.start.into_usize() + delimiters.open.len());
+        let close = Span::new(span.end.into_usize() - delimiters.close.len(), span.end);
+
+        if items.is_empty() && !self.has_comments(span) {
+            self.write(delimiters.open);
+            self.write(delimiters.close);
+            self.last_end = span.end.into_usize();
+            return;
+        }
+
+        if !delimiters.multiline && !self.has_comments(span) {
+            let snapshot = self.snapshot();
+            let line_start = self.out.rfind('\n').map_or(0, |n| n + 1);
+
+            if self.inline_list(items, delimiters, line_start, &f) {
+                let line = self.out[line_start..].lines().next().unwrap_or_default();
+
+                if line.chars().count() <= MAX_WIDTH {
+                    self.last_end = span.end.into_usize();
+                    return;
+                }
+            }
+
+            self.restore(snapshot);
+        }
+
+        self.open(delimiters.open, open);
+
+        for (item, separator) in items {
+            self.line(item.span());
+            f(self, item);
+            self.write(",");
+            self.last_end = end_with(item.span(), separator.as_ref());
+        }
+
+        self.close(delimiters.close, close);
+    }
+
+    /// Try to write a list on a single line, returning `false` if any element
+    /// except the last spans multiple lines or if any element has doc
+    /// comments.
+    fn inline_list<T, S>(
+        &mut self,
+        items: &[(T, Option<S>)],
+        delimiters: Delimiters,
+        line_start: usize,
+        f: &impl Fn(&mut Self, &T),
+    ) -> bool {
+        let inline_docs = self.inline_docs;
+        self.write(delimiters.open);
+
+        if delimiters.pad {
+            self.write(" ");
+        }
+
+        for (index, (item, _)) in items.iter().enumerate() {
+            if index > 0 {
+                self.write(", ");
+            }
+
+            f(self, item);
+
+            if self.inline_docs != inline_docs {
+                return false;
+            }
+
+            if index + 1 < items.len() && self.out[line_start..].contains('\n') {
+                return false;
+            }
+        }
+
+        if delimiters.single_comma && items.len() == 1 {
+            self.write(",");
+        }
+
+        if delimiters.pad {
+            self.write(" ");
+        }
+
+        self.write(delimiters.close);
+        true
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            len: self.out.len(),
+            comments: self.comments.clone(),
+            indent: self.indent,
+            last_end: self.last_end,
+            fresh: self.fresh,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.out.truncate(snapshot.len);
+        self.comments = snapshot.comments;
+        self.indent = snapshot.indent;
+        self.last_end = snapshot.last_end;
+        self.fresh = snapshot.fresh;
+    }
+
+    /// Open a delimited block which has one element per line.
+    fn open(&mut self, open: &str, span: Span) {
+        self.write(open);
+        self.indent += 1;
+        self.fresh = true;
+        self.last_end = span.end.into_usize();
+    }
+
+    /// Close a block opened with [Printer::open], emitting any comments
+    /// which are left inside of it.
+    fn close(&mut self, close: &str, span: Span) {
+        self.comments_before(span.start.into_usize());
+        self.indent -= 1;
+
+        if !self.at_line_start() {
+            self.newline();
+        }
+
+        self.write(close);
+        self.last_end = span.end.into_usize();
+    }
+
+    /// Start a new line for the element at the given span, emitting comments
+    /// which precede it and preserving a blank line if there was one.
+    fn line(&mut self, span: Span) {
+        let pos = span.start.into_usize();
+        self.comments_before(pos);
+
+        if !self.at_line_start() {
+            self.newline();
+        }
+
+        if !self.fresh && has_blank_line(self.gap(pos)) {
+            self.blank_line();
+        }
+    }
+
+    /// Emit all comments which start before the given position.
+    ///
+    /// Comments on the same line as the last element are kept at the end of
+    /// that line, all other comments are put on lines of their own.
+    fn comments_before(&mut self, pos: usize) {
+        while let Some(comment) = self.comments.first().copied() {
+            if comment.start.into_usize() >= pos {
+                break;
+            }
+
+            self.comments.remove(0);
+
+            let start = comment.start.into_usize();
+            let gap = self.gap(start);
+            let text = self.source[comment.range()].trim_end();
+
+            if !self.at_line_start() && !gap.contains('\n') {
+                self.write(" ");
+            } else {
+                if !self.at_line_start() {
+                    self.newline();
+                }
+
+                if !self.fresh && has_blank_line(gap) {
+                    self.blank_line();
+                }
+            }
+
+            self.write(text);
+            // NB: the span of a line comment includes its line ending.
+            self.last_end = start + text.len();
+
+            // NB: a line comment runs to the end of the line.
+            if text.starts_with("//") {
+                self.newline();
+            }
+        }
+    }
+
+    /// Test if there are any pending comments inside of the given span.
+    fn has_comments(&self, span: Span) -> bool {
+        self.comments
+            .iter()
+            .any(|c| c.start >= span.start && c.start < span.end)
+    }
+
+    /// The source between the last emitted element and the given position.
+    fn gap(&self, pos: usize) -> &'a str {
+        self.source
+            .get(self.last_end.min(pos)..pos)
+            .unwrap_or_default()
+    }
+
+    /// Write the source of the given span as-is.
+    fn verbatim(&mut self, span: Span) {
+        // NB: comments inside of the span are part of the output.
+        self.comments
+            .retain(|c| !(c.start >= span.start && c.end <= span.end));
+
+        let text = self.source.get(span.range()).unwrap_or_default();
+        self.write(text);
+    }
+
+    fn write(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+
+        if self.at_line_start() {
+            for _ in 0..self.indent {
+                self.out.push_str(INDENT);
+            }
+        }
+
+        self.out.push_str(text);
+        self.fresh = false;
+    }
+
+    fn newline(&mut self) {
+        let len = self.out.trim_end_matches(' ').len();
+        self.out.truncate(len);
+        self.out.push('\n');
+    }
+
+    fn blank_line(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.out.is_empty() || self.out.ends_with('\n')
+    }
+}
+
+/// A link in a method chain like `a.b(c).await?`.
+enum Link<'a> {
+    /// A field access or the name of a method.
+    Field(&'a ast::ExprField),
+    /// A `.await`.
+    Await,
+    /// The arguments to a method call.
+    Call(&'a ast::Parenthesized<ast::Expr, T![,]>),
+    /// A `?`.
+    Try,
+}
+
+/// Split an expression into the root of a method chain and its links.
+fn chain(mut expr: &ast::Expr) -> (&ast::Expr, Vec<Link<'_>>) {
+    let mut links = Vec::new();
+
+    loop {
+        match expr {
+            ast::Expr::Try(e) if e.attributes.is_empty() => {
+                links.push(Link::Try);
+                expr = &e.expr;
+            }
+            ast::Expr::Await(e) if e.attributes.is_empty() => {
+                links.push(Link::Await);
+                expr = &e.expr;
+            }
+            ast::Expr::Call(e)
+                if e.attributes.is_empty() && matches!(*e.expr, ast::Expr::FieldAccess(..)) =>
+            {
+                links.push(Link::Call(&e.args));
+                expr = &e.expr;
+            }
+            ast::Expr::FieldAccess(e) if e.attributes.is_empty() => {
+                links.push(Link::Field(&e.expr_field));
+                expr = &e.expr;
+            }
+            _ => break,
+        }
+    }
+
+    links.reverse();
+    (expr, links)
+}
+
+/// Test if the given whitespace contains an empty line.
+fn has_blank_line(gap: &str) -> bool {
+    let lines = gap.split('\n').collect::<Vec<_>>();
+    lines.len() > 2
+        && lines[1..lines.len() - 1]
+            .iter()
+            .any(|l| l.trim().is_empty())
+}
+
+/// The end of a span, extended to cover an optional trailing separator.
+fn end_with<S>(span: Span, separator: Option<&S>) -> usize
+where
+    S: Spanned,
+{
+    match separator {
+        Some(separator) => separator.span().end.into_usize(),
+        None => span.end.into_usize(),
+    }
+}
+
+/// The end of an item including its optional trailing semicolon.
+fn item_end(item: &ast::Item, semi: Option<&T![;]>) -> usize {
+    end_with(item.span(), semi)
+}
+
+/// Test if the item needs to be terminated with a semicolon, which is the case
+/// for all items which do not end in braces.
+fn needs_semi(item: &ast::Item) -> bool {
+    match item {
+        ast::Item::Fn(..)
+        | ast::Item::Impl(..)
+        | ast::Item::Trait(..)
+        | ast::Item::Enum(..)
+        | ast::Item::Mod(..) => false,
+        ast::Item::Struct(item) => !matches!(item.body, ast::ItemStructBody::StructBody(..)),
+        ast::Item::MacroCall(call) => {
+            !matches!(call.close.kind, ast::Kind::Close(ast::Delimiter::Brace))
+        }
+        ast::Item::Use(..) | ast::Item::Const(..) => true,
+    }
+}
@@ -25,6 +25,8 @@ pub enum HirErrorKind {
     ArenaAllocError { requested: usize },
     #[error("`..` is not supported in this location")]
     UnsupportedPatternRest,
+    #[error("`let` expressions are only supported in `&&` chains of conditions")]
+    UnsupportedLet,
     #[error("{error}")]
     QueryError {
         #[source]
@@ -55,6 +55,10 @@ pub enum PatKind<'hir> {
     PatObject(&'hir PatItems<'hir>),
     /// A binding `a: pattern` or `"foo": pattern`.
     PatBinding(&'hir PatBinding<'hir>),
+    /// An or-pattern `a | b`, with at least two alternatives.
+    PatOr(&'hir [Pat<'hir>]),
+    /// A range pattern `a..=b`.
+    PatRange(&'hir PatRange<'hir>),
 }
 
 /// A tuple pattern.
@@ -71,6 +75,18 @@ pub struct PatItems<'hir> {
     pub count: usize,
 }
 
+/// A range pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PatRange<'hir> {
+    /// The start of the range.
+    pub start: Option<&'hir Expr<'hir>>,
+    /// The range limits.
+    pub limits: ExprRangeLimits,
+    /// The end of the range.
+    pub end: Option<&'hir Expr<'hir>>,
+}
+
 /// An object item.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
@@ -547,6 +563,19 @@ pub enum Condition<'hir> {
     Expr(&'hir Expr<'hir>),
     /// A pattern match.
     ExprLet(&'hir ExprLet<'hir>),
+    /// A `&&`-chain of conditions, containing at least one pattern match.
+    Chain(&'hir ConditionChain<'hir>),
+}
+
+/// A `&&`-chain of conditions, like `let Some(a) = b && a > 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Spanned)]
+#[non_exhaustive]
+pub struct ConditionChain<'hir> {
+    /// The span of the chain.
+    #[rune(span)]
+    pub span: Span,
+    /// The conditions in the chain, evaluated in order.
+    pub conditions: &'hir [Condition<'hir>],
 }
 
 /// A path.
@@ -684,6 +713,8 @@ pub struct ItemFn<'hir> {
     pub name: &'hir ast::Ident,
     /// The arguments of the function.
     pub args: &'hir [FnArg<'hir>],
+    /// The optional return type of the function.
+    pub output: Option<&'hir Type<'hir>>,
     /// The body of the function.
     pub body: &'hir Block<'hir>,
 }
@@ -696,6 +727,29 @@ pub enum FnArg<'hir> {
     SelfValue(Span),
     /// Function argument is a pattern binding.
     Pat(&'hir Pat<'hir>),
+    /// Function argument is a pattern binding with a type annotation.
+    Typed(&'hir Pat<'hir>, &'hir Type<'hir>),
+}
+
+/// A type annotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Spanned)]
+#[non_exhaustive]
+pub struct Type<'hir> {
+    /// The span of the type.
+    #[rune(span)]
+    pub span: Span,
+    /// The kind of the type.
+    pub kind: TypeKind<'hir>,
+}
+
+/// The kind of a [Type].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TypeKind<'hir> {
+    /// A type referenced by path.
+    Path(&'hir Path<'hir>),
+    /// A tuple type.
+    Tuple(&'hir [Type<'hir>]),
 }
 
 /// A block of statements.
@@ -735,7 +789,8 @@ pub enum Stmt<'hir> {
     Item(Span),
 }
 
-/// A local variable declaration `let <pattern> = <expr>;`
+/// A local variable declaration `let <pattern> = <expr>;`, optionally followed
+/// by a diverging `else` block.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Spanned)]
 #[non_exhaustive]
 pub struct Local<'hir> {
@@ -744,6 +799,10 @@ pub struct Local<'hir> {
     pub span: Span,
     /// The name of the binding.
     pub pat: &'hir Pat<'hir>,
+    /// The optional type annotation of the binding.
+    pub ty: Option<&'hir Type<'hir>>,
     /// The expression the binding is assigned to.
     pub expr: &'hir Expr<'hir>,
+    /// The diverging `else` block to run if the pattern doesn't match.
+    pub expr_else: Option<&'hir ExprElse<'hir>>,
 }
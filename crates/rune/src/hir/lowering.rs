@@ -85,6 +85,7 @@ pub fn item_fn<'hir>(
         visibility: alloc!(ctx, ast; visibility(ctx, &ast.visibility)?),
         name: alloc!(ctx, ast; ast.name),
         args: iter!(ctx, ast; &ast.args, |(ast, _)| fn_arg(ctx, ast)?),
+        output: option!(ctx, ast; &ast.output, |(_, ast)| ty(ctx, ast)?),
         body: alloc!(ctx, ast; block(ctx, &ast.body)?),
     })
 }
@@ -142,10 +143,7 @@ pub fn expr<'hir>(ctx: &Ctx<'hir, '_>, ast: &ast::Expr) -> Result<hir::Expr<'hir
             iter: alloc!(ctx, ast; expr(ctx, &ast.iter)?),
             body: alloc!(ctx, ast; block(ctx, &ast.body)?),
         })),
-        ast::Expr::Let(ast) => hir::ExprKind::Let(alloc!(ctx, ast; hir::ExprLet {
-            pat: alloc!(ctx, ast; pat(ctx, &ast.pat)?),
-            expr: alloc!(ctx, ast; expr(ctx, &ast.expr)?),
-        })),
+        ast::Expr::Let(ast) => hir::ExprKind::Let(alloc!(ctx, ast; expr_let(ctx, ast)?)),
         ast::Expr::If(ast) => hir::ExprKind::If(alloc!(ctx, ast; hir::ExprIf {
             condition: alloc!(ctx, ast; condition(ctx, &ast.condition)?),
             block: alloc!(ctx, ast; block(ctx, &ast.block)?),
@@ -183,15 +181,24 @@ pub fn expr<'hir>(ctx: &Ctx<'hir, '_>, ast: &ast::Expr) -> Result<hir::Expr<'hir
             }))
         }
         ast::Expr::Empty(ast) => hir::ExprKind::Group(alloc!(ctx, ast; expr(ctx, &ast.expr)?)),
-        ast::Expr::Binary(ast) => hir::ExprKind::Binary(alloc!(ctx, ast; hir::ExprBinary {
-            lhs: alloc!(ctx, ast; expr(ctx, &ast.lhs)?),
-            op: ast.op,
-            rhs: alloc!(ctx, ast; expr(ctx, &ast.rhs)?),
-        })),
-        ast::Expr::Unary(ast) => hir::ExprKind::Unary(alloc!(ctx, ast; hir::ExprUnary {
-            op: ast.op,
-            expr: alloc!(ctx, ast; expr(ctx, &ast.expr)?),
-        })),
+        ast::Expr::Binary(ast) => {
+            let_operand(&ast.lhs)?;
+            let_operand(&ast.rhs)?;
+
+            hir::ExprKind::Binary(alloc!(ctx, ast; hir::ExprBinary {
+                lhs: alloc!(ctx, ast; expr(ctx, &ast.lhs)?),
+                op: ast.op,
+                rhs: alloc!(ctx, ast; expr(ctx, &ast.rhs)?),
+            }))
+        }
+        ast::Expr::Unary(ast) => {
+            let_operand(&ast.expr)?;
+
+            hir::ExprKind::Unary(alloc!(ctx, ast; hir::ExprUnary {
+                op: ast.op,
+                expr: alloc!(ctx, ast; expr(ctx, &ast.expr)?),
+            }))
+        }
         ast::Expr::Index(ast) => hir::ExprKind::Index(alloc!(ctx, ast; hir::ExprIndex {
             target: alloc!(ctx, ast; expr(ctx, &ast.target)?),
             index: alloc!(ctx, ast; expr(ctx, &ast.index)?),
@@ -252,7 +259,10 @@ pub fn expr<'hir>(ctx: &Ctx<'hir, '_>, ast: &ast::Expr) -> Result<hir::Expr<'hir
             },
             to: option!(ctx, ast; &ast.to, |ast| expr(ctx, ast)?),
         })),
-        ast::Expr::Group(ast) => hir::ExprKind::Group(alloc!(ctx, ast; expr(ctx, &ast.expr)?)),
+        ast::Expr::Group(ast) => {
+            let_operand(&ast.expr)?;
+            hir::ExprKind::Group(alloc!(ctx, ast; expr(ctx, &ast.expr)?))
+        }
         ast::Expr::MacroCall(ast) => {
             hir::ExprKind::MacroCall(alloc!(ctx, ast; match ctx.q.builtin_macro_for(ast)? {
                 query::BuiltInMacro::Template(ast) => hir::MacroCall::Template(alloc!(ctx, ast; hir::BuiltInTemplate {
@@ -327,6 +337,23 @@ fn fn_arg<'hir>(ctx: &Ctx<'hir, '_>, ast: &ast::FnArg) -> Result<hir::FnArg<'hir
     Ok(match ast {
         ast::FnArg::SelfValue(ast) => hir::FnArg::SelfValue(ast.span()),
         ast::FnArg::Pat(ast) => hir::FnArg::Pat(alloc!(ctx, ast; pat(ctx, ast)?)),
+        ast::FnArg::Typed(ast) => hir::FnArg::Typed(
+            alloc!(ctx, ast; pat(ctx, &ast.pat)?),
+            alloc!(ctx, ast; ty(ctx, &ast.ty)?),
+        ),
+    })
+}
+
+/// Lower a type annotation.
+fn ty<'hir>(ctx: &Ctx<'hir, '_>, ast: &ast::Type) -> Result<hir::Type<'hir>, HirError> {
+    Ok(hir::Type {
+        span: ast.span(),
+        kind: match ast {
+            ast::Type::Path(ast) => hir::TypeKind::Path(alloc!(ctx, ast; path(ctx, ast)?)),
+            ast::Type::Tuple(ast) => {
+                hir::TypeKind::Tuple(iter!(ctx, ast; ast, |(ast, _)| ty(ctx, ast)?))
+            }
+        },
     })
 }
 
@@ -335,7 +362,12 @@ fn local<'hir>(ctx: &Ctx<'hir, '_>, ast: &ast::Local) -> Result<hir::Local<'hir>
     Ok(hir::Local {
         span: ast.span(),
         pat: alloc!(ctx, ast; pat(ctx, &ast.pat)?),
+        ty: option!(ctx, ast; &ast.ty, |(_, ast)| ty(ctx, ast)?),
         expr: alloc!(ctx, ast; expr(ctx, &ast.expr)?),
+        expr_else: option!(ctx, ast; &ast.expr_else, |ast| hir::ExprElse {
+            span: ast.span(),
+            block: alloc!(ctx, ast; block(ctx, &ast.block)?)
+        }),
     })
 }
 
@@ -398,6 +430,18 @@ fn pat<'hir>(ctx: &Ctx<'hir, '_>, ast: &ast::Pat) -> Result<hir::Pat<'hir>, HirE
                     pat: alloc!(ctx, ast; pat(ctx, &ast.pat)?),
                 }))
             }
+            ast::Pat::PatOr(ast) => {
+                let alternatives = ast.alternatives().collect::<Vec<_>>();
+                hir::PatKind::PatOr(iter!(ctx, ast; alternatives, |ast| pat(ctx, ast)?))
+            }
+            ast::Pat::PatRange(ast) => hir::PatKind::PatRange(alloc!(ctx, ast; hir::PatRange {
+                start: option!(ctx, ast; &ast.start, |ast| expr(ctx, ast)?),
+                limits: match ast.limits {
+                    ast::ExprRangeLimits::HalfOpen(_) => hir::ExprRangeLimits::HalfOpen,
+                    ast::ExprRangeLimits::Closed(_) => hir::ExprRangeLimits::Closed,
+                },
+                end: option!(ctx, ast; &ast.end, |ast| expr(ctx, ast)?),
+            })),
         },
     })
 }
@@ -468,11 +512,59 @@ fn condition<'hir>(
     ast: &ast::Condition,
 ) -> Result<hir::Condition<'hir>, HirError> {
     Ok(match ast {
-        ast::Condition::Expr(ast) => hir::Condition::Expr(alloc!(ctx, ast; expr(ctx, ast)?)),
-        ast::Condition::ExprLet(ast) => hir::Condition::ExprLet(alloc!(ctx, ast; hir::ExprLet {
-            pat: alloc!(ctx, ast; pat(ctx, &ast.pat)?),
-            expr: alloc!(ctx, ast; expr(ctx, &ast.expr)?),
-        })),
+        ast::Condition::Expr(ast) => {
+            let mut chain = Vec::new();
+            condition_chain(ast, &mut chain);
+
+            if chain.iter().any(|e| matches!(e, ast::Expr::Let(..))) {
+                hir::Condition::Chain(alloc!(ctx, ast; hir::ConditionChain {
+                    span: ast.span(),
+                    conditions: iter!(ctx, ast; chain, |ast| match ast {
+                        ast::Expr::Let(ast) => hir::Condition::ExprLet(alloc!(ctx, ast; expr_let(ctx, ast)?)),
+                        ast => hir::Condition::Expr(alloc!(ctx, ast; expr(ctx, ast)?)),
+                    }),
+                }))
+            } else {
+                hir::Condition::Expr(alloc!(ctx, ast; expr(ctx, ast)?))
+            }
+        }
+        ast::Condition::ExprLet(ast) => {
+            hir::Condition::ExprLet(alloc!(ctx, ast; expr_let(ctx, ast)?))
+        }
+    })
+}
+
+/// Flatten a `&&`-chain of expressions.
+fn condition_chain<'a>(ast: &'a ast::Expr, chain: &mut Vec<&'a ast::Expr>) {
+    match ast {
+        ast::Expr::Binary(binary) if matches!(binary.op, ast::BinOp::And(..)) => {
+            condition_chain(&binary.lhs, chain);
+            condition_chain(&binary.rhs, chain);
+        }
+        ast => chain.push(ast),
+    }
+}
+
+/// Error if the given operand is a `let` expression, since those are only
+/// supported as links in the `&&` chain of a condition.
+fn let_operand(ast: &ast::Expr) -> Result<(), HirError> {
+    let mut current = ast;
+
+    while let ast::Expr::Group(group) = current {
+        current = &group.expr;
+    }
+
+    if let ast::Expr::Let(..) = current {
+        return Err(HirError::new(current, HirErrorKind::UnsupportedLet));
+    }
+
+    Ok(())
+}
+
+fn expr_let<'hir>(ctx: &Ctx<'hir, '_>, ast: &ast::ExprLet) -> Result<hir::ExprLet<'hir>, HirError> {
+    Ok(hir::ExprLet {
+        pat: alloc!(ctx, ast; pat(ctx, &ast.pat)?),
+        expr: alloc!(ctx, ast; expr(ctx, &ast.expr)?),
     })
 }
 
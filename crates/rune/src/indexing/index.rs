@@ -3,8 +3,8 @@ use crate::ast::{OptionSpanned, Span, Spanned};
 use crate::collections::HashMap;
 use crate::compile::attrs::Attributes;
 use crate::compile::{
-    attrs, ir, CompileError, CompileErrorKind, CompileResult, Doc, ItemId, Location, ModId,
-    Options, SourceLoader, Visibility,
+    attrs, ir, CompileError, CompileErrorKind, CompileResult, Doc, ItemId, ItemMeta, Location,
+    ModId, Options, SourceLoader, Visibility,
 };
 use crate::indexing::locals;
 use crate::indexing::{IndexFnKind, IndexScopes};
@@ -12,7 +12,7 @@ use crate::macros::MacroCompiler;
 use crate::parse::{Parse, ParseError, ParseErrorKind, Parser, Resolve};
 use crate::query::{
     BuiltInFile, BuiltInFormat, BuiltInLine, BuiltInMacro, BuiltInTemplate, Function, Indexed,
-    IndexedEntry, IndexedFunction, InstanceFunction, Query,
+    IndexedEntry, IndexedFunction, InstanceFunction, Query, Trait, TraitFunction, TraitImpl,
 };
 use crate::runtime::format;
 use crate::runtime::Call;
@@ -641,58 +641,13 @@ fn item_fn(ast: &mut ast::ItemFn, idx: &mut Indexer<'_>) -> CompileResult<()> {
         &docs,
     )?;
 
-    let kind = match (ast.const_token, ast.async_token) {
-        (Some(const_token), Some(async_token)) => {
-            return Err(CompileError::new(
-                const_token.span().join(async_token.span()),
-                CompileErrorKind::FnConstAsyncConflict,
-            ));
-        }
-        (Some(..), _) => IndexFnKind::Const,
-        (_, Some(..)) => IndexFnKind::Async,
-        _ => IndexFnKind::None,
-    };
-
-    if let (Some(const_token), Some(async_token)) = (ast.const_token, ast.async_token) {
-        return Err(CompileError::new(
-            const_token.span().join(async_token.span()),
-            CompileErrorKind::FnConstAsyncConflict,
-        ));
-    }
-
-    let guard = idx.scopes.push_function(kind);
-
-    for (arg, _) in &mut ast.args {
-        match arg {
-            ast::FnArg::SelfValue(s) => {
-                let span = s.span();
-                idx.scopes.declare(SELF, span)?;
-            }
-            ast::FnArg::Pat(p) => {
-                locals::pat(p, idx)?;
-            }
-        }
-    }
-
-    // Take and restore item nesting.
-    let last = idx.nested_item.replace(ast.descriptive_span());
-    block(&mut ast.body, idx)?;
-    idx.nested_item = last;
-
-    let f = guard.into_function(span)?;
+    let call = fn_body(ast, idx)?;
     ast.id = item_meta.id;
 
-    let call = match Indexer::call(f.generator, f.kind) {
+    let call = match call {
         Some(call) => call,
         // const function.
         None => {
-            if f.generator {
-                return Err(CompileError::new(
-                    span,
-                    CompileErrorKind::FnConstNotGenerator,
-                ));
-            }
-
             idx.q.index_const_fn(item_meta, Box::new(ast.clone()))?;
             return Ok(());
         }
@@ -762,6 +717,9 @@ fn item_fn(ast: &mut ast::ItemFn, idx: &mut Indexer<'_>) -> CompileResult<()> {
             CompileError::new(span, CompileErrorKind::InstanceFunctionOutsideImpl)
         })?;
 
+        let name: Box<str> = ast.name.resolve(resolve_context!(idx.q))?.into();
+        idx.q.index_instance_fn_name(&name);
+
         idx.q.index_and_build(IndexedEntry {
             item_meta,
             indexed: Indexed::InstanceFunction(InstanceFunction {
@@ -790,6 +748,64 @@ fn item_fn(ast: &mut ast::ItemFn, idx: &mut Indexer<'_>) -> CompileResult<()> {
     Ok(())
 }
 
+/// Index the arguments and body of a function.
+///
+/// Returns the calling convention of the function, or `None` if it's a
+/// constant function.
+fn fn_body(ast: &mut ast::ItemFn, idx: &mut Indexer<'_>) -> CompileResult<Option<Call>> {
+    let span = ast.span();
+
+    let kind = match (ast.const_token, ast.async_token) {
+        (Some(const_token), Some(async_token)) => {
+            return Err(CompileError::new(
+                const_token.span().join(async_token.span()),
+                CompileErrorKind::FnConstAsyncConflict,
+            ));
+        }
+        (Some(..), _) => IndexFnKind::Const,
+        (_, Some(..)) => IndexFnKind::Async,
+        _ => IndexFnKind::None,
+    };
+
+    let guard = idx.scopes.push_function(kind);
+
+    for (arg, _) in &mut ast.args {
+        match arg {
+            ast::FnArg::SelfValue(s) => {
+                let span = s.span();
+                idx.scopes.declare(SELF, span)?;
+            }
+            ast::FnArg::Pat(p) => {
+                locals::pat(p, idx)?;
+            }
+            ast::FnArg::Typed(typed) => {
+                locals::pat(&mut typed.pat, idx)?;
+                ty(&mut typed.ty, idx)?;
+            }
+        }
+    }
+
+    if let Some((_, output)) = &mut ast.output {
+        ty(output, idx)?;
+    }
+
+    // Take and restore item nesting.
+    let last = idx.nested_item.replace(ast.descriptive_span());
+    block(&mut ast.body, idx)?;
+    idx.nested_item = last;
+
+    let f = guard.into_function(span)?;
+
+    match Indexer::call(f.generator, f.kind) {
+        Some(call) => Ok(Some(call)),
+        None if f.generator => Err(CompileError::new(
+            span,
+            CompileErrorKind::FnConstNotGenerator,
+        )),
+        None => Ok(None),
+    }
+}
+
 #[instrument]
 fn expr_block(ast: &mut ast::ExprBlock, idx: &mut Indexer<'_>) -> CompileResult<()> {
     let span = ast.span();
@@ -938,7 +954,34 @@ fn local(ast: &mut ast::Local, idx: &mut Indexer<'_>) -> CompileResult<()> {
     // We index the rhs expression first so that it doesn't see it's own
     // declaration and use that instead of capturing from the outside.
     expr(&mut ast.expr, idx, IS_USED)?;
+
+    if let Some(expr_else) = &mut ast.expr_else {
+        block(&mut expr_else.block, idx)?;
+    }
+
     pat(&mut ast.pat, idx, NOT_USED)?;
+
+    if let Some((_, ty_ast)) = &mut ast.ty {
+        ty(ty_ast, idx)?;
+    }
+
+    Ok(())
+}
+
+/// Index a type annotation.
+#[instrument]
+fn ty(ast: &mut ast::Type, idx: &mut Indexer<'_>) -> CompileResult<()> {
+    match ast {
+        ast::Type::Path(p) => {
+            path(p, idx, NOT_USED)?;
+        }
+        ast::Type::Tuple(tuple) => {
+            for (ty_ast, _) in tuple.iter_mut() {
+                ty(ty_ast, idx)?;
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -981,9 +1024,13 @@ fn pat(ast: &mut ast::Pat, idx: &mut Indexer<'_>, is_used: IsUsed) -> CompileRes
         ast::Pat::PatBinding(pat) => {
             pat_binding(pat, idx)?;
         }
+        ast::Pat::PatOr(pat) => {
+            pat_or(pat, idx, is_used)?;
+        }
         ast::Pat::PatIgnore(..) => (),
         ast::Pat::PatLit(..) => (),
         ast::Pat::PatRest(..) => (),
+        ast::Pat::PatRange(..) => (),
     }
 
     Ok(())
@@ -1003,6 +1050,17 @@ fn pat_tuple(ast: &mut ast::PatTuple, idx: &mut Indexer<'_>) -> CompileResult<()
     Ok(())
 }
 
+#[instrument]
+fn pat_or(ast: &mut ast::PatOr, idx: &mut Indexer<'_>, is_used: IsUsed) -> CompileResult<()> {
+    pat(&mut ast.first, idx, is_used)?;
+
+    for (_, p) in &mut ast.rest {
+        pat(p, idx, is_used)?;
+    }
+
+    Ok(())
+}
+
 #[instrument]
 fn pat_binding(ast: &mut ast::PatBinding, idx: &mut Indexer<'_>) -> CompileResult<()> {
     pat(&mut ast.pat, idx, NOT_USED)?;
@@ -1371,6 +1429,11 @@ fn item_impl(ast: &mut ast::ItemImpl, idx: &mut Indexer<'_>) -> CompileResult<()
         ));
     }
 
+    if let Some((trait_path, _)) = &mut ast.trait_ {
+        path(trait_path, idx, NOT_USED)?;
+        path(&mut ast.path, idx, NOT_USED)?;
+    }
+
     let mut guards = Vec::new();
 
     if let Some(global) = &ast.path.global {
@@ -1391,14 +1454,153 @@ fn item_impl(ast: &mut ast::ItemImpl, idx: &mut Indexer<'_>) -> CompileResult<()
     let new = idx.q.pool.alloc_item(&*idx.items.item());
     let old = std::mem::replace(&mut idx.impl_item, Some(new));
 
-    for i in &mut ast.functions {
-        item_fn(i, idx)?;
+    if let Some((trait_path, _)) = &ast.trait_ {
+        let mut functions = Vec::new();
+
+        for i in &mut ast.functions {
+            functions.push(trait_fn(i, idx)?);
+        }
+
+        let item_meta = idx.q.insert_new_item(
+            &idx.items,
+            Location::new(idx.source_id, ast.span()),
+            idx.mod_item,
+            Visibility::Inherited,
+            &[],
+        )?;
+
+        idx.q.queue_trait_impl(
+            item_meta,
+            TraitImpl {
+                trait_path: Box::new(trait_path.clone()),
+                type_path: Box::new(ast.path.clone()),
+                functions,
+            },
+        );
+    } else {
+        for i in &mut ast.functions {
+            item_fn(i, idx)?;
+        }
     }
 
     idx.impl_item = old;
     Ok(())
 }
 
+#[instrument]
+fn item_trait(ast: &mut ast::ItemTrait, idx: &mut Indexer<'_>) -> CompileResult<()> {
+    let span = ast.span();
+
+    let mut attrs = Attributes::new(ast.attributes.to_vec());
+    let docs = Doc::collect_from(resolve_context!(idx.q), &mut attrs)?;
+
+    if let Some(first) = attrs.remaining() {
+        return Err(CompileError::msg(
+            first,
+            "trait attributes are not supported",
+        ));
+    }
+
+    let ident = ast.ident.resolve(resolve_context!(idx.q))?;
+    let _guard = idx.items.push_name(ident);
+
+    let visibility = ast_to_visibility(&ast.visibility)?;
+    let item_meta = idx.q.insert_new_item(
+        &idx.items,
+        Location::new(idx.source_id, span),
+        idx.mod_item,
+        visibility,
+        &docs,
+    )?;
+
+    let mut functions = Vec::<TraitFunction>::new();
+
+    for f in &mut ast.functions {
+        let span = f.span();
+        let name: Box<str> = f.name().resolve(resolve_context!(idx.q))?.into();
+
+        if functions.iter().any(|f| f.name == name) {
+            return Err(CompileError::new(
+                span,
+                CompileErrorKind::DuplicateTraitFunction { name },
+            ));
+        }
+
+        let default = match f {
+            ast::ItemTraitFn::Required(decl) => {
+                if !matches!(decl.args.first(), Some((ast::FnArg::SelfValue(..), _))) {
+                    return Err(CompileError::new(
+                        span,
+                        CompileErrorKind::TraitFunctionNotInstance,
+                    ));
+                }
+
+                None
+            }
+            ast::ItemTraitFn::Provided(item_fn) => Some(trait_fn(item_fn, idx)?.1),
+        };
+
+        functions.push(TraitFunction {
+            name,
+            location: Location::new(idx.source_id, span),
+            default,
+        });
+    }
+
+    idx.q.index(IndexedEntry {
+        item_meta,
+        indexed: Indexed::Trait(Trait {
+            functions: functions.into(),
+        }),
+    });
+
+    Ok(())
+}
+
+/// Index a function which is declared in a trait or in a trait
+/// implementation.
+fn trait_fn(ast: &mut ast::ItemFn, idx: &mut Indexer<'_>) -> CompileResult<(ItemMeta, Function)> {
+    let span = ast.span();
+
+    if !ast.is_instance() {
+        return Err(CompileError::new(
+            span,
+            CompileErrorKind::TraitFunctionNotInstance,
+        ));
+    }
+
+    let name: Box<str> = ast.name.resolve(resolve_context!(idx.q))?.into();
+    let _guard = idx.items.push_name(name.as_ref());
+    idx.q.index_instance_fn_name(&name);
+
+    let mut attributes = attrs::Attributes::new(ast.attributes.clone());
+    let docs = Doc::collect_from(resolve_context!(idx.q), &mut attributes)?;
+
+    if let Some(attrs) = attributes.remaining() {
+        return Err(CompileError::msg(attrs, "unrecognized function attribute"));
+    }
+
+    let item_meta = idx.q.insert_new_item(
+        &idx.items,
+        Location::new(idx.source_id, span),
+        idx.mod_item,
+        Visibility::Inherited,
+        &docs,
+    )?;
+
+    let call = fn_body(ast, idx)?;
+    ast.id = item_meta.id;
+
+    let call = call.ok_or_else(|| CompileError::msg(span, "trait functions cannot be `const`"))?;
+
+    let function = Function {
+        ast: Box::new(ast.clone()),
+        call,
+    };
+
+    Ok((item_meta, function))
+}
+
 #[instrument]
 fn item_mod(ast: &mut ast::ItemMod, idx: &mut Indexer<'_>) -> CompileResult<()> {
     let mut attrs = Attributes::new(ast.attributes.clone());
@@ -1501,6 +1703,9 @@ fn item(ast: &mut ast::Item, idx: &mut Indexer<'_>) -> CompileResult<()> {
         ast::Item::Impl(item) => {
             item_impl(item, idx)?;
         }
+        ast::Item::Trait(item) => {
+            item_trait(item, idx)?;
+        }
         ast::Item::Mod(item) => {
             item_mod(item, idx)?;
         }
@@ -1632,6 +1837,10 @@ fn expr_closure(ast: &mut ast::ExprClosure, idx: &mut Indexer<'_>) -> CompileRes
             ast::FnArg::Pat(p) => {
                 locals::pat(p, idx)?;
             }
+            ast::FnArg::Typed(typed) => {
+                locals::pat(&mut typed.pat, idx)?;
+                ty(&mut typed.ty, idx)?;
+            }
         }
     }
 
@@ -24,9 +24,24 @@ pub(crate) fn pat(ast: &mut ast::Pat, idx: &mut Indexer<'_>) -> CompileResult<()
         ast::Pat::PatBinding(p) => {
             pat_binding(p, idx)?;
         }
+        ast::Pat::PatOr(p) => {
+            pat_or(p, idx)?;
+        }
         ast::Pat::PatIgnore(..) => (),
         ast::Pat::PatLit(..) => (),
         ast::Pat::PatRest(..) => (),
+        ast::Pat::PatRange(..) => (),
+    }
+
+    Ok(())
+}
+
+#[instrument]
+fn pat_or(ast: &mut ast::PatOr, idx: &mut Indexer<'_>) -> CompileResult<()> {
+    pat(&mut ast.first, idx)?;
+
+    for (_, p) in &mut ast.rest {
+        pat(p, idx)?;
     }
 
     Ok(())
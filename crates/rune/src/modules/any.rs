@@ -2,17 +2,16 @@
 
 use crate::runtime::{Protocol, Value};
 use crate::{Any, ContextError, Module};
-use std::any::TypeId as StdTypeId;
 use std::fmt;
 use std::fmt::Write;
 
 #[derive(Any, Debug)]
 #[rune(module = "crate")]
 #[repr(transparent)]
-struct TypeId(StdTypeId);
+struct TypeId(crate::Hash);
 
 fn type_id_of_val(item: Value) -> TypeId {
-    unsafe { std::mem::transmute(item.type_hash().expect("no type known for item!")) }
+    TypeId(item.type_hash().expect("no type known for item!"))
 }
 
 fn format_type_id(item: &TypeId, buf: &mut String) -> fmt::Result {
@@ -1,12 +1,73 @@
 //! The `std::cmp` module.
 
+use crate::compile::Variant;
+use crate::runtime::{Protocol, Value, VmError};
 use crate::{ContextError, Module};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::Write;
 
 /// Construct the `std::cmp` module.
 pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::with_crate_item("std", ["cmp"]);
 
-    module.ty::<std::cmp::Ordering>()?;
+    module.ty::<Ordering>()?;
+    module.enum_meta::<Ordering, 3>([
+        ("Less", Variant::unit()),
+        ("Equal", Variant::unit()),
+        ("Greater", Variant::unit()),
+    ])?;
+    module.variant_constructor(0, || Ordering::Less)?;
+    module.variant_constructor(1, || Ordering::Equal)?;
+    module.variant_constructor(2, || Ordering::Greater)?;
 
+    module.inst_fn(Protocol::IS_VARIANT, ordering_is_variant)?;
+    module.inst_fn(Protocol::EQ, ordering_eq)?;
+    module.inst_fn(Protocol::STRING_DEBUG, ordering_string_debug)?;
+
+    module.inst_fn("reverse", |this: &Ordering| this.reverse())?;
+    module.inst_fn("then", |this: &Ordering, other: &Ordering| {
+        this.then(*other)
+    })?;
+    module.inst_fn("is_eq", |this: &Ordering| this.is_eq())?;
+    module.inst_fn("is_ne", |this: &Ordering| this.is_ne())?;
+    module.inst_fn("is_lt", |this: &Ordering| this.is_lt())?;
+    module.inst_fn("is_gt", |this: &Ordering| this.is_gt())?;
+    module.inst_fn("is_le", |this: &Ordering| this.is_le())?;
+    module.inst_fn("is_ge", |this: &Ordering| this.is_ge())?;
+
+    module.function(["min"], min)?;
+    module.function(["max"], max)?;
     Ok(module)
 }
+
+fn ordering_is_variant(this: &Ordering, index: usize) -> bool {
+    matches!(
+        (this, index),
+        (Ordering::Less, 0) | (Ordering::Equal, 1) | (Ordering::Greater, 2)
+    )
+}
+
+fn ordering_eq(this: &Ordering, other: &Ordering) -> bool {
+    this == other
+}
+
+fn ordering_string_debug(this: &Ordering, s: &mut String) -> fmt::Result {
+    write!(s, "{:?}", this)
+}
+
+/// Return the smaller of two values, or the first if they are equal.
+fn min(a: Value, b: Value) -> Result<Value, VmError> {
+    Ok(match Value::cmp(&a, &b)? {
+        Ordering::Greater => b,
+        _ => a,
+    })
+}
+
+/// Return the larger of two values, or the second if they are equal.
+fn max(a: Value, b: Value) -> Result<Value, VmError> {
+    Ok(match Value::cmp(&a, &b)? {
+        Ordering::Greater => a,
+        _ => b,
+    })
+}
@@ -1,8 +1,14 @@
 //! `std::collections` module.
 
-use crate::runtime::{Iterator, IteratorTrait, Key, Protocol, Ref, Value, VmError, VmErrorKind};
+use crate::runtime::{
+    Function, Iterator, IteratorTrait, Key, Protocol, RangeLimits, Ref, Shared, Value, VmError,
+    VmErrorKind,
+};
 use crate::{Any, ContextError, Module};
+use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::fmt;
+use std::ops::Bound;
 
 #[derive(Any, Clone)]
 #[rune(module = "crate")]
@@ -77,7 +83,7 @@ impl HashMap {
         let value = self.map.get(&key).ok_or_else(|| {
             VmError::from(VmErrorKind::MissingIndexKey {
                 target: Self::type_info(),
-                index: key,
+                index: format!("{:?}", key),
             })
         })?;
 
@@ -411,6 +417,703 @@ impl VecDeque {
     }
 }
 
+/// A key which is ordered by its natural ordering, which uses the `CMP`
+/// protocol for values which aren't built in.
+///
+/// Since ordering can't fail, the first error raised while comparing keys is
+/// put aside and reported by [ordered] once the operation is done.
+#[derive(Clone)]
+struct SortKey(Value);
+
+thread_local! {
+    static ORDER_ERROR: RefCell<Option<VmError>> = const { RefCell::new(None) };
+}
+
+impl PartialEq for SortKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SortKey {}
+
+impl PartialOrd for SortKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match Value::cmp(&self.0, &other.0) {
+            Ok(ordering) => ordering,
+            Err(error) => {
+                ORDER_ERROR.with(|slot| {
+                    slot.borrow_mut().get_or_insert(error);
+                });
+
+                Ordering::Equal
+            }
+        }
+    }
+}
+
+/// Run an operation which compares keys, reporting the first error raised
+/// while comparing them.
+fn ordered<O>(f: impl FnOnce() -> O) -> Result<O, VmError> {
+    // NB: comparing keys might call a script which uses another collection,
+    // so an error belonging to an outer operation is kept aside.
+    let outer = ORDER_ERROR.with(|slot| slot.borrow_mut().take());
+    let output = f();
+    let error = ORDER_ERROR.with(|slot| std::mem::replace(&mut *slot.borrow_mut(), outer));
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(output),
+    }
+}
+
+/// Entries which are kept sorted by their keys.
+#[derive(Clone, Default)]
+struct Sorted<T> {
+    entries: std::collections::BTreeMap<SortKey, T>,
+}
+
+impl<T> Sorted<T> {
+    fn insert(&mut self, key: Value, value: T) -> Result<Option<T>, VmError> {
+        let key = SortKey(key_value(key));
+
+        // NB: the key is looked up first, so that nothing is modified if
+        // comparing it fails.
+        if let Some(existing) = ordered(|| self.entries.get_mut(&key))? {
+            return Ok(Some(std::mem::replace(existing, value)));
+        }
+
+        ordered(|| self.entries.insert(key, value))
+    }
+
+    fn get(&self, key: &Value) -> Result<Option<&T>, VmError> {
+        let key = SortKey(key.clone());
+        ordered(|| self.entries.get(&key))
+    }
+
+    fn remove(&mut self, key: &Value) -> Result<Option<(Value, T)>, VmError> {
+        let key = SortKey(key.clone());
+
+        if !ordered(|| self.entries.contains_key(&key))? {
+            return Ok(None);
+        }
+
+        let entry = ordered(|| self.entries.remove_entry(&key))?;
+        Ok(entry.map(|(key, value)| (key.0, value)))
+    }
+
+    fn first(&self) -> Option<(&Value, &T)> {
+        let (key, value) = self.entries.iter().next()?;
+        Some((&key.0, value))
+    }
+
+    fn last(&self) -> Option<(&Value, &T)> {
+        let (key, value) = self.entries.iter().next_back()?;
+        Some((&key.0, value))
+    }
+
+    fn pop_first(&mut self) -> Option<(Value, T)> {
+        let key = self.entries.keys().next()?.clone();
+        let (key, value) = self.entries.remove_entry(&key)?;
+        Some((key.0, value))
+    }
+
+    fn pop_last(&mut self) -> Option<(Value, T)> {
+        let key = self.entries.keys().next_back()?.clone();
+        let (key, value) = self.entries.remove_entry(&key)?;
+        Some((key.0, value))
+    }
+
+    fn iter(&self) -> impl DoubleEndedIterator<Item = (&Value, &T)> + Clone {
+        self.entries.iter().map(|(key, value)| (&key.0, value))
+    }
+
+    fn keys(&self) -> impl DoubleEndedIterator<Item = &Value> + Clone {
+        self.entries.keys().map(|key| &key.0)
+    }
+
+    /// Get the entries whose keys are in the given range.
+    fn range(&self, range: Value) -> Result<std::vec::Vec<(&Value, &T)>, VmError> {
+        let (start, end) = range_bounds(range)?;
+
+        ordered(|| {
+            self.entries
+                .range((start, end))
+                .map(|(key, value)| (&key.0, value))
+                .collect()
+        })
+    }
+
+    /// Walk the keys of two sorted collections in order, collecting the keys
+    /// which are only in `self`, in both, or only in `other` depending on the
+    /// given flags.
+    fn merge_keys<U>(
+        &self,
+        other: &Sorted<U>,
+        only_self: bool,
+        both: bool,
+        only_other: bool,
+    ) -> Result<std::vec::Vec<Value>, VmError> {
+        let a = self.keys().collect::<std::vec::Vec<_>>();
+        let b = other.keys().collect::<std::vec::Vec<_>>();
+
+        let mut output = std::vec::Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        loop {
+            let ordering = match (a.get(i), b.get(j)) {
+                (Some(a), Some(b)) => Value::cmp(a, b)?,
+                (Some(..), None) => Ordering::Less,
+                (None, Some(..)) => Ordering::Greater,
+                (None, None) => break,
+            };
+
+            match ordering {
+                Ordering::Less => {
+                    if only_self {
+                        output.push(a[i].clone());
+                    }
+
+                    i += 1;
+                }
+                Ordering::Equal => {
+                    if both {
+                        output.push(a[i].clone());
+                    }
+
+                    i += 1;
+                    j += 1;
+                }
+                Ordering::Greater => {
+                    if only_other {
+                        output.push(b[j].clone());
+                    }
+
+                    j += 1;
+                }
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+/// Copy a value which is used as a key, so that modifying the original value
+/// later doesn't affect the ordering of a collection.
+///
+/// Values which can't be converted into a [Key] are used as they are.
+fn key_value(value: Value) -> Value {
+    match Key::from_value(&value) {
+        Ok(key) => key.into_value(),
+        Err(..) => value,
+    }
+}
+
+#[derive(Any, Clone, Default)]
+#[rune(module = "crate")]
+struct BTreeMap {
+    map: Sorted<Value>,
+}
+
+impl BTreeMap {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Extend this map from an iterator.
+    #[inline]
+    fn extend(&mut self, value: Value) -> Result<(), VmError> {
+        use crate::runtime::FromValue;
+
+        let mut it = value.into_iter()?;
+
+        while let Some(value) = it.next()? {
+            let (key, value) = <(Value, Value)>::from_value(value)?;
+            self.map.insert(key, value)?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn iter(&self) -> Iterator {
+        let iter = self.map.iter().map(|(k, v)| (k.clone(), v.clone()));
+        let iter = iter.collect::<Vec<_>>().into_iter();
+        Iterator::from_double_ended("std::collections::btree_map::Iter", iter)
+    }
+
+    #[inline]
+    fn keys(&self) -> Iterator {
+        let iter = self.map.keys().cloned().collect::<Vec<_>>().into_iter();
+        Iterator::from_double_ended("std::collections::btree_map::Keys", iter)
+    }
+
+    #[inline]
+    fn values(&self) -> Iterator {
+        let iter = self.map.iter().map(|(_, v)| v.clone());
+        let iter = iter.collect::<Vec<_>>().into_iter();
+        Iterator::from_double_ended("std::collections::btree_map::Values", iter)
+    }
+
+    /// Iterate over the entries whose keys are in the given range, in order.
+    #[inline]
+    fn range(&self, range: Value) -> Result<Iterator, VmError> {
+        let iter = self.map.range(range)?.into_iter();
+        let iter = iter.map(|(k, v)| (k.clone(), v.clone()));
+        let iter = iter.collect::<Vec<_>>().into_iter();
+
+        Ok(Iterator::from_double_ended(
+            "std::collections::btree_map::Range",
+            iter,
+        ))
+    }
+
+    #[inline]
+    fn contains_key(&self, key: Value) -> Result<bool, VmError> {
+        Ok(self.map.get(&key)?.is_some())
+    }
+
+    #[inline]
+    fn index_set(&mut self, key: Value, value: Value) -> Result<(), VmError> {
+        self.map.insert(key, value)?;
+        Ok(())
+    }
+
+    #[inline]
+    fn insert(&mut self, key: Value, value: Value) -> Result<Option<Value>, VmError> {
+        self.map.insert(key, value)
+    }
+
+    #[inline]
+    fn get(&self, key: Value) -> Result<Option<Value>, VmError> {
+        Ok(self.map.get(&key)?.cloned())
+    }
+
+    #[inline]
+    fn index_get(&self, key: Value) -> Result<Value, VmError> {
+        use crate::runtime::TypeOf;
+
+        let value = self.map.get(&key)?.ok_or_else(|| {
+            VmError::from(VmErrorKind::MissingIndexKey {
+                target: Self::type_info(),
+                index: format!("{:?}", key),
+            })
+        })?;
+
+        Ok(value.clone())
+    }
+
+    #[inline]
+    fn first(&self) -> Option<(Value, Value)> {
+        let (k, v) = self.map.first()?;
+        Some((k.clone(), v.clone()))
+    }
+
+    #[inline]
+    fn last(&self) -> Option<(Value, Value)> {
+        let (k, v) = self.map.last()?;
+        Some((k.clone(), v.clone()))
+    }
+
+    #[inline]
+    fn pop_first(&mut self) -> Option<(Value, Value)> {
+        self.map.pop_first()
+    }
+
+    #[inline]
+    fn pop_last(&mut self) -> Option<(Value, Value)> {
+        self.map.pop_last()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.map.entries.is_empty()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.map.entries.len()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.map.entries.clear()
+    }
+
+    #[inline]
+    fn remove(&mut self, key: Value) -> Result<Option<Value>, VmError> {
+        Ok(self.map.remove(&key)?.map(|(_, value)| value))
+    }
+
+    #[inline]
+    fn string_debug(&self, s: &mut String) -> fmt::Result {
+        use std::fmt::Write;
+
+        write!(s, "{:?}", DebugMap(self.map.iter()))
+    }
+}
+
+#[derive(Any, Clone, Default)]
+#[rune(module = "crate")]
+struct BTreeSet {
+    set: Sorted<()>,
+}
+
+impl BTreeSet {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Extend this set from an iterator.
+    #[inline]
+    fn extend(&mut self, value: Value) -> Result<(), VmError> {
+        let mut it = value.into_iter()?;
+
+        while let Some(value) = it.next()? {
+            self.set.insert(value, ())?;
+        }
+
+        Ok(())
+    }
+
+    fn values(&self) -> std::vec::Vec<Value> {
+        self.set.keys().cloned().collect()
+    }
+
+    #[inline]
+    fn iter(&self) -> Iterator {
+        let iter = self.values().into_iter();
+        Iterator::from_double_ended("std::collections::btree_set::Iter", iter)
+    }
+
+    /// Iterate over the values in the given range, in order.
+    #[inline]
+    fn range(&self, range: Value) -> Result<Iterator, VmError> {
+        let iter = self.set.range(range)?.into_iter().map(|(k, _)| k.clone());
+        let iter = iter.collect::<Vec<_>>().into_iter();
+
+        Ok(Iterator::from_double_ended(
+            "std::collections::btree_set::Range",
+            iter,
+        ))
+    }
+
+    #[inline]
+    fn insert(&mut self, key: Value) -> Result<bool, VmError> {
+        Ok(self.set.insert(key, ())?.is_none())
+    }
+
+    #[inline]
+    fn contains(&self, key: Value) -> Result<bool, VmError> {
+        Ok(self.set.get(&key)?.is_some())
+    }
+
+    #[inline]
+    fn first(&self) -> Option<Value> {
+        Some(self.set.first()?.0.clone())
+    }
+
+    #[inline]
+    fn last(&self) -> Option<Value> {
+        Some(self.set.last()?.0.clone())
+    }
+
+    #[inline]
+    fn pop_first(&mut self) -> Option<Value> {
+        Some(self.set.pop_first()?.0)
+    }
+
+    #[inline]
+    fn pop_last(&mut self) -> Option<Value> {
+        Some(self.set.pop_last()?.0)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.set.entries.is_empty()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.set.entries.len()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.set.entries.clear()
+    }
+
+    #[inline]
+    fn remove(&mut self, key: Value) -> Result<bool, VmError> {
+        Ok(self.set.remove(&key)?.is_some())
+    }
+
+    #[inline]
+    fn difference(&self, other: &BTreeSet) -> Result<Iterator, VmError> {
+        let iter = self.set.merge_keys(&other.set, true, false, false)?;
+        let iter = iter.into_iter();
+        Ok(Iterator::from_double_ended(
+            "std::collections::btree_set::Difference",
+            iter,
+        ))
+    }
+
+    #[inline]
+    fn intersection(&self, other: &BTreeSet) -> Result<Iterator, VmError> {
+        let iter = self.set.merge_keys(&other.set, false, true, false)?;
+        let iter = iter.into_iter();
+        Ok(Iterator::from_double_ended(
+            "std::collections::btree_set::Intersection",
+            iter,
+        ))
+    }
+
+    #[inline]
+    fn union(&self, other: &BTreeSet) -> Result<Iterator, VmError> {
+        let iter = self.set.merge_keys(&other.set, true, true, true)?;
+        let iter = iter.into_iter();
+        Ok(Iterator::from_double_ended(
+            "std::collections::btree_set::Union",
+            iter,
+        ))
+    }
+
+    #[inline]
+    fn string_debug(&self, s: &mut String) -> fmt::Result {
+        use std::fmt::Write;
+        write!(s, "{:?}", DebugSet(self.set.keys()))
+    }
+
+    #[inline]
+    fn eq(&self, other: &Self) -> Result<bool, VmError> {
+        if self.len() != other.len() {
+            return Ok(false);
+        }
+
+        for (a, b) in self.set.keys().zip(other.set.keys()) {
+            if Value::cmp(a, b)? != Ordering::Equal {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+/// Helper to debug format the entries of a map.
+struct DebugMap<I>(I);
+
+impl<'a, I> fmt::Debug for DebugMap<I>
+where
+    I: Clone + std::iter::Iterator<Item = (&'a Value, &'a Value)>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.0.clone()).finish()
+    }
+}
+
+/// Helper to debug format the values of a set.
+struct DebugSet<I>(I);
+
+impl<'a, I> fmt::Debug for DebugSet<I>
+where
+    I: Clone + std::iter::Iterator<Item = &'a Value>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.0.clone()).finish()
+    }
+}
+
+/// A priority queue implemented as a max-heap.
+///
+/// Values are ordered by their natural ordering, which uses the `CMP` protocol
+/// for values which aren't built in, unless the heap is constructed with a
+/// comparator.
+#[derive(Any, Clone, Default)]
+#[rune(module = "crate")]
+struct BinaryHeap {
+    data: std::vec::Vec<Value>,
+    comparator: Option<Shared<Function>>,
+}
+
+impl BinaryHeap {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Construct a heap ordered by the given comparator, which is called
+    /// with two values and returns an `Ordering`.
+    fn with_comparator(comparator: Shared<Function>) -> Self {
+        Self {
+            data: std::vec::Vec::new(),
+            comparator: Some(comparator),
+        }
+    }
+
+    /// Extend this heap from an iterator.
+    fn extend(&mut self, value: Value) -> Result<(), VmError> {
+        let mut it = value.into_iter()?;
+
+        while let Some(value) = it.next()? {
+            self.push(value)?;
+        }
+
+        Ok(())
+    }
+
+    fn compare(&self, a: &Value, b: &Value) -> Result<Ordering, VmError> {
+        match &self.comparator {
+            Some(comparator) => comparator.borrow_ref()?.call::<_, Ordering>((a, b)),
+            None => Value::cmp(a, b),
+        }
+    }
+
+    fn push(&mut self, value: Value) -> Result<(), VmError> {
+        self.data.push(value);
+        let mut child = self.data.len() - 1;
+
+        while child > 0 {
+            let parent = (child - 1) / 2;
+
+            if self.compare(&self.data[child], &self.data[parent])? != Ordering::Greater {
+                break;
+            }
+
+            self.data.swap(child, parent);
+            child = parent;
+        }
+
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<Option<Value>, VmError> {
+        if self.data.is_empty() {
+            return Ok(None);
+        }
+
+        let value = self.data.swap_remove(0);
+        let len = self.data.len();
+        let mut parent = 0;
+
+        loop {
+            let mut largest = parent;
+
+            for child in [2 * parent + 1, 2 * parent + 2] {
+                if child < len
+                    && self.compare(&self.data[child], &self.data[largest])? == Ordering::Greater
+                {
+                    largest = child;
+                }
+            }
+
+            if largest == parent {
+                break;
+            }
+
+            self.data.swap(parent, largest);
+            parent = largest;
+        }
+
+        Ok(Some(value))
+    }
+
+    fn peek(&self) -> Option<Value> {
+        self.data.first().cloned()
+    }
+
+    /// Consume the heap and return its values sorted in ascending order.
+    fn into_sorted_vec(self) -> Result<std::vec::Vec<Value>, VmError> {
+        let Self {
+            mut data,
+            comparator,
+        } = self;
+
+        let heap = Self {
+            data: std::vec::Vec::new(),
+            comparator,
+        };
+
+        let mut error = None;
+
+        data.sort_by(|a, b| match heap.compare(a, b) {
+            Ok(ordering) => ordering,
+            Err(e) => {
+                error.get_or_insert(e);
+                Ordering::Equal
+            }
+        });
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(data),
+        }
+    }
+
+    /// Consume the heap and return its values in arbitrary order.
+    fn into_vec(self) -> std::vec::Vec<Value> {
+        self.data
+    }
+
+    /// Iterate over the values of the heap in arbitrary order.
+    #[inline]
+    fn iter(&self) -> Iterator {
+        let iter = self.data.clone().into_iter();
+        Iterator::from_double_ended("std::collections::binary_heap::Iter", iter)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.data.clear()
+    }
+
+    #[inline]
+    fn string_debug(&self, s: &mut String) -> fmt::Result {
+        use std::fmt::Write;
+        write!(s, "{:?}", self.data)
+    }
+}
+
+/// Convert a range value, like `1..10` or `"a"..`, into bounds over keys.
+fn range_bounds(range: Value) -> Result<(Bound<SortKey>, Bound<SortKey>), VmError> {
+    let range = range.into_range()?;
+    let range = range.borrow_ref()?;
+
+    let start = match &range.start {
+        Some(start) => Bound::Included(SortKey(start.clone())),
+        None => Bound::Unbounded,
+    };
+
+    let end = match (&range.end, range.limits) {
+        (Some(end), RangeLimits::HalfOpen) => Bound::Excluded(SortKey(end.clone())),
+        (Some(end), RangeLimits::Closed) => Bound::Included(SortKey(end.clone())),
+        (None, _) => Bound::Unbounded,
+    };
+
+    if let (Bound::Included(start), Bound::Included(end) | Bound::Excluded(end)) = (&start, &end) {
+        if Value::cmp(&start.0, &end.0)? == Ordering::Greater {
+            return Err(VmError::panic("range start is greater than range end"));
+        }
+    }
+
+    Ok((start, end))
+}
+
 /// The `std::collections` module.
 pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::with_crate_item("std", ["collections"]);
@@ -475,6 +1178,76 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn(Protocol::INTO_ITER, VecDeque::iter)?;
     module.inst_fn(Protocol::STRING_DEBUG, VecDeque::string_debug)?;
 
+    module.ty::<BTreeMap>()?;
+    module.function(["BTreeMap", "new"], BTreeMap::new)?;
+    module.function(["BTreeMap", "from"], btreemap_from)?;
+    module.inst_fn("clear", BTreeMap::clear)?;
+    module.inst_fn("clone", BTreeMap::clone)?;
+    module.inst_fn("contains_key", BTreeMap::contains_key)?;
+    module.inst_fn("extend", BTreeMap::extend)?;
+    module.inst_fn("first", BTreeMap::first)?;
+    module.inst_fn("get", BTreeMap::get)?;
+    module.inst_fn("insert", BTreeMap::insert)?;
+    module.inst_fn("is_empty", BTreeMap::is_empty)?;
+    module.inst_fn("iter", BTreeMap::iter)?;
+    module.inst_fn("keys", BTreeMap::keys)?;
+    module.inst_fn("last", BTreeMap::last)?;
+    module.inst_fn("len", BTreeMap::len)?;
+    module.inst_fn("pop_first", BTreeMap::pop_first)?;
+    module.inst_fn("pop_last", BTreeMap::pop_last)?;
+    module.inst_fn("range", BTreeMap::range)?;
+    module.inst_fn("remove", BTreeMap::remove)?;
+    module.inst_fn("values", BTreeMap::values)?;
+    module.inst_fn(Protocol::INTO_ITER, BTreeMap::iter)?;
+    module.inst_fn(Protocol::INDEX_SET, BTreeMap::index_set)?;
+    module.inst_fn(Protocol::INDEX_GET, BTreeMap::index_get)?;
+    module.inst_fn(Protocol::STRING_DEBUG, BTreeMap::string_debug)?;
+
+    module.ty::<BTreeSet>()?;
+    module.function(["BTreeSet", "new"], BTreeSet::new)?;
+    module.function(["BTreeSet", "from"], btreeset_from)?;
+    module.inst_fn("clear", BTreeSet::clear)?;
+    module.inst_fn("clone", BTreeSet::clone)?;
+    module.inst_fn("contains", BTreeSet::contains)?;
+    module.inst_fn("difference", BTreeSet::difference)?;
+    module.inst_fn("extend", BTreeSet::extend)?;
+    module.inst_fn("first", BTreeSet::first)?;
+    module.inst_fn("insert", BTreeSet::insert)?;
+    module.inst_fn("intersection", BTreeSet::intersection)?;
+    module.inst_fn("is_empty", BTreeSet::is_empty)?;
+    module.inst_fn("iter", BTreeSet::iter)?;
+    module.inst_fn("last", BTreeSet::last)?;
+    module.inst_fn("len", BTreeSet::len)?;
+    module.inst_fn("pop_first", BTreeSet::pop_first)?;
+    module.inst_fn("pop_last", BTreeSet::pop_last)?;
+    module.inst_fn("range", BTreeSet::range)?;
+    module.inst_fn("remove", BTreeSet::remove)?;
+    module.inst_fn("union", BTreeSet::union)?;
+    module.inst_fn(Protocol::INTO_ITER, BTreeSet::iter)?;
+    module.inst_fn(Protocol::STRING_DEBUG, BTreeSet::string_debug)?;
+    module.inst_fn(Protocol::EQ, BTreeSet::eq)?;
+
+    module.ty::<BinaryHeap>()?;
+    module.function(["BinaryHeap", "new"], BinaryHeap::new)?;
+    module.function(
+        ["BinaryHeap", "with_comparator"],
+        BinaryHeap::with_comparator,
+    )?;
+    module.function(["BinaryHeap", "from"], binaryheap_from)?;
+    module.inst_fn("clear", BinaryHeap::clear)?;
+    module.inst_fn("clone", BinaryHeap::clone)?;
+    module.inst_fn("extend", BinaryHeap::extend)?;
+    module.inst_fn("into_sorted_vec", BinaryHeap::into_sorted_vec)?;
+    module.inst_fn("into_vec", BinaryHeap::into_vec)?;
+    module.inst_fn("is_empty", BinaryHeap::is_empty)?;
+    module.inst_fn("iter", BinaryHeap::iter)?;
+    module.inst_fn("len", BinaryHeap::len)?;
+    module.inst_fn("peek", BinaryHeap::peek)?;
+    module.inst_fn("pop", BinaryHeap::pop)?;
+    module.inst_fn("push", BinaryHeap::push)?;
+    module.inst_fn(Protocol::INTO_ITER, BinaryHeap::iter)?;
+    module.inst_fn(Protocol::STRING_DEBUG, BinaryHeap::string_debug)?;
+
     Ok(module)
 }
 
@@ -513,3 +1286,21 @@ fn hashset_from(value: Value) -> Result<HashSet, VmError> {
 
     Ok(set)
 }
+
+fn btreemap_from(value: Value) -> Result<BTreeMap, VmError> {
+    let mut map = BTreeMap::new();
+    map.extend(value)?;
+    Ok(map)
+}
+
+fn btreeset_from(value: Value) -> Result<BTreeSet, VmError> {
+    let mut set = BTreeSet::new();
+    set.extend(value)?;
+    Ok(set)
+}
+
+fn binaryheap_from(value: Value) -> Result<BinaryHeap, VmError> {
+    let mut heap = BinaryHeap::new();
+    heap.extend(value)?;
+    Ok(heap)
+}
@@ -1,6 +1,6 @@
 //! The core `std` module.
 
-use crate::runtime::{Panic, Value};
+use crate::runtime::{Panic, SizedInteger, Value};
 use crate::{ContextError, Module};
 
 /// Construct the `std` module.
@@ -13,6 +13,10 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<u8>()?;
     module.ty::<f64>()?;
     module.ty::<i64>()?;
+    module.ty::<SizedInteger<u16>>()?;
+    module.ty::<SizedInteger<u32>>()?;
+    module.ty::<SizedInteger<u64>>()?;
+    module.ty::<SizedInteger<i32>>()?;
 
     module.function(["panic"], panic_impl)?;
     module.function(["is_readable"], is_readable)?;
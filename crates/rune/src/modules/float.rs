@@ -1,6 +1,8 @@
 //! The `std::float` module.
 
+use crate::runtime::{SizedInteger, VmError};
 use crate::{ContextError, Module};
+use std::cmp::Ordering;
 use std::num::ParseFloatError;
 
 /// Parse an integer.
@@ -13,6 +15,27 @@ fn to_integer(value: f64) -> i64 {
     value as i64
 }
 
+/// Compare two floats, returning `None` if either is `NaN`.
+fn partial_cmp(this: f64, other: f64) -> Option<Ordering> {
+    this.partial_cmp(&other)
+}
+
+/// Restrict a value to a certain interval unless it is NaN.
+fn clamp(value: f64, min: f64, max: f64) -> Result<f64, VmError> {
+    if min.is_nan() || max.is_nan() || min > max {
+        return Err(VmError::panic(
+            "clamp requires min <= max and neither can be NaN",
+        ));
+    }
+
+    Ok(value.clamp(min, max))
+}
+
+/// Get the raw bits of a float, which don't fit in an `int`.
+fn to_bits(value: f64) -> SizedInteger<u64> {
+    SizedInteger(value.to_bits())
+}
+
 crate::__internal_impl_any!(ParseFloatError);
 
 /// Install the core package into the given functions namespace.
@@ -21,13 +44,74 @@ pub fn module() -> Result<Module, ContextError> {
 
     module.ty::<ParseFloatError>()?;
     module.function(["parse"], parse)?;
+    module.inst_fn("partial_cmp", partial_cmp)?;
     module.inst_fn("max", f64::max)?;
     module.inst_fn("min", f64::min)?;
     module.inst_fn("abs", f64::abs)?;
     module.inst_fn("powf", f64::powf)?;
     module.inst_fn("powi", f64::powi)?;
+    module.inst_fn("sqrt", f64::sqrt)?;
+    module.inst_fn("cbrt", f64::cbrt)?;
+    module.inst_fn("hypot", f64::hypot)?;
+    module.inst_fn("clamp", clamp)?;
+    module.inst_fn("signum", f64::signum)?;
+    module.inst_fn("copysign", f64::copysign)?;
+    module.inst_fn("mul_add", f64::mul_add)?;
+    module.inst_fn("rem_euclid", f64::rem_euclid)?;
+    module.inst_fn("div_euclid", f64::div_euclid)?;
+    module.inst_fn("recip", f64::recip)?;
+    module.inst_fn("to_degrees", f64::to_degrees)?;
+    module.inst_fn("to_radians", f64::to_radians)?;
+
+    module.inst_fn("floor", f64::floor)?;
+    module.inst_fn("ceil", f64::ceil)?;
+    module.inst_fn("round", f64::round)?;
+    module.inst_fn("trunc", f64::trunc)?;
+    module.inst_fn("fract", f64::fract)?;
+
+    module.inst_fn("sin", f64::sin)?;
+    module.inst_fn("cos", f64::cos)?;
+    module.inst_fn("tan", f64::tan)?;
+    module.inst_fn("asin", f64::asin)?;
+    module.inst_fn("acos", f64::acos)?;
+    module.inst_fn("atan", f64::atan)?;
+    module.inst_fn("atan2", f64::atan2)?;
+    module.inst_fn("sinh", f64::sinh)?;
+    module.inst_fn("cosh", f64::cosh)?;
+    module.inst_fn("tanh", f64::tanh)?;
 
+    module.inst_fn("exp", f64::exp)?;
+    module.inst_fn("exp2", f64::exp2)?;
+    module.inst_fn("ln", f64::ln)?;
+    module.inst_fn("log", f64::log)?;
+    module.inst_fn("log10", f64::log10)?;
+    module.inst_fn("log2", f64::log2)?;
+
+    module.inst_fn("is_nan", f64::is_nan)?;
+    module.inst_fn("is_finite", f64::is_finite)?;
+    module.inst_fn("is_infinite", f64::is_infinite)?;
+    module.inst_fn("is_normal", f64::is_normal)?;
+    module.inst_fn("is_sign_positive", f64::is_sign_positive)?;
+    module.inst_fn("is_sign_negative", f64::is_sign_negative)?;
+
+    module.inst_fn("to_bits", to_bits)?;
+    module.function(["from_bits"], f64::from_bits)?;
     module.inst_fn("to_integer", to_integer)?;
 
+    module.constant(["consts", "PI"], std::f64::consts::PI)?;
+    module.constant(["consts", "TAU"], std::f64::consts::TAU)?;
+    module.constant(["consts", "E"], std::f64::consts::E)?;
+    module.constant(["consts", "SQRT_2"], std::f64::consts::SQRT_2)?;
+    module.constant(["consts", "LN_2"], std::f64::consts::LN_2)?;
+    module.constant(["consts", "LN_10"], std::f64::consts::LN_10)?;
+    module.constant(["consts", "FRAC_PI_2"], std::f64::consts::FRAC_PI_2)?;
+    module.constant(["consts", "INFINITY"], f64::INFINITY)?;
+    module.constant(["consts", "NEG_INFINITY"], f64::NEG_INFINITY)?;
+    module.constant(["consts", "NAN"], f64::NAN)?;
+    module.constant(["consts", "EPSILON"], f64::EPSILON)?;
+    module.constant(["consts", "MIN"], f64::MIN)?;
+    module.constant(["consts", "MAX"], f64::MAX)?;
+    module.constant(["consts", "MIN_POSITIVE"], f64::MIN_POSITIVE)?;
+
     Ok(module)
 }
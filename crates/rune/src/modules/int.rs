@@ -1,8 +1,97 @@
 //! The `std::int` module.
 
+use crate::runtime::SizedInteger;
 use crate::{ContextError, Module};
+use std::cmp::Ordering;
 use std::num::ParseIntError;
 
+/// Install conversions and arithmetic instance functions for a sized integer
+/// type.
+///
+/// Values are passed through [SizedInteger] so that results keep their sized
+/// representation instead of being converted into an `int`.
+macro_rules! sized {
+    ($module:ident, $ty:ty, to_integer: $to_integer:expr) => {{
+        type S = SizedInteger<$ty>;
+
+        $module.inst_fn("to_integer", |SizedInteger(v): S| $to_integer(v))?;
+        $module.inst_fn("to_byte", |SizedInteger(v): S| convert::<$ty, u8>(v))?;
+        $module.inst_fn("to_u16", |SizedInteger(v): S| convert_sized::<$ty, u16>(v))?;
+        $module.inst_fn("to_u32", |SizedInteger(v): S| convert_sized::<$ty, u32>(v))?;
+        $module.inst_fn("to_u64", |SizedInteger(v): S| convert_sized::<$ty, u64>(v))?;
+        $module.inst_fn("to_i32", |SizedInteger(v): S| convert_sized::<$ty, i32>(v))?;
+        $module.inst_fn("to_float", |SizedInteger(v): S| v as f64)?;
+
+        $module.inst_fn("cmp", |SizedInteger(a): S, b: $ty| a.cmp(&b))?;
+        $module.inst_fn("max", |SizedInteger(a): S, b: $ty| SizedInteger(a.max(b)))?;
+        $module.inst_fn("min", |SizedInteger(a): S, b: $ty| SizedInteger(a.min(b)))?;
+        $module.inst_fn("pow", |SizedInteger(a): S, b: u32| SizedInteger(a.pow(b)))?;
+        $module.inst_fn("count_ones", |SizedInteger(v): S| v.count_ones())?;
+        $module.inst_fn("leading_zeros", |SizedInteger(v): S| v.leading_zeros())?;
+        $module.inst_fn("trailing_zeros", |SizedInteger(v): S| v.trailing_zeros())?;
+        $module.inst_fn("rotate_left", |SizedInteger(a): S, b: u32| {
+            SizedInteger(a.rotate_left(b))
+        })?;
+        $module.inst_fn("rotate_right", |SizedInteger(a): S, b: u32| {
+            SizedInteger(a.rotate_right(b))
+        })?;
+        $module.inst_fn("swap_bytes", |SizedInteger(v): S| {
+            SizedInteger(v.swap_bytes())
+        })?;
+
+        $module.inst_fn("checked_add", |SizedInteger(a): S, b: $ty| {
+            a.checked_add(b).map(SizedInteger)
+        })?;
+        $module.inst_fn("checked_sub", |SizedInteger(a): S, b: $ty| {
+            a.checked_sub(b).map(SizedInteger)
+        })?;
+        $module.inst_fn("checked_div", |SizedInteger(a): S, b: $ty| {
+            a.checked_div(b).map(SizedInteger)
+        })?;
+        $module.inst_fn("checked_mul", |SizedInteger(a): S, b: $ty| {
+            a.checked_mul(b).map(SizedInteger)
+        })?;
+        $module.inst_fn("checked_rem", |SizedInteger(a): S, b: $ty| {
+            a.checked_rem(b).map(SizedInteger)
+        })?;
+
+        $module.inst_fn("wrapping_add", |SizedInteger(a): S, b: $ty| {
+            SizedInteger(a.wrapping_add(b))
+        })?;
+        $module.inst_fn("wrapping_sub", |SizedInteger(a): S, b: $ty| {
+            SizedInteger(a.wrapping_sub(b))
+        })?;
+        $module.inst_fn("wrapping_div", |SizedInteger(a): S, b: $ty| {
+            SizedInteger(a.wrapping_div(b))
+        })?;
+        $module.inst_fn("wrapping_mul", |SizedInteger(a): S, b: $ty| {
+            SizedInteger(a.wrapping_mul(b))
+        })?;
+        $module.inst_fn("wrapping_rem", |SizedInteger(a): S, b: $ty| {
+            SizedInteger(a.wrapping_rem(b))
+        })?;
+        $module.inst_fn("wrapping_shl", |SizedInteger(a): S, b: u32| {
+            SizedInteger(a.wrapping_shl(b))
+        })?;
+        $module.inst_fn("wrapping_shr", |SizedInteger(a): S, b: u32| {
+            SizedInteger(a.wrapping_shr(b))
+        })?;
+
+        $module.inst_fn("saturating_add", |SizedInteger(a): S, b: $ty| {
+            SizedInteger(a.saturating_add(b))
+        })?;
+        $module.inst_fn("saturating_sub", |SizedInteger(a): S, b: $ty| {
+            SizedInteger(a.saturating_sub(b))
+        })?;
+        $module.inst_fn("saturating_mul", |SizedInteger(a): S, b: $ty| {
+            SizedInteger(a.saturating_mul(b))
+        })?;
+        $module.inst_fn("saturating_pow", |SizedInteger(a): S, b: u32| {
+            SizedInteger(a.saturating_pow(b))
+        })?;
+    }};
+}
+
 /// Construct the `std::int` module.
 pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::with_crate_item("std", ["int"]);
@@ -12,6 +101,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.function(["parse"], parse)?;
     module.inst_fn("to_float", to_float)?;
 
+    module.inst_fn("cmp", cmp)?;
     module.inst_fn("max", i64::max)?;
     module.inst_fn("min", i64::min)?;
     module.inst_fn("abs", i64::abs)?;
@@ -35,9 +125,38 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("saturating_abs", i64::saturating_abs)?;
     module.inst_fn("saturating_pow", i64::saturating_pow)?;
 
+    module.inst_fn("to_byte", convert::<i64, u8>)?;
+    module.inst_fn("to_u16", convert_sized::<i64, u16>)?;
+    module.inst_fn("to_u32", convert_sized::<i64, u32>)?;
+    module.inst_fn("to_u64", convert_sized::<i64, u64>)?;
+    module.inst_fn("to_i32", convert_sized::<i64, i32>)?;
+
+    sized!(module, u8, to_integer: <i64 as From<u8>>::from);
+    sized!(module, u16, to_integer: <i64 as From<u16>>::from);
+    sized!(module, u32, to_integer: <i64 as From<u32>>::from);
+    sized!(module, u64, to_integer: convert::<u64, i64>);
+    sized!(module, i32, to_integer: <i64 as From<i32>>::from);
+
     Ok(module)
 }
 
+/// Convert between integer types, returning `None` if the value doesn't fit.
+fn convert<T, U>(value: T) -> Option<U>
+where
+    U: TryFrom<T>,
+{
+    U::try_from(value).ok()
+}
+
+/// Convert into a sized integer type, returning `None` if the value doesn't
+/// fit.
+fn convert_sized<T, U>(value: T) -> Option<SizedInteger<U>>
+where
+    U: TryFrom<T>,
+{
+    convert(value).map(SizedInteger)
+}
+
 /// Parse an integer.
 fn parse(s: &str) -> Result<i64, ParseIntError> {
     str::parse::<i64>(s)
@@ -48,4 +167,9 @@ fn to_float(value: i64) -> f64 {
     value as f64
 }
 
+/// Compare two integers.
+fn cmp(this: i64, other: i64) -> Ordering {
+    this.cmp(&other)
+}
+
 crate::__internal_impl_any!(ParseIntError);
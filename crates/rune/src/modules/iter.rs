@@ -10,27 +10,47 @@ pub fn module() -> Result<Module, ContextError> {
 
     // Sorted for ease of finding
     module.inst_fn("chain", Iterator::chain)?;
+    module.inst_fn("chunks", Iterator::chunks)?;
     module.inst_fn(Params("collect", [Object::type_hash()]), collect_object)?;
     module.inst_fn(Params("collect", [Vec::type_hash()]), collect_vec)?;
     module.inst_fn(Params("collect", [Tuple::type_hash()]), collect_tuple)?;
+    module.inst_fn("cycle", Iterator::cycle)?;
+    module.inst_fn("dedup", Iterator::dedup)?;
     module.inst_fn("enumerate", Iterator::enumerate)?;
     module.inst_fn("filter", Iterator::filter)?;
     module.inst_fn("find", Iterator::find)?;
     module.inst_fn("flat_map", Iterator::flat_map)?;
+    module.inst_fn("flatten", Iterator::flatten)?;
+    module.inst_fn("inspect", Iterator::inspect)?;
+    module.inst_fn("last", Iterator::last)?;
     module.inst_fn("map", Iterator::map)?;
+    module.inst_fn("max", Iterator::max)?;
+    module.inst_fn("max_by_key", Iterator::max_by_key)?;
+    module.inst_fn("min", Iterator::min)?;
+    module.inst_fn("min_by_key", Iterator::min_by_key)?;
     module.inst_fn("next", Iterator::next)?;
     module.inst_fn("next_back", Iterator::next_back)?;
+    module.inst_fn("nth", Iterator::nth)?;
+    module.inst_fn("partition", Iterator::partition)?;
     module.inst_fn("peek", Iterator::peek)?;
     module.inst_fn("peekable", Iterator::peekable)?;
+    module.inst_fn("position", Iterator::position)?;
     module.inst_fn("product", Iterator::product)?;
     module.inst_fn("fold", Iterator::fold)?;
     module.inst_fn("rev", Iterator::rev)?;
+    module.inst_fn("scan", Iterator::scan)?;
     module.inst_fn("size_hint", Iterator::size_hint)?;
     module.inst_fn("sum", Iterator::sum)?;
     module.inst_fn("skip", Iterator::skip)?;
+    module.inst_fn("skip_while", Iterator::skip_while)?;
+    module.inst_fn("step_by", Iterator::step_by)?;
     module.inst_fn("take", Iterator::take)?;
+    module.inst_fn("take_while", Iterator::take_while)?;
     module.inst_fn("count", Iterator::count)?;
     module.inst_fn("all", Iterator::all)?;
+    module.inst_fn("any", Iterator::any)?;
+    module.inst_fn("windows", Iterator::windows)?;
+    module.inst_fn("zip", Iterator::zip)?;
     module.inst_fn(Protocol::NEXT, Iterator::next)?;
     module.inst_fn(Protocol::INTO_ITER, <Iterator as From<Iterator>>::from)?;
 
@@ -15,6 +15,7 @@ pub mod int;
 pub mod io;
 pub mod iter;
 pub mod mem;
+pub mod num;
 pub mod object;
 pub mod ops;
 pub mod option;
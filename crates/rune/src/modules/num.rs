@@ -0,0 +1,807 @@
+//! The `std::num` module.
+
+use crate::runtime::{Protocol, ToValue, TypeOf, Value, VmError, VmErrorKind};
+use crate::{Any, ContextError, Module};
+use num::bigint::ParseBigIntError;
+use num::{Signed, ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// The number of fractional digits kept when dividing decimals which don't
+/// divide evenly.
+const DIVISION_SCALE: u32 = 28;
+
+/// The largest number of fractional digits a decimal can have, which bounds
+/// the size of the numbers used when rescaling it.
+const MAX_SCALE: u32 = 1000;
+
+/// Construct the `std::num` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", ["num"]);
+
+    module.ty::<BigInt>()?;
+    module.ty::<Decimal>()?;
+    module.ty::<ParseBigIntError>()?;
+    module.ty::<ParseDecimalError>()?;
+
+    module.function(["checked_add"], checked_add)?;
+    module.function(["checked_sub"], checked_sub)?;
+    module.function(["checked_mul"], checked_mul)?;
+    module.function(["checked_div"], checked_div)?;
+    module.function(["checked_rem"], checked_rem)?;
+    module.function(["checked_pow"], checked_pow)?;
+
+    module.function(["BigInt", "new"], BigInt::new)?;
+    module.function(["BigInt", "parse"], BigInt::parse)?;
+    module.function(["BigInt", "parse_radix"], BigInt::parse_radix)?;
+
+    module.inst_fn("to_integer", BigInt::to_integer)?;
+    module.inst_fn("to_float", BigInt::to_float)?;
+    module.inst_fn("to_decimal", BigInt::to_decimal)?;
+    module.inst_fn("to_string", BigInt::to_string)?;
+    module.inst_fn("to_string_radix", BigInt::to_string_radix)?;
+    module.inst_fn("abs", BigInt::abs)?;
+    module.inst_fn("neg", BigInt::neg)?;
+    module.inst_fn("pow", BigInt::pow)?;
+    module.inst_fn("bits", BigInt::bits)?;
+    module.inst_fn("is_zero", BigInt::is_zero)?;
+    module.inst_fn("is_negative", BigInt::is_negative)?;
+    module.inst_fn("clone", BigInt::clone)?;
+
+    module.inst_fn(Protocol::ADD, BigInt::add)?;
+    module.inst_fn(Protocol::SUB, BigInt::sub)?;
+    module.inst_fn(Protocol::MUL, BigInt::mul)?;
+    module.inst_fn(Protocol::DIV, BigInt::div)?;
+    module.inst_fn(Protocol::REM, BigInt::rem)?;
+    module.inst_fn(Protocol::ADD_ASSIGN, BigInt::add_assign)?;
+    module.inst_fn(Protocol::SUB_ASSIGN, BigInt::sub_assign)?;
+    module.inst_fn(Protocol::MUL_ASSIGN, BigInt::mul_assign)?;
+    module.inst_fn(Protocol::DIV_ASSIGN, BigInt::div_assign)?;
+    module.inst_fn(Protocol::REM_ASSIGN, BigInt::rem_assign)?;
+    module.inst_fn(Protocol::EQ, BigInt::eq)?;
+    module.inst_fn(Protocol::PARTIAL_CMP, BigInt::partial_cmp)?;
+    module.inst_fn(Protocol::CMP, BigInt::cmp)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, BigInt::string_display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, BigInt::string_display)?;
+
+    module.function(["Decimal", "new"], Decimal::new)?;
+    module.function(["Decimal", "parse"], Decimal::parse)?;
+    module.function(["Decimal", "from_float"], Decimal::from_float)?;
+
+    module.inst_fn("to_integer", Decimal::to_integer)?;
+    module.inst_fn("to_float", Decimal::to_float)?;
+    module.inst_fn("to_string", Decimal::to_string)?;
+    module.inst_fn("scale", Decimal::scale)?;
+    module.inst_fn("round", Decimal::round)?;
+    module.inst_fn("trunc", Decimal::trunc)?;
+    module.inst_fn("normalize", Decimal::normalize)?;
+    module.inst_fn("abs", Decimal::abs)?;
+    module.inst_fn("neg", Decimal::neg)?;
+    module.inst_fn("is_zero", Decimal::is_zero)?;
+    module.inst_fn("is_negative", Decimal::is_negative)?;
+    module.inst_fn("clone", Decimal::clone)?;
+
+    module.inst_fn(Protocol::ADD, Decimal::add)?;
+    module.inst_fn(Protocol::SUB, Decimal::sub)?;
+    module.inst_fn(Protocol::MUL, Decimal::mul)?;
+    module.inst_fn(Protocol::DIV, Decimal::div)?;
+    module.inst_fn(Protocol::REM, Decimal::rem)?;
+    module.inst_fn(Protocol::ADD_ASSIGN, Decimal::add_assign)?;
+    module.inst_fn(Protocol::SUB_ASSIGN, Decimal::sub_assign)?;
+    module.inst_fn(Protocol::MUL_ASSIGN, Decimal::mul_assign)?;
+    module.inst_fn(Protocol::DIV_ASSIGN, Decimal::div_assign)?;
+    module.inst_fn(Protocol::REM_ASSIGN, Decimal::rem_assign)?;
+    module.inst_fn(Protocol::EQ, Decimal::eq)?;
+    module.inst_fn(Protocol::PARTIAL_CMP, Decimal::partial_cmp)?;
+    module.inst_fn(Protocol::CMP, Decimal::cmp)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Decimal::string_display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Decimal::string_display)?;
+
+    module.inst_fn(Protocol::STRING_DISPLAY, ParseDecimalError::string_display)?;
+
+    Ok(module)
+}
+
+/// An arbitrary-precision integer.
+#[derive(Any, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[rune(module = "crate")]
+pub struct BigInt(num::BigInt);
+
+impl BigInt {
+    /// Construct a big integer from an integer or another big integer.
+    fn new(value: Value) -> Result<Self, VmError> {
+        match to_bigint(&value)? {
+            Some(value) => Ok(Self(value)),
+            None => Err(VmError::expected::<i64>(value.type_info()?)),
+        }
+    }
+
+    /// Parse a big integer in base 10.
+    fn parse(s: &str) -> Result<Self, ParseBigIntError> {
+        Ok(Self(s.parse()?))
+    }
+
+    /// Parse a big integer in the given radix.
+    fn parse_radix(s: &str, radix: u32) -> Result<Self, ParseBigIntError> {
+        use num::Num as _;
+        Ok(Self(num::BigInt::from_str_radix(s, radix)?))
+    }
+
+    /// Convert into an integer, returning `None` if it doesn't fit.
+    fn to_integer(&self) -> Option<i64> {
+        self.0.to_i64()
+    }
+
+    /// Convert into the nearest float.
+    fn to_float(&self) -> f64 {
+        self.0.to_f64().unwrap_or(f64::NAN)
+    }
+
+    /// Convert into a decimal with no fractional digits.
+    fn to_decimal(&self) -> Decimal {
+        Decimal::from_bigint(self.0.clone())
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    fn to_string(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn to_string_radix(&self, radix: u32) -> String {
+        self.0.to_str_radix(radix)
+    }
+
+    fn abs(&self) -> Self {
+        Self(self.0.abs())
+    }
+
+    fn neg(&self) -> Self {
+        Self(-&self.0)
+    }
+
+    fn pow(&self, exp: u32) -> Self {
+        Self(num::pow::Pow::pow(&self.0, exp))
+    }
+
+    fn bits(&self) -> u64 {
+        self.0.bits()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    fn is_negative(&self) -> bool {
+        self.0.is_negative()
+    }
+
+    /// Apply a binary operation with another big integer or integer.
+    fn op(
+        &self,
+        protocol: Protocol,
+        rhs: &Value,
+        op: fn(&num::BigInt, &num::BigInt) -> Result<num::BigInt, VmErrorKind>,
+    ) -> Result<Self, VmError> {
+        match to_bigint(rhs)? {
+            Some(rhs) => Ok(Self(op(&self.0, &rhs)?)),
+            None => Err(unsupported::<Self>(protocol, rhs)?),
+        }
+    }
+
+    fn add(&self, rhs: Value) -> Result<Self, VmError> {
+        self.op(Protocol::ADD, &rhs, |a, b| Ok(a + b))
+    }
+
+    fn sub(&self, rhs: Value) -> Result<Self, VmError> {
+        self.op(Protocol::SUB, &rhs, |a, b| Ok(a - b))
+    }
+
+    fn mul(&self, rhs: Value) -> Result<Self, VmError> {
+        self.op(Protocol::MUL, &rhs, |a, b| Ok(a * b))
+    }
+
+    fn div(&self, rhs: Value) -> Result<Self, VmError> {
+        self.op(Protocol::DIV, &rhs, |a, b| {
+            if b.is_zero() {
+                return Err(VmErrorKind::DivideByZero);
+            }
+
+            Ok(a / b)
+        })
+    }
+
+    fn rem(&self, rhs: Value) -> Result<Self, VmError> {
+        self.op(Protocol::REM, &rhs, |a, b| {
+            if b.is_zero() {
+                return Err(VmErrorKind::DivideByZero);
+            }
+
+            Ok(a % b)
+        })
+    }
+
+    fn add_assign(&mut self, rhs: Value) -> Result<(), VmError> {
+        *self = self.add(rhs)?;
+        Ok(())
+    }
+
+    fn sub_assign(&mut self, rhs: Value) -> Result<(), VmError> {
+        *self = self.sub(rhs)?;
+        Ok(())
+    }
+
+    fn mul_assign(&mut self, rhs: Value) -> Result<(), VmError> {
+        *self = self.mul(rhs)?;
+        Ok(())
+    }
+
+    fn div_assign(&mut self, rhs: Value) -> Result<(), VmError> {
+        *self = self.div(rhs)?;
+        Ok(())
+    }
+
+    fn rem_assign(&mut self, rhs: Value) -> Result<(), VmError> {
+        *self = self.rem(rhs)?;
+        Ok(())
+    }
+
+    fn eq(&self, rhs: Value) -> Result<bool, VmError> {
+        Ok(self.partial_cmp(rhs)? == Some(Ordering::Equal))
+    }
+
+    fn partial_cmp(&self, rhs: Value) -> Result<Option<Ordering>, VmError> {
+        Ok(to_bigint(&rhs)?.map(|rhs| self.0.cmp(&rhs)))
+    }
+
+    fn cmp(&self, rhs: Value) -> Result<Ordering, VmError> {
+        match to_bigint(&rhs)? {
+            Some(rhs) => Ok(self.0.cmp(&rhs)),
+            None => Err(unsupported::<Self>(Protocol::CMP, &rhs)?),
+        }
+    }
+
+    fn string_display(&self, s: &mut String) -> fmt::Result {
+        use std::fmt::Write as _;
+        write!(s, "{}", self.0)
+    }
+}
+
+/// A fixed-point decimal number, stored as an arbitrary-precision mantissa and
+/// the number of fractional digits.
+///
+/// Unlike floats, decimals represent numbers like `0.1` exactly, which makes
+/// them suitable for currency calculations.
+#[derive(Any, Debug, Clone)]
+#[rune(module = "crate")]
+pub struct Decimal {
+    mantissa: num::BigInt,
+    scale: u32,
+}
+
+impl Decimal {
+    /// Construct a decimal from a mantissa and a scale, so that
+    /// `Decimal::new(1234, 2)` is `12.34`.
+    fn new(mantissa: Value, scale: u32) -> Result<Self, VmError> {
+        if scale > MAX_SCALE {
+            return Err(VmError::panic(format!(
+                "decimal scale must be at most {}",
+                MAX_SCALE
+            )));
+        }
+
+        match to_bigint(&mantissa)? {
+            Some(mantissa) => Ok(Self { mantissa, scale }),
+            None => Err(VmError::expected::<i64>(mantissa.type_info()?)),
+        }
+    }
+
+    fn from_bigint(mantissa: num::BigInt) -> Self {
+        Self { mantissa, scale: 0 }
+    }
+
+    /// Parse a decimal like `-12.34`.
+    fn parse(s: &str) -> Result<Self, ParseDecimalError> {
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+
+        let (whole, fraction) = match rest.split_once('.') {
+            Some((whole, fraction)) => (whole, fraction),
+            None => (rest, ""),
+        };
+
+        let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+
+        if whole.is_empty() && fraction.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+            return Err(ParseDecimalError);
+        }
+
+        let digits = format!("{}{}", whole, fraction);
+
+        let mut mantissa = if digits.is_empty() {
+            num::BigInt::zero()
+        } else {
+            digits.parse().map_err(|_| ParseDecimalError)?
+        };
+
+        if negative {
+            mantissa = -mantissa;
+        }
+
+        let scale = match u32::try_from(fraction.len()) {
+            Ok(scale) if scale <= MAX_SCALE => scale,
+            _ => return Err(ParseDecimalError),
+        };
+
+        Ok(Self { mantissa, scale })
+    }
+
+    /// Convert a float into the decimal with the shortest representation
+    /// that rounds back to it. Returns `None` for infinite and NaN values.
+    fn from_float(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        Self::parse(&value.to_string()).ok()
+    }
+
+    /// Convert into an integer, truncating any fractional digits. Returns
+    /// `None` if the value doesn't fit.
+    fn to_integer(&self) -> Option<i64> {
+        self.trunc(0).mantissa.to_i64()
+    }
+
+    /// Convert into the nearest float.
+    fn to_float(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    fn to_string(&self) -> String {
+        let mut s = String::new();
+        let _ = self.string_display(&mut s);
+        s
+    }
+
+    fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Round to the given number of fractional digits, with halfway values
+    /// rounded away from zero. The scale is clamped to at most `1000` digits.
+    fn round(&self, scale: u32) -> Self {
+        let scale = scale.min(MAX_SCALE);
+
+        if scale >= self.scale {
+            return self.rescale(scale);
+        }
+
+        let divisor = pow10(self.scale - scale);
+        let mantissa = div_round(&self.mantissa, &divisor);
+        Self { mantissa, scale }
+    }
+
+    /// Truncate to the given number of fractional digits, rounding towards
+    /// zero. The scale is clamped to at most `1000` digits.
+    fn trunc(&self, scale: u32) -> Self {
+        let scale = scale.min(MAX_SCALE);
+
+        if scale >= self.scale {
+            return self.rescale(scale);
+        }
+
+        let mantissa = &self.mantissa / pow10(self.scale - scale);
+        Self { mantissa, scale }
+    }
+
+    /// Remove trailing zeros from the fractional digits.
+    fn normalize(&self) -> Self {
+        let ten = num::BigInt::from(10);
+        let mut mantissa = self.mantissa.clone();
+        let mut scale = self.scale;
+
+        while scale > 0 && (&mantissa % &ten).is_zero() {
+            mantissa /= &ten;
+            scale -= 1;
+        }
+
+        Self { mantissa, scale }
+    }
+
+    fn abs(&self) -> Self {
+        Self {
+            mantissa: self.mantissa.abs(),
+            scale: self.scale,
+        }
+    }
+
+    fn neg(&self) -> Self {
+        Self {
+            mantissa: -&self.mantissa,
+            scale: self.scale,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.mantissa.is_zero()
+    }
+
+    fn is_negative(&self) -> bool {
+        self.mantissa.is_negative()
+    }
+
+    /// Widen to the given scale, which must not be smaller than the current
+    /// one. The scale is clamped to [MAX_SCALE].
+    fn rescale(&self, scale: u32) -> Self {
+        let scale = scale.min(MAX_SCALE);
+
+        Self {
+            mantissa: &self.mantissa * pow10(scale - self.scale),
+            scale,
+        }
+    }
+
+    /// Apply a binary operation with another decimal, big integer or integer.
+    fn op(
+        &self,
+        protocol: Protocol,
+        rhs: &Value,
+        op: fn(&Decimal, &Decimal) -> Result<Decimal, VmErrorKind>,
+    ) -> Result<Self, VmError> {
+        match to_decimal(rhs)? {
+            Some(rhs) => Ok(op(self, &rhs)?),
+            None => Err(unsupported::<Self>(protocol, rhs)?),
+        }
+    }
+
+    fn add(&self, rhs: Value) -> Result<Self, VmError> {
+        self.op(Protocol::ADD, &rhs, |a, b| {
+            let (a, b) = align(a, b);
+            Ok(Self {
+                mantissa: a.mantissa + b.mantissa,
+                scale: a.scale,
+            })
+        })
+    }
+
+    fn sub(&self, rhs: Value) -> Result<Self, VmError> {
+        self.op(Protocol::SUB, &rhs, |a, b| {
+            let (a, b) = align(a, b);
+            Ok(Self {
+                mantissa: a.mantissa - b.mantissa,
+                scale: a.scale,
+            })
+        })
+    }
+
+    fn mul(&self, rhs: Value) -> Result<Self, VmError> {
+        self.op(Protocol::MUL, &rhs, |a, b| {
+            let product = Self {
+                mantissa: &a.mantissa * &b.mantissa,
+                scale: a.scale + b.scale,
+            };
+
+            // NB: round products of very precise decimals so that the scale
+            // stays in bounds.
+            if product.scale > MAX_SCALE {
+                return Ok(product.round(MAX_SCALE));
+            }
+
+            Ok(product)
+        })
+    }
+
+    /// Divide, keeping at least [DIVISION_SCALE] fractional digits for
+    /// quotients which don't divide evenly. Trailing zeros beyond the scale
+    /// of the operands are removed.
+    fn div(&self, rhs: Value) -> Result<Self, VmError> {
+        self.op(Protocol::DIV, &rhs, |a, b| {
+            if b.mantissa.is_zero() {
+                return Err(VmErrorKind::DivideByZero);
+            }
+
+            let min_scale = a.scale.max(b.scale);
+            let scale = min_scale.max(DIVISION_SCALE);
+            let numerator = &a.mantissa * pow10(scale + b.scale - a.scale);
+            let mut quotient = Self {
+                mantissa: div_round(&numerator, &b.mantissa),
+                scale,
+            }
+            .normalize();
+
+            if quotient.scale < min_scale {
+                quotient = quotient.rescale(min_scale);
+            }
+
+            Ok(quotient)
+        })
+    }
+
+    fn rem(&self, rhs: Value) -> Result<Self, VmError> {
+        self.op(Protocol::REM, &rhs, |a, b| {
+            if b.mantissa.is_zero() {
+                return Err(VmErrorKind::DivideByZero);
+            }
+
+            let (a, b) = align(a, b);
+            Ok(Self {
+                mantissa: a.mantissa % b.mantissa,
+                scale: a.scale,
+            })
+        })
+    }
+
+    fn add_assign(&mut self, rhs: Value) -> Result<(), VmError> {
+        *self = self.add(rhs)?;
+        Ok(())
+    }
+
+    fn sub_assign(&mut self, rhs: Value) -> Result<(), VmError> {
+        *self = self.sub(rhs)?;
+        Ok(())
+    }
+
+    fn mul_assign(&mut self, rhs: Value) -> Result<(), VmError> {
+        *self = self.mul(rhs)?;
+        Ok(())
+    }
+
+    fn div_assign(&mut self, rhs: Value) -> Result<(), VmError> {
+        *self = self.div(rhs)?;
+        Ok(())
+    }
+
+    fn rem_assign(&mut self, rhs: Value) -> Result<(), VmError> {
+        *self = self.rem(rhs)?;
+        Ok(())
+    }
+
+    fn eq(&self, rhs: Value) -> Result<bool, VmError> {
+        Ok(self.partial_cmp(rhs)? == Some(Ordering::Equal))
+    }
+
+    fn partial_cmp(&self, rhs: Value) -> Result<Option<Ordering>, VmError> {
+        Ok(to_decimal(&rhs)?.map(|rhs| {
+            let (a, b) = align(self, &rhs);
+            a.mantissa.cmp(&b.mantissa)
+        }))
+    }
+
+    fn cmp(&self, rhs: Value) -> Result<Ordering, VmError> {
+        match self.partial_cmp(rhs.clone())? {
+            Some(ordering) => Ok(ordering),
+            None => Err(unsupported::<Self>(Protocol::CMP, &rhs)?),
+        }
+    }
+
+    fn string_display(&self, s: &mut String) -> fmt::Result {
+        use std::fmt::Write as _;
+
+        if self.mantissa.is_negative() {
+            s.push('-');
+        }
+
+        let digits = self.mantissa.magnitude().to_string();
+        let scale = self.scale as usize;
+
+        if scale == 0 {
+            return write!(s, "{}", digits);
+        }
+
+        if digits.len() > scale {
+            let (whole, fraction) = digits.split_at(digits.len() - scale);
+            write!(s, "{}.{}", whole, fraction)
+        } else {
+            write!(s, "0.{:0>width$}", digits, width = scale)
+        }
+    }
+}
+
+/// An error raised when parsing a [Decimal] fails.
+#[derive(Any, Debug, Clone, Copy)]
+#[rune(module = "crate")]
+pub struct ParseDecimalError;
+
+impl ParseDecimalError {
+    fn string_display(&self, s: &mut String) -> fmt::Result {
+        use std::fmt::Write as _;
+        write!(s, "{}", self)
+    }
+}
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid decimal literal")
+    }
+}
+
+impl std::error::Error for ParseDecimalError {}
+
+/// Add two integers, promoting the result to a [BigInt] if it overflows.
+fn checked_add(a: Value, b: Value) -> Result<Value, VmError> {
+    promote(a, b, Protocol::ADD, i64::checked_add, |a, b| Ok(a + b))
+}
+
+/// Subtract two integers, promoting the result to a [BigInt] if it overflows.
+fn checked_sub(a: Value, b: Value) -> Result<Value, VmError> {
+    promote(a, b, Protocol::SUB, i64::checked_sub, |a, b| Ok(a - b))
+}
+
+/// Multiply two integers, promoting the result to a [BigInt] if it overflows.
+fn checked_mul(a: Value, b: Value) -> Result<Value, VmError> {
+    promote(a, b, Protocol::MUL, i64::checked_mul, |a, b| Ok(a * b))
+}
+
+/// Divide two integers, promoting the result to a [BigInt] if it overflows.
+fn checked_div(a: Value, b: Value) -> Result<Value, VmError> {
+    promote(a, b, Protocol::DIV, i64::checked_div, |a, b| {
+        if b.is_zero() {
+            return Err(VmErrorKind::DivideByZero);
+        }
+
+        Ok(a / b)
+    })
+}
+
+/// Calculate the remainder of two integers, promoting the result to a
+/// [BigInt] if it overflows.
+fn checked_rem(a: Value, b: Value) -> Result<Value, VmError> {
+    promote(a, b, Protocol::REM, i64::checked_rem, |a, b| {
+        if b.is_zero() {
+            return Err(VmErrorKind::DivideByZero);
+        }
+
+        Ok(a % b)
+    })
+}
+
+/// Raise an integer to the given power, promoting the result to a [BigInt] if
+/// it overflows.
+fn checked_pow(a: Value, exp: u32) -> Result<Value, VmError> {
+    if let Value::Integer(a) = a {
+        if let Some(value) = a.checked_pow(exp) {
+            return Ok(Value::Integer(value));
+        }
+    }
+
+    match to_bigint(&a)? {
+        Some(a) => demote(num::pow::Pow::pow(&a, exp)),
+        None => Err(VmError::expected::<i64>(a.type_info()?)),
+    }
+}
+
+/// Apply an integer operation, falling back to big integers if either
+/// operand is a [BigInt] or if the integer operation overflows. The result is
+/// an integer if it fits in one.
+fn promote(
+    a: Value,
+    b: Value,
+    protocol: Protocol,
+    integer_op: fn(i64, i64) -> Option<i64>,
+    bigint_op: fn(&num::BigInt, &num::BigInt) -> Result<num::BigInt, VmErrorKind>,
+) -> Result<Value, VmError> {
+    if let (Value::Integer(a), Value::Integer(b)) = (&a, &b) {
+        if *b == 0 && (protocol.hash == Protocol::DIV.hash || protocol.hash == Protocol::REM.hash) {
+            return Err(VmError::from(VmErrorKind::DivideByZero));
+        }
+
+        if let Some(value) = integer_op(*a, *b) {
+            return Ok(Value::Integer(value));
+        }
+    }
+
+    match (to_bigint(&a)?, to_bigint(&b)?) {
+        (Some(a), Some(b)) => demote(bigint_op(&a, &b)?),
+        _ => Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+            op: protocol.name,
+            lhs: a.type_info()?,
+            rhs: b.type_info()?,
+        })),
+    }
+}
+
+/// Convert a big integer back into an integer value if it fits.
+fn demote(value: num::BigInt) -> Result<Value, VmError> {
+    match value.to_i64() {
+        Some(value) => Ok(Value::Integer(value)),
+        None => BigInt(value).to_value(),
+    }
+}
+
+/// Coerce an integer of any kind or a [BigInt] into a big integer.
+fn to_bigint(value: &Value) -> Result<Option<num::BigInt>, VmError> {
+    if let Some(value) = value.as_wide_integer() {
+        return Ok(Some(num::BigInt::from(value)));
+    }
+
+    if let Value::Any(any) = value {
+        let any = any.borrow_ref()?;
+
+        if let Some(value) = any.downcast_borrow_ref::<BigInt>() {
+            return Ok(Some(value.0.clone()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Coerce an integer of any kind, a [BigInt] or a [Decimal] into a decimal.
+fn to_decimal(value: &Value) -> Result<Option<Decimal>, VmError> {
+    if let Value::Any(any) = value {
+        let any = any.borrow_ref()?;
+
+        if let Some(value) = any.downcast_borrow_ref::<Decimal>() {
+            return Ok(Some(value.clone()));
+        }
+    }
+
+    Ok(to_bigint(value)?.map(Decimal::from_bigint))
+}
+
+/// Promote an integer on the left hand side of an operation to the [BigInt]
+/// or [Decimal] on the right hand side, so that `100 - d` works just like
+/// `d - 100` does.
+///
+/// This is used by the virtual machine once the integer itself turns out not
+/// to support the operation.
+pub(crate) fn promote_integer(lhs: &Value, rhs: &Value) -> Result<Option<Value>, VmError> {
+    let (lhs, rhs) = match (lhs, rhs) {
+        (Value::Integer(lhs), Value::Any(rhs)) => (*lhs, rhs.borrow_ref()?),
+        _ => return Ok(None),
+    };
+
+    let lhs = num::BigInt::from(lhs);
+
+    if rhs.is::<BigInt>() {
+        return Ok(Some(BigInt(lhs).to_value()?));
+    }
+
+    if rhs.is::<Decimal>() {
+        return Ok(Some(Decimal::from_bigint(lhs).to_value()?));
+    }
+
+    Ok(None)
+}
+
+/// Widen two decimals to the same scale.
+fn align(a: &Decimal, b: &Decimal) -> (Decimal, Decimal) {
+    let scale = a.scale.max(b.scale);
+    (a.rescale(scale), b.rescale(scale))
+}
+
+/// Raise ten to the given power. Since decimal scales are bounded by
+/// [MAX_SCALE], so is the exponent.
+fn pow10(exp: u32) -> num::BigInt {
+    num::pow::Pow::pow(num::BigInt::from(10), exp.min(2 * MAX_SCALE))
+}
+
+/// Divide, rounding halfway values away from zero.
+fn div_round(a: &num::BigInt, b: &num::BigInt) -> num::BigInt {
+    let quotient = a / b;
+    let remainder = a % b;
+
+    if (remainder.abs() * 2u32) >= b.abs() {
+        if a.is_negative() != b.is_negative() {
+            quotient - 1
+        } else {
+            quotient + 1
+        }
+    } else {
+        quotient
+    }
+}
+
+fn unsupported<T>(protocol: Protocol, rhs: &Value) -> Result<VmError, VmError>
+where
+    T: TypeOf,
+{
+    Ok(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+        op: protocol.name,
+        lhs: T::type_info(),
+        rhs: rhs.type_info()?,
+    }))
+}
+
+crate::__internal_impl_any!(ParseBigIntError);
@@ -1,7 +1,10 @@
 //! The `std::object` module.
 
-use crate::runtime::{Iterator, Object, Protocol, Value};
-use crate::{ContextError, Module};
+use crate::runtime::{
+    EnvProtocolCaller, FromValue, Function, Iterator, Object, Protocol, Shared, Value, VmError,
+};
+use crate::{Any, ContextError, Module};
+use std::fmt;
 
 /// Construct the `std::object` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -10,19 +13,87 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<Object>()?;
 
     module.inst_fn("len", Object::len)?;
+    module.inst_fn("is_empty", Object::is_empty)?;
     module.inst_fn("insert", Object::insert)?;
     module.inst_fn("remove", remove)?;
     module.inst_fn("clear", Object::clear)?;
+    module.inst_fn("clone", Object::clone)?;
     module.inst_fn("contains_key", contains_key)?;
     module.inst_fn("get", get)?;
+    module.inst_fn("get_or_insert", get_or_insert)?;
+    module.inst_fn("update", update)?;
+    module.inst_fn("entry", entry)?;
+    module.inst_fn("extend", extend)?;
+    module.inst_fn("retain", retain)?;
+    module.inst_fn("merge", merge)?;
 
     module.inst_fn("iter", Object::into_iterator)?;
     module.inst_fn(Protocol::INTO_ITER, Object::into_iterator)?;
+    module.inst_fn(Protocol::EQ, eq)?;
+    module.inst_fn(Protocol::STRING_DEBUG, string_debug)?;
     module.inst_fn("keys", keys)?;
     module.inst_fn("values", values)?;
+
+    module.ty::<Entry>()?;
+    module.inst_fn("key", Entry::key)?;
+    module.inst_fn("or_insert", Entry::or_insert)?;
+    module.inst_fn("or_insert_with", Entry::or_insert_with)?;
+    module.inst_fn("and_modify", Entry::and_modify)?;
     Ok(module)
 }
 
+/// A view into a single entry of an object, which may either be vacant or
+/// occupied.
+///
+/// Constructed through `Object::entry`.
+#[derive(Any)]
+#[rune(module = "crate")]
+struct Entry {
+    object: Shared<Object>,
+    key: String,
+}
+
+impl Entry {
+    /// The key of the entry.
+    fn key(&self) -> String {
+        self.key.clone()
+    }
+
+    /// Insert the given value if the entry is vacant, and return the value of
+    /// the entry.
+    fn or_insert(self, default: Value) -> Result<Value, VmError> {
+        let mut object = self.object.borrow_mut()?;
+        Ok(object.inner_entry(self.key).or_insert(default).clone())
+    }
+
+    /// Insert the result of calling the given function if the entry is vacant,
+    /// and return the value of the entry.
+    fn or_insert_with(self, default: Function) -> Result<Value, VmError> {
+        if let Some(value) = self.object.borrow_ref()?.get(&self.key) {
+            return Ok(value.clone());
+        }
+
+        // NB: the object is not borrowed while calling the function, so that
+        // it is free to access it.
+        let value = default.call::<_, Value>(())?;
+        self.object.borrow_mut()?.insert(self.key, value.clone());
+        Ok(value)
+    }
+
+    /// Replace the value of an occupied entry with the result of calling the
+    /// given function with the current value.
+    fn and_modify(self, f: Function) -> Result<Self, VmError> {
+        let current = self.object.borrow_ref()?.get(&self.key).cloned();
+
+        if let Some(current) = current {
+            let value = f.call::<_, Value>((current,))?;
+            self.object.borrow_mut()?.insert(self.key.clone(), value);
+        }
+
+        Ok(self)
+    }
+}
+
 fn contains_key(object: &Object, key: &str) -> bool {
     object.contains_key(key)
 }
@@ -35,6 +106,116 @@ fn get(object: &Object, key: &str) -> Option<Value> {
     object.get(key).cloned()
 }
 
+/// Get the value of the given key, inserting the default if it is missing.
+fn get_or_insert(object: &mut Object, key: String, default: Value) -> Value {
+    object.inner_entry(key).or_insert(default).clone()
+}
+
+/// Replace the value of the given key with the result of calling the function
+/// with the current value.
+///
+/// Returns `false` and does nothing if the key is missing.
+fn update(object: Shared<Object>, key: &str, f: Function) -> Result<bool, VmError> {
+    let current = match object.borrow_ref()?.get(key) {
+        Some(current) => current.clone(),
+        None => return Ok(false),
+    };
+
+    let value = f.call::<_, Value>((current,))?;
+    object.borrow_mut()?.insert(key.to_owned(), value);
+    Ok(true)
+}
+
+fn entry(object: Shared<Object>, key: String) -> Entry {
+    Entry { object, key }
+}
+
+/// Extend the object from another object or an iterator of key-value pairs.
+fn extend(object: &mut Object, value: Value) -> Result<(), VmError> {
+    let mut it = value.into_iter()?;
+
+    while let Some(value) = it.next()? {
+        let (key, value) = <(String, Value)>::from_value(value)?;
+        object.insert(key, value);
+    }
+
+    Ok(())
+}
+
+/// Retain only the entries for which the predicate returns `true` when called
+/// with the key and value.
+fn retain(object: &mut Object, predicate: &Function) -> Result<(), VmError> {
+    let mut error = None;
+
+    object.retain(|key, value| {
+        if error.is_some() {
+            return true;
+        }
+
+        match predicate.call::<_, bool>((key.as_str(), value.clone())) {
+            Ok(keep) => keep,
+            Err(e) => {
+                error = Some(e);
+                true
+            }
+        }
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Deeply merge another object into this one.
+///
+/// Nested objects present in both are merged recursively, any other value in
+/// `other` replaces the existing one.
+fn merge(object: &mut Object, other: &Object) -> Result<(), VmError> {
+    for (key, value) in other.iter() {
+        let source = match value {
+            Value::Object(source) => source,
+            value => {
+                object.insert(key.clone(), value.clone());
+                continue;
+            }
+        };
+
+        match object.get(key) {
+            Some(Value::Object(target)) => {
+                // NB: merging an object into itself does nothing.
+                if target.as_ptr() == source.as_ptr() {
+                    continue;
+                }
+
+                // NB: copy the nested object before merging into it, since it
+                // might be shared with `other` or some other object.
+                let mut copy = target.borrow_ref()?.clone();
+                merge(&mut copy, &*source.borrow_ref()?)?;
+                object.insert(key.clone(), Value::from(copy));
+            }
+            _ => {
+                // NB: copy nested objects so that merging into this object
+                // later doesn't modify `other`.
+                let mut copy = Object::new();
+                merge(&mut copy, &*source.borrow_ref()?)?;
+                object.insert(key.clone(), Value::from(copy));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn eq(object: &Object, other: &Object) -> Result<bool, VmError> {
+    Object::value_ptr_eq(&mut EnvProtocolCaller, object, other)
+}
+
+fn string_debug(object: &Object, s: &mut String) -> fmt::Result {
+    use std::fmt::Write as _;
+    write!(s, "{:?}", object)
+}
+
 fn keys(object: &Object) -> Iterator {
     let iter = object.keys().cloned().collect::<Vec<_>>().into_iter();
     Iterator::from_double_ended("std::object::Keys", iter)
@@ -28,13 +28,28 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("shrink_to_fit", String::shrink_to_fit)?;
     module.inst_fn("char_at", char_at)?;
     module.inst_fn("split", string_split)?;
+    module.inst_fn("splitn", string_splitn)?;
+    module.inst_fn("split_once", string_split_once)?;
+    module.inst_fn("split_whitespace", string_split_whitespace)?;
+    module.inst_fn("lines", string_lines)?;
     module.inst_fn("trim", string_trim)?;
+    module.inst_fn("trim_start", string_trim_start)?;
     module.inst_fn("trim_end", string_trim_end)?;
+    module.inst_fn("strip_prefix", string_strip_prefix)?;
+    module.inst_fn("strip_suffix", string_strip_suffix)?;
+    module.inst_fn("find", string_find)?;
+    module.inst_fn("rfind", string_rfind)?;
+    module.inst_fn("contains", string_contains)?;
+    module.inst_fn("to_lowercase", str::to_lowercase)?;
+    module.inst_fn("to_uppercase", str::to_uppercase)?;
+    module.inst_fn("repeat", str::repeat)?;
+    module.inst_fn("is_char_boundary", str::is_char_boundary)?;
     module.inst_fn("replace", str::replace::<&str>)?;
     // TODO: deprecate this variant.
     module.inst_fn("split_str", string_split)?;
     module.inst_fn("is_empty", str::is_empty)?;
     module.inst_fn("chars", string_chars)?;
+    module.inst_fn("char_indices", string_char_indices)?;
     module.inst_fn(Protocol::ADD, add)?;
     module.inst_fn(Protocol::ADD_ASSIGN, String::push_str)?;
     module.inst_fn(Protocol::INDEX_GET, string_index_get)?;
@@ -87,7 +102,7 @@ fn string_split(this: &str, value: Value) -> Result<Iterator, VmError> {
             .map(String::from)
             .collect::<Vec<String>>(),
         Value::Char(pat) => this.split(pat).map(String::from).collect::<Vec<String>>(),
-        value => return Err(VmError::bad_argument::<String>(0, &value)?),
+        value => return Err(VmError::bad_argument::<String>(1, &value)?),
     };
 
     Ok(Iterator::from_double_ended(
@@ -96,10 +111,123 @@ fn string_split(this: &str, value: Value) -> Result<Iterator, VmError> {
     ))
 }
 
+/// A pattern used to search a string, which is either a string or a
+/// character.
+enum Pattern<'a> {
+    Str(&'a str),
+    Char(char),
+}
+
+/// Call the given closure with the pattern the value represents.
+///
+/// `arg` is the index of the pattern among the arguments of the instance
+/// function, where `self` is argument `0`, like for errors raised when
+/// converting arguments.
+fn with_pattern<T>(
+    arg: usize,
+    value: &Value,
+    f: impl FnOnce(Pattern<'_>) -> T,
+) -> Result<T, VmError> {
+    Ok(match value {
+        Value::String(s) => f(Pattern::Str(s.borrow_ref()?.as_str())),
+        Value::StaticString(s) => f(Pattern::Str(s.as_str())),
+        Value::Char(c) => f(Pattern::Char(*c)),
+        value => return Err(VmError::bad_argument::<String>(arg, value)?),
+    })
+}
+
+fn string_splitn(this: &str, n: usize, value: Value) -> Result<Iterator, VmError> {
+    let parts = with_pattern(2, &value, |pat| match pat {
+        Pattern::Str(pat) => this.splitn(n, pat).map(String::from).collect::<Vec<_>>(),
+        Pattern::Char(pat) => this.splitn(n, pat).map(String::from).collect::<Vec<_>>(),
+    })?;
+
+    Ok(Iterator::from_double_ended(
+        "std::str::SplitN",
+        parts.into_iter(),
+    ))
+}
+
+fn string_split_once(this: &str, value: Value) -> Result<Option<(String, String)>, VmError> {
+    let parts = with_pattern(1, &value, |pat| match pat {
+        Pattern::Str(pat) => this.split_once(pat),
+        Pattern::Char(pat) => this.split_once(pat),
+    })?;
+
+    Ok(parts.map(|(a, b)| (a.to_owned(), b.to_owned())))
+}
+
+fn string_split_whitespace(this: &str) -> Iterator {
+    let iter = this
+        .split_whitespace()
+        .map(String::from)
+        .collect::<Vec<_>>()
+        .into_iter();
+    Iterator::from_double_ended("std::str::SplitWhitespace", iter)
+}
+
+fn string_lines(this: &str) -> Iterator {
+    let iter = this
+        .lines()
+        .map(String::from)
+        .collect::<Vec<_>>()
+        .into_iter();
+    Iterator::from_double_ended("std::str::Lines", iter)
+}
+
+fn string_strip_prefix(this: &str, value: Value) -> Result<Option<String>, VmError> {
+    with_pattern(1, &value, |pat| {
+        let rest = match pat {
+            Pattern::Str(pat) => this.strip_prefix(pat),
+            Pattern::Char(pat) => this.strip_prefix(pat),
+        };
+
+        rest.map(String::from)
+    })
+}
+
+fn string_strip_suffix(this: &str, value: Value) -> Result<Option<String>, VmError> {
+    with_pattern(1, &value, |pat| {
+        let rest = match pat {
+            Pattern::Str(pat) => this.strip_suffix(pat),
+            Pattern::Char(pat) => this.strip_suffix(pat),
+        };
+
+        rest.map(String::from)
+    })
+}
+
+/// Find the byte index of the first match of a pattern.
+fn string_find(this: &str, value: Value) -> Result<Option<usize>, VmError> {
+    with_pattern(1, &value, |pat| match pat {
+        Pattern::Str(pat) => this.find(pat),
+        Pattern::Char(pat) => this.find(pat),
+    })
+}
+
+/// Find the byte index of the last match of a pattern.
+fn string_rfind(this: &str, value: Value) -> Result<Option<usize>, VmError> {
+    with_pattern(1, &value, |pat| match pat {
+        Pattern::Str(pat) => this.rfind(pat),
+        Pattern::Char(pat) => this.rfind(pat),
+    })
+}
+
+fn string_contains(this: &str, value: Value) -> Result<bool, VmError> {
+    with_pattern(1, &value, |pat| match pat {
+        Pattern::Str(pat) => this.contains(pat),
+        Pattern::Char(pat) => this.contains(pat),
+    })
+}
+
 fn string_trim(this: &str) -> String {
     this.trim().to_owned()
 }
 
+fn string_trim_start(this: &str) -> String {
+    this.trim_start().to_owned()
+}
+
 fn string_trim_end(this: &str) -> String {
     this.trim_end().to_owned()
 }
@@ -125,6 +253,12 @@ fn string_chars(s: &str) -> Iterator {
     Iterator::from_double_ended("std::str::Chars", iter)
 }
 
+/// Iterate over the characters of a string along with their byte index.
+fn string_char_indices(s: &str) -> Iterator {
+    let iter = s.char_indices().collect::<Vec<_>>().into_iter();
+    Iterator::from_double_ended("std::str::CharIndices", iter)
+}
+
 /// Get a specific string index.
 fn string_get(s: &str, key: Value) -> Result<Option<String>, VmError> {
     use crate::runtime::{FromValue, RangeLimits, TypeOf};
@@ -1,7 +1,11 @@
 //! The `std::vec` module.
 
-use crate::runtime::{Function, Protocol, TypeOf, Value, Vec};
+use crate::runtime::{
+    EnvProtocolCaller, FromValue, Function, Protocol, Range, RangeLimits, TypeOf, Value, Vec,
+    VmError, VmErrorKind,
+};
 use crate::{ContextError, Module, Params};
+use std::cmp;
 
 /// Construct the `std::vec` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -10,18 +14,34 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<Vec>()?;
 
     module.function(["Vec", "new"], Vec::new)?;
+    module.inst_fn("binary_search", binary_search)?;
     module.inst_fn("clear", Vec::clear)?;
     module.inst_fn("clone", Vec::clone)?;
+    module.inst_fn("concat", concat)?;
+    module.inst_fn("contains", contains)?;
+    module.inst_fn("dedup", dedup)?;
     module.inst_fn("extend", Vec::extend)?;
+    module.inst_fn("first", first)?;
     module.inst_fn("get", vec_get)?;
+    module.inst_fn("is_empty", Vec::is_empty)?;
     module.inst_fn("iter", Vec::into_iterator)?;
+    module.inst_fn("join", join)?;
+    module.inst_fn("last", last)?;
     module.inst_fn("len", Vec::len)?;
     module.inst_fn("pop", Vec::pop)?;
     module.inst_fn("push", Vec::push)?;
     module.inst_fn("remove", Vec::remove)?;
+    module.inst_fn("retain", retain)?;
+    module.inst_fn("reverse", reverse)?;
+    module.inst_fn("sort", sort)?;
     module.inst_fn("sort_by", sort_by)?;
+    module.inst_fn("sort_by_key", sort_by_key)?;
+    module.inst_fn("split_off", Vec::split_off)?;
+    module.inst_fn("swap", Vec::swap)?;
+    module.inst_fn("truncate", Vec::truncate)?;
     module.inst_fn("insert", Vec::insert)?;
     module.inst_fn(Protocol::INTO_ITER, Vec::into_iterator)?;
+    module.inst_fn(Protocol::INDEX_GET, vec_index_get)?;
     module.inst_fn(Protocol::INDEX_SET, Vec::set)?;
 
     // TODO: parameterize with generics.
@@ -38,8 +58,241 @@ fn sort_int(vec: &mut Vec) {
     });
 }
 
-fn vec_get(vec: &Vec, index: usize) -> Option<Value> {
-    vec.get(index).cloned()
+/// Sort a vector by the total ordering of its values.
+fn sort(vec: &mut Vec) -> Result<(), VmError> {
+    let mut error = None;
+
+    vec.sort_by(|a, b| match Value::cmp(a, b) {
+        Ok(ordering) => ordering,
+        Err(e) => {
+            error.get_or_insert(e);
+            std::cmp::Ordering::Equal
+        }
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Sort a vector by a key extracted from each value.
+///
+/// The key function is called exactly once for each element.
+fn sort_by_key(vec: &mut Vec, key: &Function) -> Result<(), VmError> {
+    let values = std::mem::replace(vec, Vec::new()).into_inner();
+    let mut keyed = std::vec::Vec::with_capacity(values.len());
+
+    for value in values {
+        keyed.push((key.call::<_, Value>((value.clone(),))?, value));
+    }
+
+    let mut error = None;
+
+    keyed.sort_by(|(a, _), (b, _)| match Value::cmp(a, b) {
+        Ok(ordering) => ordering,
+        Err(e) => {
+            error.get_or_insert(e);
+            cmp::Ordering::Equal
+        }
+    });
+
+    *vec = Vec::from(
+        keyed
+            .into_iter()
+            .map(|(_, v)| v)
+            .collect::<std::vec::Vec<_>>(),
+    );
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Remove consecutive repeated elements.
+fn dedup(vec: &mut Vec) -> Result<(), VmError> {
+    let mut error = None;
+
+    vec.dedup_by(|a, b| {
+        if error.is_some() {
+            return false;
+        }
+
+        match Value::value_ptr_eq(&mut EnvProtocolCaller, a, b) {
+            Ok(same) => same,
+            Err(e) => {
+                error = Some(e);
+                false
+            }
+        }
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Retain only the elements for which the predicate returns `true`.
+fn retain(vec: &mut Vec, predicate: &Function) -> Result<(), VmError> {
+    let mut error = None;
+
+    vec.retain(|value| {
+        if error.is_some() {
+            return true;
+        }
+
+        match predicate.call::<_, bool>((value.clone(),)) {
+            Ok(keep) => keep,
+            Err(e) => {
+                error = Some(e);
+                true
+            }
+        }
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Reverse the order of elements in place.
+fn reverse(vec: &mut Vec) {
+    vec.reverse();
+}
+
+/// Test if the vector contains the given value.
+fn contains(vec: &Vec, value: Value) -> Result<bool, VmError> {
+    for v in vec.iter() {
+        if Value::value_ptr_eq(&mut EnvProtocolCaller, v, &value)? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Binary search a sorted vector for the given value.
+///
+/// Returns `Ok(index)` if the value is found and `Err(index)` with the index
+/// where it could be inserted otherwise.
+fn binary_search(vec: &Vec, value: Value) -> Result<Result<usize, usize>, VmError> {
+    let mut error = None;
+
+    let result = vec.binary_search_by(|v| match Value::cmp(v, &value) {
+        Ok(ordering) => ordering,
+        Err(e) => {
+            error.get_or_insert(e);
+            cmp::Ordering::Equal
+        }
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(result),
+    }
+}
+
+fn first(vec: &Vec) -> Option<Value> {
+    vec.first().cloned()
+}
+
+fn last(vec: &Vec) -> Option<Value> {
+    vec.last().cloned()
+}
+
+/// Join a vector of strings with the given separator.
+fn join(vec: &Vec, sep: &str) -> Result<String, VmError> {
+    let mut out = String::new();
+
+    for (n, value) in vec.iter().enumerate() {
+        if n > 0 {
+            out.push_str(sep);
+        }
+
+        match value {
+            Value::String(string) => out.push_str(&string.borrow_ref()?),
+            Value::StaticString(string) => out.push_str(string.as_ref()),
+            actual => return Err(VmError::expected::<String>(actual.type_info()?)),
+        }
+    }
+
+    Ok(out)
+}
+
+/// Flatten a vector of vectors into a vector, or a vector of strings into a
+/// string.
+fn concat(vec: &Vec) -> Result<Value, VmError> {
+    if let Some(Value::String(..) | Value::StaticString(..)) = vec.first() {
+        return Ok(Value::from(join(vec, "")?));
+    }
+
+    let mut out = std::vec::Vec::new();
+
+    for value in vec.iter() {
+        match value {
+            Value::Vec(vec) => out.extend(vec.borrow_ref()?.iter().cloned()),
+            actual => return Err(VmError::expected::<Vec>(actual.type_info()?)),
+        }
+    }
+
+    Ok(Value::vec(out))
+}
+
+/// Get the slice of the vector covered by the given range.
+fn slice<'a>(vec: &'a [Value], range: &Range) -> Result<Option<&'a [Value]>, VmError> {
+    let start = match range.start.clone() {
+        Some(value) => Some(<usize>::from_value(value)?),
+        None => None,
+    };
+
+    let end = match range.end.clone() {
+        Some(value) => Some(<usize>::from_value(value)?),
+        None => None,
+    };
+
+    Ok(match range.limits {
+        RangeLimits::HalfOpen => match (start, end) {
+            (Some(start), Some(end)) => vec.get(start..end),
+            (Some(start), None) => vec.get(start..),
+            (None, Some(end)) => vec.get(..end),
+            (None, None) => vec.get(..),
+        },
+        RangeLimits::Closed => match (start, end) {
+            (Some(start), Some(end)) => vec.get(start..=end),
+            (None, Some(end)) => vec.get(..=end),
+            _ => return Err(VmError::from(VmErrorKind::UnsupportedRange)),
+        },
+    })
+}
+
+/// Get an element or a sub-vector by index or range.
+fn vec_get(vec: &Vec, key: Value) -> Result<Option<Value>, VmError> {
+    match key {
+        Value::Integer(index) => {
+            let index = match usize::try_from(index) {
+                Ok(index) => index,
+                Err(..) => return Ok(None),
+            };
+
+            Ok(vec.get(index).cloned())
+        }
+        Value::Range(range) => {
+            let range = range.borrow_ref()?;
+            Ok(slice(vec, &range)?.map(|values| Value::vec(values.to_vec())))
+        }
+        index => Err(VmError::from(VmErrorKind::UnsupportedIndexGet {
+            target: Vec::type_info(),
+            index: index.type_info()?,
+        })),
+    }
+}
+
+/// Get a sub-vector by range.
+fn vec_index_get(vec: &Vec, key: Value) -> Result<Value, VmError> {
+    vec_get(vec, key)?.ok_or_else(|| VmError::panic("missing vector slice"))
 }
 
 fn sort_by(vec: &mut Vec, comparator: &Function) {
@@ -98,6 +98,12 @@ pub enum ParseErrorKind {
     PrecedenceGroupRequired,
     #[error("number literal out of bounds `-9223372036854775808` to `9223372036854775807`")]
     BadNumberOutOfBounds,
+    #[error("number literal out of bounds `{min}` to `{max}` for type `{suffix}`")]
+    BadSizedNumberOutOfBounds {
+        suffix: ast::NumberSuffix,
+        min: i128,
+        max: i128,
+    },
     #[error("unsupported field access")]
     BadFieldAccess,
     #[error("expected close delimiter `{expected}`, but got `{actual}`")]
@@ -117,6 +117,8 @@ pub(crate) struct QueryInner {
     items: HashMap<NonZeroId, ItemMeta>,
     /// All available names in the context.
     names: Names,
+    /// Names of all instance functions declared in sources.
+    instance_fn_names: HashSet<Box<str>>,
 }
 
 /// Query system of the rune compiler.
@@ -441,6 +443,29 @@ impl<'a> Query<'a> {
         self.index(entry);
     }
 
+    /// Queue up a trait implementation to be built.
+    #[tracing::instrument(skip_all)]
+    pub(crate) fn queue_trait_impl(&mut self, item_meta: ItemMeta, trait_impl: TraitImpl) {
+        self.inner.queue.push_back(BuildEntry {
+            item_meta,
+            used: Used::Used,
+            build: Build::TraitImpl(trait_impl),
+        });
+    }
+
+    /// Register the name of an instance function declared in a source.
+    pub(crate) fn index_instance_fn_name(&mut self, name: &str) {
+        if !self.inner.instance_fn_names.contains(name) {
+            self.inner.instance_fn_names.insert(name.into());
+        }
+    }
+
+    /// Test if an instance function with the given name is declared in any
+    /// source, regardless of which type it's declared for.
+    pub(crate) fn contains_instance_fn_name(&self, name: &str) -> bool {
+        self.inner.instance_fn_names.contains(name)
+    }
+
     /// Index a constant expression.
     #[tracing::instrument(skip_all)]
     pub(crate) fn index_const<T>(
@@ -646,6 +671,7 @@ impl<'a> Query<'a> {
             ContextMetaKind::Enum { type_hash } => PrivMetaKind::Enum { type_hash },
             ContextMetaKind::Function { type_hash } => PrivMetaKind::Function {
                 type_hash,
+                args: None,
                 is_test: true,
                 is_bench: true,
             },
@@ -1066,6 +1092,8 @@ impl<'a> Query<'a> {
                 resolve_context!(self),
             )?,
             Indexed::Function(f) => {
+                let args = f.function.ast.args.len();
+
                 self.inner.queue.push_back(BuildEntry {
                     item_meta,
                     build: Build::Function(f.function),
@@ -1074,11 +1102,14 @@ impl<'a> Query<'a> {
 
                 PrivMetaKind::Function {
                     type_hash: self.pool.item_type_hash(item_meta.item),
+                    args: Some(args),
                     is_test: f.is_test,
                     is_bench: f.is_bench,
                 }
             }
             Indexed::InstanceFunction(f) => {
+                let args = f.function.ast.args.len();
+
                 self.inner.queue.push_back(BuildEntry {
                     item_meta,
                     build: Build::InstanceFunction(f),
@@ -1087,6 +1118,7 @@ impl<'a> Query<'a> {
 
                 PrivMetaKind::Function {
                     type_hash: self.pool.item_type_hash(item_meta.item),
+                    args: Some(args),
                     is_test: false,
                     is_bench: false,
                 }
@@ -1169,6 +1201,19 @@ impl<'a> Query<'a> {
 
                 PrivMetaKind::ConstFn { id: Id::new(id) }
             }
+            Indexed::Trait(t) => {
+                if used.is_unused() {
+                    self.inner.queue.push_back(BuildEntry {
+                        item_meta,
+                        build: Build::Unused,
+                        used,
+                    });
+                }
+
+                PrivMetaKind::Trait {
+                    functions: t.functions,
+                }
+            }
             Indexed::Import(import) => {
                 if !import.wildcard {
                     self.inner.queue.push_back(BuildEntry {
@@ -1441,6 +1486,8 @@ pub(crate) enum Indexed {
     Const(Const),
     /// A constant function.
     ConstFn(ConstFn),
+    /// A trait.
+    Trait(Trait),
     /// An import.
     Import(Import),
     /// An indexed module.
@@ -1559,11 +1606,39 @@ pub(crate) struct ConstFn {
     pub(crate) item_fn: Box<ast::ItemFn>,
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct Trait {
+    /// The functions declared by the trait.
+    pub(crate) functions: Arc<[TraitFunction]>,
+}
+
+/// A function declared by a trait.
+#[derive(Debug, Clone)]
+pub(crate) struct TraitFunction {
+    /// The name of the function.
+    pub(crate) name: Box<str>,
+    /// Where the function is declared.
+    pub(crate) location: Location,
+    /// The default implementation of the function, if any.
+    pub(crate) default: Option<Function>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TraitImpl {
+    /// The path to the trait being implemented.
+    pub(crate) trait_path: Box<ast::Path>,
+    /// The path to the type the trait is implemented for.
+    pub(crate) type_path: Box<ast::Path>,
+    /// The functions provided by the implementation.
+    pub(crate) functions: Vec<(ItemMeta, Function)>,
+}
+
 /// An entry in the build queue.
 #[derive(Debug, Clone)]
 pub(crate) enum Build {
     Function(Function),
     InstanceFunction(InstanceFunction),
+    TraitImpl(TraitImpl),
     Closure(Closure),
     AsyncBlock(AsyncBlock),
     Unused,
@@ -20,6 +20,14 @@ pub enum ConstValue {
     Bool(bool),
     /// An integer constant.
     Integer(i64),
+    /// An unsigned 16-bit integer constant.
+    U16(u16),
+    /// An unsigned 32-bit integer constant.
+    U32(u32),
+    /// An unsigned 64-bit integer constant.
+    U64(u64),
+    /// A signed 32-bit integer constant.
+    I32(i32),
     /// An float constant.
     Float(f64),
     /// A string constant designated by its slot.
@@ -51,6 +59,10 @@ impl ConstValue {
             Self::Char(c) => Value::Char(c),
             Self::Bool(b) => Value::Bool(b),
             Self::Integer(n) => Value::Integer(n),
+            Self::U16(n) => Value::U16(n),
+            Self::U32(n) => Value::U32(n),
+            Self::U64(n) => Value::U64(n),
+            Self::I32(n) => Value::I32(n),
             Self::Float(n) => Value::Float(n),
             Self::String(s) => Value::String(Shared::new(s)),
             Self::StaticString(s) => Value::StaticString(s),
@@ -107,6 +119,10 @@ impl ConstValue {
             Self::StaticString(..) => TypeInfo::StaticType(crate::runtime::STRING_TYPE),
             Self::Bytes(..) => TypeInfo::StaticType(crate::runtime::BYTES_TYPE),
             Self::Integer(..) => TypeInfo::StaticType(crate::runtime::INTEGER_TYPE),
+            Self::U16(..) => TypeInfo::StaticType(crate::runtime::U16_TYPE),
+            Self::U32(..) => TypeInfo::StaticType(crate::runtime::U32_TYPE),
+            Self::U64(..) => TypeInfo::StaticType(crate::runtime::U64_TYPE),
+            Self::I32(..) => TypeInfo::StaticType(crate::runtime::I32_TYPE),
             Self::Float(..) => TypeInfo::StaticType(crate::runtime::FLOAT_TYPE),
             Self::Vec(..) => TypeInfo::StaticType(crate::runtime::VEC_TYPE),
             Self::Tuple(..) => TypeInfo::StaticType(crate::runtime::TUPLE_TYPE),
@@ -124,6 +140,10 @@ impl FromValue for ConstValue {
             Value::Char(c) => Self::Char(c),
             Value::Bool(b) => Self::Bool(b),
             Value::Integer(n) => Self::Integer(n),
+            Value::U16(n) => Self::U16(n),
+            Value::U32(n) => Self::U32(n),
+            Value::U64(n) => Self::U64(n),
+            Value::I32(n) => Self::I32(n),
             Value::Float(f) => Self::Float(f),
             Value::String(s) => {
                 let s = s.take()?;
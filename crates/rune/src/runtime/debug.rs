@@ -18,6 +18,8 @@ pub struct DebugInfo {
     pub functions: HashMap<Hash, DebugSignature>,
     /// Reverse lookup of a function.
     pub functions_rev: HashMap<usize, Hash>,
+    /// Named variables, ordered by the instruction they are declared at.
+    pub variables: Vec<DebugVariable>,
 }
 
 impl DebugInfo {
@@ -32,6 +34,49 @@ impl DebugInfo {
         let signature = self.functions.get(&hash)?;
         Some((hash, signature))
     }
+
+    /// Get the offset of the first instruction in the function which contains
+    /// the given instruction pointer.
+    pub fn function_start(&self, ip: usize) -> Option<usize> {
+        self.functions_rev
+            .keys()
+            .copied()
+            .filter(|offset| *offset <= ip)
+            .max()
+    }
+
+    /// Get the named variables which are visible at the given instruction
+    /// pointer, ordered by their stack offset.
+    ///
+    /// A variable is visible from where it is declared until its stack slot is
+    /// re-used by another declaration. It is up to the caller to check that
+    /// the slot is still part of the stack.
+    pub fn variables_at(&self, ip: usize) -> Vec<&DebugVariable> {
+        let start = match self.function_start(ip) {
+            Some(start) => start,
+            None => return Vec::new(),
+        };
+
+        let mut variables = Vec::<&DebugVariable>::new();
+
+        for variable in &self.variables {
+            if variable.ip < start {
+                continue;
+            }
+
+            if variable.ip > ip {
+                break;
+            }
+
+            match variables.iter_mut().find(|v| v.offset == variable.offset) {
+                Some(existing) => *existing = variable,
+                None => variables.push(variable),
+            }
+        }
+
+        variables.sort_by_key(|v| v.offset);
+        variables
+    }
 }
 
 /// Debug information for every instruction.
@@ -65,6 +110,25 @@ impl DebugInst {
     }
 }
 
+/// Debug information on a named variable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct DebugVariable {
+    /// The name of the variable.
+    pub name: Box<str>,
+    /// The stack offset of the variable, relative to its call frame.
+    pub offset: usize,
+    /// The instruction pointer from which the variable is declared.
+    pub ip: usize,
+}
+
+impl DebugVariable {
+    /// Construct debug information on a new variable.
+    pub fn new(name: Box<str>, offset: usize, ip: usize) -> Self {
+        Self { name, offset, ip }
+    }
+}
+
 /// Debug information on function arguments.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DebugArgs {
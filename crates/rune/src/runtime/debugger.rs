@@ -0,0 +1,399 @@
+//! A breakpoint debugger for the virtual machine.
+//!
+//! The [Debugger] drives a [VmExecution] one instruction at a time and maps
+//! instructions back to source lines through the [DebugInst] spans of the
+//! unit. This requires the unit to be compiled with debug information, which
+//! is the default.
+//!
+//! ```
+//! use rune::{Context, Source, Sources, Vm};
+//! use rune::runtime::debugger::{Debugger, Stop};
+//! use std::sync::Arc;
+//!
+//! let context = Context::with_default_modules()?;
+//! let runtime = Arc::new(context.runtime());
+//!
+//! let mut sources = Sources::new();
+//! let id = sources.insert(Source::new("script", r#"
+//! pub fn main() {
+//!     let a = 1;
+//!     let b = a + 2;
+//!     b
+//! }
+//! "#));
+//!
+//! let unit = rune::prepare(&mut sources)
+//!     .with_context(&context)
+//!     .build()?;
+//!
+//! let mut vm = Vm::new(runtime, Arc::new(unit));
+//! let mut debugger = Debugger::new(vm.execute(["main"], ())?, &sources);
+//!
+//! // Lines are zero-based.
+//! assert!(debugger.set_breakpoint(id, 4));
+//! assert!(matches!(debugger.resume()?, Stop::Breakpoint));
+//!
+//! let frames = debugger.frames();
+//! let locals = debugger.locals(&frames[0]);
+//! assert_eq!(locals.len(), 2);
+//! assert_eq!(locals[0].0, "a");
+//! assert_eq!(locals[1].0, "b");
+//!
+//! assert!(matches!(debugger.resume()?, Stop::Complete(..)));
+//! # Ok::<_, rune::Error>(())
+//! ```
+
+use crate::collections::HashSet;
+use crate::runtime::debug::DebugSignature;
+use crate::runtime::{DebugInst, Value, Vm, VmError, VmExecution};
+use crate::{SourceId, Sources};
+
+/// A line in a source file. Lines are zero-based, like
+/// [Source::line_index][crate::Source::line_index].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct SourceLine {
+    /// The source the line belongs to.
+    pub source_id: SourceId,
+    /// The zero-based line number.
+    pub line: usize,
+}
+
+impl SourceLine {
+    /// Construct a new source line.
+    pub fn new(source_id: SourceId, line: usize) -> Self {
+        Self { source_id, line }
+    }
+}
+
+/// The reason why the debugger stopped.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Stop {
+    /// Execution reached a line with a breakpoint.
+    Breakpoint,
+    /// The requested step was completed.
+    Step,
+    /// Execution completed with the given value.
+    Complete(Value),
+}
+
+/// A call frame of the virtual machine being debugged.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct DebugFrame {
+    /// The instruction pointer the frame is currently at. For frames which are
+    /// not the innermost one, this is the call which is being executed.
+    pub ip: usize,
+    /// The source line the frame is currently at.
+    pub line: Option<SourceLine>,
+    /// The bottom of the stack for the frame.
+    pub stack_bottom: usize,
+    /// The top of the stack for the frame.
+    pub stack_top: usize,
+}
+
+/// How far to step.
+#[derive(Debug, Clone, Copy)]
+enum Mode {
+    /// Run until a breakpoint is hit.
+    Resume,
+    /// Stop at the next line, entering calls.
+    Into,
+    /// Stop at the next line in the current frame or its callers.
+    Over,
+    /// Stop once the current frame has returned.
+    Out,
+}
+
+/// Where the execution is at.
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    depth: usize,
+    line: Option<SourceLine>,
+}
+
+/// A debugger which steps through a virtual machine execution.
+pub struct Debugger<T = Vm>
+where
+    T: AsRef<Vm> + AsMut<Vm>,
+{
+    execution: VmExecution<T>,
+    /// The source line of each instruction.
+    lines: Vec<Option<SourceLine>>,
+    breakpoints: HashSet<SourceLine>,
+}
+
+impl<T> Debugger<T>
+where
+    T: AsRef<Vm> + AsMut<Vm>,
+{
+    /// Construct a debugger for the given execution. The sources are used to
+    /// map instructions to lines.
+    pub fn new(execution: VmExecution<T>, sources: &Sources) -> Self {
+        let lines = match execution.vm().unit().debug_info() {
+            Some(debug) => debug
+                .instructions
+                .iter()
+                .map(|inst| line_of(sources, inst))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Self {
+            execution,
+            lines,
+            breakpoints: HashSet::new(),
+        }
+    }
+
+    /// Access the execution being debugged.
+    pub fn execution(&self) -> &VmExecution<T> {
+        &self.execution
+    }
+
+    /// Mutably access the execution being debugged.
+    ///
+    /// This can be used to call into the virtual machine, like when
+    /// formatting values. Stepping the execution directly means that the
+    /// debugger won't stop at breakpoints.
+    pub fn execution_mut(&mut self) -> &mut VmExecution<T> {
+        &mut self.execution
+    }
+
+    /// Convert the debugger into the execution being debugged.
+    pub fn into_execution(self) -> VmExecution<T> {
+        self.execution
+    }
+
+    /// Set a breakpoint on the given zero-based line.
+    ///
+    /// Returns `false` if there are no instructions on the line, in which case
+    /// the breakpoint could never be hit and is not set.
+    pub fn set_breakpoint(&mut self, source_id: SourceId, line: usize) -> bool {
+        let line = SourceLine::new(source_id, line);
+
+        if !self.lines.contains(&Some(line)) {
+            return false;
+        }
+
+        self.breakpoints.insert(line);
+        true
+    }
+
+    /// Remove the breakpoint on the given line. Returns `true` if there was a
+    /// breakpoint to remove.
+    pub fn remove_breakpoint(&mut self, source_id: SourceId, line: usize) -> bool {
+        self.breakpoints.remove(&SourceLine::new(source_id, line))
+    }
+
+    /// Iterate over all breakpoints which are set.
+    pub fn breakpoints(&self) -> impl Iterator<Item = SourceLine> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    /// The source line that the execution is currently at.
+    pub fn line(&self) -> Option<SourceLine> {
+        self.line_at(self.execution.vm().ip())
+    }
+
+    /// The call frames of the execution, starting with the innermost one.
+    pub fn frames(&self) -> Vec<DebugFrame> {
+        let vm = self.execution.vm();
+        let stack = vm.stack();
+
+        let mut frames = vec![DebugFrame {
+            ip: vm.ip(),
+            line: self.line_at(vm.ip()),
+            stack_bottom: stack.stack_bottom(),
+            stack_top: stack.len(),
+        }];
+
+        let mut stack_top = stack.stack_bottom();
+
+        for frame in vm.call_frames().iter().rev() {
+            frames.push(DebugFrame {
+                ip: frame.ip(),
+                line: self.line_at(frame.ip()),
+                stack_bottom: frame.stack_bottom(),
+                stack_top,
+            });
+
+            stack_top = frame.stack_bottom();
+        }
+
+        frames
+    }
+
+    /// The signature of the function the given frame is in.
+    pub fn signature(&self, frame: &DebugFrame) -> Option<&DebugSignature> {
+        let debug = self.execution.vm().unit().debug_info()?;
+        let start = debug.function_start(frame.ip)?;
+        let (_, signature) = debug.function_at(start)?;
+        Some(signature)
+    }
+
+    /// The named local variables of the given frame, ordered by their stack
+    /// offset.
+    pub fn locals(&self, frame: &DebugFrame) -> Vec<(&str, &Value)> {
+        let vm = self.execution.vm();
+
+        let debug = match vm.unit().debug_info() {
+            Some(debug) => debug,
+            None => return Vec::new(),
+        };
+
+        let mut locals = Vec::new();
+
+        for variable in debug.variables_at(frame.ip) {
+            let index = frame.stack_bottom + variable.offset;
+
+            if index >= frame.stack_top {
+                continue;
+            }
+
+            if let Some(value) = vm.stack().get(index) {
+                locals.push((variable.name.as_ref(), value));
+            }
+        }
+
+        locals
+    }
+
+    /// Run until a breakpoint is hit or the execution completes.
+    pub fn resume(&mut self) -> Result<Stop, VmError> {
+        self.run(Mode::Resume)
+    }
+
+    /// Run until the next line is reached, entering any function calls.
+    pub fn step_into(&mut self) -> Result<Stop, VmError> {
+        self.run(Mode::Into)
+    }
+
+    /// Run until the next line in the current function is reached, stepping
+    /// over any function calls.
+    pub fn step_over(&mut self) -> Result<Stop, VmError> {
+        self.run(Mode::Over)
+    }
+
+    /// Run until the current function returns.
+    pub fn step_out(&mut self) -> Result<Stop, VmError> {
+        self.run(Mode::Out)
+    }
+
+    /// Asynchronously run until a breakpoint is hit or the execution
+    /// completes.
+    pub async fn async_resume(&mut self) -> Result<Stop, VmError> {
+        self.async_run(Mode::Resume).await
+    }
+
+    /// Asynchronously run until the next line is reached, entering any
+    /// function calls.
+    pub async fn async_step_into(&mut self) -> Result<Stop, VmError> {
+        self.async_run(Mode::Into).await
+    }
+
+    /// Asynchronously run until the next line in the current function is
+    /// reached, stepping over any function calls.
+    pub async fn async_step_over(&mut self) -> Result<Stop, VmError> {
+        self.async_run(Mode::Over).await
+    }
+
+    /// Asynchronously run until the current function returns.
+    pub async fn async_step_out(&mut self) -> Result<Stop, VmError> {
+        self.async_run(Mode::Out).await
+    }
+
+    fn run(&mut self, mode: Mode) -> Result<Stop, VmError> {
+        let start = self.position();
+        let mut previous = start;
+
+        loop {
+            if let Some(value) = self.execution.step()? {
+                return Ok(Stop::Complete(value));
+            }
+
+            let current = self.position();
+
+            if let Some(stop) = self.should_stop(mode, start, previous, current) {
+                return Ok(stop);
+            }
+
+            previous = current;
+        }
+    }
+
+    async fn async_run(&mut self, mode: Mode) -> Result<Stop, VmError> {
+        let start = self.position();
+        let mut previous = start;
+
+        loop {
+            if let Some(value) = self.execution.async_step().await? {
+                return Ok(Stop::Complete(value));
+            }
+
+            let current = self.position();
+
+            if let Some(stop) = self.should_stop(mode, start, previous, current) {
+                return Ok(stop);
+            }
+
+            previous = current;
+        }
+    }
+
+    /// Test if execution should stop after moving from `previous` to
+    /// `current`, given the position it started stepping from.
+    fn should_stop(
+        &self,
+        mode: Mode,
+        start: Position,
+        previous: Position,
+        current: Position,
+    ) -> Option<Stop> {
+        let line = current.line?;
+
+        // NB: a breakpoint is only hit when entering its line, so that every
+        // instruction on the line doesn't stop the execution.
+        let entered = previous.depth != current.depth || previous.line != current.line;
+
+        if entered && self.breakpoints.contains(&line) {
+            return Some(Stop::Breakpoint);
+        }
+
+        let new_line = current.line != start.line;
+
+        let done = match mode {
+            Mode::Resume => false,
+            Mode::Into => current.depth != start.depth || new_line,
+            Mode::Over => current.depth < start.depth || (current.depth == start.depth && new_line),
+            Mode::Out => current.depth < start.depth,
+        };
+
+        if done {
+            Some(Stop::Step)
+        } else {
+            None
+        }
+    }
+
+    fn position(&self) -> Position {
+        let vm = self.execution.vm();
+
+        Position {
+            depth: vm.call_frames().len(),
+            line: self.line_at(vm.ip()),
+        }
+    }
+
+    fn line_at(&self, ip: usize) -> Option<SourceLine> {
+        self.lines.get(ip).copied().flatten()
+    }
+}
+
+/// Get the source line of an instruction.
+fn line_of(sources: &Sources, inst: &DebugInst) -> Option<SourceLine> {
+    let source = sources.get(inst.source_id)?;
+    let line = source.line_index(inst.span.start.into_usize());
+    Some(SourceLine::new(inst.source_id, line))
+}
@@ -187,7 +187,7 @@ impl FormatSpec {
         value: &Value,
         out: &mut String,
         buf: &mut String,
-        caller: impl ProtocolCaller,
+        caller: &mut impl ProtocolCaller,
     ) -> Result<(), VmError> {
         match value {
             Value::Char(c) => {
@@ -226,7 +226,7 @@ impl FormatSpec {
         value: &Value,
         out: &mut String,
         buf: &mut String,
-        caller: impl ProtocolCaller,
+        caller: &mut impl ProtocolCaller,
     ) -> Result<(), VmError> {
         match value {
             Value::String(s) => {
@@ -341,7 +341,7 @@ impl FormatSpec {
         value: &Value,
         out: &mut String,
         buf: &mut String,
-        caller: impl ProtocolCaller,
+        caller: &mut impl ProtocolCaller,
     ) -> Result<(), VmError> {
         match self.format_type {
             Type::Display => self.format_display(value, out, buf, caller)?,
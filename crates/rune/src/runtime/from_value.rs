@@ -384,18 +384,16 @@ impl FromValue for char {
     }
 }
 
-impl FromValue for i64 {
-    fn from_value(value: Value) -> Result<Self, VmError> {
-        value.into_integer()
-    }
-}
-
 macro_rules! impl_number {
     ($ty:ty) => {
         impl FromValue for $ty {
             fn from_value(value: Value) -> Result<Self, VmError> {
                 use std::convert::TryInto as _;
-                let integer = value.into_integer()?;
+
+                let integer = match value.as_wide_integer() {
+                    Some(integer) => integer,
+                    None => return Err(VmError::expected::<i64>(value.type_info()?)),
+                };
 
                 match integer.try_into() {
                     Ok(number) => Ok(number),
@@ -417,6 +415,7 @@ impl_number!(usize);
 impl_number!(i8);
 impl_number!(i16);
 impl_number!(i32);
+impl_number!(i64);
 impl_number!(i128);
 impl_number!(isize);
 
@@ -823,6 +823,43 @@ pub enum Inst {
         /// The slot to test against.
         slot: usize,
     },
+    /// Test if the top of the stack is an integer, character or byte which is
+    /// contained in the given range. Values of any other type, or of a
+    /// different type than the bounds of the range, never match.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <boolean>
+    /// ```
+    MatchRange {
+        /// The start of the range.
+        start: Option<InstValue>,
+        /// The end of the range.
+        end: Option<InstValue>,
+        /// The limits of the range.
+        limits: InstRangeLimits,
+    },
+    /// Check that the function argument at the given stack offset has the
+    /// given type, erroring with [VmErrorKind::BadArgumentType] if it
+    /// doesn't.
+    ///
+    /// [VmErrorKind::BadArgumentType]: crate::runtime::VmErrorKind::BadArgumentType
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// =>
+    /// ```
+    CheckArgType {
+        /// The offset of the argument on the stack.
+        offset: usize,
+        /// The index of the argument.
+        arg: usize,
+        /// The type hash the argument is expected to have.
+        hash: Hash,
+    },
     /// Test that the top of the stack has the given type.
     ///
     /// # Operation
@@ -1235,6 +1272,26 @@ impl fmt::Display for Inst {
             Self::EqBytes { slot } => {
                 write!(fmt, "eq-bytes slot={}", slot)?;
             }
+            Self::MatchRange { start, end, limits } => {
+                write!(fmt, "match-range")?;
+
+                if let Some(start) = start {
+                    write!(fmt, " start={}", start)?;
+                }
+
+                if let Some(end) = end {
+                    write!(fmt, " end={}", end)?;
+                }
+
+                write!(fmt, " limits={}", limits)?;
+            }
+            Self::CheckArgType { offset, arg, hash } => {
+                write!(
+                    fmt,
+                    "check-arg-type offset={}, arg={}, hash={}",
+                    offset, arg, hash
+                )?;
+            }
             Self::MatchType { hash } => {
                 write!(fmt, "match-type hash={}", hash,)?;
             }
@@ -1616,6 +1673,14 @@ pub enum InstValue {
     Char(char),
     /// An integer.
     Integer(i64),
+    /// An unsigned 16-bit integer.
+    U16(u16),
+    /// An unsigned 32-bit integer.
+    U32(u32),
+    /// An unsigned 64-bit integer.
+    U64(u64),
+    /// A signed 32-bit integer.
+    I32(i32),
     /// A float.
     Float(f64),
     /// A type hash.
@@ -1631,6 +1696,10 @@ impl InstValue {
             Self::Byte(v) => Value::Byte(v),
             Self::Char(v) => Value::Char(v),
             Self::Integer(v) => Value::Integer(v),
+            Self::U16(v) => Value::U16(v),
+            Self::U32(v) => Value::U32(v),
+            Self::U64(v) => Value::U64(v),
+            Self::I32(v) => Value::I32(v),
             Self::Float(v) => Value::Float(v),
             Self::Type(v) => Value::Type(v),
         }
@@ -1651,6 +1720,10 @@ impl fmt::Display for InstValue {
             }
             Self::Char(v) => write!(f, "{:?}", v)?,
             Self::Integer(v) => write!(f, "{}", v)?,
+            Self::U16(v) => write!(f, "{}u16", v)?,
+            Self::U32(v) => write!(f, "{}u32", v)?,
+            Self::U64(v) => write!(f, "{}u64", v)?,
+            Self::I32(v) => write!(f, "{}i32", v)?,
             Self::Float(v) => write!(f, "{}", v)?,
             Self::Type(v) => write!(f, "{}", v)?,
         }
@@ -1,9 +1,11 @@
 use crate::compile::Named;
 use crate::runtime::{
-    FromValue, Function, Mut, RawMut, RawRef, RawStr, Ref, ToValue, UnsafeFromValue, Value,
-    VmError, VmErrorKind,
+    EnvProtocolCaller, FromValue, Function, Mut, RawMut, RawRef, RawStr, Ref, ToValue,
+    UnsafeFromValue, Value, Vec, VmError, VmErrorKind,
 };
 use crate::InstallWith;
+use std::cmp;
+use std::collections::VecDeque;
 use std::fmt;
 use std::iter;
 use std::vec;
@@ -335,6 +337,253 @@ impl Iterator {
         let sum = Sum { iter: self.iter };
         sum.resolve()
     }
+
+    /// Zip this iterator together with another, producing pairs of values
+    /// until either of them is exhausted.
+    pub fn zip(self, other: Value) -> Result<Self, VmError> {
+        let other = other.into_iter()?;
+
+        Ok(Self {
+            iter: IterRepr::Zip(Box::new(Zip {
+                a: self.iter,
+                b: other.iter,
+            })),
+        })
+    }
+
+    /// Take elements from the iterator while the given predicate holds.
+    pub fn take_while(self, predicate: Function) -> Self {
+        Self {
+            iter: IterRepr::TakeWhile(Box::new(TakeWhile {
+                iter: self.iter,
+                predicate,
+                done: false,
+            })),
+        }
+    }
+
+    /// Skip elements from the iterator while the given predicate holds.
+    pub fn skip_while(self, predicate: Function) -> Self {
+        Self {
+            iter: IterRepr::SkipWhile(Box::new(SkipWhile {
+                iter: self.iter,
+                predicate,
+                done: false,
+            })),
+        }
+    }
+
+    /// Step over the iterator by the given amount, always starting with the
+    /// first element.
+    pub fn step_by(self, step: usize) -> Result<Self, VmError> {
+        if step == 0 {
+            return Err(VmError::panic("step must be non-zero"));
+        }
+
+        Ok(Self {
+            iter: IterRepr::StepBy(Box::new(StepBy {
+                iter: self.iter,
+                step: step - 1,
+                first_take: true,
+            })),
+        })
+    }
+
+    /// Produce overlapping vectors of `size` consecutive elements.
+    pub fn windows(self, size: usize) -> Result<Self, VmError> {
+        if size == 0 {
+            return Err(VmError::panic("window size must be non-zero"));
+        }
+
+        Ok(Self {
+            iter: IterRepr::Windows(Box::new(Windows {
+                iter: self.iter,
+                size,
+                window: VecDeque::with_capacity(size),
+            })),
+        })
+    }
+
+    /// Produce non-overlapping vectors of up to `size` elements.
+    pub fn chunks(self, size: usize) -> Result<Self, VmError> {
+        if size == 0 {
+            return Err(VmError::panic("chunk size must be non-zero"));
+        }
+
+        Ok(Self {
+            iter: IterRepr::Chunks(Box::new(Chunks {
+                iter: self.iter,
+                size,
+            })),
+        })
+    }
+
+    /// Produce a running state over the iterator.
+    ///
+    /// The function is called with the current state and the next element,
+    /// and returns `Some(state)` to produce the new state or `None` to stop.
+    pub fn scan(self, state: Value, f: Function) -> Self {
+        Self {
+            iter: IterRepr::Scan(Box::new(Scan {
+                iter: self.iter,
+                state: Some(state),
+                f,
+            })),
+        }
+    }
+
+    /// Flatten an iterator of iterables.
+    pub fn flatten(self) -> Self {
+        Self {
+            iter: IterRepr::Flatten(Box::new(FlatMap {
+                map: Fuse::new(self.iter),
+                frontiter: None,
+                backiter: None,
+            })),
+        }
+    }
+
+    /// Call the given function with each element before passing it on.
+    pub fn inspect(self, f: Function) -> Self {
+        Self {
+            iter: IterRepr::Inspect(Box::new(Inspect { iter: self.iter, f })),
+        }
+    }
+
+    /// Repeat the iterator endlessly.
+    ///
+    /// Elements are buffered on the first pass, so the underlying iterator is
+    /// only consumed once.
+    pub fn cycle(self) -> Self {
+        Self {
+            iter: IterRepr::Cycle(Box::new(Cycle {
+                iter: Some(self.iter),
+                buffer: vec::Vec::new(),
+                index: 0,
+            })),
+        }
+    }
+
+    /// Remove consecutive repeated elements.
+    pub fn dedup(self) -> Self {
+        Self {
+            iter: IterRepr::Dedup(Box::new(Dedup {
+                iter: self.iter,
+                last: None,
+            })),
+        }
+    }
+
+    /// Consume the iterator, returning the last element.
+    pub fn last(mut self) -> Result<Option<Value>, VmError> {
+        let mut last = None;
+
+        while let Some(value) = self.next()? {
+            last = Some(value);
+        }
+
+        Ok(last)
+    }
+
+    /// Get the `n`th element of the iterator, consuming all elements before
+    /// it.
+    pub fn nth(&mut self, n: usize) -> Result<Option<Value>, VmError> {
+        for _ in 0..n {
+            if self.next()?.is_none() {
+                return Ok(None);
+            }
+        }
+
+        self.next()
+    }
+
+    /// Find the index of the first element matching the given predicate.
+    pub fn position(mut self, find: Function) -> Result<Option<usize>, VmError> {
+        let mut index = 0;
+
+        while let Some(value) = self.next()? {
+            if find.call::<_, bool>((value,))? {
+                return Ok(Some(index));
+            }
+
+            index += 1;
+        }
+
+        Ok(None)
+    }
+
+    /// Find the minimum element of the iterator.
+    ///
+    /// If several elements are equally minimum, the first one is returned.
+    pub fn min(self) -> Result<Option<Value>, VmError> {
+        self.select_by_key(None, cmp::Ordering::Less)
+    }
+
+    /// Find the maximum element of the iterator.
+    ///
+    /// If several elements are equally maximum, the last one is returned.
+    pub fn max(self) -> Result<Option<Value>, VmError> {
+        self.select_by_key(None, cmp::Ordering::Greater)
+    }
+
+    /// Find the element which gives the minimum value from the given key
+    /// function.
+    pub fn min_by_key(self, key: Function) -> Result<Option<Value>, VmError> {
+        self.select_by_key(Some(key), cmp::Ordering::Less)
+    }
+
+    /// Find the element which gives the maximum value from the given key
+    /// function.
+    pub fn max_by_key(self, key: Function) -> Result<Option<Value>, VmError> {
+        self.select_by_key(Some(key), cmp::Ordering::Greater)
+    }
+
+    /// Split the iterator into two vectors, the first with all elements for
+    /// which the predicate holds and the second with the rest.
+    pub fn partition(mut self, predicate: Function) -> Result<(Vec, Vec), VmError> {
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+
+        while let Some(value) = self.next()? {
+            if predicate.call::<_, bool>((value.clone(),))? {
+                left.push(value);
+            } else {
+                right.push(value);
+            }
+        }
+
+        Ok((left, right))
+    }
+
+    /// Select the element whose key compares as `ordering` against all
+    /// others, keeping the first minimum and the last maximum like Rust does.
+    fn select_by_key(
+        mut self,
+        key: Option<Function>,
+        ordering: cmp::Ordering,
+    ) -> Result<Option<Value>, VmError> {
+        let key_of = |value: &Value| match &key {
+            Some(key) => key.call::<_, Value>((value.clone(),)),
+            None => Ok(value.clone()),
+        };
+
+        let mut selected = match self.next()? {
+            Some(value) => (key_of(&value)?, value),
+            None => return Ok(None),
+        };
+
+        while let Some(value) = self.next()? {
+            let k = key_of(&value)?;
+
+            match Value::cmp(&k, &selected.0)? {
+                cmp::Ordering::Equal if ordering == cmp::Ordering::Greater => selected = (k, value),
+                o if o == ordering => selected = (k, value),
+                _ => (),
+            }
+        }
+
+        Ok(Some(selected.1))
+    }
 }
 
 impl fmt::Debug for Iterator {
@@ -397,6 +646,17 @@ enum IterRepr {
     Skip(Box<Skip<Self>>),
     Take(Box<Take<Self>>),
     Peekable(Box<Peekable<Self>>),
+    Zip(Box<Zip<Self, Self>>),
+    TakeWhile(Box<TakeWhile<Self>>),
+    SkipWhile(Box<SkipWhile<Self>>),
+    StepBy(Box<StepBy<Self>>),
+    Windows(Box<Windows<Self>>),
+    Chunks(Box<Chunks<Self>>),
+    Scan(Box<Scan<Self>>),
+    Flatten(Box<FlatMap<Self>>),
+    Inspect(Box<Inspect<Self>>),
+    Cycle(Box<Cycle<Self>>),
+    Dedup(Box<Dedup<Self>>),
     Empty,
     Once(Option<Value>),
 }
@@ -416,6 +676,17 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.is_double_ended(),
             Self::Take(iter) => iter.is_double_ended(),
             Self::Peekable(iter) => iter.is_double_ended(),
+            Self::Zip(iter) => iter.is_double_ended(),
+            Self::TakeWhile(iter) => iter.is_double_ended(),
+            Self::SkipWhile(iter) => iter.is_double_ended(),
+            Self::StepBy(iter) => iter.is_double_ended(),
+            Self::Windows(iter) => iter.is_double_ended(),
+            Self::Chunks(iter) => iter.is_double_ended(),
+            Self::Scan(iter) => iter.is_double_ended(),
+            Self::Flatten(iter) => iter.is_double_ended(),
+            Self::Inspect(iter) => iter.is_double_ended(),
+            Self::Cycle(iter) => iter.is_double_ended(),
+            Self::Dedup(iter) => iter.is_double_ended(),
             Self::Empty => true,
             Self::Once(..) => true,
         }
@@ -435,6 +706,17 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.size_hint(),
             Self::Take(iter) => iter.size_hint(),
             Self::Peekable(iter) => iter.size_hint(),
+            Self::Zip(iter) => iter.size_hint(),
+            Self::TakeWhile(iter) => iter.size_hint(),
+            Self::SkipWhile(iter) => iter.size_hint(),
+            Self::StepBy(iter) => iter.size_hint(),
+            Self::Windows(iter) => iter.size_hint(),
+            Self::Chunks(iter) => iter.size_hint(),
+            Self::Scan(iter) => iter.size_hint(),
+            Self::Flatten(iter) => iter.size_hint(),
+            Self::Inspect(iter) => iter.size_hint(),
+            Self::Cycle(iter) => iter.size_hint(),
+            Self::Dedup(iter) => iter.size_hint(),
             Self::Empty => (0, Some(0)),
             Self::Once(..) => (1, Some(1)),
         }
@@ -453,6 +735,17 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.next(),
             Self::Take(iter) => iter.next(),
             Self::Peekable(iter) => iter.next(),
+            Self::Zip(iter) => iter.next(),
+            Self::TakeWhile(iter) => iter.next(),
+            Self::SkipWhile(iter) => iter.next(),
+            Self::StepBy(iter) => iter.next(),
+            Self::Windows(iter) => iter.next(),
+            Self::Chunks(iter) => iter.next(),
+            Self::Scan(iter) => iter.next(),
+            Self::Flatten(iter) => iter.next(),
+            Self::Inspect(iter) => iter.next(),
+            Self::Cycle(iter) => iter.next(),
+            Self::Dedup(iter) => iter.next(),
             Self::Empty => Ok(None),
             Self::Once(v) => Ok(v.take()),
         }
@@ -474,6 +767,17 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.next_back(),
             Self::Take(iter) => iter.next_back(),
             Self::Peekable(iter) => iter.next_back(),
+            Self::Zip(iter) => iter.next_back(),
+            Self::TakeWhile(iter) => iter.next_back(),
+            Self::SkipWhile(iter) => iter.next_back(),
+            Self::StepBy(iter) => iter.next_back(),
+            Self::Windows(iter) => iter.next_back(),
+            Self::Chunks(iter) => iter.next_back(),
+            Self::Scan(iter) => iter.next_back(),
+            Self::Flatten(iter) => iter.next_back(),
+            Self::Inspect(iter) => iter.next_back(),
+            Self::Cycle(iter) => iter.next_back(),
+            Self::Dedup(iter) => iter.next_back(),
             Self::Empty => Ok(None),
             Self::Once(v) => Ok(v.take()),
         }
@@ -494,6 +798,17 @@ impl fmt::Debug for IterRepr {
             Self::Skip(iter) => write!(f, "{:?}", iter),
             Self::Take(iter) => write!(f, "{:?}", iter),
             Self::Peekable(iter) => write!(f, "{:?}", iter),
+            Self::Zip(iter) => write!(f, "{:?}", iter),
+            Self::TakeWhile(iter) => write!(f, "{:?}", iter),
+            Self::SkipWhile(iter) => write!(f, "{:?}", iter),
+            Self::StepBy(iter) => write!(f, "{:?}", iter),
+            Self::Windows(iter) => write!(f, "{:?}", iter),
+            Self::Chunks(iter) => write!(f, "{:?}", iter),
+            Self::Scan(iter) => write!(f, "{:?}", iter),
+            Self::Flatten(iter) => write!(f, "{:?}", iter),
+            Self::Inspect(iter) => write!(f, "{:?}", iter),
+            Self::Cycle(iter) => write!(f, "{:?}", iter),
+            Self::Dedup(iter) => write!(f, "{:?}", iter),
             Self::Empty => write!(f, "std::iter::Empty"),
             Self::Once(..) => write!(f, "std::iter::Once"),
         }
@@ -1107,6 +1422,516 @@ where
     }
 }
 
+/// Error raised when calling `next_back` on an iterator which isn't
+/// double-ended.
+fn not_double_ended(iter: &dyn fmt::Debug) -> VmError {
+    VmError::panic(format!("`{:?}` is not a double-ended iterator", iter))
+}
+
+#[derive(Debug)]
+struct Zip<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> RuneIterator for Zip<A, B>
+where
+    A: RuneIterator,
+    B: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+
+        let lower = cmp::min(a_lower, b_lower);
+
+        let upper = match (a_upper, b_upper) {
+            (Some(x), Some(y)) => Some(cmp::min(x, y)),
+            (Some(x), None) => Some(x),
+            (None, Some(y)) => Some(y),
+            (None, None) => None,
+        };
+
+        (lower, upper)
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let a = match self.a.next()? {
+            Some(a) => a,
+            None => return Ok(None),
+        };
+
+        let b = match self.b.next()? {
+            Some(b) => b,
+            None => return Ok(None),
+        };
+
+        Ok(Some((a, b).to_value()?))
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
+#[derive(Debug)]
+struct TakeWhile<I> {
+    iter: I,
+    predicate: Function,
+    done: bool,
+}
+
+impl<I> RuneIterator for TakeWhile<I>
+where
+    I: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        let (_, upper) = self.iter.size_hint();
+        (0, upper)
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if self.done {
+            return Ok(None);
+        }
+
+        if let Some(value) = self.iter.next()? {
+            if self.predicate.call::<_, bool>((value.clone(),))? {
+                return Ok(Some(value));
+            }
+        }
+
+        self.done = true;
+        Ok(None)
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
+#[derive(Debug)]
+struct SkipWhile<I> {
+    iter: I,
+    predicate: Function,
+    done: bool,
+}
+
+impl<I> RuneIterator for SkipWhile<I>
+where
+    I: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+
+        if self.done {
+            (lower, upper)
+        } else {
+            (0, upper)
+        }
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if self.done {
+            return self.iter.next();
+        }
+
+        while let Some(value) = self.iter.next()? {
+            if !self.predicate.call::<_, bool>((value.clone(),))? {
+                self.done = true;
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
+#[derive(Debug)]
+struct StepBy<I> {
+    iter: I,
+    /// The number of elements to skip between each produced element.
+    step: usize,
+    first_take: bool,
+}
+
+impl<I> RuneIterator for StepBy<I>
+where
+    I: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let step = self.step + 1;
+
+        let count = |n: usize| {
+            if self.first_take {
+                if n == 0 {
+                    0
+                } else {
+                    1 + (n - 1) / step
+                }
+            } else {
+                n / step
+            }
+        };
+
+        (count(lower), upper.map(count))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if self.first_take {
+            self.first_take = false;
+            return self.iter.next();
+        }
+
+        for _ in 0..self.step {
+            if self.iter.next()?.is_none() {
+                return Ok(None);
+            }
+        }
+
+        self.iter.next()
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
+#[derive(Debug)]
+struct Windows<I> {
+    iter: I,
+    size: usize,
+    window: VecDeque<Value>,
+}
+
+impl<I> RuneIterator for Windows<I>
+where
+    I: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        // Elements needed before the first window can be produced.
+        let missing = self.size.saturating_sub(self.window.len());
+
+        if self.window.len() < self.size {
+            let count = |n: usize| (n + 1).saturating_sub(missing);
+            (count(lower), upper.map(count))
+        } else {
+            (lower, upper)
+        }
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if self.window.len() == self.size {
+            self.window.pop_front();
+        }
+
+        while self.window.len() < self.size {
+            match self.iter.next()? {
+                Some(value) => self.window.push_back(value),
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(Value::vec(self.window.iter().cloned().collect())))
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
+#[derive(Debug)]
+struct Chunks<I> {
+    iter: I,
+    size: usize,
+}
+
+impl<I> RuneIterator for Chunks<I>
+where
+    I: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let count = |n: usize| n / self.size + usize::from(n % self.size != 0);
+        (count(lower), upper.map(count))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let mut chunk = vec::Vec::with_capacity(self.size);
+
+        while chunk.len() < self.size {
+            match self.iter.next()? {
+                Some(value) => chunk.push(value),
+                None => break,
+            }
+        }
+
+        if chunk.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Value::vec(chunk)))
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
+#[derive(Debug)]
+struct Scan<I> {
+    iter: I,
+    /// The current state, or `None` once the scan function stopped it.
+    state: Option<Value>,
+    f: Function,
+}
+
+impl<I> RuneIterator for Scan<I>
+where
+    I: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.state.is_none() {
+            return (0, Some(0));
+        }
+
+        let (_, upper) = self.iter.size_hint();
+        (0, upper)
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let state = match self.state.take() {
+            Some(state) => state,
+            None => return Ok(None),
+        };
+
+        let value = match self.iter.next()? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        let state = self.f.call::<_, Option<Value>>((state, value))?;
+        self.state = state.clone();
+        Ok(state)
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
+#[derive(Debug)]
+struct Inspect<I> {
+    iter: I,
+    f: Function,
+}
+
+impl<I> RuneIterator for Inspect<I>
+where
+    I: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        self.iter.is_double_ended()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if let Some(value) = self.iter.next()? {
+            self.f.call::<_, ()>((value.clone(),))?;
+            return Ok(Some(value));
+        }
+
+        Ok(None)
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        if let Some(value) = self.iter.next_back()? {
+            self.f.call::<_, ()>((value.clone(),))?;
+            return Ok(Some(value));
+        }
+
+        Ok(None)
+    }
+}
+
+#[derive(Debug)]
+struct Cycle<I> {
+    /// The iterator being cycled, until it has been exhausted once.
+    iter: Option<I>,
+    /// Elements seen during the first pass.
+    buffer: vec::Vec<Value>,
+    index: usize,
+}
+
+impl<I> RuneIterator for Cycle<I>
+where
+    I: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.iter {
+            Some(iter) => match iter.size_hint() {
+                (0, Some(0)) if self.buffer.is_empty() => (0, Some(0)),
+                (0, _) if self.buffer.is_empty() => (0, None),
+                _ => (usize::MAX, None),
+            },
+            None if self.buffer.is_empty() => (0, Some(0)),
+            None => (usize::MAX, None),
+        }
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if let Some(iter) = &mut self.iter {
+            if let Some(value) = iter.next()? {
+                self.buffer.push(value.clone());
+                return Ok(Some(value));
+            }
+
+            self.iter = None;
+        }
+
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+
+        let value = self.buffer[self.index].clone();
+        self.index = (self.index + 1) % self.buffer.len();
+        Ok(Some(value))
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
+#[derive(Debug)]
+struct Dedup<I> {
+    iter: I,
+    last: Option<Value>,
+}
+
+impl<I> RuneIterator for Dedup<I>
+where
+    I: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+
+        if self.last.is_some() {
+            (0, upper)
+        } else {
+            (cmp::min(lower, 1), upper)
+        }
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        while let Some(value) = self.iter.next()? {
+            if let Some(last) = &self.last {
+                if Value::value_ptr_eq(&mut EnvProtocolCaller, last, &value)? {
+                    continue;
+                }
+            }
+
+            self.last = Some(value.clone());
+            return Ok(Some(value));
+        }
+
+        Ok(None)
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(not_double_ended(self))
+    }
+}
+
 struct Product<I>
 where
     I: RuneIterator,
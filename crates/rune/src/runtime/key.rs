@@ -1,7 +1,9 @@
+use crate::runtime::vm::CallResult;
 use crate::runtime::{
-    Bytes, FromValue, Object, Shared, StaticString, ToValue, Tuple, TypeInfo, Value, Variant,
-    VariantData, VariantRtti, Vec, VmError, VmErrorKind,
+    Bytes, EnvProtocolCaller, FromValue, Object, Protocol, ProtocolCaller, Shared, StaticString,
+    ToValue, Tuple, TypeInfo, Value, Variant, VariantData, VariantRtti, Vec, VmError, VmErrorKind,
 };
+use crate::Hash;
 use serde::{de, ser};
 use std::cmp;
 use std::fmt;
@@ -22,6 +24,14 @@ pub enum Key {
     Bool(bool),
     /// An integer constant.
     Integer(i64),
+    /// An unsigned 16-bit integer constant.
+    U16(u16),
+    /// An unsigned 32-bit integer constant.
+    U32(u32),
+    /// An unsigned 64-bit integer constant.
+    U64(u64),
+    /// A signed 32-bit integer constant.
+    I32(i32),
     /// A string constant designated by its slot.
     String(StringKey),
     /// A byte string.
@@ -34,10 +44,16 @@ pub enum Key {
     Option(Option<Box<Key>>),
     /// A variant.
     Variant(VariantKey),
+    /// A value which implements the [Protocol::HASH] protocol.
+    Hashed(HashedKey),
 }
 
 impl Key {
     /// Convert a value reference into a key.
+    ///
+    /// Values which aren't built in are hashed through [Protocol::HASH],
+    /// which can only be called inside of a virtual machine. Outside of one
+    /// such values result in [VmErrorKind::KeyNotSupported].
     pub fn from_value(value: &Value) -> Result<Self, VmError> {
         return Ok(match value {
             Value::Unit => Self::Unit,
@@ -45,6 +61,10 @@ impl Key {
             Value::Char(c) => Self::Char(*c),
             Value::Bool(b) => Self::Bool(*b),
             Value::Integer(n) => Self::Integer(*n),
+            Value::U16(n) => Self::U16(*n),
+            Value::U32(n) => Self::U32(*n),
+            Value::U64(n) => Self::U64(*n),
+            Value::I32(n) => Self::I32(*n),
             Value::String(s) => {
                 let s = s.borrow_ref()?;
                 Self::String(StringKey::String((**s).into()))
@@ -88,11 +108,7 @@ impl Key {
                     data,
                 })
             }
-            value => {
-                return Err(VmError::from(VmErrorKind::KeyNotSupported {
-                    actual: value.type_info()?,
-                }))
-            }
+            value => Key::Hashed(HashedKey::from_value(value)?),
         });
 
         fn tuple_from_value(tuple: &Tuple) -> Result<Box<[Key]>, VmError> {
@@ -128,6 +144,10 @@ impl Key {
             Self::Char(c) => Value::Char(c),
             Self::Bool(b) => Value::Bool(b),
             Self::Integer(n) => Value::Integer(n),
+            Self::U16(n) => Value::U16(n),
+            Self::U32(n) => Value::U32(n),
+            Self::U64(n) => Value::U64(n),
+            Self::I32(n) => Value::I32(n),
             Self::String(s) => match s {
                 StringKey::String(s) => Value::String(Shared::new(String::from(s))),
                 StringKey::StaticString(s) => Value::StaticString(s),
@@ -158,6 +178,7 @@ impl Key {
                     data,
                 }))
             }
+            Self::Hashed(hashed) => hashed.value,
         };
 
         fn tuple_into_value(data: Box<[Key]>) -> Tuple {
@@ -199,10 +220,15 @@ impl Key {
             Self::String(..) => TypeInfo::StaticType(crate::runtime::STRING_TYPE),
             Self::Bytes(..) => TypeInfo::StaticType(crate::runtime::BYTES_TYPE),
             Self::Integer(..) => TypeInfo::StaticType(crate::runtime::INTEGER_TYPE),
+            Self::U16(..) => TypeInfo::StaticType(crate::runtime::U16_TYPE),
+            Self::U32(..) => TypeInfo::StaticType(crate::runtime::U32_TYPE),
+            Self::U64(..) => TypeInfo::StaticType(crate::runtime::U64_TYPE),
+            Self::I32(..) => TypeInfo::StaticType(crate::runtime::I32_TYPE),
             Self::Vec(..) => TypeInfo::StaticType(crate::runtime::VEC_TYPE),
             Self::Tuple(..) => TypeInfo::StaticType(crate::runtime::TUPLE_TYPE),
             Self::Option(..) => TypeInfo::StaticType(crate::runtime::OPTION_TYPE),
             Self::Variant(variant) => TypeInfo::Variant(variant.rtti.clone()),
+            Self::Hashed(hashed) => hashed.type_info.clone(),
         }
     }
 }
@@ -215,12 +241,17 @@ impl fmt::Debug for Key {
             Key::Char(c) => write!(f, "{:?}", c),
             Key::Bool(b) => write!(f, "{}", b),
             Key::Integer(n) => write!(f, "{}", n),
+            Key::U16(n) => write!(f, "{}u16", n),
+            Key::U32(n) => write!(f, "{}u32", n),
+            Key::U64(n) => write!(f, "{}u64", n),
+            Key::I32(n) => write!(f, "{}i32", n),
             Key::String(s) => write!(f, "{:?}", s),
             Key::Bytes(b) => write!(f, "{:?}", b),
             Key::Vec(vec) => write!(f, "{:?}", vec),
             Key::Tuple(tuple) => write!(f, "{:?}", tuple),
             Key::Option(opt) => write!(f, "{:?}", opt),
             Key::Variant(variant) => write!(f, "{:?}", variant),
+            Key::Hashed(hashed) => write!(f, "{:?}", hashed.value),
         }
     }
 }
@@ -261,6 +292,10 @@ impl ser::Serialize for Key {
             Self::Char(c) => serializer.serialize_char(*c),
             Self::Byte(c) => serializer.serialize_u8(*c),
             Self::Integer(integer) => serializer.serialize_i64(*integer),
+            Self::U16(integer) => serializer.serialize_u16(*integer),
+            Self::U32(integer) => serializer.serialize_u32(*integer),
+            Self::U64(integer) => serializer.serialize_u64(*integer),
+            Self::I32(integer) => serializer.serialize_i32(*integer),
             Self::String(string) => serializer.serialize_str(string.as_str()),
             Self::Bytes(bytes) => serializer.serialize_bytes(bytes),
             Self::Vec(vec) => {
@@ -283,6 +318,7 @@ impl ser::Serialize for Key {
             }
             Self::Option(option) => <Option<Box<Key>>>::serialize(option, serializer),
             Self::Variant(..) => Err(ser::Error::custom("cannot serialize variants")),
+            Self::Hashed(hashed) => hashed.value.serialize(serializer),
         }
     }
 }
@@ -397,7 +433,10 @@ impl<'de> de::Visitor<'de> for KeyVisitor {
     where
         E: de::Error,
     {
-        Ok(Key::Integer(v as i64))
+        match i64::try_from(v) {
+            Ok(v) => Ok(Key::Integer(v)),
+            Err(..) => Ok(Key::U64(v)),
+        }
     }
 
     #[inline]
@@ -556,3 +595,122 @@ pub enum VariantKeyData {
     /// An struct variant with a specific type hash.
     Struct(Box<[(Box<str>, Key)]>),
 }
+
+/// A value which is used as a key by hashing it through the [Protocol::HASH]
+/// protocol.
+///
+/// Equality between hashed keys is determined through the [Protocol::EQ]
+/// protocol, or structurally for values which support it. Since the
+/// collections keys are stored in can't report errors while comparing them,
+/// keys for which [Protocol::EQ] raises an error are considered to not be
+/// equal.
+#[derive(Clone)]
+pub struct HashedKey {
+    type_hash: Hash,
+    type_info: TypeInfo,
+    hash: i64,
+    value: Value,
+}
+
+impl HashedKey {
+    /// Construct a hashed key by calling the [Protocol::HASH] protocol on
+    /// the given value.
+    ///
+    /// Protocols can only be called inside of a virtual machine, so outside
+    /// of one no value is supported.
+    fn from_value(value: &Value) -> Result<Self, VmError> {
+        let type_hash = value.type_hash()?;
+        let type_info = value.type_info()?;
+
+        let hash = match EnvProtocolCaller.try_call_protocol_fn(Protocol::HASH, value.clone(), ()) {
+            Ok(CallResult::Ok(hash)) => i64::from_value(hash)?,
+            Ok(CallResult::Unsupported(..)) => {
+                return Err(VmError::from(VmErrorKind::KeyNotSupported {
+                    actual: type_info,
+                }));
+            }
+            Err(error) if matches!(error.kind(), VmErrorKind::MissingInterfaceEnvironment) => {
+                return Err(VmError::from(VmErrorKind::KeyNotSupported {
+                    actual: type_info,
+                }));
+            }
+            Err(error) => return Err(error),
+        };
+
+        Ok(Self {
+            type_hash,
+            type_info,
+            hash,
+            value: value.clone(),
+        })
+    }
+}
+
+impl cmp::PartialEq for HashedKey {
+    fn eq(&self, other: &Self) -> bool {
+        if self.type_hash != other.type_hash || self.hash != other.hash {
+            return false;
+        }
+
+        // NB: values which can't be compared, or raise an error while being
+        // compared, are never considered equal.
+        Value::value_ptr_eq(&mut EnvProtocolCaller, &self.value, &other.value).unwrap_or(false)
+    }
+}
+
+impl cmp::Eq for HashedKey {}
+
+impl hash::Hash for HashedKey {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.type_hash.hash(state);
+        self.hash.hash(state);
+    }
+}
+
+impl cmp::PartialOrd for HashedKey {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Hashed keys are ordered by their type and hash first. Keys with colliding
+/// hashes which aren't equal are ordered through the [Protocol::CMP] protocol,
+/// or by identity for values which can't be ordered.
+impl cmp::Ord for HashedKey {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        let ordering = (self.type_hash, self.hash).cmp(&(other.type_hash, other.hash));
+
+        if ordering != cmp::Ordering::Equal || self == other {
+            return ordering;
+        }
+
+        match Value::cmp(&self.value, &other.value) {
+            Ok(cmp::Ordering::Equal) | Err(..) => {
+                value_identity(&self.value).cmp(&value_identity(&other.value))
+            }
+            Ok(ordering) => ordering,
+        }
+    }
+}
+
+/// The identity of a hashed value, which is used to tell apart values which
+/// are neither equal nor ordered.
+fn value_identity(value: &Value) -> *const () {
+    match value {
+        Value::UnitStruct(value) => value.as_ptr(),
+        Value::TupleStruct(value) => value.as_ptr(),
+        Value::Struct(value) => value.as_ptr(),
+        Value::Variant(value) => value.as_ptr(),
+        Value::Object(value) => value.as_ptr(),
+        Value::Range(value) => value.as_ptr(),
+        Value::Function(value) => value.as_ptr(),
+        Value::Any(value) => value.as_ptr(),
+        _ => std::ptr::null(),
+    }
+}
+
+impl fmt::Debug for HashedKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.value)
+    }
+}
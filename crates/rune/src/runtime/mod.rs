@@ -9,6 +9,7 @@ mod bytes;
 mod call;
 mod const_value;
 pub mod debug;
+pub mod debugger;
 mod env;
 pub mod format;
 mod from_value;
@@ -30,6 +31,7 @@ mod raw_str;
 mod runtime_context;
 mod select;
 mod shared;
+mod sized_integer;
 mod stack;
 mod static_string;
 mod static_type;
@@ -84,13 +86,14 @@ pub use self::runtime_context::RuntimeContext;
 pub(crate) use self::runtime_context::{FunctionHandler, MacroHandler};
 pub use self::select::Select;
 pub use self::shared::{Mut, RawMut, RawRef, Ref, Shared, SharedPointerGuard};
+pub use self::sized_integer::SizedInteger;
 pub use self::stack::{Stack, StackError};
 pub use self::static_string::StaticString;
 pub use self::static_type::{
     StaticType, BOOL_TYPE, BYTES_TYPE, BYTE_TYPE, CHAR_TYPE, FLOAT_TYPE, FORMAT_TYPE,
-    FUNCTION_TYPE, FUTURE_TYPE, GENERATOR_STATE_TYPE, GENERATOR_TYPE, INTEGER_TYPE, ITERATOR_TYPE,
-    OBJECT_TYPE, OPTION_TYPE, RANGE_TYPE, RESULT_TYPE, STREAM_TYPE, STRING_TYPE, TUPLE_TYPE, TYPE,
-    UNIT_TYPE, VEC_TYPE,
+    FUNCTION_TYPE, FUTURE_TYPE, GENERATOR_STATE_TYPE, GENERATOR_TYPE, I32_TYPE, INTEGER_TYPE,
+    ITERATOR_TYPE, OBJECT_TYPE, OPTION_TYPE, RANGE_TYPE, RESULT_TYPE, STREAM_TYPE, STRING_TYPE,
+    TUPLE_TYPE, TYPE, U16_TYPE, U32_TYPE, U64_TYPE, UNIT_TYPE, VEC_TYPE,
 };
 pub use self::stream::Stream;
 pub use self::to_value::{ToValue, UnsafeToValue};
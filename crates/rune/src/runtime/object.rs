@@ -1,8 +1,8 @@
 use crate::collections::{btree_map, BTreeMap};
 use crate::compile::{ItemBuf, Named};
 use crate::runtime::{
-    FromValue, Iterator, Mut, RawMut, RawRef, RawStr, Ref, ToValue, UnsafeFromValue, Value, Vm,
-    VmError,
+    FromValue, Iterator, Mut, ProtocolCaller, RawMut, RawRef, RawStr, Ref, Shared, ToValue,
+    UnsafeFromValue, Value, VmError,
 };
 use crate::InstallWith;
 use std::borrow;
@@ -189,6 +189,20 @@ impl Object {
         self.inner.clear();
     }
 
+    /// Gets the given key's corresponding entry in the object for in-place
+    /// manipulation.
+    pub fn inner_entry(&mut self, key: String) -> btree_map::Entry<'_, String, Value> {
+        self.inner.entry(key)
+    }
+
+    /// Retains only the entries specified by the predicate.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&String, &mut Value) -> bool,
+    {
+        self.inner.retain(f);
+    }
+
     /// Convert into inner.
     pub fn into_inner(self) -> BTreeMap<String, Value> {
         self.inner
@@ -220,8 +234,12 @@ impl Object {
     }
 
     /// Value pointer equals implementation for an Object.
-    pub(crate) fn value_ptr_eq(vm: &mut Vm, a: &Self, b: &Self) -> Result<bool, VmError> {
-        map_ptr_eq(vm, &a.inner, &b.inner)
+    pub(crate) fn value_ptr_eq(
+        caller: &mut impl ProtocolCaller,
+        a: &Self,
+        b: &Self,
+    ) -> Result<bool, VmError> {
+        map_ptr_eq(caller, &a.inner, &b.inner)
     }
 
     /// Debug implementation for a struct. This assumes that all fields
@@ -286,6 +304,12 @@ impl FromValue for Object {
     }
 }
 
+impl FromValue for Shared<Object> {
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        value.into_object()
+    }
+}
+
 impl FromValue for Mut<Object> {
     fn from_value(value: Value) -> Result<Self, VmError> {
         let object = value.into_object()?;
@@ -357,7 +381,7 @@ impl fmt::Display for DebugStruct<'_> {
 
 /// Helper function two compare two hashmaps of values.
 pub(crate) fn map_ptr_eq<K>(
-    vm: &mut Vm,
+    caller: &mut impl ProtocolCaller,
     a: &BTreeMap<K, Value>,
     b: &BTreeMap<K, Value>,
 ) -> Result<bool, VmError>
@@ -375,7 +399,7 @@ where
             None => return Ok(false),
         };
 
-        if !Value::value_ptr_eq(vm, a, b)? {
+        if !Value::value_ptr_eq(caller, a, b)? {
             return Ok(false);
         }
     }
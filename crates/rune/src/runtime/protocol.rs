@@ -75,12 +75,58 @@ impl hash::Hash for Protocol {
 }
 
 impl Protocol {
+    /// Look up a protocol which a script can implement by declaring an
+    /// instance function with the same name, like `fn cmp(self, other)` in an
+    /// `impl` block.
+    pub(crate) fn from_script_name(name: &str) -> Option<Protocol> {
+        match name {
+            "partial_cmp" => Some(Self::PARTIAL_CMP),
+            "cmp" => Some(Self::CMP),
+            "hash" => Some(Self::HASH),
+            _ => None,
+        }
+    }
+
     /// Check two types for equality.
     pub const EQ: Protocol = Protocol {
         name: "eq",
         hash: Hash::new(0x418f5becbf885806),
     };
 
+    /// Hash a value so that it can be used as a key in a hash map or set.
+    ///
+    /// Values which are equal according to [Protocol::EQ] must produce the
+    /// same hash, so a type implementing this should also implement
+    /// [Protocol::EQ]. Keys for which [Protocol::EQ] raises an error are
+    /// considered to not be equal.
+    ///
+    /// Signature: `fn(self) -> int`.
+    pub const HASH: Protocol = Protocol {
+        name: "hash",
+        hash: Hash::new(0x2c6ea3f0b18d5a97),
+    };
+
+    /// Compare two values to establish a partial ordering between them.
+    ///
+    /// This is used by the comparison operators (`<`, `<=`, `>`, `>=`).
+    ///
+    /// Signature: `fn(self, other) -> Option<Ordering>`.
+    pub const PARTIAL_CMP: Protocol = Protocol {
+        name: "partial_cmp",
+        hash: Hash::new(0x627ff8eb598cd2c4),
+    };
+
+    /// Compare two values to establish a total ordering between them.
+    ///
+    /// This is used among other things when sorting, and by the comparison
+    /// operators if [Protocol::PARTIAL_CMP] is not implemented.
+    ///
+    /// Signature: `fn(self, other) -> Ordering`.
+    pub const CMP: Protocol = Protocol {
+        name: "cmp",
+        hash: Hash::new(0x9144083d8539e478),
+    };
+
     /// The function to access a field.
     pub const GET: Protocol = Protocol {
         name: "get",
@@ -1,16 +1,39 @@
 use crate::runtime::vm::CallResult;
-use crate::runtime::{GuardedArgs, Protocol, Stack, UnitFn, Value, Vm, VmError, VmErrorKind};
+use crate::runtime::{
+    GuardedArgs, Protocol, RuntimeContext, Stack, Unit, UnitFn, Value, Vm, VmError, VmErrorKind,
+};
 use crate::Hash;
+use std::sync::Arc;
 
 /// Trait used for integrating an instance function call.
 pub(crate) trait ProtocolCaller {
     /// Call the given protocol function.
     fn call_protocol_fn<A>(
-        self,
+        &mut self,
         protocol: Protocol,
         target: Value,
         args: A,
     ) -> Result<Value, VmError>
+    where
+        A: GuardedArgs,
+    {
+        match self.try_call_protocol_fn(protocol, target, args)? {
+            CallResult::Ok(value) => Ok(value),
+            CallResult::Unsupported(target) => Err(VmError::from(VmErrorKind::MissingFunction {
+                hash: Hash::instance_function(target.type_hash()?, protocol.hash),
+            })),
+        }
+    }
+
+    /// Try to call the given protocol function, returning
+    /// [CallResult::Unsupported] with the target if the protocol is not
+    /// implemented for it.
+    fn try_call_protocol_fn<A>(
+        &mut self,
+        protocol: Protocol,
+        target: Value,
+        args: A,
+    ) -> Result<CallResult<Value>, VmError>
     where
         A: GuardedArgs;
 }
@@ -21,83 +44,93 @@ pub(crate) trait ProtocolCaller {
 pub(crate) struct EnvProtocolCaller;
 
 impl ProtocolCaller for EnvProtocolCaller {
-    fn call_protocol_fn<A>(
-        self,
+    fn try_call_protocol_fn<A>(
+        &mut self,
         protocol: Protocol,
         target: Value,
         args: A,
-    ) -> Result<Value, VmError>
+    ) -> Result<CallResult<Value>, VmError>
     where
         A: GuardedArgs,
     {
-        return crate::runtime::env::with(|context, unit| {
-            let count = args.count() + 1;
-            let hash = Hash::instance_function(target.type_hash()?, protocol.hash);
-
-            if let Some(UnitFn::Offset {
-                offset,
-                args: expected,
-                call,
-            }) = unit.function(hash)
-            {
-                check_args(count, expected)?;
-
-                let mut stack = Stack::with_capacity(count);
-                stack.push(target);
-
-                // Safety: We hold onto the guard until the vm has completed.
-                let _guard = unsafe { args.unsafe_into_stack(&mut stack)? };
-
-                let mut vm = Vm::with_stack(context.clone(), unit.clone(), stack);
-                vm.set_ip(offset);
-                return call.call_with_vm(vm);
-            }
-
-            let handler = match context.function(hash) {
-                Some(handler) => handler,
-                None => return Err(VmError::from(VmErrorKind::MissingFunction { hash })),
-            };
-
-            let mut stack = Stack::with_capacity(count);
-            stack.push(target);
-
-            // Safety: We hold onto the guard until the vm has completed.
-            let _guard = unsafe { args.unsafe_into_stack(&mut stack)? };
-
-            handler(&mut stack, count)?;
-            Ok(stack.pop()?)
-        });
-
-        /// Check that arguments matches expected or raise the appropriate error.
-        fn check_args(args: usize, expected: usize) -> Result<(), VmError> {
-            if args != expected {
-                return Err(VmError::from(VmErrorKind::BadArgumentCount {
-                    actual: args,
-                    expected,
-                }));
-            }
-
-            Ok(())
-        }
+        crate::runtime::env::with(|context, unit| {
+            call_protocol_fn(context, unit, protocol, target, args)
+        })
     }
 }
 
-impl ProtocolCaller for &mut Vm {
-    fn call_protocol_fn<A>(
-        self,
+/// Calls the protocol on a virtual machine of its own, so that protocols
+/// implemented in the unit run to completion before the result is returned.
+impl ProtocolCaller for Vm {
+    fn try_call_protocol_fn<A>(
+        &mut self,
         protocol: Protocol,
         target: Value,
         args: A,
-    ) -> Result<Value, VmError>
+    ) -> Result<CallResult<Value>, VmError>
     where
         A: GuardedArgs,
     {
-        if let CallResult::Unsupported(..) = self.call_instance_fn(target, protocol, args)? {
-            return Err(VmError::from(VmErrorKind::MissingFunction {
-                hash: protocol.hash,
-            }));
-        }
+        call_protocol_fn(self.context(), self.unit(), protocol, target, args)
+    }
+}
+
+/// Call the given protocol function using the given context and unit.
+fn call_protocol_fn<A>(
+    context: &Arc<RuntimeContext>,
+    unit: &Arc<Unit>,
+    protocol: Protocol,
+    target: Value,
+    args: A,
+) -> Result<CallResult<Value>, VmError>
+where
+    A: GuardedArgs,
+{
+    let count = args.count() + 1;
+    let hash = Hash::instance_function(target.type_hash()?, protocol.hash);
+
+    if let Some(UnitFn::Offset {
+        offset,
+        args: expected,
+        call,
+    }) = unit.function(hash)
+    {
+        check_args(count, expected)?;
+
+        let mut stack = Stack::with_capacity(count);
+        stack.push(target);
 
-        Ok(self.stack_mut().pop()?)
+        // Safety: We hold onto the guard until the vm has completed.
+        let _guard = unsafe { args.unsafe_into_stack(&mut stack)? };
+
+        let mut vm = Vm::with_stack(context.clone(), unit.clone(), stack);
+        vm.set_ip(offset);
+        return Ok(CallResult::Ok(call.call_with_vm(vm)?));
     }
+
+    let handler = match context.function(hash) {
+        Some(handler) => handler,
+        None => return Ok(CallResult::Unsupported(target)),
+    };
+
+    let mut stack = Stack::with_capacity(count);
+    stack.push(target);
+
+    // Safety: We hold onto the guard until the vm has completed.
+    let _guard = unsafe { args.unsafe_into_stack(&mut stack)? };
+
+    handler(&mut stack, count)?;
+    Ok(CallResult::Ok(stack.pop()?))
+}
+
+/// Check that arguments matches expected or raise the appropriate error.
+fn check_args(args: usize, expected: usize) -> Result<(), VmError> {
+    if args != expected {
+        return Err(VmError::from(VmErrorKind::BadArgumentCount {
+            actual: args,
+            expected,
+        }));
+    }
+
+    Ok(())
 }
@@ -273,6 +273,12 @@ impl<T> Shared<T> {
 }
 
 impl<T: ?Sized> Shared<T> {
+    /// Get the address of the shared box, which identifies the value for as
+    /// long as any reference to it is alive.
+    pub(crate) fn as_ptr(&self) -> *const () {
+        self.inner.as_ptr() as *const ()
+    }
+
     /// Get a reference to the interior value while checking for shared access.
     ///
     /// This prevents other exclusive accesses from being performed while the
@@ -0,0 +1,55 @@
+use crate::runtime::{FromValue, ToValue, Value, VmError};
+
+/// A native integer which is represented by its sized value in scripts, like
+/// `u32`, rather than being converted into an `int`.
+///
+/// Native integers such as `u32` convert to and from `int` by default. Wrap
+/// them in a `SizedInteger` to opt into passing typed integers instead, which
+/// also allows values like `u64::MAX` which don't fit in an `int`.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::{SizedInteger, ToValue, Value};
+///
+/// let value = SizedInteger(u64::MAX).to_value()?;
+/// assert!(matches!(value, Value::U64(u64::MAX)));
+///
+/// let value = 42u64.to_value()?;
+/// assert!(matches!(value, Value::Integer(42)));
+/// # Ok::<_, rune::runtime::VmError>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SizedInteger<T>(pub T);
+
+macro_rules! impl_sized_integer {
+    ($($variant:ident => $ty:ty),* $(,)?) => {
+        $(
+            impl From<SizedInteger<$ty>> for Value {
+                fn from(value: SizedInteger<$ty>) -> Self {
+                    Self::$variant(value.0)
+                }
+            }
+
+            impl ToValue for SizedInteger<$ty> {
+                fn to_value(self) -> Result<Value, VmError> {
+                    Ok(Value::from(self))
+                }
+            }
+
+            impl FromValue for SizedInteger<$ty> {
+                fn from_value(value: Value) -> Result<Self, VmError> {
+                    Ok(Self(<$ty>::from_value(value)?))
+                }
+            }
+        )*
+    };
+}
+
+impl_sized_integer! {
+    Byte => u8,
+    U16 => u16,
+    U32 => u32,
+    U64 => u64,
+    I32 => i32,
+}
@@ -45,6 +45,7 @@ pub static BYTE_TYPE: &StaticType = &StaticType {
 };
 
 impl_static_type!(u8 => BYTE_TYPE);
+impl_static_type!(rt::SizedInteger<u8> => BYTE_TYPE);
 
 /// The specialized type information for a bool type.
 pub static BOOL_TYPE: &StaticType = &StaticType {
@@ -78,6 +79,38 @@ impl_static_type!(i64 => INTEGER_TYPE);
 impl_static_type!(u128 => INTEGER_TYPE);
 impl_static_type!(i128 => INTEGER_TYPE);
 
+/// The specialized type information for an unsigned 16-bit integer type.
+pub static U16_TYPE: &StaticType = &StaticType {
+    name: RawStr::from_str("u16"),
+    hash: Hash::new(0x781ef86f5c8cc1ab),
+};
+
+impl_static_type!(rt::SizedInteger<u16> => U16_TYPE);
+
+/// The specialized type information for an unsigned 32-bit integer type.
+pub static U32_TYPE: &StaticType = &StaticType {
+    name: RawStr::from_str("u32"),
+    hash: Hash::new(0x48f165d57b00c7f4),
+};
+
+impl_static_type!(rt::SizedInteger<u32> => U32_TYPE);
+
+/// The specialized type information for an unsigned 64-bit integer type.
+pub static U64_TYPE: &StaticType = &StaticType {
+    name: RawStr::from_str("u64"),
+    hash: Hash::new(0x3a0562d56abd685a),
+};
+
+impl_static_type!(rt::SizedInteger<u64> => U64_TYPE);
+
+/// The specialized type information for a signed 32-bit integer type.
+pub static I32_TYPE: &StaticType = &StaticType {
+    name: RawStr::from_str("i32"),
+    hash: Hash::new(0x017f9ee6725ed09d),
+};
+
+impl_static_type!(rt::SizedInteger<i32> => I32_TYPE);
+
 /// The specialized type information for a float type.
 pub static FLOAT_TYPE: &StaticType = &StaticType {
     name: RawStr::from_str("float"),
@@ -131,6 +164,7 @@ pub static OBJECT_TYPE: &StaticType = &StaticType {
 };
 
 impl_static_type!(rt::Object => OBJECT_TYPE);
+impl_static_type!(rt::Shared<rt::Object> => OBJECT_TYPE);
 impl_static_type!(rt::Struct => OBJECT_TYPE);
 
 /// The specialized type information for the range type.
@@ -1,5 +1,6 @@
 use crate::runtime::{
-    ConstValue, FromValue, Mut, Ref, ToValue, Value, Vm, VmError, VmErrorKind, TUPLE_TYPE,
+    ConstValue, FromValue, Mut, ProtocolCaller, Ref, ToValue, Value, VmError, VmErrorKind,
+    TUPLE_TYPE,
 };
 use std::fmt;
 use std::ops;
@@ -59,13 +60,17 @@ impl Tuple {
     }
 
     /// Value pointer equals implementation for a Tuple.
-    pub(crate) fn value_ptr_eq(vm: &mut Vm, a: &Self, b: &Self) -> Result<bool, VmError> {
+    pub(crate) fn value_ptr_eq(
+        caller: &mut impl ProtocolCaller,
+        a: &Self,
+        b: &Self,
+    ) -> Result<bool, VmError> {
         if a.len() != b.len() {
             return Ok(false);
         }
 
         for (a, b) in a.iter().zip(b.iter()) {
-            if !Value::value_ptr_eq(vm, a, b)? {
+            if !Value::value_ptr_eq(caller, a, b)? {
                 return Ok(false);
             }
         }
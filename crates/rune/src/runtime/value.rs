@@ -1,4 +1,5 @@
 use crate::compile::ItemBuf;
+use crate::modules::num::promote_integer;
 use crate::runtime::vm::CallResult;
 use crate::runtime::{
     AccessKind, AnyObj, Bytes, ConstValue, EnvProtocolCaller, Format, FromValue, Function, Future,
@@ -232,6 +233,14 @@ pub enum Value {
     Char(char),
     /// A number.
     Integer(i64),
+    /// An unsigned 16-bit integer.
+    U16(u16),
+    /// An unsigned 32-bit integer.
+    U32(u32),
+    /// An unsigned 64-bit integer.
+    U64(u64),
+    /// A signed 32-bit integer.
+    I32(i32),
     /// A float.
     Float(f64),
     /// A type hash. Describes a type in the virtual machine.
@@ -301,7 +310,7 @@ impl Value {
     ///
     /// This function will panic if called outside of a virtual machine.
     pub fn string_display(&self, s: &mut String, buf: &mut String) -> Result<fmt::Result, VmError> {
-        self.string_display_with(s, buf, EnvProtocolCaller)
+        self.string_display_with(s, buf, &mut EnvProtocolCaller)
     }
 
     /// Internal impl of string_display with a customizable caller.
@@ -309,7 +318,7 @@ impl Value {
         &self,
         s: &mut String,
         buf: &mut String,
-        caller: impl ProtocolCaller,
+        caller: &mut impl ProtocolCaller,
     ) -> Result<fmt::Result, VmError> {
         match self {
             Value::Format(format) => {
@@ -328,6 +337,22 @@ impl Value {
                 let mut buffer = itoa::Buffer::new();
                 s.push_str(buffer.format(*integer));
             }
+            Value::U16(integer) => {
+                let mut buffer = itoa::Buffer::new();
+                s.push_str(buffer.format(*integer));
+            }
+            Value::U32(integer) => {
+                let mut buffer = itoa::Buffer::new();
+                s.push_str(buffer.format(*integer));
+            }
+            Value::U64(integer) => {
+                let mut buffer = itoa::Buffer::new();
+                s.push_str(buffer.format(*integer));
+            }
+            Value::I32(integer) => {
+                let mut buffer = itoa::Buffer::new();
+                s.push_str(buffer.format(*integer));
+            }
             Value::Float(float) => {
                 let mut buffer = ryu::Buffer::new();
                 s.push_str(buffer.format(*float));
@@ -365,14 +390,14 @@ impl Value {
     ///
     /// This function will panic if called outside of a virtual machine.
     pub fn string_debug(&self, s: &mut String) -> Result<fmt::Result, VmError> {
-        self.string_debug_with(s, EnvProtocolCaller)
+        self.string_debug_with(s, &mut EnvProtocolCaller)
     }
 
     /// Internal impl of string_debug with a customizable caller.
     pub(crate) fn string_debug_with(
         &self,
         s: &mut String,
-        caller: impl ProtocolCaller,
+        caller: &mut impl ProtocolCaller,
     ) -> Result<fmt::Result, VmError> {
         use std::fmt::Write as _;
 
@@ -392,6 +417,18 @@ impl Value {
             Value::Integer(value) => {
                 write!(s, "{:?}", value)
             }
+            Value::U16(value) => {
+                write!(s, "{:?}u16", value)
+            }
+            Value::U32(value) => {
+                write!(s, "{:?}u32", value)
+            }
+            Value::U64(value) => {
+                write!(s, "{:?}u64", value)
+            }
+            Value::I32(value) => {
+                write!(s, "{:?}i32", value)
+            }
             Value::Float(value) => {
                 write!(s, "{:?}", value)
             }
@@ -608,6 +645,10 @@ impl Value {
             Self::Byte(value) => Self::Byte(value),
             Self::Char(value) => Self::Char(value),
             Self::Integer(value) => Self::Integer(value),
+            Self::U16(value) => Self::U16(value),
+            Self::U32(value) => Self::U32(value),
+            Self::U64(value) => Self::U64(value),
+            Self::I32(value) => Self::I32(value),
             Self::Float(value) => Self::Float(value),
             Self::Type(value) => Self::Type(value),
             Self::StaticString(value) => Self::StaticString(value),
@@ -688,6 +729,35 @@ impl Value {
         }
     }
 
+    /// Try to coerce any kind of integer value into a wide integer, which can
+    /// then be converted without loss into a native integer type.
+    #[inline]
+    pub(crate) fn as_wide_integer(&self) -> Option<i128> {
+        Some(match *self {
+            Self::Byte(integer) => i128::from(integer),
+            Self::Integer(integer) => i128::from(integer),
+            Self::U16(integer) => i128::from(integer),
+            Self::U32(integer) => i128::from(integer),
+            Self::U64(integer) => i128::from(integer),
+            Self::I32(integer) => i128::from(integer),
+            _ => return None,
+        })
+    }
+
+    /// Coerce a plain integer and a sized integer, in either order, into wide
+    /// integers so that they can be compared. Plain integers adapt to sized
+    /// integers the same way integer literals do in Rust.
+    #[inline]
+    pub(crate) fn as_mixed_integers(a: &Value, b: &Value) -> Option<(i128, i128)> {
+        match (a, b) {
+            (Self::Integer(..), Self::U16(..) | Self::U32(..) | Self::U64(..) | Self::I32(..))
+            | (Self::U16(..) | Self::U32(..) | Self::U64(..) | Self::I32(..), Self::Integer(..)) => {
+                Some((a.as_wide_integer()?, b.as_wide_integer()?))
+            }
+            _ => None,
+        }
+    }
+
     /// Try to coerce value into a float.
     #[inline]
     pub fn into_float(self) -> Result<f64, VmError> {
@@ -893,6 +963,10 @@ impl Value {
             Self::Byte(..) => crate::runtime::BYTE_TYPE.hash,
             Self::Char(..) => crate::runtime::CHAR_TYPE.hash,
             Self::Integer(..) => crate::runtime::INTEGER_TYPE.hash,
+            Self::U16(..) => crate::runtime::U16_TYPE.hash,
+            Self::U32(..) => crate::runtime::U32_TYPE.hash,
+            Self::U64(..) => crate::runtime::U64_TYPE.hash,
+            Self::I32(..) => crate::runtime::I32_TYPE.hash,
             Self::Float(..) => crate::runtime::FLOAT_TYPE.hash,
             Self::StaticString(..) => crate::runtime::STRING_TYPE.hash,
             Self::String(..) => crate::runtime::STRING_TYPE.hash,
@@ -927,6 +1001,10 @@ impl Value {
             Self::Byte(..) => TypeInfo::StaticType(crate::runtime::BYTE_TYPE),
             Self::Char(..) => TypeInfo::StaticType(crate::runtime::CHAR_TYPE),
             Self::Integer(..) => TypeInfo::StaticType(crate::runtime::INTEGER_TYPE),
+            Self::U16(..) => TypeInfo::StaticType(crate::runtime::U16_TYPE),
+            Self::U32(..) => TypeInfo::StaticType(crate::runtime::U32_TYPE),
+            Self::U64(..) => TypeInfo::StaticType(crate::runtime::U64_TYPE),
+            Self::I32(..) => TypeInfo::StaticType(crate::runtime::I32_TYPE),
             Self::Float(..) => TypeInfo::StaticType(crate::runtime::FLOAT_TYPE),
             Self::StaticString(..) => TypeInfo::StaticType(crate::runtime::STRING_TYPE),
             Self::String(..) => TypeInfo::StaticType(crate::runtime::STRING_TYPE),
@@ -957,33 +1035,45 @@ impl Value {
     /// each other.
     ///
     /// This is the basis for the eq operation (`==`).
-    pub(crate) fn value_ptr_eq(vm: &mut Vm, a: &Value, b: &Value) -> Result<bool, VmError> {
+    pub(crate) fn value_ptr_eq(
+        caller: &mut impl ProtocolCaller,
+        a: &Value,
+        b: &Value,
+    ) -> Result<bool, VmError> {
+        if let Some((a, b)) = Self::as_mixed_integers(a, b) {
+            return Ok(a == b);
+        }
+
         match (a, b) {
             (Self::Unit, Self::Unit) => return Ok(true),
             (Self::Bool(a), Self::Bool(b)) => return Ok(a == b),
             (Self::Byte(a), Self::Byte(b)) => return Ok(a == b),
             (Self::Char(a), Self::Char(b)) => return Ok(a == b),
             (Self::Integer(a), Self::Integer(b)) => return Ok(a == b),
+            (Self::U16(a), Self::U16(b)) => return Ok(a == b),
+            (Self::U32(a), Self::U32(b)) => return Ok(a == b),
+            (Self::U64(a), Self::U64(b)) => return Ok(a == b),
+            (Self::I32(a), Self::I32(b)) => return Ok(a == b),
             (Self::Float(a), Self::Float(b)) => return Ok(a == b),
             (Self::Vec(a), Self::Vec(b)) => {
                 let a = a.borrow_ref()?;
                 let b = b.borrow_ref()?;
-                return Vec::value_ptr_eq(vm, &a, &b);
+                return Vec::value_ptr_eq(caller, &a, &b);
             }
             (Self::Tuple(a), Self::Tuple(b)) => {
                 let a = a.borrow_ref()?;
                 let b = b.borrow_ref()?;
-                return Tuple::value_ptr_eq(vm, &a, &b);
+                return Tuple::value_ptr_eq(caller, &a, &b);
             }
             (Self::Object(a), Self::Object(b)) => {
                 let a = a.borrow_ref()?;
                 let b = b.borrow_ref()?;
-                return Object::value_ptr_eq(vm, &a, &b);
+                return Object::value_ptr_eq(caller, &a, &b);
             }
             (Self::Range(a), Self::Range(b)) => {
                 let a = a.borrow_ref()?;
                 let b = b.borrow_ref()?;
-                return Range::value_ptr_eq(vm, &a, &b);
+                return Range::value_ptr_eq(caller, &a, &b);
             }
             (Self::UnitStruct(a), Self::UnitStruct(b)) => {
                 if a.borrow_ref()?.rtti.hash == b.borrow_ref()?.rtti.hash {
@@ -1000,7 +1090,7 @@ impl Value {
                 let b = b.borrow_ref()?;
 
                 if a.rtti.hash == b.rtti.hash {
-                    return Tuple::value_ptr_eq(vm, &a.data, &b.data);
+                    return Tuple::value_ptr_eq(caller, &a.data, &b.data);
                 }
             }
             (Self::Struct(a), Self::Struct(b)) => {
@@ -1008,7 +1098,7 @@ impl Value {
                 let b = b.borrow_ref()?;
 
                 if a.rtti.hash == b.rtti.hash {
-                    return Object::value_ptr_eq(vm, &a.data, &b.data);
+                    return Object::value_ptr_eq(caller, &a.data, &b.data);
                 }
             }
             (Self::Variant(a), Self::Variant(b)) => {
@@ -1016,7 +1106,7 @@ impl Value {
                 let b = b.borrow_ref()?;
 
                 if a.rtti().enum_hash == b.rtti().enum_hash {
-                    return Variant::value_ptr_eq(vm, &a, &b);
+                    return Variant::value_ptr_eq(caller, &a, &b);
                 }
             }
             (Self::String(a), Self::String(b)) => {
@@ -1035,19 +1125,26 @@ impl Value {
                 return Ok(***a == ***b);
             }
             (Self::Option(a), Self::Option(b)) => match (&*a.borrow_ref()?, &*b.borrow_ref()?) {
-                (Some(a), Some(b)) => return Self::value_ptr_eq(vm, a, b),
+                (Some(a), Some(b)) => return Self::value_ptr_eq(caller, a, b),
                 (None, None) => return Ok(true),
                 _ => return Ok(false),
             },
             (Self::Result(a), Self::Result(b)) => match (&*a.borrow_ref()?, &*b.borrow_ref()?) {
-                (Ok(a), Ok(b)) => return Self::value_ptr_eq(vm, a, b),
-                (Err(a), Err(b)) => return Self::value_ptr_eq(vm, a, b),
+                (Ok(a), Ok(b)) => return Self::value_ptr_eq(caller, a, b),
+                (Err(a), Err(b)) => return Self::value_ptr_eq(caller, a, b),
                 _ => return Ok(false),
             },
-            (a, b) => match vm.call_instance_fn(a.clone(), Protocol::EQ, (b.clone(),))? {
-                CallResult::Ok(()) => return bool::from_value(vm.stack_mut().pop()?),
-                CallResult::Unsupported(..) => {}
-            },
+            (a, b) => {
+                if let CallResult::Ok(value) =
+                    caller.try_call_protocol_fn(Protocol::EQ, a.clone(), (b.clone(),))?
+                {
+                    return bool::from_value(value);
+                }
+
+                if let Some(a) = promote_integer(a, b)? {
+                    return Self::value_ptr_eq(caller, &a, b);
+                }
+            }
         }
 
         Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
@@ -1056,6 +1153,229 @@ impl Value {
             rhs: b.type_info()?,
         }))
     }
+
+    /// Perform a partial ordering comparison between two values.
+    ///
+    /// Built-in values are compared structurally, anything else is compared
+    /// using the [Protocol::PARTIAL_CMP] protocol, falling back to
+    /// [Protocol::CMP] if it is not implemented.
+    ///
+    /// You must use [Vm::with] to specify which virtual machine this function
+    /// is called inside.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called outside of a virtual machine.
+    pub fn partial_cmp(a: &Value, b: &Value) -> Result<Option<cmp::Ordering>, VmError> {
+        Self::partial_cmp_with(a, b, &mut EnvProtocolCaller)
+    }
+
+    /// Internal impl of partial_cmp with a customizable caller.
+    pub(crate) fn partial_cmp_with(
+        a: &Value,
+        b: &Value,
+        caller: &mut impl ProtocolCaller,
+    ) -> Result<Option<cmp::Ordering>, VmError> {
+        Self::partial_cmp_op(a, b, caller, "partial_cmp")
+    }
+
+    /// Partial ordering comparison which reports errors for the given
+    /// operator.
+    ///
+    /// This is the basis for the comparison operators (`<`, `<=`, `>`, `>=`).
+    pub(crate) fn partial_cmp_op(
+        a: &Value,
+        b: &Value,
+        caller: &mut impl ProtocolCaller,
+        op: &'static str,
+    ) -> Result<Option<cmp::Ordering>, VmError> {
+        if let Some((a, b)) = Self::as_mixed_integers(a, b) {
+            return Ok(a.partial_cmp(&b));
+        }
+
+        match (a, b) {
+            (Self::Unit, Self::Unit) => return Ok(Some(cmp::Ordering::Equal)),
+            (Self::Bool(a), Self::Bool(b)) => return Ok(a.partial_cmp(b)),
+            (Self::Byte(a), Self::Byte(b)) => return Ok(a.partial_cmp(b)),
+            (Self::Char(a), Self::Char(b)) => return Ok(a.partial_cmp(b)),
+            (Self::Integer(a), Self::Integer(b)) => return Ok(a.partial_cmp(b)),
+            (Self::U16(a), Self::U16(b)) => return Ok(a.partial_cmp(b)),
+            (Self::U32(a), Self::U32(b)) => return Ok(a.partial_cmp(b)),
+            (Self::U64(a), Self::U64(b)) => return Ok(a.partial_cmp(b)),
+            (Self::I32(a), Self::I32(b)) => return Ok(a.partial_cmp(b)),
+            (Self::Float(a), Self::Float(b)) => return Ok(a.partial_cmp(b)),
+            (Self::String(a), Self::String(b)) => {
+                return Ok(a
+                    .borrow_ref()?
+                    .as_str()
+                    .partial_cmp(b.borrow_ref()?.as_str()));
+            }
+            (Self::StaticString(a), Self::String(b)) => {
+                return Ok(a.as_str().partial_cmp(b.borrow_ref()?.as_str()));
+            }
+            (Self::String(a), Self::StaticString(b)) => {
+                return Ok(a.borrow_ref()?.as_str().partial_cmp(b.as_str()));
+            }
+            (Self::StaticString(a), Self::StaticString(b)) => {
+                return Ok(a.as_str().partial_cmp(b.as_str()));
+            }
+            (Self::Bytes(a), Self::Bytes(b)) => {
+                let a = a.borrow_ref()?;
+                let b = b.borrow_ref()?;
+                return Ok(a[..].partial_cmp(&b[..]));
+            }
+            (Self::Vec(a), Self::Vec(b)) => {
+                let a = a.borrow_ref()?;
+                let b = b.borrow_ref()?;
+                return Vec::partial_cmp_with(&a, &b, caller);
+            }
+            (Self::Tuple(a), Self::Tuple(b)) => {
+                let a = a.borrow_ref()?;
+                let b = b.borrow_ref()?;
+                return Vec::partial_cmp_with(&a, &b, caller);
+            }
+            (Self::Option(a), Self::Option(b)) => match (&*a.borrow_ref()?, &*b.borrow_ref()?) {
+                (Some(a), Some(b)) => return Self::partial_cmp_with(a, b, caller),
+                (a, b) => return Ok(a.is_some().partial_cmp(&b.is_some())),
+            },
+            (Self::Result(a), Self::Result(b)) => match (&*a.borrow_ref()?, &*b.borrow_ref()?) {
+                (Ok(a), Ok(b)) => return Self::partial_cmp_with(a, b, caller),
+                (Err(a), Err(b)) => return Self::partial_cmp_with(a, b, caller),
+                (a, b) => return Ok(a.is_err().partial_cmp(&b.is_err())),
+            },
+            (a, b) => {
+                if let CallResult::Ok(value) =
+                    caller.try_call_protocol_fn(Protocol::PARTIAL_CMP, a.clone(), (b.clone(),))?
+                {
+                    return match Option::<Value>::from_value(value)? {
+                        Some(ordering) => Ok(Some(ordering_from_value(ordering)?)),
+                        None => Ok(None),
+                    };
+                }
+
+                if let CallResult::Ok(value) =
+                    caller.try_call_protocol_fn(Protocol::CMP, a.clone(), (b.clone(),))?
+                {
+                    return Ok(Some(ordering_from_value(value)?));
+                }
+
+                if let Some(a) = promote_integer(a, b)? {
+                    return Self::partial_cmp_op(&a, b, caller, op);
+                }
+            }
+        }
+
+        Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+            op,
+            lhs: a.type_info()?,
+            rhs: b.type_info()?,
+        }))
+    }
+
+    /// Perform a total ordering comparison between two values.
+    ///
+    /// Built-in values are compared structurally, anything else is compared
+    /// using the [Protocol::CMP] protocol.
+    ///
+    /// You must use [Vm::with] to specify which virtual machine this function
+    /// is called inside.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called outside of a virtual machine.
+    pub fn cmp(a: &Value, b: &Value) -> Result<cmp::Ordering, VmError> {
+        Self::cmp_with(a, b, &mut EnvProtocolCaller)
+    }
+
+    /// Internal impl of cmp with a customizable caller.
+    pub(crate) fn cmp_with(
+        a: &Value,
+        b: &Value,
+        caller: &mut impl ProtocolCaller,
+    ) -> Result<cmp::Ordering, VmError> {
+        if let Some((a, b)) = Self::as_mixed_integers(a, b) {
+            return Ok(a.cmp(&b));
+        }
+
+        match (a, b) {
+            (Self::Unit, Self::Unit) => return Ok(cmp::Ordering::Equal),
+            (Self::Bool(a), Self::Bool(b)) => return Ok(a.cmp(b)),
+            (Self::Byte(a), Self::Byte(b)) => return Ok(a.cmp(b)),
+            (Self::Char(a), Self::Char(b)) => return Ok(a.cmp(b)),
+            (Self::Integer(a), Self::Integer(b)) => return Ok(a.cmp(b)),
+            (Self::U16(a), Self::U16(b)) => return Ok(a.cmp(b)),
+            (Self::U32(a), Self::U32(b)) => return Ok(a.cmp(b)),
+            (Self::U64(a), Self::U64(b)) => return Ok(a.cmp(b)),
+            (Self::I32(a), Self::I32(b)) => return Ok(a.cmp(b)),
+            (Self::Float(a), Self::Float(b)) => {
+                return match a.partial_cmp(b) {
+                    Some(ordering) => Ok(ordering),
+                    None => Err(VmError::from(VmErrorKind::IllegalFloatComparison {
+                        lhs: *a,
+                        rhs: *b,
+                    })),
+                };
+            }
+            (Self::String(a), Self::String(b)) => {
+                return Ok(a.borrow_ref()?.as_str().cmp(b.borrow_ref()?.as_str()));
+            }
+            (Self::StaticString(a), Self::String(b)) => {
+                return Ok(a.as_str().cmp(b.borrow_ref()?.as_str()));
+            }
+            (Self::String(a), Self::StaticString(b)) => {
+                return Ok(a.borrow_ref()?.as_str().cmp(b.as_str()));
+            }
+            (Self::StaticString(a), Self::StaticString(b)) => {
+                return Ok(a.as_str().cmp(b.as_str()));
+            }
+            (Self::Bytes(a), Self::Bytes(b)) => {
+                let a = a.borrow_ref()?;
+                let b = b.borrow_ref()?;
+                return Ok(a[..].cmp(&b[..]));
+            }
+            (Self::Vec(a), Self::Vec(b)) => {
+                let a = a.borrow_ref()?;
+                let b = b.borrow_ref()?;
+                return Vec::cmp_with(&a, &b, caller);
+            }
+            (Self::Tuple(a), Self::Tuple(b)) => {
+                let a = a.borrow_ref()?;
+                let b = b.borrow_ref()?;
+                return Vec::cmp_with(&a, &b, caller);
+            }
+            (Self::Option(a), Self::Option(b)) => match (&*a.borrow_ref()?, &*b.borrow_ref()?) {
+                (Some(a), Some(b)) => return Self::cmp_with(a, b, caller),
+                (a, b) => return Ok(a.is_some().cmp(&b.is_some())),
+            },
+            (Self::Result(a), Self::Result(b)) => match (&*a.borrow_ref()?, &*b.borrow_ref()?) {
+                (Ok(a), Ok(b)) => return Self::cmp_with(a, b, caller),
+                (Err(a), Err(b)) => return Self::cmp_with(a, b, caller),
+                (a, b) => return Ok(a.is_err().cmp(&b.is_err())),
+            },
+            (a, b) => {
+                if let CallResult::Ok(value) =
+                    caller.try_call_protocol_fn(Protocol::CMP, a.clone(), (b.clone(),))?
+                {
+                    return ordering_from_value(value);
+                }
+
+                if let Some(a) = promote_integer(a, b)? {
+                    return Self::cmp_with(&a, b, caller);
+                }
+            }
+        }
+
+        Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+            op: "cmp",
+            lhs: a.type_info()?,
+            rhs: b.type_info()?,
+        }))
+    }
+}
+
+/// Convert a value returned from a comparison protocol into an ordering.
+fn ordering_from_value(value: Value) -> Result<cmp::Ordering, VmError> {
+    Ok(*Ref::<cmp::Ordering>::from_value(value)?)
 }
 
 impl fmt::Debug for Value {
@@ -1076,6 +1396,18 @@ impl fmt::Debug for Value {
             Value::Integer(value) => {
                 write!(f, "{:?}", value)?;
             }
+            Value::U16(value) => {
+                write!(f, "{:?}u16", value)?;
+            }
+            Value::U32(value) => {
+                write!(f, "{:?}u32", value)?;
+            }
+            Value::U64(value) => {
+                write!(f, "{:?}u64", value)?;
+            }
+            Value::I32(value) => {
+                write!(f, "{:?}i32", value)?;
+            }
             Value::Float(value) => {
                 write!(f, "{:?}", value)?;
             }
@@ -1294,6 +1626,10 @@ impl ser::Serialize for Value {
             Value::Char(c) => serializer.serialize_char(*c),
             Value::Byte(c) => serializer.serialize_u8(*c),
             Value::Integer(integer) => serializer.serialize_i64(*integer),
+            Value::U16(integer) => serializer.serialize_u16(*integer),
+            Value::U32(integer) => serializer.serialize_u32(*integer),
+            Value::U64(integer) => serializer.serialize_u64(*integer),
+            Value::I32(integer) => serializer.serialize_i32(*integer),
             Value::Float(float) => serializer.serialize_f64(*float),
             Value::StaticString(string) => serializer.serialize_str(string.as_ref()),
             Value::String(string) => {
@@ -1469,7 +1805,12 @@ impl<'de> de::Visitor<'de> for VmVisitor {
     where
         E: de::Error,
     {
-        Ok(Value::Integer(v as i64))
+        // NB: only values which don't fit an integer are kept as `u64`, since
+        // deserializers commonly use this for any positive number.
+        match i64::try_from(v) {
+            Ok(v) => Ok(Value::Integer(v)),
+            Err(..) => Ok(Value::U64(v)),
+        }
     }
 
     #[inline]
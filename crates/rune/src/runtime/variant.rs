@@ -1,4 +1,4 @@
-use crate::runtime::{Object, Tuple, TypeInfo, VariantRtti, Vm, VmError};
+use crate::runtime::{Object, ProtocolCaller, Tuple, TypeInfo, VariantRtti, VmError};
 use std::fmt;
 use std::sync::Arc;
 
@@ -54,7 +54,11 @@ impl Variant {
     }
 
     /// Perform a deep value comparison of two variants.
-    pub(crate) fn value_ptr_eq(vm: &mut Vm, a: &Self, b: &Self) -> Result<bool, VmError> {
+    pub(crate) fn value_ptr_eq(
+        caller: &mut impl ProtocolCaller,
+        a: &Self,
+        b: &Self,
+    ) -> Result<bool, VmError> {
         debug_assert_eq!(
             a.rtti.enum_hash, b.rtti.enum_hash,
             "comparison only makes sense if enum hashes match"
@@ -66,9 +70,11 @@ impl Variant {
 
         Ok(match (&a.data, &b.data) {
             (VariantData::Unit, VariantData::Unit) => true,
-            (VariantData::Tuple(a), VariantData::Tuple(b)) => return Tuple::value_ptr_eq(vm, a, b),
+            (VariantData::Tuple(a), VariantData::Tuple(b)) => {
+                return Tuple::value_ptr_eq(caller, a, b)
+            }
             (VariantData::Struct(a), VariantData::Struct(b)) => {
-                return Object::value_ptr_eq(vm, a, b)
+                return Object::value_ptr_eq(caller, a, b)
             }
             _ => false,
         })
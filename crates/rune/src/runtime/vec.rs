@@ -1,7 +1,7 @@
 use crate::compile::{InstallWith, Named};
 use crate::runtime::{
-    FromValue, Iterator, Mut, RawMut, RawRef, RawStr, Ref, Shared, ToValue, UnsafeFromValue, Value,
-    Vm, VmError, VmErrorKind,
+    FromValue, Iterator, Mut, ProtocolCaller, RawMut, RawRef, RawStr, Ref, Shared, ToValue,
+    UnsafeFromValue, Value, VmError, VmErrorKind,
 };
 use std::cmp;
 use std::fmt;
@@ -149,6 +149,59 @@ impl Vec {
         self.inner.insert(index, value);
     }
 
+    /// Shortens the vector, keeping the first `len` elements and dropping the
+    /// rest.
+    pub fn truncate(&mut self, len: usize) {
+        self.inner.truncate(len);
+    }
+
+    /// Splits the vector into two at the given index, returning the elements
+    /// from `at` onwards.
+    pub fn split_off(&mut self, at: usize) -> Result<Self, VmError> {
+        if at > self.len() {
+            return Err(VmError::from(VmErrorKind::OutOfRange {
+                index: at.into(),
+                len: self.len().into(),
+            }));
+        }
+
+        Ok(Self {
+            inner: self.inner.split_off(at),
+        })
+    }
+
+    /// Swaps two elements in the vector.
+    pub fn swap(&mut self, a: usize, b: usize) -> Result<(), VmError> {
+        for index in [a, b] {
+            if index >= self.len() {
+                return Err(VmError::from(VmErrorKind::OutOfRange {
+                    index: index.into(),
+                    len: self.len().into(),
+                }));
+            }
+        }
+
+        self.inner.swap(a, b);
+        Ok(())
+    }
+
+    /// Retains only the elements specified by the predicate.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&Value) -> bool,
+    {
+        self.inner.retain(f);
+    }
+
+    /// Removes consecutive elements which the given function considers to be
+    /// the same.
+    pub fn dedup_by<F>(&mut self, same: F)
+    where
+        F: FnMut(&mut Value, &mut Value) -> bool,
+    {
+        self.inner.dedup_by(same);
+    }
+
     /// Extend this vector with something that implements the into_iter
     /// protocol.
     pub fn extend(&mut self, value: Value) -> Result<(), VmError> {
@@ -167,19 +220,56 @@ impl Vec {
     }
 
     /// Compare two vectors for equality.
-    pub(crate) fn value_ptr_eq(vm: &mut Vm, a: &Self, b: &Self) -> Result<bool, VmError> {
+    pub(crate) fn value_ptr_eq(
+        caller: &mut impl ProtocolCaller,
+        a: &Self,
+        b: &Self,
+    ) -> Result<bool, VmError> {
         if a.len() != b.len() {
             return Ok(false);
         }
 
         for (a, b) in a.iter().zip(b.iter()) {
-            if !Value::value_ptr_eq(vm, a, b)? {
+            if !Value::value_ptr_eq(caller, a, b)? {
                 return Ok(false);
             }
         }
 
         Ok(true)
     }
+
+    /// Compare two slices of values lexicographically using a partial
+    /// ordering.
+    pub(crate) fn partial_cmp_with(
+        a: &[Value],
+        b: &[Value],
+        caller: &mut impl ProtocolCaller,
+    ) -> Result<Option<cmp::Ordering>, VmError> {
+        for (a, b) in a.iter().zip(b.iter()) {
+            match Value::partial_cmp_with(a, b, caller)? {
+                Some(cmp::Ordering::Equal) => continue,
+                ordering => return Ok(ordering),
+            }
+        }
+
+        Ok(a.len().partial_cmp(&b.len()))
+    }
+
+    /// Compare two slices of values lexicographically using a total ordering.
+    pub(crate) fn cmp_with(
+        a: &[Value],
+        b: &[Value],
+        caller: &mut impl ProtocolCaller,
+    ) -> Result<cmp::Ordering, VmError> {
+        for (a, b) in a.iter().zip(b.iter()) {
+            match Value::cmp_with(a, b, caller)? {
+                cmp::Ordering::Equal => continue,
+                ordering => return Ok(ordering),
+            }
+        }
+
+        Ok(a.len().cmp(&b.len()))
+    }
 }
 
 impl Named for Vec {
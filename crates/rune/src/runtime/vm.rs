@@ -1,14 +1,17 @@
+use crate::modules::num::promote_integer;
 use crate::runtime::budget;
 use crate::runtime::future::SelectFuture;
 use crate::runtime::unit::UnitFn;
 use crate::runtime::{
-    Args, Awaited, BorrowMut, Bytes, Call, Format, FormatSpec, FromValue, Function, Future,
-    Generator, GuardedArgs, Inst, InstAddress, InstAssignOp, InstOp, InstRangeLimits, InstTarget,
-    InstValue, InstVariant, Object, Panic, Protocol, Range, RangeLimits, RuntimeContext, Select,
-    Shared, Stack, Stream, Struct, Tuple, TypeCheck, Unit, UnitStruct, Value, Variant, VariantData,
-    Vec, VmError, VmErrorKind, VmExecution, VmHalt, VmIntegerRepr, VmSendExecution,
+    Args, Awaited, BorrowMut, Bytes, Call, ConstValue, Format, FormatSpec, FromValue, Function,
+    Future, Generator, GuardedArgs, Inst, InstAddress, InstAssignOp, InstOp, InstRangeLimits,
+    InstTarget, InstValue, InstVariant, Object, Panic, Protocol, Range, RangeLimits,
+    RuntimeContext, Select, Shared, Stack, Stream, Struct, Tuple, TypeCheck, Unit, UnitStruct,
+    Value, Variant, VariantData, Vec, VmError, VmErrorKind, VmExecution, VmHalt, VmIntegerRepr,
+    VmSendExecution,
 };
 use crate::{Hash, IntoTypeHash};
+use std::cmp;
 use std::fmt;
 use std::mem;
 use std::sync::Arc;
@@ -567,6 +570,7 @@ impl Vm {
         &mut self,
         int_op: fn(i64, i64) -> bool,
         float_op: fn(f64, f64) -> bool,
+        ordering_op: fn(Option<cmp::Ordering>) -> bool,
         op: &'static str,
         lhs: InstAddress,
         rhs: InstAddress,
@@ -577,13 +581,7 @@ impl Vm {
         let out = match (lhs, rhs) {
             (Value::Integer(lhs), Value::Integer(rhs)) => int_op(lhs, rhs),
             (Value::Float(lhs), Value::Float(rhs)) => float_op(lhs, rhs),
-            (lhs, rhs) => {
-                return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
-                    op,
-                    lhs: lhs.type_info()?,
-                    rhs: rhs.type_info()?,
-                }))
-            }
+            (lhs, rhs) => ordering_op(Value::partial_cmp_op(&lhs, &rhs, self, op)?),
         };
 
         self.stack.push(out);
@@ -1156,7 +1154,14 @@ impl Vm {
                     *lhs = out;
                     return Ok(());
                 }
-                (lhs, rhs) => TargetFallback::Value(lhs.clone(), rhs),
+                (lhs, rhs) => {
+                    if let Some(value) = sized_binary(protocol, lhs, &rhs) {
+                        *lhs = value?;
+                        return Ok(());
+                    }
+
+                    TargetFallback::Value(lhs.clone(), rhs)
+                }
             },
             TargetValue::Fallback(fallback) => fallback,
         };
@@ -1231,15 +1236,27 @@ impl Vm {
             (lhs, rhs) => (lhs, rhs),
         };
 
-        if let CallResult::Unsupported(lhs) = self.call_instance_fn(lhs, protocol, (&rhs,))? {
-            Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
-                op: protocol.name,
-                lhs: lhs.type_info()?,
-                rhs: rhs.type_info()?,
-            }))
-        } else {
-            Ok(())
+        if let Some(value) = sized_binary(protocol, &lhs, &rhs) {
+            self.stack.push(value?);
+            return Ok(());
+        }
+
+        let lhs = match self.call_instance_fn(lhs, protocol, (&rhs,))? {
+            CallResult::Ok(()) => return Ok(()),
+            CallResult::Unsupported(lhs) => lhs,
+        };
+
+        if let Some(promoted) = promote_integer(&lhs, &rhs)? {
+            if let CallResult::Ok(()) = self.call_instance_fn(promoted, protocol, (&rhs,))? {
+                return Ok(());
+            }
         }
+
+        Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+            op: protocol.name,
+            lhs: lhs.type_info()?,
+            rhs: rhs.type_info()?,
+        }))
     }
 
     /// Internal impl of a numeric operation.
@@ -1261,6 +1278,11 @@ impl Vm {
             (lhs, rhs) => (lhs, rhs),
         };
 
+        if let Some(value) = sized_binary(protocol, &lhs, &rhs) {
+            self.stack.push(value?);
+            return Ok(());
+        }
+
         if let CallResult::Unsupported(lhs) = self.call_instance_fn(lhs, protocol, (&rhs,))? {
             Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
                 op: protocol.name,
@@ -1296,6 +1318,11 @@ impl Vm {
             (lhs, rhs) => (lhs, rhs),
         };
 
+        if let Some(value) = sized_binary(protocol, &lhs, &rhs) {
+            self.stack.push(value?);
+            return Ok(());
+        }
+
         if let CallResult::Unsupported(lhs) = self.call_instance_fn(lhs, protocol, (&rhs,))? {
             Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
                 op: protocol.name,
@@ -1322,7 +1349,14 @@ impl Vm {
                     integer_op(lhs, rhs);
                     return Ok(());
                 }
-                (lhs, rhs) => TargetFallback::Value(lhs.clone(), rhs),
+                (lhs, rhs) => {
+                    if let Some(value) = sized_binary(protocol, lhs, &rhs) {
+                        *lhs = value?;
+                        return Ok(());
+                    }
+
+                    TargetFallback::Value(lhs.clone(), rhs)
+                }
             },
             TargetValue::Fallback(fallback) => fallback,
         };
@@ -1349,6 +1383,11 @@ impl Vm {
             (lhs, rhs) => (lhs, rhs),
         };
 
+        if let Some(value) = sized_binary(protocol, &lhs, &rhs) {
+            self.stack.push(value?);
+            return Ok(());
+        }
+
         if let CallResult::Unsupported(lhs) = self.call_instance_fn(lhs, protocol, (&rhs,))? {
             Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
                 op: protocol.name,
@@ -1377,7 +1416,14 @@ impl Vm {
                     *lhs = out;
                     return Ok(());
                 }
-                (lhs, rhs) => TargetFallback::Value(lhs.clone(), rhs),
+                (lhs, rhs) => {
+                    if let Some(value) = sized_binary(protocol, lhs, &rhs) {
+                        *lhs = value?;
+                        return Ok(());
+                    }
+
+                    TargetFallback::Value(lhs.clone(), rhs)
+                }
             },
             TargetValue::Fallback(fallback) => fallback,
         };
@@ -1607,6 +1653,11 @@ impl Vm {
         let value = match value {
             Value::Bool(value) => Value::from(!value),
             Value::Integer(value) => Value::from(!value),
+            Value::Byte(value) => Value::from(!value),
+            Value::U16(value) => Value::U16(!value),
+            Value::U32(value) => Value::U32(!value),
+            Value::U64(value) => Value::U64(!value),
+            Value::I32(value) => Value::I32(!value),
             other => {
                 let operand = other.type_info()?;
                 return Err(VmError::from(VmErrorKind::UnsupportedUnaryOperation {
@@ -1627,6 +1678,7 @@ impl Vm {
         let value = match value {
             Value::Float(value) => Value::from(-value),
             Value::Integer(value) => Value::from(-value),
+            Value::I32(value) => Value::I32(value.checked_neg().ok_or(VmErrorKind::Overflow)?),
             other => {
                 let operand = other.type_info()?;
                 return Err(VmError::from(VmErrorKind::UnsupportedUnaryOperation {
@@ -1738,16 +1790,44 @@ impl Vm {
                 self.internal_infallible_bitwise(Protocol::SHR, std::ops::Shr::shr, lhs, rhs)?;
             }
             InstOp::Gt => {
-                self.internal_boolean_ops(|a, b| a > b, |a, b| a > b, ">", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a > b,
+                    |a, b| a > b,
+                    |o| matches!(o, Some(cmp::Ordering::Greater)),
+                    ">",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Gte => {
-                self.internal_boolean_ops(|a, b| a >= b, |a, b| a >= b, ">=", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a >= b,
+                    |a, b| a >= b,
+                    |o| matches!(o, Some(cmp::Ordering::Greater | cmp::Ordering::Equal)),
+                    ">=",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Lt => {
-                self.internal_boolean_ops(|a, b| a < b, |a, b| a < b, "<", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a < b,
+                    |a, b| a < b,
+                    |o| matches!(o, Some(cmp::Ordering::Less)),
+                    "<",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Lte => {
-                self.internal_boolean_ops(|a, b| a <= b, |a, b| a <= b, "<=", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a <= b,
+                    |a, b| a <= b,
+                    |o| matches!(o, Some(cmp::Ordering::Less | cmp::Ordering::Equal)),
+                    "<=",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Eq => {
                 let rhs = self.stack.address(rhs)?;
@@ -2300,7 +2380,7 @@ impl Vm {
         for value in values {
             buf.clear();
 
-            if let Err(fmt::Error) = value.string_display_with(&mut out, &mut buf, &mut *self)? {
+            if let Err(fmt::Error) = value.string_display_with(&mut out, &mut buf, self)? {
                 return Err(VmError::from(VmErrorKind::FormatError));
             }
         }
@@ -2388,6 +2468,9 @@ impl Vm {
 
         self.stack.push(match value {
             Value::Integer(actual) => actual == integer,
+            Value::U16(..) | Value::U32(..) | Value::U64(..) | Value::I32(..) => {
+                value.as_wide_integer() == Some(i128::from(integer))
+            }
             _ => false,
         });
 
@@ -2436,6 +2519,45 @@ impl Vm {
         Ok(())
     }
 
+    /// Test if the top of the stack is contained in the given range.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_match_range(
+        &mut self,
+        start: Option<InstValue>,
+        end: Option<InstValue>,
+        limits: InstRangeLimits,
+    ) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+
+        let is_match = match value {
+            Value::Integer(value) => range_contains(value, start, end, limits, |v| match v {
+                InstValue::Integer(v) => Some(v),
+                _ => None,
+            }),
+            Value::Char(value) => range_contains(value, start, end, limits, |v| match v {
+                InstValue::Char(v) => Some(v),
+                _ => None,
+            }),
+            Value::Byte(value) => range_contains(value, start, end, limits, |v| match v {
+                InstValue::Byte(v) => Some(v),
+                _ => None,
+            }),
+            Value::U16(..) | Value::U32(..) | Value::U64(..) | Value::I32(..) => {
+                match value.as_wide_integer() {
+                    Some(value) => range_contains(value, start, end, limits, |v| match v {
+                        InstValue::Integer(v) => Some(i128::from(v)),
+                        _ => None,
+                    }),
+                    None => false,
+                }
+            }
+            _ => false,
+        };
+
+        self.stack.push(is_match);
+        Ok(())
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_match_sequence(&mut self, ty: TypeCheck, len: usize, exact: bool) -> Result<(), VmError> {
         let value = self.stack.pop()?;
@@ -2452,6 +2574,41 @@ impl Vm {
         Ok(())
     }
 
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_check_arg_type(&mut self, offset: usize, arg: usize, hash: Hash) -> Result<(), VmError> {
+        let value = self.stack.at_offset(offset)?;
+
+        if value.type_hash()? == hash {
+            return Ok(());
+        }
+
+        let actual = value.type_info()?;
+
+        Err(VmError::from(VmErrorKind::BadArgumentType {
+            arg,
+            expected: self.type_name(hash),
+            actual,
+        }))
+    }
+
+    /// Get a human readable name for the given type hash.
+    fn type_name(&self, hash: Hash) -> String {
+        let key = Hash::instance_function(hash, Protocol::INTO_TYPE_NAME);
+
+        let name = self
+            .context
+            .constant(key)
+            .or_else(|| self.unit.constant(key));
+
+        match name {
+            Some(ConstValue::String(name)) => name.clone(),
+            Some(ConstValue::StaticString(name)) => name.to_string(),
+            _ if hash == crate::runtime::UNIT_TYPE.hash => String::from("()"),
+            _ if hash == crate::runtime::TUPLE_TYPE.hash => String::from("Tuple"),
+            _ => hash.to_string(),
+        }
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_match_type(&mut self, hash: Hash) -> Result<(), VmError> {
         let value = self.stack.pop()?;
@@ -3101,6 +3258,9 @@ impl Vm {
                 Inst::EqBytes { slot } => {
                     self.op_eq_bytes(slot)?;
                 }
+                Inst::MatchRange { start, end, limits } => {
+                    self.op_match_range(start, end, limits)?;
+                }
                 Inst::MatchSequence {
                     type_check,
                     len,
@@ -3108,6 +3268,9 @@ impl Vm {
                 } => {
                     self.op_match_sequence(type_check, len, exact)?;
                 }
+                Inst::CheckArgType { offset, arg, hash } => {
+                    self.op_check_arg_type(offset, arg, hash)?;
+                }
                 Inst::MatchType { hash } => {
                     self.op_match_type(hash)?;
                 }
@@ -3204,3 +3367,133 @@ impl Drop for ClearStack<'_> {
         self.0.stack.clear();
     }
 }
+
+/// Test if a value is contained in a range whose bounds are converted with
+/// `convert`. A bound of a different type never matches.
+fn range_contains<T>(
+    value: T,
+    start: Option<InstValue>,
+    end: Option<InstValue>,
+    limits: InstRangeLimits,
+    convert: impl Fn(InstValue) -> Option<T>,
+) -> bool
+where
+    T: PartialOrd,
+{
+    if let Some(start) = start {
+        match convert(start) {
+            Some(start) if value >= start => (),
+            _ => return false,
+        }
+    }
+
+    if let Some(end) = end {
+        match (convert(end), limits) {
+            (Some(end), InstRangeLimits::HalfOpen) if value < end => (),
+            (Some(end), InstRangeLimits::Closed) if value <= end => (),
+            _ => return false,
+        }
+    }
+
+    true
+}
+
+/// Apply a binary operator protocol to two sized integers of the same type,
+/// like `u32` or `i32`. A plain integer operand is converted into the type of
+/// the sized integer it's used with, and shifts accept a plain integer as the
+/// right hand side.
+///
+/// Returns `None` if the operands aren't sized integers this applies to.
+fn sized_binary(
+    protocol: Protocol,
+    lhs: &Value,
+    rhs: &Value,
+) -> Option<Result<Value, VmErrorKind>> {
+    use std::convert::TryFrom as _;
+
+    let hash = protocol.hash;
+    let is = |op: Protocol, assign: Protocol| hash == op.hash || hash == assign.hash;
+    let is_shift =
+        is(Protocol::SHL, Protocol::SHL_ASSIGN) || is(Protocol::SHR, Protocol::SHR_ASSIGN);
+
+    macro_rules! shift {
+        ($variant:ident, $a:expr, $b:expr) => {{
+            let shift = u32::try_from($b).ok();
+
+            let out = if is(Protocol::SHL, Protocol::SHL_ASSIGN) {
+                shift.and_then(|b| $a.checked_shl(b))
+            } else {
+                shift.and_then(|b| $a.checked_shr(b))
+            };
+
+            out.map(Value::$variant).ok_or(VmErrorKind::Overflow)
+        }};
+    }
+
+    macro_rules! binary {
+        ($variant:ident, $a:expr, $b:expr) => {{
+            let (a, b) = ($a, $b);
+
+            if is_shift {
+                shift!($variant, a, b)
+            } else if is(Protocol::ADD, Protocol::ADD_ASSIGN) {
+                a.checked_add(b)
+                    .map(Value::$variant)
+                    .ok_or(VmErrorKind::Overflow)
+            } else if is(Protocol::SUB, Protocol::SUB_ASSIGN) {
+                a.checked_sub(b)
+                    .map(Value::$variant)
+                    .ok_or(VmErrorKind::Underflow)
+            } else if is(Protocol::MUL, Protocol::MUL_ASSIGN) {
+                a.checked_mul(b)
+                    .map(Value::$variant)
+                    .ok_or(VmErrorKind::Overflow)
+            } else if is(Protocol::DIV, Protocol::DIV_ASSIGN) {
+                a.checked_div(b)
+                    .map(Value::$variant)
+                    .ok_or(VmErrorKind::DivideByZero)
+            } else if is(Protocol::REM, Protocol::REM_ASSIGN) {
+                a.checked_rem(b)
+                    .map(Value::$variant)
+                    .ok_or(VmErrorKind::DivideByZero)
+            } else if is(Protocol::BIT_AND, Protocol::BIT_AND_ASSIGN) {
+                Ok(Value::$variant(a & b))
+            } else if is(Protocol::BIT_XOR, Protocol::BIT_XOR_ASSIGN) {
+                Ok(Value::$variant(a ^ b))
+            } else if is(Protocol::BIT_OR, Protocol::BIT_OR_ASSIGN) {
+                Ok(Value::$variant(a | b))
+            } else {
+                return None;
+            }
+        }};
+    }
+
+    macro_rules! coerce {
+        ($ty:ty, $integer:expr, |$n:ident| $expr:expr) => {
+            match <$ty>::try_from($integer) {
+                Ok($n) => $expr,
+                Err(..) => Err(VmErrorKind::Overflow),
+            }
+        };
+    }
+
+    macro_rules! sized {
+        ($($variant:ident => $ty:ty),*) => {
+            match (lhs, rhs) {
+                (Value::Byte(a), Value::Byte(b)) => binary!(Byte, *a, *b),
+                (Value::Byte(a), Value::Integer(b)) if is_shift => shift!(Byte, *a, *b),
+                $((Value::$variant(a), Value::$variant(b)) => binary!($variant, *a, *b),)*
+                $((Value::$variant(a), Value::Integer(b)) if is_shift => shift!($variant, *a, *b),)*
+                $((Value::$variant(a), Value::Integer(b)) => {
+                    coerce!($ty, *b, |b| binary!($variant, *a, b))
+                })*
+                $((Value::Integer(a), Value::$variant(b)) if !is_shift => {
+                    coerce!($ty, *a, |a| binary!($variant, a, *b))
+                })*
+                _ => return None,
+            }
+        };
+    }
+
+    Some(sized!(U16 => u16, U32 => u32, U64 => u64, I32 => i32))
+}
@@ -1,8 +1,8 @@
 use crate::compile::ItemBuf;
 use crate::runtime::panic::BoxedPanic;
 use crate::runtime::{
-    AccessError, CallFrame, ExecutionState, Key, Panic, Protocol, StackError, TypeInfo, TypeOf,
-    Unit, Value, VmHaltInfo,
+    AccessError, CallFrame, ExecutionState, Panic, Protocol, StackError, TypeInfo, TypeOf, Unit,
+    Value, VmHaltInfo,
 };
 use crate::Hash;
 use std::fmt;
@@ -202,6 +202,8 @@ pub enum VmErrorKind {
         lhs: TypeInfo,
         rhs: TypeInfo,
     },
+    #[error("cannot establish an ordering between the floats `{lhs}` and `{rhs}`")]
+    IllegalFloatComparison { lhs: f64, rhs: f64 },
     #[error("unsupported vm operation `{op}{operand}`")]
     UnsupportedUnaryOperation { op: &'static str, operand: TypeInfo },
     #[error("`{actual}` does not implement the `{protocol}` protocol")]
@@ -225,6 +227,12 @@ pub enum VmErrorKind {
         expected: TypeInfo,
         actual: TypeInfo,
     },
+    #[error("bad argument #{arg}, expected `{expected}` but got `{actual}`")]
+    BadArgumentType {
+        arg: usize,
+        expected: String,
+        actual: TypeInfo,
+    },
     #[error("bad argument #{arg}: {error}")]
     BadArgument {
         #[source]
@@ -263,8 +271,8 @@ pub enum VmErrorKind {
         target: TypeInfo,
         index: VmIntegerRepr,
     },
-    #[error("`{target}` missing index `{index:?}`")]
-    MissingIndexKey { target: TypeInfo, index: Key },
+    #[error("`{target}` missing index `{index}`")]
+    MissingIndexKey { target: TypeInfo, index: String },
     #[error("index out of bounds: the len is ${len} but the index is {index}")]
     OutOfRange {
         index: VmIntegerRepr,
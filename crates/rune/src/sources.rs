@@ -72,6 +72,11 @@ impl Sources {
         source.path()
     }
 
+    /// Get the number of sources.
+    pub(crate) fn len(&self) -> usize {
+        self.sources.len()
+    }
+
     /// Get all available source ids.
     pub(crate) fn source_ids(&self) -> impl Iterator<Item = SourceId> {
         (0..self.sources.len()).map(|index| SourceId::new(index as u32))
@@ -62,6 +62,9 @@ export class Config {
             Object.entries(extraEnv).map(([k, v]) => [k, typeof v !== "string" ? v.toString() : v])
         );
     }
+    get debuggerPath() {
+        return this.get<string>("debugger.path");
+    }
     get updatesCheckInterval() {
         return this.get<number>("updates.checkInterval");
     }
@@ -10,6 +10,7 @@ import { download, fetchRelease } from "./net";
 
 let client: lc.LanguageClient | undefined;
 let reload: lc.Disposable | undefined;
+let debugAdapter: vscode.Disposable | undefined;
 
 const RUNE_PROJECT_CONTEXT_NAME = "inRuneProject";
 
@@ -43,6 +44,16 @@ export async function activate(
 
 async function tryActivate(context: vscode.ExtensionContext): Promise<lc.LanguageClient> {
 	const config = new Config(context);
+
+	// The debug adapter is served by the `rune` binary, so it doesn't depend
+	// on the language server being available.
+	debugAdapter?.dispose();
+	debugAdapter = vscode.debug.registerDebugAdapterDescriptorFactory("rune", {
+		createDebugAdapterDescriptor() {
+			return new vscode.DebugAdapterExecutable(config.debuggerPath, ["dap"]);
+		}
+	});
+
 	const state = new PersistentState(context.globalState);
 	const serverPath = await bootstrap(context, config, state).catch((err) => {
 		let message = "bootstrap error. ";
@@ -80,6 +91,8 @@ export async function deactivate() {
 	OUTPUT_CHANNEL = null;
 	reload?.dispose();
 	reload = undefined;
+	debugAdapter?.dispose();
+	debugAdapter = undefined;
 	await doDeactivate();
 }
 
@@ -1,5 +1,11 @@
+use rune::runtime::{Key, Protocol, VmError, VmErrorKind};
+use rune::{Any, Module};
 use rune_tests::*;
 
+#[derive(Debug, Any, Clone, Copy, PartialEq, Eq, Hash)]
+#[rune(eq, hash)]
+struct UserId(u32);
+
 #[test]
 fn test_hash_map_tile() {
     let _: () = rune! {
@@ -41,3 +47,306 @@ fn test_hash_set_tuple() {
         }
     };
 }
+
+#[test]
+fn test_hash_map_script_struct_key() {
+    let out: (i64, i64, bool) = rune! {
+        use std::collections::HashMap;
+
+        struct Point { x, y }
+
+        impl Point {
+            fn hash(self) {
+                self.x * 31 + self.y
+            }
+        }
+
+        pub fn main() {
+            let m = HashMap::new();
+            m.insert(Point { x: 1, y: 2 }, 10);
+            m.insert(Point { x: 2, y: 1 }, 20);
+            m[Point { x: 1, y: 2 }] = 30;
+
+            (m[Point { x: 1, y: 2 }], m.len(), m.contains_key(Point { x: 3, y: 3 }))
+        }
+    };
+    assert_eq!(out, (30, 2, false));
+}
+
+#[test]
+fn test_hash_set_native_key() {
+    let mut module = Module::new();
+    module.ty::<UserId>().unwrap();
+
+    let out: (bool, bool, i64) = rune_n! {
+        module,
+        (UserId(1), UserId(1), UserId(2)),
+        (bool, bool, i64) => pub fn main(a, b, c) {
+            let set = std::collections::HashSet::new();
+            set.insert(a);
+            set.insert(b);
+            (set.contains(b), set.contains(c), set.len())
+        }
+    };
+    assert_eq!(out, (true, false, 1));
+}
+
+#[derive(Debug, Any, Clone, Copy)]
+struct Flaky;
+
+#[test]
+fn test_hash_map_key_outside_vm() -> rune::Result<()> {
+    let value = rune::ToValue::to_value(UserId(1))?;
+
+    // NB: the hash protocol can't be called outside of a virtual machine.
+    let error = Key::from_value(&value).unwrap_err();
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::KeyNotSupported { .. }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_hash_map_key_eq_error() {
+    let mut module = Module::new();
+    module.ty::<Flaky>().unwrap();
+    module
+        .inst_fn(Protocol::HASH, |_: &Flaky| 0i64)
+        .unwrap();
+    module
+        .inst_fn(Protocol::EQ, |_: &Flaky, _: &Flaky| {
+            Err::<bool, _>(VmError::panic("incomparable"))
+        })
+        .unwrap();
+
+    // NB: keys which raise an error when compared are never equal.
+    let out: (i64, bool) = rune_n! {
+        module,
+        (Flaky, Flaky),
+        (i64, bool) => pub fn main(a, b) {
+            let map = std::collections::HashMap::new();
+            map.insert(a, 1);
+            map.insert(b, 2);
+            (map.len(), map.contains_key(b))
+        }
+    };
+    assert_eq!(out, (2, false));
+}
+
+#[test]
+fn test_btree_map() {
+    let _: () = rune! {
+        pub fn main() {
+            use std::collections::BTreeMap;
+
+            let m = BTreeMap::new();
+            m.insert("c", 3);
+            m.insert("a", 1);
+            m["b"] = 2;
+            m.insert("d", 4);
+
+            assert_eq!(m.keys().collect::<Vec>(), ["a", "b", "c", "d"]);
+            assert_eq!(m.values().collect::<Vec>(), [1, 2, 3, 4]);
+            assert_eq!(m.range("b".."d").collect::<Vec>(), [("b", 2), ("c", 3)]);
+            assert_eq!(m.range("b"..="d").collect::<Vec>(), [("b", 2), ("c", 3), ("d", 4)]);
+            assert_eq!(m.range(.."b").collect::<Vec>(), [("a", 1)]);
+            assert_eq!(m.first(), Some(("a", 1)));
+            assert_eq!(m.last(), Some(("d", 4)));
+            assert_eq!(m.pop_first(), Some(("a", 1)));
+            assert_eq!(m.pop_last(), Some(("d", 4)));
+            assert_eq!(m.remove("b"), Some(2));
+            assert_eq!(m.len(), 1);
+            assert_eq!(m["c"], 3);
+
+            let keys = [];
+
+            for (k, v) in BTreeMap::from([(3, "c"), (1, "a"), (2, "b")]) {
+                keys.push(k);
+            }
+
+            assert_eq!(keys, [1, 2, 3]);
+        }
+    };
+}
+
+#[test]
+fn test_btree_set() {
+    let _: () = rune! {
+        pub fn main() {
+            use std::collections::BTreeSet;
+
+            let s = BTreeSet::from([5, 1, 4, 2, 3]);
+            assert_eq!(s.iter().collect::<Vec>(), [1, 2, 3, 4, 5]);
+            assert_eq!(s.range(2..4).collect::<Vec>(), [2, 3]);
+            assert_eq!(s.range(4..).collect::<Vec>(), [4, 5]);
+            assert_eq!(s.first(), Some(1));
+            assert_eq!(s.last(), Some(5));
+            assert_eq!(s.pop_first(), Some(1));
+            assert_eq!(s.pop_last(), Some(5));
+            assert!(s.contains(3));
+            assert!(s.remove(3));
+            assert!(!s.insert(2));
+
+            let other = BTreeSet::from([2, 6]);
+            assert_eq!(s.union(other).collect::<Vec>(), [2, 4, 6]);
+            assert_eq!(s.intersection(other).collect::<Vec>(), [2]);
+            assert_eq!(s.difference(other).collect::<Vec>(), [4]);
+            assert_eq!(s, BTreeSet::from([4, 2]));
+        }
+    };
+}
+
+#[test]
+fn test_btree_colliding_hash_keys() {
+    let out: (i64, i64, i64, Vec<i64>, Option<i64>) = rune! {
+        use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+        struct Id { id }
+
+        impl Id {
+            fn hash(self) {
+                1
+            }
+
+            fn cmp(self, other) {
+                self.id.cmp(other.id)
+            }
+        }
+
+        pub fn main() {
+            let m = BTreeMap::new();
+            m.insert(Id { id: 3 }, "c");
+            m.insert(Id { id: 1 }, "a");
+            m.insert(Id { id: 2 }, "b");
+            m.insert(Id { id: 1 }, "d");
+
+            let s = BTreeSet::from([Id { id: 2 }, Id { id: 3 }, Id { id: 1 }]);
+            let h = HashMap::from([(Id { id: 1 }, 1), (Id { id: 2 }, 2), (Id { id: 3 }, 3)]);
+
+            let ids = m.keys().map(|k| k.id).collect::<Vec>();
+            (m.len(), s.len(), h.len(), ids, m.get(Id { id: 1 }).map(|v| v.len()))
+        }
+    };
+    assert_eq!(out, (3, 3, 3, vec![1, 2, 3], Some(1)));
+}
+
+#[test]
+fn test_btree_incomparable_keys() -> rune::Result<()> {
+    let context = modules::default_context()?;
+
+    let mut sources = sources(
+        r#"
+        use std::collections::BTreeMap;
+
+        pub fn new() { BTreeMap::from([(1, "a"), (2, "b")]) }
+        pub fn insert(m, key) { m.insert(key, "c") }
+        pub fn check(m) { m.len() == 2 && m.get(1) == Some("a") }
+        "#,
+    );
+
+    let mut vm = vm(&context, &mut sources, &mut Default::default())?;
+    let m = vm.call(["new"], ())?;
+
+    let (error, _) = vm
+        .call(["insert"], (m.clone(), "c"))
+        .unwrap_err()
+        .into_unwound();
+
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::UnsupportedBinaryOperation { .. }
+    ));
+
+    // NB: a failed insert leaves the map as it was.
+    let ok: bool = rune::FromValue::from_value(vm.call(["check"], (m,))?)?;
+    assert!(ok);
+    Ok(())
+}
+
+#[test]
+fn test_binary_heap() {
+    let _: () = rune! {
+        pub fn main() {
+            use std::collections::BinaryHeap;
+
+            let heap = BinaryHeap::new();
+            heap.push(3);
+            heap.push(7);
+            heap.push(1);
+            heap.push(5);
+
+            assert_eq!(heap.len(), 4);
+            assert_eq!(heap.peek(), Some(7));
+            assert_eq!(heap.pop(), Some(7));
+            assert_eq!(heap.pop(), Some(5));
+            assert_eq!(heap.into_sorted_vec(), [1, 3]);
+
+            let heap = BinaryHeap::from([(2, "b"), (9, "z"), (5, "e")]);
+            assert_eq!(heap.pop(), Some((9, "z")));
+            assert_eq!(heap.pop(), Some((5, "e")));
+            assert_eq!(heap.pop(), Some((2, "b")));
+            assert_eq!(heap.pop(), None);
+            assert!(heap.is_empty());
+
+            let heap = BinaryHeap::with_comparator(|a, b| b.cmp(a));
+            heap.extend([4, 8, 2, 6]);
+            assert_eq!(heap.pop(), Some(2));
+            assert_eq!(heap.pop(), Some(4));
+            assert_eq!(heap.into_sorted_vec(), [8, 6]);
+
+            let heap = BinaryHeap::from([3, 1, 2]);
+            let values = heap.into_vec();
+            values.sort();
+            assert_eq!(values, [1, 2, 3]);
+        }
+    };
+
+    // NB: the heap is consumed by `into_sorted_vec`.
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let heap = std::collections::BinaryHeap::from([1, 2]);
+            heap.into_sorted_vec();
+            heap.len()
+        }
+        "#,
+        VmErrorKind::AccessError { .. } => {}
+    );
+}
+
+#[test]
+fn test_binary_heap_protocol() {
+    #[derive(Any, Clone, Copy)]
+    struct Task {
+        #[rune(get)]
+        priority: i64,
+    }
+
+    let mut module = Module::new();
+    module.ty::<Task>().unwrap();
+    module.function(["Task", "new"], |priority: i64| Task { priority }).unwrap();
+    module
+        .inst_fn(rune::runtime::Protocol::CMP, |a: &Task, b: &Task| {
+            a.priority.cmp(&b.priority)
+        })
+        .unwrap();
+
+    let out: Vec<i64> = rune_n! {
+        module,
+        (),
+        Vec<i64> => pub fn main() {
+            let heap = std::collections::BinaryHeap::from([Task::new(2), Task::new(10), Task::new(4)]);
+            let out = [];
+
+            while let Some(task) = heap.pop() {
+                out.push(task.priority);
+            }
+
+            out
+        }
+    };
+
+    assert_eq!(out, vec![10, 4, 2]);
+}
@@ -42,4 +42,21 @@ fn test_number_literals() {
             assert_eq!(span, span!(16, 82));
         }
     };
+    assert_compile_error! {
+        r#"pub fn main() { 256u8 }"#,
+        span, ParseError { error: error @ BadSizedNumberOutOfBounds { min: 0, max: 255, .. }} => {
+            assert_eq!(span, span!(16, 21));
+            assert_eq!(
+                error.to_string(),
+                "number literal out of bounds `0` to `255` for type `u8`"
+            );
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { -2147483649i32 }"#,
+        span, ParseError { error: BadSizedNumberOutOfBounds { min: -2147483648, max: 2147483647, .. }} => {
+            assert_eq!(span, span!(16, 30));
+        }
+    };
 }
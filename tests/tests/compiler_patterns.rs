@@ -32,3 +32,14 @@ fn illegal_pattern_in_match() {
         }
     };
 }
+
+#[test]
+fn or_pattern_binding_mismatch() {
+    assert_compile_error! {
+        r#"pub fn main() { match (1, 2) { (a, 1) | (1, b) => a, _ => 0 } }"#,
+        span, VariableNotBoundInAllPatterns { name } => {
+            assert_eq!(name, "a");
+            assert_eq!(span, span!(40, 46));
+        }
+    };
+}
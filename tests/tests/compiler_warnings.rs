@@ -12,6 +12,16 @@ fn test_let_pattern_might_panic() {
     };
 }
 
+#[test]
+fn test_irrefutable_let_else() {
+    assert_warnings! {
+        r#"pub fn main() { let a = 1 else { return; }; a }"#,
+        NotUsed { span, .. } => {
+            assert_eq!(span, span!(26, 42));
+        }
+    };
+}
+
 #[test]
 fn test_template_without_variables() {
     assert_warnings! {
@@ -0,0 +1,110 @@
+use rune::runtime::debugger::{Debugger, Stop};
+use rune::{Context, Source, SourceId, Sources, Unit, Value, Vm};
+use std::sync::Arc;
+
+const SOURCE: &str = r#"
+fn add(a, b) {
+    let sum = a + b;
+    sum
+}
+
+pub fn main() {
+    let x = 1;
+    let y = add(x, 2);
+    let z = y * 2;
+    z
+}
+"#;
+
+fn prepare() -> rune::Result<(Vm, Sources, SourceId)> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime());
+
+    let mut sources = Sources::new();
+    let id = sources.insert(Source::new("script", SOURCE));
+
+    let unit: Unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    Ok((Vm::new(runtime, Arc::new(unit)), sources, id))
+}
+
+fn names(debugger: &Debugger<&mut Vm>, frame: usize) -> Vec<String> {
+    let frames = debugger.frames();
+
+    debugger
+        .locals(&frames[frame])
+        .into_iter()
+        .map(|(name, _)| name.to_owned())
+        .collect()
+}
+
+#[test]
+fn test_breakpoint_and_locals() -> rune::Result<()> {
+    let (mut vm, sources, id) = prepare()?;
+    let mut debugger = Debugger::new(vm.execute(["main"], ())?, &sources);
+
+    assert!(debugger.set_breakpoint(id, 3));
+    assert!(!debugger.set_breakpoint(id, 5));
+
+    assert!(matches!(debugger.resume()?, Stop::Breakpoint));
+    assert_eq!(debugger.line().map(|l| l.line), Some(3));
+
+    let frames = debugger.frames();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[1].line.map(|l| l.line), Some(8));
+
+    assert_eq!(names(&debugger, 0), ["a", "b", "sum"]);
+    assert_eq!(names(&debugger, 1), ["x"]);
+
+    let locals = debugger.locals(&frames[0]);
+    assert!(matches!(locals[2].1, Value::Integer(3)));
+
+    match debugger.resume()? {
+        Stop::Complete(Value::Integer(6)) => (),
+        stop => panic!("unexpected stop: {:?}", stop),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_stepping() -> rune::Result<()> {
+    let (mut vm, sources, id) = prepare()?;
+    let mut debugger = Debugger::new(vm.execute(["main"], ())?, &sources);
+
+    assert!(debugger.set_breakpoint(id, 8));
+    assert!(matches!(debugger.resume()?, Stop::Breakpoint));
+
+    assert!(matches!(debugger.step_into()?, Stop::Step));
+    assert_eq!(debugger.line().map(|l| l.line), Some(1));
+    assert_eq!(debugger.frames().len(), 2);
+
+    // NB: the call is the last instruction on its line, so returning from it
+    // continues on the next line.
+    assert!(matches!(debugger.step_out()?, Stop::Step));
+    assert_eq!(debugger.line().map(|l| l.line), Some(9));
+    assert_eq!(debugger.frames().len(), 1);
+    assert_eq!(names(&debugger, 0), ["x", "y"]);
+
+    assert!(matches!(debugger.step_over()?, Stop::Step));
+    assert_eq!(debugger.line().map(|l| l.line), Some(10));
+    assert_eq!(names(&debugger, 0), ["x", "y", "z"]);
+
+    assert!(debugger.remove_breakpoint(id, 8));
+    assert!(matches!(debugger.resume()?, Stop::Complete(..)));
+    Ok(())
+}
+
+#[test]
+fn test_step_over_call() -> rune::Result<()> {
+    let (mut vm, sources, id) = prepare()?;
+    let mut debugger = Debugger::new(vm.execute(["main"], ())?, &sources);
+
+    assert!(debugger.set_breakpoint(id, 8));
+    assert!(matches!(debugger.resume()?, Stop::Breakpoint));
+    assert_eq!(names(&debugger, 0), ["x"]);
+
+    assert!(matches!(debugger.step_over()?, Stop::Step));
+    assert_eq!(debugger.line().map(|l| l.line), Some(9));
+    assert_eq!(debugger.frames().len(), 1);
+    Ok(())
+}
@@ -0,0 +1,180 @@
+use rune::fmt::format_source;
+
+#[track_caller]
+fn assert_format(input: &str, expected: &str) {
+    let actual = format_source(input).expect("source should parse");
+    assert_eq!(actual, expected);
+
+    let again = format_source(&actual).expect("formatted source should parse");
+    assert_eq!(again, actual, "formatting should be idempotent");
+}
+
+#[test]
+fn test_fmt_items() {
+    assert_format(
+        "use std::collections::{HashMap,HashSet as Set};\nstruct Point{x,y}\nenum Shape{Circle(r),Rect{w,h}}\nconst LIMIT=10;\n",
+        r#"use std::collections::{HashMap, HashSet as Set};
+struct Point {
+    x,
+    y,
+}
+enum Shape {
+    Circle(r),
+    Rect { w, h },
+}
+const LIMIT = 10;
+"#,
+    );
+
+    assert_format(
+        "trait Shape{fn area(self);fn double(self){self.area()*2}}\nimpl Shape for Point{fn area(self){0}}\n",
+        r#"trait Shape {
+    fn area(self);
+    fn double(self) {
+        self.area() * 2
+    }
+}
+impl Shape for Point {
+    fn area(self) {
+        0
+    }
+}
+"#,
+    );
+
+    assert_format(
+        "fn f(a:int,(b,c):(int,String))->float{let x:float=1.0;x}",
+        r#"fn f(a: int, (b, c): (int, String)) -> float {
+    let x: float = 1.0;
+    x
+}
+"#,
+    );
+}
+
+#[test]
+fn test_fmt_expressions() {
+    assert_format(
+        "pub fn main(){let t=(1,);let o=#{a:1};if t.0>1{return;}else{o}}",
+        r#"pub fn main() {
+    let t = (1,);
+    let o = #{a: 1};
+    if t.0 > 1 {
+        return;
+    } else {
+        o
+    }
+}
+"#,
+    );
+
+    assert_format(
+        "fn f(v){match v{Some(n) if n>2=>{n}_=>0}}",
+        r#"fn f(v) {
+    match v {
+        Some(n) if n > 2 => {
+            n
+        }
+        _ => 0,
+    }
+}
+"#,
+    );
+
+    assert_format(
+        "fn f(v){match v{1|2=>0,'a'..='z'=>1,..=-1=>2,_=>3}}",
+        r#"fn f(v) {
+    match v {
+        1 | 2 => 0,
+        'a'..='z' => 1,
+        ..=-1 => 2,
+        _ => 3,
+    }
+}
+"#,
+    );
+
+    assert_format(
+        "fn f(v){let Some(a)=v else{return;};if let Some(b)=a&&b>1&&let [c]=b{c}else{0}}",
+        r#"fn f(v) {
+    let Some(a) = v else {
+        return;
+    };
+    if let Some(b) = a && b > 1 && let [c] = b {
+        c
+    } else {
+        0
+    }
+}
+"#,
+    );
+}
+
+#[test]
+fn test_fmt_long_lists() {
+    assert_format(
+        "fn f() { call_something(argument_number_one, argument_number_two, argument_number_three, argument_number_four) }",
+        r#"fn f() {
+    call_something(
+        argument_number_one,
+        argument_number_two,
+        argument_number_three,
+        argument_number_four,
+    )
+}
+"#,
+    );
+}
+
+#[test]
+fn test_fmt_comments() {
+    assert_format(
+        r#"// leading
+/// Docs.
+fn f() { // after open
+    let a = 1; // trailing
+
+
+    /* block */
+    a
+    // end
+}
+"#,
+        r#"// leading
+/// Docs.
+fn f() { // after open
+    let a = 1; // trailing
+
+    /* block */
+    a
+    // end
+}
+"#,
+    );
+}
+
+#[test]
+fn test_fmt_field_docs() {
+    let source = r#"/// Docs.
+struct Foo {
+    /// Field A.
+    /// More about A.
+    a,
+    /** Field B. */
+    b,
+}
+enum Bar {
+    Baz {
+        /// Field C.
+        c,
+    },
+}
+"#;
+
+    assert_format(source, source);
+}
+
+#[test]
+fn test_fmt_parse_error() {
+    assert!(format_source("fn main( {").is_err());
+}
@@ -1,3 +1,4 @@
+use rune::runtime::VmErrorKind::*;
 use rune_tests::*;
 
 #[test]
@@ -58,3 +59,73 @@ fn test_float_fns() {
     };
     assert_eq!(n, 1728.0);
 }
+
+#[test]
+fn test_float_math() {
+    let _: () = rune! {
+        pub fn main() {
+            assert_eq!(16.0.sqrt(), 4.0);
+            assert_eq!(27.0.cbrt(), 3.0);
+            assert_eq!(3.0.hypot(4.0), 5.0);
+            assert_eq!(0.0.sin(), 0.0);
+            assert_eq!(0.0.cos(), 1.0);
+            assert_eq!(0.0.tan(), 0.0);
+            assert_eq!(1.0.atan2(1.0), std::float::consts::PI / 4.0);
+            assert_eq!(0.0.exp(), 1.0);
+            assert_eq!(std::float::consts::E.ln(), 1.0);
+            assert_eq!(1000.0.log10(), 3.0);
+            assert_eq!(8.0.log2(), 3.0);
+            assert_eq!(81.0.log(3.0), 4.0);
+        }
+    };
+}
+
+#[test]
+fn test_float_rounding() {
+    let _: () = rune! {
+        pub fn main() {
+            assert_eq!(2.5.floor(), 2.0);
+            assert_eq!(2.1.ceil(), 3.0);
+            assert_eq!(2.5.round(), 3.0);
+            assert_eq!((-2.5).round(), -3.0);
+            assert_eq!((-2.7).trunc(), -2.0);
+            assert_eq!(5.5.clamp(0.0, 5.0), 5.0);
+            assert_eq!((-1.0).clamp(0.0, 5.0), 0.0);
+            assert_eq!((-3.0).signum(), -1.0);
+        }
+    };
+
+    assert_vm_error!(
+        "pub fn main() { 1.0.clamp(2.0, 1.0) }",
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "clamp requires min <= max and neither can be NaN");
+        }
+    );
+}
+
+#[test]
+fn test_float_classification() {
+    let _: () = rune! {
+        pub fn main() {
+            assert!(std::float::consts::NAN.is_nan());
+            assert!(!1.0.is_nan());
+            assert!(1.0.is_finite());
+            assert!(!std::float::consts::INFINITY.is_finite());
+            assert!(std::float::consts::NEG_INFINITY.is_infinite());
+            assert!(std::float::consts::EPSILON > 0.0);
+            assert_eq!(std::float::consts::TAU, std::float::consts::PI * 2.0);
+        }
+    };
+}
+
+#[test]
+fn test_float_bits() {
+    let out: u64 = rune!(pub fn main() { 1.0.to_bits() });
+    assert_eq!(out, 1.0f64.to_bits());
+
+    let out: f64 = rune!(pub fn main() { std::float::from_bits(4607182418800017408u64) });
+    assert_eq!(out, 1.0);
+
+    let out: f64 = rune!(pub fn main() { std::float::from_bits((-2.5).to_bits()) });
+    assert_eq!(out, -2.5);
+}
@@ -0,0 +1,216 @@
+use rune::{Context, FromValue};
+use std::path::{Path, PathBuf};
+
+/// A temporary directory which is removed when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("rune-fs-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+fn run_fs<T>(root: Option<&Path>, source: &str, arg: String) -> T
+where
+    T: FromValue,
+{
+    let mut context = Context::with_default_modules().unwrap();
+    context
+        .install(&rune_modules::fmt::module(true).unwrap())
+        .unwrap();
+    context
+        .install(&rune_modules::test::module(true).unwrap())
+        .unwrap();
+
+    let module = match root {
+        Some(root) => rune_modules::fs::module_with_root(root).unwrap(),
+        None => rune_modules::fs::module(true).unwrap(),
+    };
+
+    context.install(&module).unwrap();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let _guard = runtime.enter();
+
+    let result: Result<T, rune::Value> =
+        rune_tests::run(&context, source, ["main"], (arg,)).unwrap();
+    result.expect("script returned an error")
+}
+
+#[test]
+fn test_fs_read_write() {
+    let dir = TempDir::new("read-write");
+
+    let out: (String, i64, bool, bool) = run_fs(
+        None,
+        r#"
+        pub async fn main(dir) {
+            let path = format!("{}/hello.txt", dir);
+            fs::write(path, "hello").await?;
+            fs::append(path, b" world").await?;
+
+            let text = fs::read_to_string(path).await?;
+            let bytes = fs::read(path).await?;
+            let meta = fs::metadata(path).await?;
+            assert!(meta.is_file());
+            assert!(meta.modified()? > 0.0);
+
+            Ok((text, bytes.len(), meta.is_dir(), fs::exists(format!("{}/missing", dir)).await?))
+        }
+        "#,
+        dir.0.display().to_string(),
+    );
+
+    assert_eq!(out, (String::from("hello world"), 11, false, false));
+}
+
+#[test]
+fn test_fs_directories() {
+    let dir = TempDir::new("directories");
+
+    let out: Vec<String> = run_fs(
+        None,
+        r#"
+        pub async fn main(dir) {
+            fs::create_dir_all(format!("{}/a/b", dir)).await?;
+            fs::write(format!("{}/a/one.txt", dir), "1").await?;
+            assert_eq!(fs::copy(format!("{}/a/one.txt", dir), format!("{}/a/two.txt", dir)).await?, 1);
+            fs::rename(format!("{}/a/two.txt", dir), format!("{}/a/b/three.txt", dir)).await?;
+
+            let names = [];
+            let entries = fs::read_dir(format!("{}/a", dir)).await?;
+
+            while let Some(entry) = entries.next().await? {
+                let suffix = if entry.metadata().await?.is_dir() { "/" } else { "" };
+                names.push(format!("{}{}", entry.file_name(), suffix));
+            }
+
+            names.sort();
+            fs::remove_file(format!("{}/a/one.txt", dir)).await?;
+            assert!(!fs::exists(format!("{}/a/one.txt", dir)).await?);
+            fs::remove_dir_all(format!("{}/a", dir)).await?;
+            assert!(!fs::exists(format!("{}/a", dir)).await?);
+            assert!(fs::read_to_string(format!("{}/a/b/three.txt", dir)).await.is_err());
+            Ok(names)
+        }
+        "#,
+        dir.0.display().to_string(),
+    );
+
+    assert_eq!(out, vec![String::from("b/"), String::from("one.txt")]);
+}
+
+#[test]
+fn test_fs_root() {
+    let dir = TempDir::new("root");
+    let root = dir.0.join("root");
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(dir.0.join("secret.txt"), "secret").unwrap();
+
+    let out: Vec<String> = run_fs(
+        Some(&root),
+        r#"
+        pub async fn main(outside) {
+            fs::create_dir_all("/data").await?;
+            fs::write("data/file.txt", "inside").await?;
+            assert_eq!(fs::read_to_string("/data/../data/./file.txt").await?, "inside");
+
+            let entries = fs::read_dir("data").await?;
+            let paths = [];
+
+            while let Some(entry) = entries.next().await? {
+                paths.push(entry.path());
+                assert_eq!(fs::read_to_string(entry.path()).await?, "inside");
+            }
+
+            assert!(fs::read_to_string("../secret.txt").await.is_err());
+            assert!(fs::read_to_string("data/../../secret.txt").await.is_err());
+            assert!(fs::read_to_string(outside).await.is_err());
+            assert!(fs::write("../escape.txt", "x").await.is_err());
+
+            match fs::read_to_string("../secret.txt").await {
+                Err(error) => paths.push(format!("{}", error)),
+                Ok(..) => panic("expected error"),
+            }
+
+            Ok(paths)
+        }
+        "#,
+        dir.0.join("secret.txt").display().to_string(),
+    );
+
+    assert_eq!(
+        out,
+        vec![
+            String::from("data/file.txt"),
+            String::from("path escapes the filesystem root"),
+        ]
+    );
+    assert_eq!(
+        std::fs::read_to_string(root.join("data/file.txt")).unwrap(),
+        "inside"
+    );
+    assert!(!dir.0.join("escape.txt").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_fs_root_symlink() {
+    use std::os::unix::fs::symlink;
+
+    let dir = TempDir::new("symlink");
+    let root = dir.0.join("root");
+    std::fs::create_dir_all(root.join("data")).unwrap();
+    std::fs::write(dir.0.join("secret.txt"), "secret").unwrap();
+    std::fs::write(root.join("data/file.txt"), "inside").unwrap();
+
+    symlink(&dir.0, root.join("link")).unwrap();
+    symlink("../outside.txt", root.join("dangling")).unwrap();
+    symlink("data/../../outside.txt", root.join("dangling-nested")).unwrap();
+    symlink("data", root.join("inner")).unwrap();
+    symlink("data/new.txt", root.join("inner-dangling")).unwrap();
+    symlink("loop-b", root.join("loop-a")).unwrap();
+    symlink("loop-a", root.join("loop-b")).unwrap();
+
+    let out: bool = run_fs(
+        Some(&root),
+        r#"
+        pub async fn main(_) {
+            assert!(fs::read_to_string("link/secret.txt").await.is_err());
+            assert!(fs::write("link/new.txt", "x").await.is_err());
+            assert!(fs::write("dangling", "pwned").await.is_err());
+            assert!(fs::append("dangling", "pwned").await.is_err());
+            assert!(fs::write("dangling-nested", "pwned").await.is_err());
+            assert!(fs::read_to_string("loop-a").await.is_err());
+
+            assert_eq!(fs::read_to_string("inner/file.txt").await?, "inside");
+            fs::write("inner-dangling", "through link").await?;
+            assert_eq!(fs::read_to_string("data/new.txt").await?, "through link");
+
+            let meta = fs::metadata("inner").await?;
+            assert!(meta.is_symlink());
+            assert!(meta.is_dir());
+            assert!(!fs::metadata("data").await?.is_symlink());
+
+            fs::remove_file("inner").await?;
+            Ok(fs::exists("data/file.txt").await? && !fs::exists("inner").await?)
+        }
+        "#,
+        String::new(),
+    );
+
+    assert!(out);
+    assert!(!dir.0.join("new.txt").exists());
+    assert!(!dir.0.join("outside.txt").exists());
+}
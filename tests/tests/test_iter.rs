@@ -1,5 +1,6 @@
 //! Test for iterator functions
 
+use rune::runtime::VmErrorKind::*;
 use rune_tests::*;
 
 #[test]
@@ -61,3 +62,90 @@ fn test_prod_float_negative() {
     );
     assert_eq!(out, -3.0)
 }
+
+#[test]
+fn test_lazy_adapters() {
+    let _: () = rune! {
+        pub fn main() {
+            assert_eq!([1, 2, 3].iter().zip(["a", "b"]).collect::<Vec>(), [(1, "a"), (2, "b")]);
+            assert_eq!([1, 2, 5, 1].iter().take_while(|x| x < 3).collect::<Vec>(), [1, 2]);
+            assert_eq!([1, 2, 5, 1].iter().skip_while(|x| x < 3).collect::<Vec>(), [5, 1]);
+            assert_eq!(std::iter::range(0, 10).step_by(4).collect::<Vec>(), [0, 4, 8]);
+            assert_eq!([1, 2, 3, 4].iter().windows(3).collect::<Vec>(), [[1, 2, 3], [2, 3, 4]]);
+            assert_eq!([1, 2].iter().windows(3).collect::<Vec>(), []);
+            assert_eq!([1, 2, 3, 4, 5].iter().chunks(2).collect::<Vec>(), [[1, 2], [3, 4], [5]]);
+            assert_eq!([[1], [], [2, 3]].iter().flatten().collect::<Vec>(), [1, 2, 3]);
+            assert_eq!([1, 2].iter().cycle().take(5).collect::<Vec>(), [1, 2, 1, 2, 1]);
+            assert_eq!([].iter().cycle().next(), None);
+            assert_eq!([1, 1, 2, 2, 2, 1].iter().dedup().collect::<Vec>(), [1, 2, 1]);
+
+            let sums = [1, 2, 3, 4].iter().scan(0, |acc, x| if x < 4 { Some(acc + x) } else { None });
+            assert_eq!(sums.collect::<Vec>(), [1, 3, 6]);
+
+            let seen = [];
+            let out = [1, 2, 3].iter().inspect(|x| seen.push(x)).filter(|x| x != 2).collect::<Vec>();
+            assert_eq!(out, [1, 3]);
+            assert_eq!(seen, [1, 2, 3]);
+        }
+    };
+}
+
+#[test]
+fn test_lazy_adapters_are_lazy() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let calls = [];
+            let it = std::iter::range(0, 1000).inspect(|x| calls.push(x)).take_while(|x| x < 3);
+            assert_eq!(calls.len(), 0);
+            it.count();
+            calls.len()
+        }
+    };
+    assert_eq!(out, 4);
+}
+
+#[test]
+fn test_consumers() {
+    let _: () = rune! {
+        pub fn main() {
+            assert_eq!([1, 2, 3].iter().last(), Some(3));
+            assert_eq!([].iter().last(), None);
+
+            let it = [1, 2, 3, 4].iter();
+            assert_eq!(it.nth(1), Some(2));
+            assert_eq!(it.nth(0), Some(3));
+            assert_eq!(it.nth(5), None);
+
+            assert!([1, 2, 3].iter().any(|x| x == 2));
+            assert!(![1, 2, 3].iter().any(|x| x == 4));
+            assert_eq!([5, 6, 7].iter().position(|x| x == 7), Some(2));
+            assert_eq!([5, 6, 7].iter().position(|x| x == 8), None);
+
+            assert_eq!([3, 1, 2].iter().min(), Some(1));
+            assert_eq!([3, 1, 2].iter().max(), Some(3));
+            assert_eq!([].iter().max(), None);
+            assert_eq!(["bb", "a", "cc"].iter().min_by_key(|s| s.len()), Some("a"));
+            assert_eq!(["bb", "a", "cc"].iter().max_by_key(|s| s.len()), Some("cc"));
+            assert_eq!(["aa", "b", "cc"].iter().min_by_key(|s| s.len() % 2), Some("aa"));
+
+            let (even, odd) = std::iter::range(0, 6).partition(|x| x % 2 == 0);
+            assert_eq!(even, [0, 2, 4]);
+            assert_eq!(odd, [1, 3, 5]);
+        }
+    };
+}
+
+#[test]
+fn test_adapter_errors() {
+    assert_vm_error!(
+        "pub fn main() { [1].iter().step_by(0) }",
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "step must be non-zero");
+        }
+    );
+
+    assert_vm_error!(
+        "pub fn main() { [1, 2].iter().zip([3]).rev() }",
+        Panic { .. } => {}
+    );
+}
@@ -0,0 +1,231 @@
+use rune::runtime::VmErrorKind::*;
+use rune_tests::*;
+
+#[test]
+fn test_bigint() {
+    let out: String = rune! {
+        pub fn main() {
+            let a = std::num::BigInt::new(9223372036854775807);
+            let b = a * 4 + std::num::BigInt::parse("10").unwrap();
+            b.to_string()
+        }
+    };
+    assert_eq!(out, "36893488147419103238");
+
+    let out: String = rune! {
+        pub fn main() {
+            let a = std::num::BigInt::new(2).pow(100);
+            a -= 1;
+            format!("{} {}", a, a.to_string_radix(16))
+        }
+    };
+    assert_eq!(out, "1267650600228229401496703205375 fffffffffffffffffffffffff");
+
+    let out: bool = rune! {
+        pub fn main() {
+            let a = std::num::BigInt::new(10);
+            let b = std::num::BigInt::parse("20").unwrap();
+            a < b && a == 10 && b > 19 && a != b
+        }
+    };
+    assert!(out);
+
+    let out: Option<i64> = rune!(pub fn main() { std::num::BigInt::parse("-42").unwrap().to_integer() });
+    assert_eq!(out, Some(-42));
+
+    let out: bool = rune!(pub fn main() { std::num::BigInt::parse("nope").is_err() });
+    assert!(out);
+
+    assert_vm_error!(
+        "pub fn main() { std::num::BigInt::new(1) / 0 }",
+        DivideByZero => {}
+    );
+
+    assert_vm_error!(
+        "pub fn main() { std::num::BigInt::new(1) + 1.0 }",
+        UnsupportedBinaryOperation { op, .. } => {
+            assert_eq!(op, "+");
+        }
+    );
+}
+
+#[test]
+fn test_checked_promotion() {
+    let out: i64 = rune!(pub fn main() { std::num::checked_add(1, 2) });
+    assert_eq!(out, 3);
+
+    let out: String = rune! {
+        pub fn main() {
+            let n = std::num::checked_mul(9223372036854775807, 2);
+            assert!(n is std::num::BigInt);
+            format!("{}", n)
+        }
+    };
+    assert_eq!(out, "18446744073709551614");
+
+    let out: i64 = rune! {
+        pub fn main() {
+            let n = std::num::checked_add(9223372036854775807, 1);
+            std::num::checked_sub(n, 10)
+        }
+    };
+    assert_eq!(out, 9223372036854775798);
+
+    let out: String = rune!(pub fn main() { format!("{}", std::num::checked_pow(10, 20)) });
+    assert_eq!(out, "100000000000000000000");
+
+    assert_vm_error!(
+        "pub fn main() { std::num::checked_div(1, 0) }",
+        DivideByZero => {}
+    );
+}
+
+#[test]
+fn test_decimal() {
+    let out: String = rune! {
+        pub fn main() {
+            let a = std::num::Decimal::parse("0.1").unwrap();
+            let b = std::num::Decimal::parse("0.2").unwrap();
+            format!("{}", a + b)
+        }
+    };
+    assert_eq!(out, "0.3");
+
+    let out: String = rune! {
+        pub fn main() {
+            let price = std::num::Decimal::new(1999, 2);
+            let total = price * 3;
+            total -= std::num::Decimal::parse("0.97").unwrap();
+            format!("{}", total)
+        }
+    };
+    assert_eq!(out, "59.00");
+
+    let out: String = rune! {
+        pub fn main() {
+            let a = std::num::Decimal::new(10, 0) / 3;
+            format!("{} {} {}", a, a.round(2), std::num::Decimal::parse("2.675").unwrap().round(2))
+        }
+    };
+    assert_eq!(out, "3.3333333333333333333333333333 3.33 2.68");
+
+    let out: String = rune! {
+        pub fn main() {
+            let a = std::num::Decimal::parse("10.00").unwrap() / 4;
+            let b = std::num::Decimal::parse("-0.05").unwrap();
+            format!("{} {} {} {}", a, b, b.trunc(1), std::num::Decimal::parse("1.500").unwrap().normalize())
+        }
+    };
+    assert_eq!(out, "2.50 -0.05 0.0 1.5");
+
+    let out: bool = rune! {
+        pub fn main() {
+            let a = std::num::Decimal::parse("1.50").unwrap();
+            let b = std::num::Decimal::parse("1.5").unwrap();
+            a == b && a > 1 && a < 2 && a.to_integer() == Some(1)
+        }
+    };
+    assert!(out);
+
+    let out: f64 = rune!(pub fn main() { std::num::Decimal::from_float(0.25).unwrap().to_float() });
+    assert_eq!(out, 0.25);
+
+    let out: bool = rune!(pub fn main() { std::num::Decimal::parse("1.2.3").is_err() });
+    assert!(out);
+
+    assert_vm_error!(
+        "pub fn main() { std::num::Decimal::new(1, 0) / std::num::Decimal::new(0, 2) }",
+        DivideByZero => {}
+    );
+}
+
+#[test]
+fn test_mixed_operands() {
+    let out: String = rune! {
+        pub fn main() {
+            let d = std::num::Decimal::parse("0.25").unwrap();
+            let b = std::num::BigInt::new(9223372036854775807);
+            format!("{} {} {} {} {}", 100 - d, 1 / d, 2 * b, 10 % std::num::BigInt::new(3), 3 + d)
+        }
+    };
+    assert_eq!(out, "99.75 4.00 18446744073709551614 1 3.25");
+
+    let out: bool = rune! {
+        pub fn main() {
+            let d = std::num::Decimal::parse("1.50").unwrap();
+            let b = std::num::BigInt::new(10);
+            1 < d && 2 > d && 10 == b && 11 != b && 9 <= b
+        }
+    };
+    assert!(out);
+
+    assert_vm_error!(
+        "pub fn main() { 1 / std::num::BigInt::new(0) }",
+        DivideByZero => {}
+    );
+
+    assert_vm_error!(
+        "pub fn main() { 1 + 1.0 }",
+        UnsupportedBinaryOperation { op, .. } => {
+            assert_eq!(op, "+");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { 1 == "a" }"#,
+        UnsupportedBinaryOperation { op, .. } => {
+            assert_eq!(op, "==");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { 1 < "a" }"#,
+        UnsupportedBinaryOperation { op, .. } => {
+            assert_eq!(op, "<");
+        }
+    );
+}
+
+#[test]
+fn test_host_integer_protocols() -> rune::Result<()> {
+    // NB: integers don't claim any protocols, so a host is free to install
+    // its own.
+    let mut module = rune::Module::new();
+    module.inst_fn(rune::runtime::Protocol::EQ, |lhs: i64, rhs: bool| {
+        (lhs != 0) == rhs
+    })?;
+
+    let mut context = rune_tests::modules::default_context()?;
+    context.install(&module)?;
+
+    let out: bool = run(&context, "pub fn main() { 1 == true }", ["main"], ())?;
+    assert!(out);
+    Ok(())
+}
+
+#[test]
+fn test_decimal_scale_bounds() {
+    let out: (u32, u32, u32) = rune! {
+        pub fn main() {
+            let d = std::num::Decimal::parse("1.5").unwrap();
+            let e = std::num::Decimal::new(1, 1000);
+            (d.round(4294967295).scale(), d.trunc(4294967295).scale(), (e * e).scale())
+        }
+    };
+    assert_eq!(out, (1000, 1000, 1000));
+
+    assert_vm_error!(
+        "pub fn main() { std::num::Decimal::new(1, 4294967295) }",
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "decimal scale must be at most 1000");
+        }
+    );
+
+    let out: bool = rune! {
+        pub fn main() {
+            let digits = "1".repeat(1001);
+            std::num::Decimal::parse(format!("0.{}", digits)).is_err()
+        }
+    };
+    assert!(out);
+}
@@ -0,0 +1,123 @@
+use rune::runtime::VmErrorKind::*;
+use rune_tests::*;
+
+#[test]
+fn test_object_extend() {
+    let _: () = rune! {
+        pub fn main() {
+            let o = #{a: 1};
+            o.extend(#{b: 2, a: 3});
+            assert_eq!(o, #{a: 3, b: 2});
+
+            o.extend([("c", 4)]);
+            assert_eq!(o.keys().collect::<Vec>(), ["a", "b", "c"]);
+
+            let copy = o.clone();
+            copy.insert("d", 5);
+            assert_eq!(o.len(), 3);
+            assert_eq!(copy.len(), 4);
+        }
+    };
+
+    assert_vm_error!(
+        "pub fn main() { let o = #{}; o.extend([1]) }",
+        Expected { .. } => {}
+    );
+}
+
+#[test]
+fn test_object_updates() {
+    let _: () = rune! {
+        pub fn main() {
+            let o = #{count: 1};
+            assert!(o.update("count", |n| n + 1));
+            assert!(!o.update("missing", |n| n + 1));
+            assert_eq!(o.count, 2);
+
+            assert_eq!(o.get_or_insert("count", 10), 2);
+            assert_eq!(o.get_or_insert("other", 10), 10);
+            o.get_or_insert("list", []).push(1);
+            o.get_or_insert("list", []).push(2);
+            assert_eq!(o.list, [1, 2]);
+
+            o.retain(|key, value| key != "other");
+            assert!(!o.contains_key("other"));
+            assert!(!o.is_empty());
+        }
+    };
+}
+
+#[test]
+fn test_object_entry() {
+    let _: () = rune! {
+        pub fn main() {
+            let words = #{};
+
+            for word in ["a", "b", "a"] {
+                words.entry(word).and_modify(|n| n + 1).or_insert(1);
+            }
+
+            assert_eq!(words, #{a: 2, b: 1});
+            assert_eq!(words.entry("c").key(), "c");
+            assert_eq!(words.entry("c").or_insert_with(|| 42), 42);
+            assert_eq!(words.entry("c").or_insert_with(|| 0), 42);
+            assert_eq!(words.c, 42);
+        }
+    };
+}
+
+#[test]
+fn test_object_merge() {
+    let _: () = rune! {
+        pub fn main() {
+            let config = #{server: #{host: "localhost", port: 80}, debug: false};
+            let overrides = #{server: #{port: 8080, tls: #{enabled: true}}, debug: true};
+
+            config.merge(overrides);
+
+            assert_eq!(config, #{
+                server: #{host: "localhost", port: 8080, tls: #{enabled: true}},
+                debug: true,
+            });
+
+            config.server.tls.enabled = false;
+            assert_eq!(overrides.server.tls.enabled, true);
+        }
+    };
+}
+
+#[test]
+fn test_object_merge_clone() {
+    let _: () = rune! {
+        pub fn main() {
+            let a = #{server: #{host: "localhost", tls: #{enabled: true}}, debug: false};
+            let c = a.clone();
+            a.merge(c);
+
+            assert_eq!(a, #{server: #{host: "localhost", tls: #{enabled: true}}, debug: false});
+
+            let b = a.clone();
+            a.merge(#{server: #{port: 8080}});
+
+            assert_eq!(a.server, #{host: "localhost", port: 8080, tls: #{enabled: true}});
+            assert_eq!(b.server, #{host: "localhost", tls: #{enabled: true}});
+        }
+    };
+}
+
+#[test]
+fn test_object_protocols() {
+    let out: String = rune! {
+        pub fn main() {
+            format!("{:?}", #{b: 2, a: 1})
+        }
+    };
+    assert_eq!(out, "{\"a\": 1, \"b\": 2}");
+
+    let out: bool = rune! {
+        pub fn main() {
+            #{a: [1, 2]} == #{a: [1, 2]} && #{a: 1} != #{a: 2}
+        }
+    };
+    assert!(out);
+}
@@ -0,0 +1,93 @@
+use rune::runtime::VmErrorKind::*;
+use rune_tests::*;
+
+#[test]
+fn test_string_search() {
+    let _: () = rune! {
+        pub fn main() {
+            let s = "héllo wörld";
+
+            assert_eq!(s.find("l"), Some(3));
+            assert_eq!(s.find('ö'), Some(8));
+            assert_eq!(s.rfind("l"), Some(11));
+            assert_eq!(s.find("xyz"), None);
+            assert!(s.contains("wör"));
+            assert!(s.contains('h'));
+            assert!(!s.contains("hello"));
+
+            assert_eq!(s.strip_prefix("hé"), Some("llo wörld"));
+            assert_eq!(s.strip_prefix("x"), None);
+            assert_eq!(s.strip_suffix('d'), Some("héllo wörl"));
+
+            assert_eq!(s.get(0..3), Some("hé"));
+            assert_eq!(s.get(0..2), None);
+            assert_eq!(s[7..11], "wör");
+            assert!(!s.is_char_boundary(2));
+        }
+    };
+}
+
+#[test]
+fn test_string_iterators() {
+    let _: () = rune! {
+        pub fn main() {
+            let text = "first line\nsecond  line\r\nthird";
+
+            assert_eq!(text.lines().collect::<Vec>(), ["first line", "second  line", "third"]);
+            assert_eq!(
+                text.split_whitespace().collect::<Vec>(),
+                ["first", "line", "second", "line", "third"]
+            );
+            assert_eq!("a,b,c,d".splitn(3, ',').collect::<Vec>(), ["a", "b", "c,d"]);
+            assert_eq!("key=value=x".split_once("="), Some(("key", "value=x")));
+            assert_eq!("novalue".split_once('='), None);
+            assert_eq!("aé".char_indices().collect::<Vec>(), [(0, 'a'), (1, 'é')]);
+        }
+    };
+}
+
+#[test]
+fn test_string_transform() {
+    let _: () = rune! {
+        pub fn main() {
+            assert_eq!("Straße".to_uppercase(), "STRASSE");
+            assert_eq!("ÀB".to_lowercase(), "àb");
+            assert_eq!("ab".repeat(3), "ababab");
+            assert_eq!("  padded  ".trim_start(), "padded  ");
+            assert_eq!("  padded  ".trim_end(), "  padded");
+        }
+    };
+}
+
+#[test]
+fn test_string_bad_pattern() {
+    // NB: arguments are counted the same way as when they fail to convert,
+    // where `self` is argument `0`.
+    assert_vm_error!(
+        r#"pub fn main() { "a,b".splitn("2", ",") }"#,
+        BadArgument { arg, .. } => {
+            assert_eq!(arg, 1);
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { "a,b".splitn(2, 1) }"#,
+        BadArgumentAt { arg, .. } => {
+            assert_eq!(arg, 2);
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { "a,b".split(1) }"#,
+        BadArgumentAt { arg, .. } => {
+            assert_eq!(arg, 1);
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { "a,b".split_once(1) }"#,
+        BadArgumentAt { arg, .. } => {
+            assert_eq!(arg, 1);
+        }
+    );
+}
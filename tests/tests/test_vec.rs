@@ -0,0 +1,115 @@
+use rune::runtime::VmErrorKind::*;
+use rune_tests::*;
+
+#[test]
+fn test_vec_sorting() {
+    let _: () = rune! {
+        pub fn main() {
+            let v = ["b", "c", "a"];
+            v.sort();
+            assert_eq!(v, ["a", "b", "c"]);
+
+            let v = ["ccc", "a", "bb"];
+            v.sort_by_key(|s| s.len());
+            assert_eq!(v, ["a", "bb", "ccc"]);
+
+            let v = [1, 1, 2, 3, 3, 3, 1];
+            v.dedup();
+            assert_eq!(v, [1, 2, 3, 1]);
+
+            let v = [1, 2, 3, 4, 5];
+            v.retain(|x| x % 2 == 1);
+            assert_eq!(v, [1, 3, 5]);
+
+            v.reverse();
+            assert_eq!(v, [5, 3, 1]);
+        }
+    };
+}
+
+#[test]
+fn test_vec_search() {
+    let _: () = rune! {
+        pub fn main() {
+            let v = [1, 3, 5, 7];
+            assert!(v.contains(5));
+            assert!(!v.contains(4));
+            assert_eq!(v.binary_search(5), Ok(2));
+            assert_eq!(v.binary_search(4), Err(2));
+            assert_eq!(v.first(), Some(1));
+            assert_eq!(v.last(), Some(7));
+            assert_eq!([].first(), None);
+        }
+    };
+}
+
+#[test]
+fn test_vec_mutation() {
+    let _: () = rune! {
+        pub fn main() {
+            let v = [1, 2, 3, 4, 5];
+            v.truncate(4);
+            assert_eq!(v, [1, 2, 3, 4]);
+
+            let tail = v.split_off(2);
+            assert_eq!(v, [1, 2]);
+            assert_eq!(tail, [3, 4]);
+
+            v.swap(0, 1);
+            assert_eq!(v, [2, 1]);
+        }
+    };
+
+    assert_vm_error!(
+        "pub fn main() { let v = [1, 2]; v.split_off(3) }",
+        OutOfRange { .. } => {}
+    );
+
+    assert_vm_error!(
+        "pub fn main() { let v = [1, 2]; v.swap(0, 2) }",
+        OutOfRange { .. } => {}
+    );
+}
+
+#[test]
+fn test_vec_join_concat() {
+    let _: () = rune! {
+        pub fn main() {
+            assert_eq!(["a", "b", "c"].join(", "), "a, b, c");
+            assert_eq!([].join(", "), "");
+            assert_eq!([[1, 2], [], [3]].concat(), [1, 2, 3]);
+            assert_eq!(["ab", "cd"].concat(), "abcd");
+        }
+    };
+
+    assert_vm_error!(
+        "pub fn main() { [\"a\", 1].join(\",\") }",
+        Expected { .. } => {}
+    );
+}
+
+#[test]
+fn test_vec_range_index() {
+    let _: () = rune! {
+        pub fn main() {
+            let v = [1, 2, 3, 4, 5];
+            assert_eq!(v[1..3], [2, 3]);
+            assert_eq!(v[..2], [1, 2]);
+            assert_eq!(v[3..], [4, 5]);
+            assert_eq!(v[1..=3], [2, 3, 4]);
+            assert_eq!(v[..], [1, 2, 3, 4, 5]);
+            assert_eq!(v[2], 3);
+            assert_eq!(v.get(1..2), Some([2]));
+            assert_eq!(v.get(4..9), None);
+            assert_eq!(v.get(1), Some(2));
+            assert_eq!(v.get(10), None);
+        }
+    };
+
+    assert_vm_error!(
+        "pub fn main() { let v = [1, 2]; v[1..5] }",
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "missing vector slice");
+        }
+    );
+}
@@ -0,0 +1,204 @@
+use rune::compile::CompileErrorKind::*;
+use rune::span;
+use rune_tests::*;
+
+#[test]
+fn test_trait_default_and_required() {
+    let out: (i64, i64) = rune! {
+        trait Shape {
+            fn area(self);
+
+            fn double_area(self) {
+                self.area() * 2
+            }
+        }
+
+        struct Square { side }
+
+        impl Shape for Square {
+            fn area(self) {
+                self.side * self.side
+            }
+        }
+
+        pub fn main() {
+            let s = Square { side: 3 };
+            (s.area(), s.double_area())
+        }
+    };
+
+    assert_eq!(out, (9, 18));
+}
+
+#[test]
+fn test_trait_override_default() {
+    let out: i64 = rune! {
+        trait Greet {
+            fn value(self) {
+                1
+            }
+        }
+
+        struct A;
+        struct B;
+
+        impl Greet for A {}
+
+        impl Greet for B {
+            fn value(self) {
+                2
+            }
+        }
+
+        pub fn main() {
+            A.value() * 10 + B.value()
+        }
+    };
+
+    assert_eq!(out, 12);
+}
+
+#[test]
+fn test_trait_dynamic_dispatch() {
+    let out: i64 = rune! {
+        trait Shape {
+            fn area(self);
+
+            fn describe(self) {
+                self.area() + 1000
+            }
+        }
+
+        struct Square { side }
+        enum Quad { Rect(w, h) }
+
+        impl Shape for Square {
+            fn area(self) {
+                self.side * self.side
+            }
+        }
+
+        impl Shape for Quad {
+            fn area(self) {
+                match self {
+                    Quad::Rect(w, h) => w * h,
+                }
+            }
+        }
+
+        pub fn main() {
+            let shapes = [Square { side: 2 }, Quad::Rect(2, 3), Square { side: 1 }];
+            let total = 0;
+
+            for s in shapes {
+                total += s.describe();
+            }
+
+            total
+        }
+    };
+
+    assert_eq!(out, 3011);
+}
+
+#[test]
+fn test_trait_native_type() {
+    let out: (i64, String) = rune! {
+        trait Describe {
+            fn len_plus(self, n) {
+                self.len() + n
+            }
+
+            fn shout(self);
+        }
+
+        impl Describe for String {
+            fn shout(self) {
+                self + "!"
+            }
+        }
+
+        pub fn main() {
+            let s = "hello";
+            (s.len_plus(2), s.shout())
+        }
+    };
+
+    assert_eq!(out, (7, String::from("hello!")));
+}
+
+#[test]
+fn test_trait_declared_in_module() {
+    let out: i64 = rune! {
+        mod shapes {
+            pub trait Shape {
+                fn sides(self);
+
+                fn corners(self) {
+                    self.sides()
+                }
+            }
+        }
+
+        use shapes::Shape;
+
+        struct Triangle;
+
+        impl Shape for Triangle {
+            fn sides(self) {
+                3
+            }
+        }
+
+        pub fn main() {
+            Triangle.corners()
+        }
+    };
+
+    assert_eq!(out, 3);
+}
+
+#[test]
+fn test_trait_missing_function() {
+    assert_compile_error! {
+        r#"trait Shape { fn area(self); } struct Square; impl Shape for Square {} pub fn main() {}"#,
+        span, MissingTraitFunction { name, item } => {
+            assert_eq!(&*name, "area");
+            assert_eq!(item.to_string(), "Shape");
+            assert_eq!(span, span!(46, 70));
+        }
+    };
+}
+
+#[test]
+fn test_trait_unknown_function() {
+    assert_compile_error! {
+        r#"trait Shape {} struct Square; impl Shape for Square { fn area(self) { 1 } } pub fn main() {}"#,
+        span, NotTraitFunction { name, item } => {
+            assert_eq!(&*name, "area");
+            assert_eq!(item.to_string(), "Shape");
+            assert_eq!(span, span!(57, 61));
+        }
+    };
+}
+
+#[test]
+fn test_trait_function_without_self() {
+    assert_compile_error! {
+        r#"trait Shape { fn area(); } pub fn main() {}"#,
+        span, TraitFunctionNotInstance => {
+            assert_eq!(span, span!(14, 24));
+        }
+    };
+}
+
+#[test]
+fn test_impl_non_trait() {
+    assert_compile_error! {
+        r#"struct Shape; struct Square; impl Shape for Square {} pub fn main() {}"#,
+        span, ExpectedMeta { expected, .. } => {
+            assert_eq!(expected, "trait");
+            assert_eq!(span, span!(34, 39));
+        }
+    };
+}
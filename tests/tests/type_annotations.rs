@@ -0,0 +1,182 @@
+use rune::compile::CompileErrorKind::*;
+use rune::diagnostics::{Diagnostic, FatalDiagnosticKind};
+use rune::runtime::VmErrorKind::*;
+use rune::span;
+use rune::{Context, Diagnostics, Options, Source, Sources};
+use rune_tests::*;
+
+/// Compile the given source with static type checking enabled, returning the
+/// first compile error.
+fn static_compile_error(source: &str) -> rune::compile::CompileError {
+    let context = Context::with_default_modules().expect("default context");
+
+    let mut options = Options::default();
+    options.static_types(true);
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let mut diagnostics = Diagnostics::new();
+
+    let _ = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .with_diagnostics(&mut diagnostics)
+        .build()
+        .unwrap_err();
+
+    for diagnostic in diagnostics.into_diagnostics() {
+        if let Diagnostic::Fatal(fatal) = diagnostic {
+            if let FatalDiagnosticKind::CompileError(error) = fatal.into_kind() {
+                return error;
+            }
+        }
+    }
+
+    panic!("expected a compile error");
+}
+
+#[test]
+fn test_annotations_run() {
+    let out: (i64, String) = rune! {
+        struct Point { x, y }
+
+        fn sum(point: Point, scale: int) -> int {
+            (point.x + point.y) * scale
+        }
+
+        fn greet(name: String, unit: ()) -> String {
+            let greeting: String = "hello ";
+            greeting + name
+        }
+
+        pub fn main() {
+            let total: int = sum(Point { x: 1, y: 2 }, 2);
+            (total, greet("world", ()))
+        }
+    };
+
+    assert_eq!(out, (6, String::from("hello world")));
+}
+
+#[test]
+fn test_annotated_closure_and_instance_fn() {
+    let out: i64 = rune! {
+        struct Counter { n }
+
+        impl Counter {
+            fn add(self, n: int) {
+                self.n + n
+            }
+        }
+
+        pub fn main() {
+            let f = |a: int, (b, c): (int, int)| a + b + c;
+            Counter { n: 10 }.add(f(1, (2, 3)))
+        }
+    };
+
+    assert_eq!(out, 16);
+}
+
+#[test]
+fn test_bad_argument_type() {
+    assert_vm_error!(
+        r#"
+        fn double(n: int) { n * 2 }
+        pub fn main() { double("two") }
+        "#,
+        BadArgumentType { arg, expected, actual } => {
+            assert_eq!(arg, 0);
+            assert_eq!(expected, "::std::int");
+            assert_eq!(actual.to_string(), "String");
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        struct Foo;
+        struct Bar;
+        fn check(a, b: Foo) { b }
+        pub fn main() { check(1, Bar) }
+        "#,
+        BadArgumentType { arg, expected, .. } => {
+            assert_eq!(arg, 1);
+            assert_eq!(expected, "Foo");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let f = |a: String| a; f(1) }"#,
+        BadArgumentType { arg, .. } => {
+            assert_eq!(arg, 0);
+        }
+    );
+}
+
+#[test]
+fn test_unknown_type() {
+    assert_compile_error! {
+        r#"fn f(a: Missing) { a } pub fn main() { f(1) }"#,
+        span, MissingItem { .. } => {
+            assert_eq!(span, span!(8, 15));
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn g() {} fn f(a: g) { a } pub fn main() { f(1) }"#,
+        span, ExpectedMeta { expected, .. } => {
+            assert_eq!(expected, "type");
+            assert_eq!(span, span!(18, 19));
+        }
+    };
+}
+
+#[test]
+fn test_static_types_disabled_by_default() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let a: String = 42;
+            a
+        }
+    };
+
+    assert_eq!(out, 42);
+}
+
+#[test]
+fn test_static_type_mismatch() {
+    let error = static_compile_error(r#"pub fn main() { let a: String = 42; }"#);
+    assert_eq!(rune::ast::Spanned::span(&error), span!(32, 34));
+
+    match error.into_kind() {
+        TypeMismatch { expected, actual } => {
+            assert_eq!(&*expected, "String");
+            assert_eq!(&*actual, "integer");
+        }
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+
+    let error = static_compile_error(r#"fn f() -> float { -1 } pub fn main() { f() }"#);
+    assert_eq!(rune::ast::Spanned::span(&error), span!(18, 20));
+
+    match error.into_kind() {
+        TypeMismatch { expected, actual } => {
+            assert_eq!(&*expected, "float");
+            assert_eq!(&*actual, "integer");
+        }
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+
+    let error = static_compile_error(
+        r#"struct Foo; struct Bar { a } pub fn main() { let a: Foo = Bar { a: 1 }; }"#,
+    );
+
+    match error.into_kind() {
+        TypeMismatch { expected, actual } => {
+            assert_eq!(&*expected, "Foo");
+            assert_eq!(&*actual, "Bar");
+        }
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+}
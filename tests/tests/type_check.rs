@@ -0,0 +1,188 @@
+use rune::diagnostics::WarningDiagnosticKind::*;
+use rune::diagnostics::{Diagnostic, WarningDiagnosticKind};
+use rune::span;
+use rune::{Context, Diagnostics, Options, Source, Sources};
+
+/// Compile the given source with the type checker enabled, returning the
+/// warnings produced.
+fn type_check(source: &str) -> Vec<WarningDiagnosticKind> {
+    warnings(&compile(source, true).1)
+}
+
+fn compile(source: &str, type_check: bool) -> (Sources, Diagnostics) {
+    let context = Context::with_default_modules().expect("default context");
+
+    let mut options = Options::default();
+    options.type_check(type_check);
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let mut diagnostics = Diagnostics::new();
+
+    let _ = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .with_diagnostics(&mut diagnostics)
+        .build()
+        .expect("source should compile");
+
+    (sources, diagnostics)
+}
+
+fn warnings(diagnostics: &Diagnostics) -> Vec<WarningDiagnosticKind> {
+    diagnostics
+        .diagnostics()
+        .iter()
+        .filter_map(|diagnostic| match diagnostic {
+            Diagnostic::Warning(warning) => Some(*warning.kind()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_missing_instance_function() {
+    let (_, diagnostics) = compile(r#"pub fn main() { let s = "hello"; s.lenn() }"#, true);
+
+    match &warnings(&diagnostics)[..] {
+        [MissingInstanceFunction { span, ty }] => {
+            assert_eq!(*span, span!(35, 39));
+            assert_eq!(diagnostics.type_name(*ty), Some("String"));
+        }
+        warnings => panic!("unexpected warnings: {:?}", warnings),
+    }
+
+    let (_, diagnostics) = compile(
+        r#"struct Foo; impl Foo { fn bar(self) {} } pub fn main() { let f = Foo; f.bar(); f.baz(); }"#,
+        true,
+    );
+
+    match &warnings(&diagnostics)[..] {
+        [MissingInstanceFunction { span, ty }] => {
+            assert_eq!(*span, span!(81, 84));
+            assert_eq!(diagnostics.type_name(*ty), Some("Foo"));
+        }
+        warnings => panic!("unexpected warnings: {:?}", warnings),
+    }
+}
+
+#[test]
+fn test_annotated_argument() {
+    let (_, diagnostics) = compile(r#"fn f(v: Vec) { v.pussh(1) } pub fn main() { f([]) }"#, true);
+
+    match &warnings(&diagnostics)[..] {
+        [MissingInstanceFunction { ty, .. }] => {
+            assert_eq!(diagnostics.type_name(*ty), Some("Vec"));
+        }
+        warnings => panic!("unexpected warnings: {:?}", warnings),
+    }
+}
+
+#[test]
+fn test_bad_argument_count() {
+    let warnings = type_check(r#"fn add(a, b) { a + b } pub fn main() { add(1) }"#);
+
+    match &warnings[..] {
+        [BadArgumentCount {
+            span,
+            expected,
+            actual,
+        }] => {
+            assert_eq!(*span, span!(39, 45));
+            assert_eq!(*expected, 2);
+            assert_eq!(*actual, 1);
+        }
+        warnings => panic!("unexpected warnings: {:?}", warnings),
+    }
+
+    let warnings = type_check(r#"pub fn main() { let v = []; v.push(1, 2); "a".len(1) }"#);
+
+    match &warnings[..] {
+        [BadArgumentCount {
+            expected: 1,
+            actual: 2,
+            ..
+        }, BadArgumentCount {
+            expected: 0,
+            actual: 1,
+            ..
+        }] => {}
+        warnings => panic!("unexpected warnings: {:?}", warnings),
+    }
+
+    let warnings = type_check(r#"pub fn main() { std::string::String::from_str() }"#);
+
+    match &warnings[..] {
+        [BadArgumentCount {
+            expected: 1,
+            actual: 0,
+            ..
+        }] => {}
+        warnings => panic!("unexpected warnings: {:?}", warnings),
+    }
+}
+
+#[test]
+fn test_unsupported_binary_operation() {
+    let (_, diagnostics) = compile(r#"pub fn main() { let a = "a"; a + 1 }"#, true);
+
+    match &warnings(&diagnostics)[..] {
+        [UnsupportedBinaryOperation { span, lhs, rhs, .. }] => {
+            assert_eq!(*span, span!(29, 34));
+            assert_eq!(diagnostics.type_name(*lhs), Some("String"));
+            assert_eq!(diagnostics.type_name(*rhs), Some("integer"));
+        }
+        warnings => panic!("unexpected warnings: {:?}", warnings),
+    }
+
+    let warnings = type_check(r#"pub fn main() { 1.0 * 2 }"#);
+    assert!(matches!(&warnings[..], [UnsupportedBinaryOperation { .. }]));
+}
+
+#[test]
+fn test_no_false_positives() {
+    let warnings = type_check(
+        r#"
+        trait Shout {
+            fn shout(self) { self + "!" }
+        }
+
+        impl Shout for String {}
+
+        fn add(a: int, b: int) -> int { a + b }
+
+        pub fn main() {
+            let s = "hello";
+            let n = 1;
+            n = "reassigned";
+            let f = |a| a;
+            let v = [1, 2];
+            v.push(3);
+            let c = Some(1).map(|n| n + 1);
+            (s.shout(), n.len(), f(1, 2), add(1, 2) * 2, "a" + "b", v.len(), c)
+        }
+        "#,
+    );
+
+    assert!(warnings.is_empty(), "unexpected warnings: {:?}", warnings);
+}
+
+#[test]
+fn test_type_check_disabled_by_default() {
+    let (_, diagnostics) = compile(r#"pub fn main() { let a = "a"; a.lenn() + (a + 1) }"#, false);
+    let warnings = warnings(&diagnostics);
+    assert!(warnings.is_empty(), "unexpected warnings: {:?}", warnings);
+}
+
+#[test]
+fn test_emit_type_names() {
+    let (sources, diagnostics) = compile(r#"pub fn main() { let a = "a"; a.lenn() + (a + 1) }"#, true);
+
+    let mut out = rune::termcolor::Buffer::no_color();
+    diagnostics.emit(&mut out, &sources).unwrap();
+    let out = String::from_utf8(out.into_inner()).unwrap();
+
+    assert!(out.contains("no instance function `lenn` for `String`"), "{}", out);
+    assert!(out.contains("`String` and `integer` can't be used here"), "{}", out);
+}
@@ -0,0 +1,220 @@
+use rune::compile::{CachedUnit, FileUnitCache, UnitCache, UnitCacheError, UnitCacheKey};
+use rune::runtime::Vm;
+use rune::{Context, FromValue, Options, Source, SourceId, Sources};
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+
+/// An in-memory unit cache which counts loads.
+#[derive(Default)]
+struct MemoryCache {
+    units: HashMap<UnitCacheKey, CachedUnit>,
+    loads: usize,
+}
+
+impl UnitCache for MemoryCache {
+    fn load(&mut self, key: &UnitCacheKey) -> Result<Option<CachedUnit>, UnitCacheError> {
+        let unit = self.units.get(key).cloned();
+
+        if unit.is_some() {
+            self.loads += 1;
+        }
+
+        Ok(unit)
+    }
+
+    fn store(&mut self, key: &UnitCacheKey, unit: &CachedUnit) -> Result<(), UnitCacheError> {
+        self.units.insert(*key, unit.clone());
+        Ok(())
+    }
+}
+
+fn bytecode() -> Options {
+    let mut options = Options::default();
+    options.bytecode(true);
+    options
+}
+
+fn build(
+    context: &Context,
+    options: &Options,
+    cache: &mut dyn UnitCache,
+    source: &str,
+) -> rune::Result<i64> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(context)
+        .with_options(options)
+        .with_unit_cache(cache)
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    Ok(i64::from_value(vm.call(["main"], ())?)?)
+}
+
+#[test]
+fn test_unit_cache_hit() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+    let options = bytecode();
+    let mut cache = MemoryCache::default();
+
+    let source = "pub fn main() { [1, 2, 3].iter().fold(0, |a, b| a + b) }";
+
+    assert_eq!(build(&context, &options, &mut cache, source)?, 6);
+    assert_eq!(cache.loads, 0);
+    assert_eq!(cache.units.len(), 1);
+
+    assert_eq!(build(&context, &options, &mut cache, source)?, 6);
+    assert_eq!(cache.loads, 1);
+    Ok(())
+}
+
+#[test]
+fn test_unit_cache_key() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+    let options = bytecode();
+    let mut cache = MemoryCache::default();
+
+    assert_eq!(build(&context, &options, &mut cache, "pub fn main() { 1 }")?, 1);
+    assert_eq!(build(&context, &options, &mut cache, "pub fn main() { 2 }")?, 2);
+    assert_eq!(cache.loads, 0);
+
+    let mut other = options;
+    other.debug_info(false);
+    assert_eq!(build(&context, &other, &mut cache, "pub fn main() { 1 }")?, 1);
+    assert_eq!(cache.loads, 0);
+
+    let empty = Context::new();
+    assert_eq!(build(&empty, &options, &mut cache, "pub fn main() { 1 }")?, 1);
+    assert_eq!(cache.loads, 0);
+    assert_eq!(cache.units.len(), 4);
+    Ok(())
+}
+
+#[test]
+fn test_unit_cache_disabled() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+    let options = Options::default();
+    let mut cache = MemoryCache::default();
+
+    assert_eq!(build(&context, &options, &mut cache, "pub fn main() { 1 }")?, 1);
+    assert_eq!(build(&context, &options, &mut cache, "pub fn main() { 1 }")?, 1);
+    assert!(cache.units.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_file_unit_cache() -> rune::Result<()> {
+    let dir = std::env::temp_dir().join(format!("rune-test-unit-cache-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    let context = Context::with_default_modules()?;
+    let options = bytecode();
+    let mut cache = FileUnitCache::new(&dir);
+
+    let source = r#"pub fn main() { let s = "hello"; s.len() + 10 }"#;
+    assert_eq!(build(&context, &options, &mut cache, source)?, 15);
+    assert_eq!(fs::read_dir(&dir)?.count(), 1);
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+    let key = UnitCacheKey::new(&sources, &context, &options);
+
+    let cached = cache.load(&key)?.expect("unit should be cached");
+    assert_eq!(cached.key(), key);
+
+    assert_eq!(build(&context, &options, &mut cache, source)?, 15);
+
+    // Corrupt cache entries are ignored and replaced.
+    fs::write(cache.path(&key), b"garbage")?;
+    assert_eq!(build(&context, &options, &mut cache, source)?, 15);
+    assert!(cache.load(&key)?.is_some());
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn test_file_unit_cache_untrusted() -> rune::Result<()> {
+    use std::os::unix::fs::{chown, MetadataExt, PermissionsExt};
+
+    let dir = std::env::temp_dir().join(format!("rune-test-unit-cache-untrusted-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    let context = Context::with_default_modules()?;
+    let options = bytecode();
+    let mut cache = FileUnitCache::new(&dir);
+
+    let source = "pub fn main() { 42 }";
+    assert_eq!(build(&context, &options, &mut cache, source)?, 42);
+    assert_eq!(fs::metadata(&dir)?.permissions().mode() & 0o777, 0o700);
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+    let key = UnitCacheKey::new(&sources, &context, &options);
+    assert!(cache.load(&key)?.is_some());
+
+    // NB: changing owners requires privileges, which the test might not have.
+    let uid = fs::metadata(&dir)?.uid();
+
+    if chown(cache.path(&key), Some(65534), None).is_ok() {
+        // Entries owned by someone else are ignored.
+        assert!(cache.load(&key)?.is_none());
+        chown(cache.path(&key), Some(uid), None)?;
+        assert!(cache.load(&key)?.is_some());
+
+        // So are entries in world-writable directories owned by someone else.
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o777))?;
+        chown(&dir, Some(65534), None)?;
+        assert!(cache.load(&key)?.is_none());
+    }
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_unit_cache_loaded_sources() -> rune::Result<()> {
+    let dir = std::env::temp_dir().join(format!("rune-test-unit-cache-mod-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+
+    let main = dir.join("main.rn");
+    let module = dir.join("foo.rn");
+    fs::write(&main, "mod foo; pub fn main() { foo::value() }")?;
+    fs::write(&module, "pub fn value() { 1 }")?;
+
+    let context = Context::with_default_modules()?;
+    let options = bytecode();
+    let mut cache = MemoryCache::default();
+
+    let run = |cache: &mut MemoryCache| -> rune::Result<(i64, bool)> {
+        let mut sources = Sources::new();
+        sources.insert(Source::from_path(&main)?);
+
+        let unit = rune::prepare(&mut sources)
+            .with_context(&context)
+            .with_options(&options)
+            .with_unit_cache(cache)
+            .build()?;
+
+        let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+        let value = i64::from_value(vm.call(["main"], ())?)?;
+        Ok((value, sources.get(SourceId::new(1)).is_some()))
+    };
+
+    assert_eq!(run(&mut cache)?, (1, true));
+    assert_eq!(run(&mut cache)?, (1, true));
+    assert_eq!(cache.loads, 1);
+
+    // Changing a loaded module invalidates the cached unit.
+    fs::write(&module, "pub fn value() { 2 }")?;
+    assert_eq!(run(&mut cache)?, (2, true));
+    assert_eq!(cache.loads, 2);
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
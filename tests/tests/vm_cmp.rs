@@ -0,0 +1,107 @@
+use rune::runtime::VmErrorKind::*;
+use rune::{Any, Module};
+use rune_tests::*;
+
+#[derive(Debug, Any, PartialEq, Eq, PartialOrd, Ord)]
+#[rune(cmp)]
+struct Version {
+    major: u32,
+    minor: u32,
+}
+
+#[derive(Debug, Any, PartialEq, PartialOrd)]
+#[rune(partial_cmp)]
+struct Money(f64);
+
+#[test]
+fn test_cmp_builtin() {
+    let out: bool = rune! {
+        pub fn main() {
+            "abc" < "abd" && [1, 2] < [1, 2, 3] && (1, "b") > (1, "a") && Some(1) > None
+        }
+    };
+    assert!(out);
+
+    let out: bool = rune! {
+        pub fn main() {
+            match 1.cmp(2) {
+                std::cmp::Ordering::Less => true,
+                _ => false,
+            }
+        }
+    };
+    assert!(out);
+
+    let out: Vec<i64> = rune! {
+        pub fn main() {
+            let v = [3, 1, 2];
+            v.sort();
+            v
+        }
+    };
+    assert_eq!(out, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_cmp_script_impl() {
+    let out: (bool, bool, bool, Vec<i64>) = rune! {
+        struct Version { major, minor }
+
+        impl Version {
+            fn cmp(self, other) {
+                match self.major.cmp(other.major) {
+                    std::cmp::Ordering::Equal => self.minor.cmp(other.minor),
+                    ordering => ordering,
+                }
+            }
+        }
+
+        pub fn main() {
+            let a = Version { major: 1, minor: 2 };
+            let b = Version { major: 1, minor: 10 };
+            let c = Version { major: 0, minor: 20 };
+
+            let v = [a, b, c];
+            v.sort();
+
+            (a < b, b >= a, c > a, v.iter().map(|v| v.minor).collect::<Vec>())
+        }
+    };
+    assert_eq!(out, (true, true, false, vec![20, 2, 10]));
+}
+
+#[test]
+fn test_cmp_derive() {
+    let mut module = Module::new();
+    module.ty::<Version>().unwrap();
+    module.ty::<Money>().unwrap();
+
+    let args = (
+        Version { major: 1, minor: 2 },
+        Version { major: 2, minor: 0 },
+        Money(1.5),
+        Money(f64::NAN),
+    );
+
+    let out: (bool, bool, bool, bool) = rune_n! {
+        module,
+        args,
+        (bool, bool, bool, bool) => pub fn main(a, b, c, d) {
+            (a < b, a >= b, c <= c, c < d || c >= d)
+        }
+    };
+    assert_eq!(out, (true, false, true, false));
+}
+
+#[test]
+fn test_cmp_unsupported() {
+    assert_vm_error!(
+        r#"
+        struct Foo;
+        pub fn main() { Foo < Foo }
+        "#,
+        UnsupportedBinaryOperation { op, .. } => {
+            assert_eq!(op, "<");
+        }
+    );
+}
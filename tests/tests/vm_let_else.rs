@@ -0,0 +1,155 @@
+use rune::compile::CompileErrorKind::*;
+use rune::runtime::VmErrorKind;
+use rune::span;
+use rune_tests::*;
+
+#[test]
+fn test_let_else() {
+    let out: i64 = rune! {
+        fn first(v) {
+            let [a, ..] = v else {
+                return -1;
+            };
+
+            a
+        }
+
+        pub fn main() {
+            first([1, 2]) + first([]) * 10
+        }
+    };
+    assert_eq!(out, -9);
+
+    let out: i64 = rune! {
+        fn sum(v) {
+            let out = 0;
+
+            for value in v {
+                let Some(n) = value else {
+                    continue;
+                };
+
+                out += n;
+            }
+
+            out
+        }
+
+        pub fn main() {
+            sum([Some(1), None, Some(3)])
+        }
+    };
+    assert_eq!(out, 4);
+
+    let out: i64 = rune! {
+        pub fn main() {
+            let base = 10;
+            let (a, Some(b)) = (1, Some(2)) else { panic("unreachable") };
+            base + a + b
+        }
+    };
+    assert_eq!(out, 13);
+}
+
+#[test]
+fn test_let_else_panic() {
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let Some(a) = None else { panic("no value") };
+            a
+        }
+        "#,
+        VmErrorKind::Panic { reason } => {
+            assert_eq!(reason.to_string(), "no value");
+        }
+    );
+}
+
+#[test]
+fn test_let_else_must_diverge() {
+    assert_compile_error! {
+        r#"pub fn main() { let Some(a) = None else { 1 }; a }"#,
+        span, LetElseMustDiverge => {
+            assert_eq!(span, span!(35, 45));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main(c) { let Some(a) = None else { if c { return; } }; a }"#,
+        span, LetElseMustDiverge => {
+            assert_eq!(span, span!(36, 61));
+        }
+    };
+}
+
+#[test]
+fn test_let_chains() {
+    let out: i64 = rune! {
+        fn get(a, b) {
+            if let Some(a) = a && a > 1 && let Some(b) = b {
+                a + b
+            } else if let Some(a) = a {
+                a
+            } else {
+                0
+            }
+        }
+
+        pub fn main() {
+            get(Some(2), Some(3)) * 100 + get(Some(1), Some(3)) * 10 + get(None, Some(3))
+        }
+    };
+    assert_eq!(out, 510);
+
+    let out: i64 = rune! {
+        pub fn main() {
+            let v = [1, 2, 3, 4];
+            let it = v.iter();
+            let out = 0;
+
+            while let Some(n) = it.next() && n < 4 {
+                out += n;
+            }
+
+            out
+        }
+    };
+    assert_eq!(out, 6);
+
+    let out: bool = rune! {
+        pub fn main() {
+            let c = true;
+            if c && let (1, b) = (1, false) && !b { true } else { false }
+        }
+    };
+    assert!(out);
+}
+
+#[test]
+fn test_let_outside_of_chain() {
+    assert_compile_error! {
+        r#"pub fn main(x) { if let Some(y) = Some(1) || x > 1 {} }"#,
+        span, HirError { error } => {
+            assert_eq!(span, span!(20, 41));
+            assert_eq!(
+                error.to_string(),
+                "`let` expressions are only supported in `&&` chains of conditions"
+            );
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main(x) { if !(let Some(y) = x) {} }"#,
+        span, HirError { .. } => {
+            assert_eq!(span, span!(22, 37));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main(x) { if (let Some(y) = x) && y {} }"#,
+        span, HirError { .. } => {
+            assert_eq!(span, span!(21, 36));
+        }
+    };
+}
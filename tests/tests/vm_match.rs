@@ -121,3 +121,100 @@ fn test_struct_matching() {
     };
     assert_eq!(out, 3);
 }
+
+#[test]
+fn test_match_or_patterns() {
+    let out: Vec<i64> = rune! {
+        fn classify(n) {
+            match n {
+                1 | 2 => 1,
+                3 | 4 | 5 => 2,
+                _ => 0,
+            }
+        }
+
+        pub fn main() {
+            [classify(1), classify(2), classify(4), classify(6)]
+        }
+    };
+    assert_eq!(out, [1, 1, 2, 0]);
+
+    let out: Vec<i64> = rune! {
+        fn other(pair) {
+            match pair {
+                (a, 1) | (1, a) => a,
+                [a, b] | (a, b, _) => a + b,
+                _ => 0,
+            }
+        }
+
+        pub fn main() {
+            [other((10, 1)), other((1, 20)), other([2, 3]), other((4, 5, 6)), other((2, 2))]
+        }
+    };
+    assert_eq!(out, [10, 20, 5, 9, 0]);
+
+    let out: i64 = rune! {
+        pub fn main() {
+            let value = 3;
+            let (a, b) | [a, b] = [value, 4];
+            a * b
+        }
+    };
+    assert_eq!(out, 12);
+
+    let out: bool = rune! {
+        pub fn main() {
+            if let Some(1 | 2) = Some(2) { true } else { false }
+        }
+    };
+    assert!(out);
+}
+
+#[test]
+fn test_match_range_patterns() {
+    let out: Vec<i64> = rune! {
+        fn classify(n) {
+            match n {
+                ..=-1 => -1,
+                0 => 0,
+                1..10 => 1,
+                10..=99 => 2,
+                100.. => 3,
+            }
+        }
+
+        pub fn main() {
+            [classify(-5), classify(0), classify(9), classify(10), classify(99), classify(100)]
+        }
+    };
+    assert_eq!(out, [-1, 0, 1, 2, 2, 3]);
+
+    let out: Vec<i64> = rune! {
+        fn classify(c) {
+            match c {
+                'a'..='z' => 1,
+                'A'..='Z' => 2,
+                b'0'..=b'9' => 3,
+                -10..-1 | 1..10 => 4,
+                _ => 0,
+            }
+        }
+
+        pub fn main() {
+            [classify('q'), classify('Q'), classify(b'5'), classify('5'), classify(-3), classify(3), classify(10)]
+        }
+    };
+    assert_eq!(out, [1, 2, 3, 0, 4, 4, 0]);
+}
+
+#[test]
+fn test_closures_with_or_patterns() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let f = |a, b| match a { 1 | 2 => b, _ => a };
+            f(1, 10) + f(5, 10)
+        }
+    };
+    assert_eq!(out, 15);
+}
@@ -0,0 +1,223 @@
+use rune::runtime::SizedInteger;
+use rune::runtime::VmErrorKind::*;
+use rune::Module;
+use rune_tests::*;
+
+#[test]
+fn test_sized_literals() {
+    let out: u32 = rune!(pub fn main() { 10u32 });
+    assert_eq!(out, 10);
+
+    let out: u64 = rune!(pub fn main() { 0xffu64 });
+    assert_eq!(out, 0xff);
+
+    let out: u64 = rune!(pub fn main() { 18446744073709551615u64 });
+    assert_eq!(out, u64::MAX);
+
+    let out: i32 = rune!(pub fn main() { -2147483648i32 });
+    assert_eq!(out, i32::MIN);
+
+    let out: u8 = rune!(pub fn main() { 255u8 });
+    assert_eq!(out, 255);
+
+    let out: i64 = rune!(pub fn main() { 42i64 });
+    assert_eq!(out, 42);
+
+    let out: String = rune_s!(r#"pub fn main() { format!("{} {:?}", 10u32, 5u16) }"#);
+    assert_eq!(out, "10 5u16");
+
+    let out: bool = rune!(pub fn main() { 10u32 == 10u32 && 10u32 != 11u32 && 1u64 < 2u64 });
+    assert!(out);
+}
+
+#[test]
+fn test_sized_arithmetic() {
+    let out: u32 = rune!(pub fn main() { let a = 4000000000u32; a + 100u32 });
+    assert_eq!(out, 4000000100);
+
+    let out: u64 = rune!(pub fn main() { let a = 1u64; a << 63 });
+    assert_eq!(out, 1 << 63);
+
+    let out: u16 = rune! {
+        pub fn main() {
+            let a = 0xf0u16;
+            a |= 0x0fu16;
+            a ^= 0xffu16;
+            a += 3u16;
+            a
+        }
+    };
+    assert_eq!(out, 3);
+
+    let out: u32 = rune!(pub fn main() { !0u32 });
+    assert_eq!(out, u32::MAX);
+
+    let out: i32 = rune!(pub fn main() { let a = 5i32; -a });
+    assert_eq!(out, -5);
+
+    assert_vm_error!(
+        "pub fn main() { let a = 4294967295u32; a + 1u32 }",
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        "pub fn main() { let a = 0u16; a -= 1u16; a }",
+        Underflow => {}
+    );
+
+    assert_vm_error!(
+        "pub fn main() { let a = 10u64; a / 0u64 }",
+        DivideByZero => {}
+    );
+
+    assert_vm_error!(
+        "pub fn main() { let a = 10u32; a + 1u64 }",
+        UnsupportedBinaryOperation { op, .. } => {
+            assert_eq!(op, "+");
+        }
+    );
+}
+
+#[test]
+fn test_sized_mixed_integers() {
+    let out: u32 = rune!(pub fn main() { let a = 10u32; a + 1 });
+    assert_eq!(out, 11);
+
+    let out: u64 = rune!(pub fn main() { let a = 10u64; 100 - a });
+    assert_eq!(out, 90);
+
+    let out: bool = rune!(pub fn main() { let a = 10u32; a == 10 && 11 > a && a != 9 });
+    assert!(out);
+
+    let out: i64 = rune! {
+        pub fn main() {
+            match 7u16 {
+                0 => 0,
+                1..=10 => 1,
+                _ => 2,
+            }
+        }
+    };
+    assert_eq!(out, 1);
+
+    assert_vm_error!(
+        "pub fn main() { let a = 10u32; a + 4294967296 }",
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        "pub fn main() { let a = 10u32; a + -1 }",
+        Overflow => {}
+    );
+}
+
+#[test]
+fn test_sized_methods() {
+    let out: u32 = rune!(pub fn main() { 4294967295u32.wrapping_add(2u32) });
+    assert_eq!(out, 1);
+
+    let out: u32 = rune!(pub fn main() { 0u32.wrapping_sub(1u32) });
+    assert_eq!(out, u32::MAX);
+
+    let out: Option<u64> = rune!(pub fn main() { 18446744073709551615u64.checked_add(1u64) });
+    assert_eq!(out, None);
+
+    let out: u16 = rune!(pub fn main() { 65535u16.saturating_add(10u16) });
+    assert_eq!(out, u16::MAX);
+
+    let out: u32 = rune!(pub fn main() { 0x12345678u32.rotate_left(8) });
+    assert_eq!(out, 0x34567812);
+
+    let out: String = rune! {
+        pub fn main() { std::any::type_name_of_val(0u32.wrapping_sub(1u32)) }
+    };
+    assert_eq!(out, "::std::u32");
+
+    let out: String = rune!(pub fn main() { std::any::type_name_of_val(42.to_u64()?) });
+    assert_eq!(out, "::std::u64");
+}
+
+#[test]
+fn test_sized_conversions() {
+    let out: Option<u32> = rune!(pub fn main() { 42.to_u32() });
+    assert_eq!(out, Some(42));
+
+    let out: Option<u32> = rune!(pub fn main() { (-1).to_u32() });
+    assert_eq!(out, None);
+
+    let out: Option<u8> = rune!(pub fn main() { 256.to_byte() });
+    assert_eq!(out, None);
+
+    let out: i64 = rune!(pub fn main() { 42u32.to_integer() });
+    assert_eq!(out, 42);
+
+    let out: Option<i64> = rune!(pub fn main() { 18446744073709551615u64.to_integer() });
+    assert_eq!(out, None);
+
+    let out: Option<i32> = rune!(pub fn main() { 65535u16.to_i32() });
+    assert_eq!(out, Some(65535));
+
+    let out: f64 = rune!(pub fn main() { 3i32.to_float() });
+    assert_eq!(out, 3.0);
+}
+
+#[test]
+fn test_sized_native() {
+    fn hash(value: SizedInteger<u32>) -> SizedInteger<u64> {
+        SizedInteger(u64::from(value.0).wrapping_mul(0x9e3779b97f4a7c15))
+    }
+
+    fn len() -> u32 {
+        3
+    }
+
+    let mut module = Module::new();
+    module.function(["hash"], hash).unwrap();
+    module.function(["len"], len).unwrap();
+
+    let out: u64 = rune_n! {
+        module,
+        (SizedInteger(u64::MAX),),
+        u64 => pub fn main(n) { n }
+    };
+    assert_eq!(out, u64::MAX);
+
+    let out: u64 = rune_n! {
+        module,
+        (),
+        u64 => pub fn main() { hash(7) }
+    };
+    assert_eq!(out, hash(SizedInteger(7)).0);
+
+    let out: bool = rune_n! {
+        module,
+        (),
+        bool => pub fn main() { hash(7u32) == hash(7) }
+    };
+    assert!(out);
+
+    let out: (String, String) = rune_n! {
+        module,
+        (),
+        (String, String) => pub fn main() {
+            (std::any::type_name_of_val(len()), std::any::type_name_of_val(hash(1)))
+        }
+    };
+    assert_eq!(out, ("::std::int".to_owned(), "::std::u64".to_owned()));
+
+    let result = rune_tests::run::<_, _, u64>(
+        &rune_tests::modules::default_context().unwrap(),
+        "pub fn main() { -1 }",
+        ["main"],
+        (),
+    );
+    assert!(result.is_err());
+
+    let result = rune_tests::run::<_, _, ()>(
+        &rune_tests::modules::default_context().unwrap(),
+        "pub fn main(n) { }",
+        ["main"],
+        (u64::MAX,),
+    );
+    assert!(result.is_err());
+}